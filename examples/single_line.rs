@@ -42,6 +42,7 @@ fn main() -> io::Result<()> {
     let mut term = Terminal::new(backend)?;
 
     let mut textarea = TextArea::default();
+    textarea.set_single_line(true);
     textarea.set_cursor_line_style(Style::default());
     textarea.set_placeholder_text("Enter a valid float (e.g. 1.56)");
     let layout = Layout::default().constraints([Constraint::Length(3), Constraint::Min(1)]);
@@ -55,22 +56,15 @@ fn main() -> io::Result<()> {
 
         match crossterm::event::read()?.into() {
             Input { key: Key::Esc, .. } => break,
-            Input {
-                key: Key::Enter, ..
-            } if is_valid => break,
-            Input {
-                key: Key::Char('m'),
-                ctrl: true,
-                ..
-            }
-            | Input {
-                key: Key::Enter, ..
-            } => {}
             input => {
                 // TextArea::input returns if the input modified its text
                 if textarea.input(input) {
                     is_valid = validate(&mut textarea);
                 }
+                // Enter key was pressed in single-line mode
+                if textarea.take_submitted() && is_valid {
+                    break;
+                }
             }
         }
     }
@@ -11,7 +11,7 @@ use std::fmt;
 use std::fs;
 use std::io;
 use std::io::BufRead;
-use tui_textarea::{CursorMove, Input, Key, Scrolling, TextArea};
+use tui_textarea::{CursorMove, Input, Key, Operator, Scrolling, TextArea, TextObject};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Mode {
@@ -27,7 +27,7 @@ impl Mode {
             Self::Normal => "type q to quit, type i to enter insert mode",
             Self::Insert => "type Esc to back to normal mode",
             Self::Visual => "type y to yank, type d to delete, type Esc to back to normal mode",
-            Self::Operator(_) => "move cursor to apply operator",
+            Self::Operator(_) => "move cursor or type text object to apply operator",
         };
         let title = format!("{} MODE ({})", self, help);
         Block::default().borders(Borders::ALL).title(title)
@@ -55,6 +55,40 @@ impl fmt::Display for Mode {
     }
 }
 
+// Cursor move by the motion key. Some motions consist of two keys like gg
+fn motion(input: &Input, pending: &Input) -> Option<CursorMove> {
+    if input.ctrl {
+        return None;
+    }
+    let m = match input.key {
+        Key::Char('h') => CursorMove::Back,
+        Key::Char('j') => CursorMove::Down,
+        Key::Char('k') => CursorMove::Up,
+        Key::Char('l') => CursorMove::Forward,
+        Key::Char('e') if pending.key == Key::Char('g') && !pending.ctrl => CursorMove::WordEndBack,
+        Key::Char('E') if pending.key == Key::Char('g') && !pending.ctrl => {
+            CursorMove::BigWordEndBack
+        }
+        Key::Char('w') => CursorMove::WordForward,
+        Key::Char('e') => CursorMove::WordEnd,
+        Key::Char('b') => CursorMove::WordBack,
+        Key::Char('W') => CursorMove::BigWordForward,
+        Key::Char('E') => CursorMove::BigWordEnd,
+        Key::Char('B') => CursorMove::BigWordBack,
+        Key::Char('^') => CursorMove::Head,
+        Key::Char('$') => CursorMove::End,
+        Key::Char('%') => CursorMove::MatchingBracket,
+        Key::Char('{') => CursorMove::ParagraphBack,
+        Key::Char('}') => CursorMove::ParagraphForward,
+        Key::Char('(') => CursorMove::SentenceBack,
+        Key::Char(')') => CursorMove::SentenceForward,
+        Key::Char('G') => CursorMove::Bottom,
+        Key::Char('g') if pending.key == Key::Char('g') && !pending.ctrl => CursorMove::Top,
+        _ => return None,
+    };
+    Some(m)
+}
+
 // How the Vim emulation state transitions
 enum Transition {
     Nop,
@@ -90,51 +124,13 @@ impl Vim {
         }
 
         match self.mode {
-            Mode::Normal | Mode::Visual | Mode::Operator(_) => {
+            Mode::Normal | Mode::Visual => {
+                if let Some(m) = motion(&input, &self.pending) {
+                    textarea.move_cursor(m);
+                    return Transition::Nop;
+                }
+
                 match input {
-                    Input {
-                        key: Key::Char('h'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::Back),
-                    Input {
-                        key: Key::Char('j'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::Down),
-                    Input {
-                        key: Key::Char('k'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::Up),
-                    Input {
-                        key: Key::Char('l'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::Forward),
-                    Input {
-                        key: Key::Char('w'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::WordForward),
-                    Input {
-                        key: Key::Char('e'),
-                        ctrl: false,
-                        ..
-                    } => {
-                        textarea.move_cursor(CursorMove::WordEnd);
-                        if matches!(self.mode, Mode::Operator(_)) {
-                            textarea.move_cursor(CursorMove::Forward); // Include the text under the cursor
-                        }
-                    }
-                    Input {
-                        key: Key::Char('b'),
-                        ctrl: false,
-                        ..
-                    } => textarea.move_cursor(CursorMove::WordBack),
-                    Input {
-                        key: Key::Char('^'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::Head),
-                    Input {
-                        key: Key::Char('$'),
-                        ..
-                    } => textarea.move_cursor(CursorMove::End),
                     Input {
                         key: Key::Char('D'),
                         ..
@@ -228,6 +224,13 @@ impl Vim {
                         textarea.move_cursor(CursorMove::Head);
                         return Transition::Mode(Mode::Insert);
                     }
+                    Input {
+                        key: Key::Char('J'),
+                        ..
+                    } => {
+                        textarea.join_lines();
+                        return Transition::Mode(Mode::Normal);
+                    }
                     Input {
                         key: Key::Char('q'),
                         ..
@@ -290,45 +293,10 @@ impl Vim {
                         return Transition::Mode(Mode::Normal);
                     }
                     Input {
-                        key: Key::Char('g'),
-                        ctrl: false,
-                        ..
-                    } if matches!(
-                        self.pending,
-                        Input {
-                            key: Key::Char('g'),
-                            ctrl: false,
-                            ..
-                        }
-                    ) =>
-                    {
-                        textarea.move_cursor(CursorMove::Top)
-                    }
-                    Input {
-                        key: Key::Char('G'),
-                        ctrl: false,
-                        ..
-                    } => textarea.move_cursor(CursorMove::Bottom),
-                    Input {
-                        key: Key::Char(c),
-                        ctrl: false,
-                        ..
-                    } if self.mode == Mode::Operator(c) => {
-                        // Handle yy, dd, cc. (This is not strictly the same behavior as Vim)
-                        textarea.move_cursor(CursorMove::Head);
-                        textarea.start_selection();
-                        let cursor = textarea.cursor();
-                        textarea.move_cursor(CursorMove::Down);
-                        if cursor == textarea.cursor() {
-                            textarea.move_cursor(CursorMove::End); // At the last line, move to end of the line instead
-                        }
-                    }
-                    Input {
-                        key: Key::Char(op @ ('y' | 'd' | 'c')),
+                        key: Key::Char(op @ ('y' | 'd' | 'c' | '>' | '<')),
                         ctrl: false,
                         ..
                     } if self.mode == Mode::Normal => {
-                        textarea.start_selection();
                         return Transition::Mode(Mode::Operator(op));
                     }
                     Input {
@@ -360,22 +328,75 @@ impl Vim {
                     }
                     input => return Transition::Pending(input),
                 }
+                Transition::Nop
+            }
+            Mode::Operator(op) => {
+                let operator = match op {
+                    'y' => Operator::Yank,
+                    'd' => Operator::Delete,
+                    'c' => Operator::Change,
+                    '>' => Operator::Indent,
+                    _ => Operator::Dedent,
+                };
+                let pending = match self.pending {
+                    Input {
+                        key: Key::Char(c @ ('i' | 'a')),
+                        ctrl: false,
+                        ..
+                    } => Some(c),
+                    _ => None,
+                };
+                let object = match (&input, pending) {
+                    (
+                        Input {
+                            key: Key::Char('w'),
+                            ctrl: false,
+                            ..
+                        },
+                        Some(c),
+                    ) => Some(if c == 'i' {
+                        TextObject::InnerWord
+                    } else {
+                        TextObject::AroundWord
+                    }),
+                    (
+                        Input {
+                            key: Key::Char('(' | ')' | '[' | ']' | '{' | '}' | 'b' | 'B'),
+                            ctrl: false,
+                            ..
+                        },
+                        Some(c),
+                    ) => Some(if c == 'i' {
+                        TextObject::InnerBracket
+                    } else {
+                        TextObject::AroundBracket
+                    }),
+                    // Handle yy, dd, cc, >>, <<
+                    (
+                        Input {
+                            key: Key::Char(c),
+                            ctrl: false,
+                            ..
+                        },
+                        _,
+                    ) if *c == op => Some(TextObject::Line),
+                    _ => None,
+                };
 
-                // Handle the pending operator
-                match self.mode {
-                    Mode::Operator('y') => {
-                        textarea.copy();
-                        Transition::Mode(Mode::Normal)
-                    }
-                    Mode::Operator('d') => {
-                        textarea.cut();
-                        Transition::Mode(Mode::Normal)
-                    }
-                    Mode::Operator('c') => {
-                        textarea.cut();
-                        Transition::Mode(Mode::Insert)
-                    }
-                    _ => Transition::Nop,
+                if let Some(object) = object {
+                    textarea.apply_operator_to_object(operator, object);
+                } else if let Some(m) = motion(&input, &self.pending) {
+                    textarea.apply_operator(operator, m);
+                } else if input.key == Key::Esc {
+                    return Transition::Mode(Mode::Normal);
+                } else {
+                    return Transition::Pending(input);
+                }
+
+                if op == 'c' {
+                    Transition::Mode(Mode::Insert)
+                } else {
+                    Transition::Mode(Mode::Normal)
                 }
             }
             Mode::Insert => match input {
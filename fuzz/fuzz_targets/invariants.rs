@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tui_textarea::EditSequence;
+
+fuzz_target!(|seq: EditSequence| {
+    // Report the minimal sequence to reproduce the failure instead of the large random input
+    if let Some((min, err)) = seq.minimize() {
+        panic!("{}\n{}", err, min);
+    }
+});
@@ -0,0 +1,68 @@
+use std::cmp;
+use std::ops::RangeInclusive;
+
+/// Rectangular selection between the anchor and the corner moved with the cursor. Columns are counted in characters
+/// and can exceed the length of the line (virtual space).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlockSelection {
+    pub anchor: (usize, usize),
+    pub corner: (usize, usize),
+}
+
+impl BlockSelection {
+    pub fn new(pos: (usize, usize)) -> Self {
+        Self {
+            anchor: pos,
+            corner: pos,
+        }
+    }
+
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        let (a, c) = (self.anchor.0, self.corner.0);
+        cmp::min(a, c)..=cmp::max(a, c)
+    }
+
+    /// Columns of the left edge and the right edge (exclusive) of the rectangle.
+    pub fn cols(&self) -> (usize, usize) {
+        let (a, c) = (self.anchor.1, self.corner.1);
+        (cmp::min(a, c), cmp::max(a, c))
+    }
+
+    /// Make the selection zero-width at the column, so that following edits are applied to the column of each row.
+    pub fn collapse(&mut self, col: usize) {
+        self.anchor.1 = col;
+        self.corner.1 = col;
+    }
+
+    /// Keep the rows in the text after lines were removed by other edits.
+    pub fn clamp_rows(&mut self, len: usize) {
+        self.anchor.0 = cmp::min(self.anchor.0, len - 1);
+        self.corner.0 = cmp::min(self.corner.0, len - 1);
+    }
+}
+
+/// Characters of the line in the column range. Columns in virtual space are ignored.
+pub fn block_text(line: &str, (left, right): (usize, usize)) -> String {
+    line.chars().skip(left).take(right - left).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle() {
+        let mut b = BlockSelection::new((3, 5));
+        b.corner = (1, 8);
+        assert_eq!(b.rows(), 1..=3);
+        assert_eq!(b.cols(), (5, 8));
+        b.collapse(2);
+        assert_eq!(b.cols(), (2, 2));
+        b.clamp_rows(3);
+        assert_eq!(b.rows(), 1..=2);
+
+        assert_eq!(block_text("abcdef", (1, 3)), "bc");
+        assert_eq!(block_text("abc", (2, 6)), "c");
+        assert_eq!(block_text("abc", (4, 6)), "");
+    }
+}
@@ -0,0 +1,252 @@
+use crate::input::{Input, Key};
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::ratatui::widgets::{Block, Widget};
+use crate::textarea::TextArea;
+use crate::util::num_digits;
+use std::cmp;
+use std::iter;
+
+/// Widget listing the bookmarks of a [`TextArea`] with their line numbers and previews of the lines. The bookmarks are
+/// added with [`TextArea::toggle_bookmark`] and this list always shows the current bookmarks of the textarea, so no
+/// synchronization is necessary after editing the text.
+///
+/// The list itself only keeps the selected item and the styles. Handle key inputs with [`BookmarkList::input`] to
+/// move the selection, remove bookmarks and jump to the selected bookmark with Enter. Render the list with the widget
+/// returned from [`BookmarkList::widget`].
+///
+/// ```
+/// use tui_textarea::{BookmarkList, CursorMove, Input, Key, TextArea};
+///
+/// let mut textarea = TextArea::from(["fn main() {", "    run();", "}"]);
+/// textarea.toggle_bookmark();
+/// textarea.move_cursor(CursorMove::Down);
+/// textarea.toggle_bookmark();
+///
+/// let mut list = BookmarkList::default();
+/// textarea.move_cursor(CursorMove::Bottom);
+/// list.input(Input { key: Key::Down, ..Default::default() }, &mut textarea);
+/// assert_eq!(list.selected(), 1);
+///
+/// // Enter jumps to the selected bookmark
+/// list.input(Input { key: Key::Enter, ..Default::default() }, &mut textarea);
+/// assert_eq!(textarea.cursor(), (1, 0));
+///
+/// // Render the list with `frame.render_widget(list.widget(&textarea), area)`
+/// ```
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::toggle_bookmark`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.toggle_bookmark
+#[derive(Clone, Debug)]
+pub struct BookmarkList<'a> {
+    selected: usize,
+    block: Option<Block<'a>>,
+    style: Style,
+    selected_style: Style,
+    line_number_style: Style,
+}
+
+impl Default for BookmarkList<'_> {
+    fn default() -> Self {
+        Self {
+            selected: 0,
+            block: None,
+            style: Style::default(),
+            selected_style: Style::default().add_modifier(Modifier::REVERSED),
+            line_number_style: Style::default().fg(Color::DarkGray),
+        }
+    }
+}
+
+impl<'a> BookmarkList<'a> {
+    /// Get the index of the selected bookmark in [`TextArea::bookmarks`]. The index is clamped to the bookmarks on
+    /// rendering and on handling inputs.
+    ///
+    /// [`TextArea::bookmarks`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.bookmarks
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the bookmark at the index of [`TextArea::bookmarks`].
+    ///
+    /// [`TextArea::bookmarks`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.bookmarks
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+    }
+
+    fn clamped(&self, len: usize) -> usize {
+        cmp::min(self.selected, len.saturating_sub(1))
+    }
+
+    /// Handle a key input for the list. Up and Down (or Ctrl+P and Ctrl+N) move the selection, Home and End select the
+    /// first and the last bookmarks, Delete removes the selected bookmark and Enter moves the cursor of the textarea to
+    /// the selected bookmark. This method returns if the input was handled or not.
+    pub fn input(&mut self, input: impl Into<Input>, textarea: &mut TextArea<'_>) -> bool {
+        let len = textarea.bookmarks().len();
+        if len == 0 {
+            return false;
+        }
+        let selected = self.clamped(len);
+        match input.into() {
+            Input { key: Key::Up, .. }
+            | Input {
+                key: Key::Char('p'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.selected = selected.saturating_sub(1),
+            Input { key: Key::Down, .. }
+            | Input {
+                key: Key::Char('n'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.selected = cmp::min(selected + 1, len - 1),
+            Input { key: Key::Home, .. } => self.selected = 0,
+            Input { key: Key::End, .. } => self.selected = len - 1,
+            Input {
+                key: Key::Delete, ..
+            } => {
+                let row = textarea.bookmarks()[selected];
+                textarea.remove_bookmark(row);
+                self.selected = cmp::min(selected, len.saturating_sub(2));
+            }
+            Input {
+                key: Key::Enter, ..
+            } => return textarea.jump_to_bookmark(selected),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Set the block of the list.
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of the list which was set by [`BookmarkList::set_block`].
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of the list if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Set the style of the list. The default style is empty.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the style of the list.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Set the style of the selected bookmark. The default style is reversed.
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    /// Get the style of the selected bookmark.
+    pub fn selected_style(&self) -> Style {
+        self.selected_style
+    }
+
+    /// Set the style of the line numbers. The default style is dark gray foreground.
+    pub fn set_line_number_style(&mut self, style: Style) {
+        self.line_number_style = style;
+    }
+
+    /// Get the style of the line numbers.
+    pub fn line_number_style(&self) -> Style {
+        self.line_number_style
+    }
+
+    /// Build a widget to render the list of the bookmarks of the textarea.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{BookmarkList, CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "  ghi"]);
+    /// textarea.toggle_bookmark();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.toggle_bookmark();
+    ///
+    /// let list = BookmarkList::default();
+    /// let area = Rect::new(0, 0, 8, 2);
+    /// let mut buf = Buffer::empty(area);
+    /// list.widget(&textarea).render(area, &mut buf);
+    /// assert_eq!(buf.content[0].symbol(), "1");
+    /// assert_eq!(buf.content[8 + 2].symbol(), "g");
+    /// ```
+    pub fn widget<'w>(&'w self, textarea: &'w TextArea<'w>) -> impl Widget + 'w {
+        BookmarkListWidget {
+            list: self,
+            textarea,
+        }
+    }
+}
+
+struct BookmarkListWidget<'w> {
+    list: &'w BookmarkList<'w>,
+    textarea: &'w TextArea<'w>,
+}
+
+impl Widget for BookmarkListWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Self { list, textarea } = self;
+        let inner = if let Some(b) = list.block() {
+            let inner = b.inner(area);
+            #[cfg(feature = "tuirs")]
+            let b = b.clone();
+            b.render(area, buf);
+            inner
+        } else {
+            area
+        };
+
+        let style = textarea.cue_style(list.style, Modifier::empty());
+        buf.set_style(inner, style);
+        let bookmarks = textarea.bookmarks();
+        if bookmarks.is_empty() || inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let selected_style = textarea.cue_style(list.selected_style, Modifier::REVERSED);
+        let lnum_style = textarea.cue_style(list.line_number_style, Modifier::empty());
+        let lnum_len = num_digits(bookmarks[bookmarks.len() - 1] + 1) as usize;
+        let width = inner.width as usize;
+        let height = inner.height as usize;
+        let selected = list.clamped(bookmarks.len());
+        // Scroll the items so that the selected item is visible
+        let offset = (selected + 1).saturating_sub(height);
+
+        for (i, &row) in bookmarks.iter().enumerate().skip(offset).take(height) {
+            let y = inner.y + (i - offset) as u16;
+            let style = if i == selected {
+                style.patch(selected_style)
+            } else {
+                style
+            };
+            buf.set_stringn(inner.x, y, " ".repeat(width), width, style);
+            let lnum = format!("{:>1$} ", row + 1, lnum_len);
+            let (x, _) = buf.set_stringn(inner.x, y, lnum, width, style.patch(lnum_style));
+
+            let line = textarea.lines()[row].trim_start();
+            let preview: String = match textarea.mask_char() {
+                Some(c) => iter::repeat(c).take(line.chars().count()).collect(),
+                None => line
+                    .chars()
+                    .map(|c| if c == '\t' { ' ' } else { c })
+                    .collect(),
+            };
+            let rest = inner.right().saturating_sub(x) as usize;
+            buf.set_stringn(x, y, preview, rest, style);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+/// Find the position of the bracket which matches to the bracket at the (row, col) position. Nested pairs of the same
+/// brackets are skipped. When the character at the position is not a bracket or no matching bracket is found, this
+/// function returns `None`.
+pub fn find_matching_bracket(
+    lines: &[String],
+    (row, col): (usize, usize),
+    pairs: &[(char, char)],
+) -> Option<(usize, usize)> {
+    let c = lines.get(row)?.chars().nth(col)?;
+    for &(open, close) in pairs {
+        if c == open {
+            return find_close(lines, (row, col), open, close);
+        }
+        if c == close {
+            return find_open(lines, (row, col), open, close);
+        }
+    }
+    None
+}
+
+/// Find the positions of the innermost open and close brackets enclosing the (row, col) position. When a bracket is at
+/// the position, the pair of the bracket is returned. Pairs whose open and close brackets are the same are ignored.
+pub fn find_enclosing_brackets(
+    lines: &[String],
+    (row, col): (usize, usize),
+    pairs: &[(char, char)],
+) -> Option<((usize, usize), (usize, usize))> {
+    let pairs: Vec<_> = pairs.iter().filter(|(o, c)| o != c).copied().collect();
+    if let Some(c) = lines.get(row)?.chars().nth(col) {
+        for &(open, close) in &pairs {
+            if c == open {
+                return Some(((row, col), find_close(lines, (row, col), open, close)?));
+            }
+            if c == close {
+                return Some((find_open(lines, (row, col), open, close)?, (row, col)));
+            }
+        }
+    }
+    pairs
+        .iter()
+        .filter_map(|&(open, close)| {
+            let start = find_open(lines, (row, col), open, close)?;
+            let end = find_close(lines, start, open, close)?;
+            Some((start, end))
+        })
+        .max_by_key(|&(start, _)| start)
+}
+
+fn find_close(
+    lines: &[String],
+    (row, col): (usize, usize),
+    open: char,
+    close: char,
+) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    for (r, line) in lines.iter().enumerate().skip(row) {
+        let skip = if r == row { col + 1 } else { 0 };
+        for (c, ch) in line.chars().enumerate().skip(skip) {
+            // Check the close bracket first for the pairs whose open and close are the same such as quotes
+            if ch == close {
+                if depth == 0 {
+                    return Some((r, c));
+                }
+                depth -= 1;
+            } else if ch == open {
+                depth += 1;
+            }
+        }
+    }
+    None
+}
+
+fn find_open(
+    lines: &[String],
+    (row, col): (usize, usize),
+    open: char,
+    close: char,
+) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    for (r, line) in lines[..=row].iter().enumerate().rev() {
+        let chars: Vec<char> = if r == row {
+            line.chars().take(col).collect()
+        } else {
+            line.chars().collect()
+        };
+        for (c, &ch) in chars.iter().enumerate().rev() {
+            if ch == open {
+                if depth == 0 {
+                    return Some((r, c));
+                }
+                depth -= 1;
+            } else if ch == close {
+                depth += 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAIRS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+
+    #[test]
+    fn matching_bracket() {
+        let lines: Vec<String> = ["fn f(a: [u8; 2]) {", "    g(a[(0)]);", "}", "(("]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        for (pos, want) in [
+            ((0, 4), Some((0, 15))),
+            ((0, 15), Some((0, 4))),
+            ((0, 8), Some((0, 14))),
+            ((0, 17), Some((2, 0))),
+            ((2, 0), Some((0, 17))),
+            ((1, 5), Some((1, 12))),
+            ((1, 7), Some((1, 11))),
+            ((1, 8), Some((1, 10))),
+            ((1, 11), Some((1, 7))),
+            ((0, 0), None),
+            ((0, 18), None),
+            ((3, 0), None),
+            ((4, 0), None),
+        ] {
+            assert_eq!(find_matching_bracket(&lines, pos, PAIRS), want, "{:?}", pos);
+        }
+    }
+
+    #[test]
+    fn same_open_and_close() {
+        let lines = vec![r#"a "b" c"#.to_string()];
+        let pairs = &[('"', '"')];
+        assert_eq!(find_matching_bracket(&lines, (0, 2), pairs), Some((0, 4)));
+        assert_eq!(find_matching_bracket(&lines, (0, 4), pairs), None);
+    }
+
+    #[test]
+    fn enclosing_brackets() {
+        let lines: Vec<String> = ["f(a, [b,", "  c], {d})", ")"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        for (pos, want) in [
+            ((0, 2), Some(((0, 1), (1, 9)))),
+            ((0, 7), Some(((0, 5), (1, 3)))),
+            ((1, 2), Some(((0, 5), (1, 3)))),
+            ((0, 5), Some(((0, 5), (1, 3)))),
+            ((1, 3), Some(((0, 5), (1, 3)))),
+            ((1, 7), Some(((1, 6), (1, 8)))),
+            ((1, 9), Some(((0, 1), (1, 9)))),
+            ((0, 0), None),
+            ((2, 0), None),
+        ] {
+            assert_eq!(
+                find_enclosing_brackets(&lines, pos, PAIRS),
+                want,
+                "{:?}",
+                pos
+            );
+        }
+    }
+}
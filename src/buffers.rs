@@ -0,0 +1,440 @@
+use crate::input::Input;
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Modifier, Style};
+use crate::ratatui::widgets::Widget;
+use crate::textarea::TextArea;
+use std::cmp;
+use unicode_width::UnicodeWidthStr as _;
+
+#[derive(Clone, Debug)]
+struct Entry<'a> {
+    name: String,
+    textarea: TextArea<'a>,
+    /// Content hash of the text when it was saved last time
+    saved: u64,
+}
+
+/// Set of [`TextArea`] buffers switched with a tab bar, which is the scaffolding of small editors editing multiple
+/// files.
+///
+/// Each buffer has a name shown in the tab bar. A buffer is dirty when its text differs from the text at the time it
+/// was added or marked as saved with [`BufferSet::mark_saved`], and the tab of a dirty buffer has the dirty marker.
+/// Rendering `&BufferSet` shows the tab bar at the first row and the current buffer below it.
+///
+/// ```
+/// use tui_textarea::{BufferSet, TextArea};
+///
+/// let mut buffers = BufferSet::default();
+/// buffers.add("main.rs", TextArea::from(["fn main() {}"]));
+/// buffers.add("lib.rs", TextArea::default());
+/// assert_eq!(buffers.current(), Some(1));
+///
+/// buffers.next();
+/// assert_eq!(buffers.current_name(), Some("main.rs"));
+///
+/// buffers.current_textarea_mut().unwrap().insert_str("// ");
+/// assert!(buffers.is_dirty(0));
+/// assert!(!buffers.is_dirty(1));
+///
+/// // After saving the file
+/// buffers.mark_saved(0);
+/// assert!(!buffers.is_dirty(0));
+///
+/// // Render the tab bar and the current buffer with `frame.render_widget(&buffers, area)`
+/// ```
+#[derive(Clone, Debug)]
+pub struct BufferSet<'a> {
+    entries: Vec<Entry<'a>>,
+    current: usize,
+    tab_style: Style,
+    current_tab_style: Style,
+    dirty_marker: String,
+}
+
+impl Default for BufferSet<'_> {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            current: 0,
+            tab_style: Style::default(),
+            current_tab_style: Style::default().add_modifier(Modifier::REVERSED),
+            dirty_marker: "*".into(),
+        }
+    }
+}
+
+impl<'a> BufferSet<'a> {
+    /// Add the textarea as a buffer with the name after the last buffer and make it current. This method returns the
+    /// index of the added buffer.
+    pub fn add(&mut self, name: impl Into<String>, textarea: TextArea<'a>) -> usize {
+        let saved = textarea.content_hash();
+        self.entries.push(Entry {
+            name: name.into(),
+            textarea,
+            saved,
+        });
+        self.current = self.entries.len() - 1;
+        self.current
+    }
+
+    /// Remove the buffer at the index and return its name and textarea. When the current buffer is removed, the next
+    /// buffer becomes current, or the previous one when it was the last buffer.
+    pub fn remove(&mut self, index: usize) -> Option<(String, TextArea<'a>)> {
+        if index >= self.entries.len() {
+            return None;
+        }
+        let entry = self.entries.remove(index);
+        if index < self.current || self.current == self.entries.len() {
+            self.current = self.current.saturating_sub(1);
+        }
+        Some((entry.name, entry.textarea))
+    }
+
+    /// Get the number of the buffers.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the index of the current buffer. `None` when there is no buffer.
+    pub fn current(&self) -> Option<usize> {
+        (!self.entries.is_empty()).then(|| self.current)
+    }
+
+    /// Make the buffer at the index current. This method returns `false` when the index is out of range.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.entries.len() {
+            return false;
+        }
+        self.current = index;
+        true
+    }
+
+    /// Switch to the next buffer. The first buffer follows the last buffer.
+    pub fn next(&mut self) {
+        if !self.entries.is_empty() {
+            self.current = (self.current + 1) % self.entries.len();
+        }
+    }
+
+    /// Switch to the previous buffer. The last buffer precedes the first buffer.
+    pub fn prev(&mut self) {
+        if !self.entries.is_empty() {
+            self.current = (self.current + self.entries.len() - 1) % self.entries.len();
+        }
+    }
+
+    /// Get the textarea of the buffer at the index.
+    pub fn textarea(&self, index: usize) -> Option<&TextArea<'a>> {
+        self.entries.get(index).map(|e| &e.textarea)
+    }
+
+    /// Get the mutable textarea of the buffer at the index.
+    pub fn textarea_mut(&mut self, index: usize) -> Option<&mut TextArea<'a>> {
+        self.entries.get_mut(index).map(|e| &mut e.textarea)
+    }
+
+    /// Get the textarea of the current buffer.
+    pub fn current_textarea(&self) -> Option<&TextArea<'a>> {
+        self.textarea(self.current)
+    }
+
+    /// Get the mutable textarea of the current buffer.
+    pub fn current_textarea_mut(&mut self) -> Option<&mut TextArea<'a>> {
+        self.textarea_mut(self.current)
+    }
+
+    /// Get the name of the buffer at the index.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|e| e.name.as_str())
+    }
+
+    /// Get the name of the current buffer.
+    pub fn current_name(&self) -> Option<&str> {
+        self.name(self.current)
+    }
+
+    /// Rename the buffer at the index. This method returns `false` when the index is out of range.
+    pub fn set_name(&mut self, index: usize, name: impl Into<String>) -> bool {
+        match self.entries.get_mut(index) {
+            Some(e) => {
+                e.name = name.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the names of all buffers in the order of the tabs.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    /// Get if the text of the buffer at the index was modified since it was added or marked as saved. Undoing the edits
+    /// back to the saved text makes the buffer clean again.
+    pub fn is_dirty(&self, index: usize) -> bool {
+        self.entries
+            .get(index)
+            .map_or(false, |e| e.textarea.content_hash() != e.saved)
+    }
+
+    /// Get if some buffer is dirty, which is useful to confirm quitting the editor.
+    pub fn has_dirty(&self) -> bool {
+        (0..self.entries.len()).any(|i| self.is_dirty(i))
+    }
+
+    /// Mark the current text of the buffer at the index as saved so that the buffer is not dirty.
+    pub fn mark_saved(&mut self, index: usize) {
+        if let Some(e) = self.entries.get_mut(index) {
+            e.saved = e.textarea.content_hash();
+        }
+    }
+
+    /// Handle a key input with the textarea of the current buffer. This method returns if the text was modified or
+    /// not. See [`TextArea::input`].
+    pub fn input(&mut self, input: impl Into<Input>) -> bool {
+        match self.current_textarea_mut() {
+            Some(t) => t.input(input),
+            None => false,
+        }
+    }
+
+    /// Set the style of the tabs. The default style is empty.
+    pub fn set_tab_style(&mut self, style: Style) {
+        self.tab_style = style;
+    }
+
+    /// Get the style of the tabs.
+    pub fn tab_style(&self) -> Style {
+        self.tab_style
+    }
+
+    /// Set the style of the tab of the current buffer. The default style is reversed.
+    pub fn set_current_tab_style(&mut self, style: Style) {
+        self.current_tab_style = style;
+    }
+
+    /// Get the style of the tab of the current buffer.
+    pub fn current_tab_style(&self) -> Style {
+        self.current_tab_style
+    }
+
+    /// Set the marker appended to the names of dirty buffers in the tab bar. The default marker is `*`.
+    pub fn set_dirty_marker(&mut self, marker: impl Into<String>) {
+        self.dirty_marker = marker.into();
+    }
+
+    /// Get the marker appended to the names of dirty buffers.
+    pub fn dirty_marker(&self) -> &str {
+        &self.dirty_marker
+    }
+
+    /// Labels of the tabs such as ` main.rs* `.
+    fn tab_labels(&self) -> Vec<String> {
+        (0..self.entries.len())
+            .map(|i| {
+                let marker = if self.is_dirty(i) {
+                    self.dirty_marker.as_str()
+                } else {
+                    ""
+                };
+                format!(" {}{} ", self.entries[i].name, marker)
+            })
+            .collect()
+    }
+
+    /// Build a widget to render only the tab bar in one row. When the tabs don't fit in the width, they are scrolled
+    /// so that the tab of the current buffer is visible.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{BufferSet, TextArea};
+    ///
+    /// let mut buffers = BufferSet::default();
+    /// buffers.add("a.txt", TextArea::default());
+    /// buffers.add("b.txt", TextArea::default());
+    /// buffers.current_textarea_mut().unwrap().insert_char('x');
+    ///
+    /// let area = Rect::new(0, 0, 18, 1);
+    /// let mut buf = Buffer::empty(area);
+    /// buffers.tab_bar().render(area, &mut buf);
+    /// let row: String = buf.content.iter().map(|c| c.symbol()).collect();
+    /// assert_eq!(row, " a.txt │ b.txt* │ ");
+    /// ```
+    pub fn tab_bar<'w>(&'w self) -> impl Widget + 'w {
+        TabBar { buffers: self }
+    }
+}
+
+struct TabBar<'w> {
+    buffers: &'w BufferSet<'w>,
+}
+
+impl Widget for TabBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let buffers = self.buffers;
+        let area = Rect {
+            height: cmp::min(area.height, 1),
+            ..area
+        };
+        // Use the color profile of the current buffer for the visual cue of the current tab
+        let (style, current_style) = match buffers.current_textarea() {
+            Some(t) => (
+                t.cue_style(buffers.tab_style, Modifier::empty()),
+                t.cue_style(buffers.current_tab_style, Modifier::REVERSED),
+            ),
+            None => (buffers.tab_style, buffers.current_tab_style),
+        };
+        buf.set_style(area, style);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        // Each tab is followed by a separator
+        let labels = buffers.tab_labels();
+        let widths: Vec<usize> = labels.iter().map(|l| l.width() + 1).collect();
+        let mut first = 0;
+        while first < buffers.current
+            && widths[first..=buffers.current].iter().sum::<usize>() > area.width as usize
+        {
+            first += 1;
+        }
+
+        let mut x = area.x;
+        for (i, label) in labels.iter().enumerate().skip(first) {
+            if x >= area.right() {
+                break;
+            }
+            let tab_style = if i == buffers.current {
+                style.patch(current_style)
+            } else {
+                style
+            };
+            let rest = (area.right() - x) as usize;
+            let (next, _) = buf.set_stringn(x, area.y, label, rest, tab_style);
+            let rest = area.right().saturating_sub(next) as usize;
+            let (next, _) = buf.set_stringn(next, area.y, "│", rest, style);
+            x = next;
+        }
+    }
+}
+
+impl Widget for &BufferSet<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+        self.tab_bar().render(area, buf);
+        if let Some(t) = self.current_textarea() {
+            let text_area = Rect {
+                y: area.y + 1,
+                height: area.height - 1,
+                ..area
+            };
+            t.render(text_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_remove_and_switch() {
+        let mut buffers = BufferSet::default();
+        assert_eq!(buffers.current(), None);
+        buffers.next();
+        buffers.prev();
+        assert!(buffers.current_textarea().is_none());
+        assert!(!buffers.input(Input::default()));
+
+        for name in ["a", "b", "c"] {
+            buffers.add(name, TextArea::from([name]));
+        }
+        assert_eq!(buffers.current(), Some(2));
+        buffers.next();
+        assert_eq!(buffers.current(), Some(0));
+        buffers.prev();
+        assert_eq!(buffers.current_name(), Some("c"));
+        assert!(buffers.select(1));
+        assert!(!buffers.select(3));
+
+        // Removing a buffer before the current one keeps the current buffer
+        let (name, textarea) = buffers.remove(0).unwrap();
+        assert_eq!(
+            (name.as_str(), textarea.lines()),
+            ("a", &["a".to_string()][..])
+        );
+        assert_eq!(buffers.current_name(), Some("b"));
+        // Removing the current buffer at the end selects the previous one
+        buffers.select(1);
+        buffers.remove(1);
+        assert_eq!(buffers.current_name(), Some("b"));
+        assert!(buffers.remove(1).is_none());
+        buffers.remove(0);
+        assert!(buffers.is_empty());
+        assert_eq!(buffers.current(), None);
+    }
+
+    #[test]
+    fn dirty_buffers() {
+        let mut buffers = BufferSet::default();
+        buffers.add("a", TextArea::from(["abc"]));
+        assert!(!buffers.has_dirty());
+
+        buffers.current_textarea_mut().unwrap().insert_char('x');
+        assert!(buffers.is_dirty(0));
+        assert!(buffers.has_dirty());
+        buffers.current_textarea_mut().unwrap().undo();
+        assert!(!buffers.is_dirty(0));
+
+        buffers.current_textarea_mut().unwrap().insert_char('y');
+        buffers.mark_saved(0);
+        assert!(!buffers.is_dirty(0));
+        assert!(!buffers.is_dirty(1));
+        assert!(buffers.set_name(0, "b"));
+        assert_eq!(buffers.names().collect::<Vec<_>>(), ["b"]);
+    }
+
+    #[test]
+    #[cfg(feature = "ratatui")]
+    fn render_tabs() {
+        let render = |buffers: &BufferSet, width: u16, height: u16| {
+            let area = Rect::new(0, 0, width, height);
+            let mut buf = Buffer::empty(area);
+            buffers.render(area, &mut buf);
+            buf.content
+                .chunks(width as usize)
+                .map(|row| row.iter().map(|c| c.symbol()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        let mut buffers = BufferSet::default();
+        for name in ["one", "two", "three"] {
+            let mut t = TextArea::from([format!("{} text", name)]);
+            t.set_cursor_style(Style::default());
+            buffers.add(name, t);
+        }
+        buffers.set_dirty_marker("+");
+        buffers.select(1);
+        buffers.input(Input {
+            key: crate::input::Key::Char('!'),
+            ..Default::default()
+        });
+        assert_eq!(
+            render(&buffers, 24, 2),
+            [" one │ two+ │ three │   ", "!two text               "],
+        );
+
+        // The tabs are scrolled to show the current tab
+        buffers.next();
+        assert_eq!(render(&buffers, 12, 1), [" three │    "]);
+        assert_eq!(render(&buffers, 15, 1), [" two+ │ three │"]);
+    }
+}
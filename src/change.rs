@@ -0,0 +1,67 @@
+/// Change of [`TextArea`] state reported to the callback set by [`TextArea::set_change_hook`]. Positions are (row,
+/// column) pairs where the column is counted in characters.
+///
+/// Text changes are reported in the order they are applied, so applying each [`ChangeEvent::Inserted`] and
+/// [`ChangeEvent::Deleted`] to a copy of the text in the order reproduces the text of the textarea. This is useful to
+/// send incremental updates to other processes such as a language server.
+///
+/// This type is marked as `#[non_exhaustive]` since more events may be reported in the future.
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::set_change_hook`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_change_hook
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeEvent {
+    /// The text was inserted at `start`. `end` is the position at the end of the inserted text. Newlines in the text are
+    /// `\n`.
+    Inserted {
+        start: (usize, usize),
+        end: (usize, usize),
+        text: String,
+    },
+    /// The text between `start` and `end` was deleted. Both positions are in the text before the deletion. Newlines in
+    /// the text are `\n`.
+    Deleted {
+        start: (usize, usize),
+        end: (usize, usize),
+        text: String,
+    },
+    /// The cursor moved from `from` to `to`.
+    CursorMoved {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    /// The selection range was changed. The value is the same as `TextArea::selection_range`.
+    SelectionChanged(Option<((usize, usize), (usize, usize))>),
+}
+
+impl ChangeEvent {
+    /// Move the position to follow the text change. When the text is inserted at the position, the position stays
+    /// before the inserted text unless `stick_right` is `true`. Positions in deleted text move to the start of the
+    /// deletion.
+    pub(crate) fn adjust(&self, pos: (usize, usize), stick_right: bool) -> (usize, usize) {
+        match *self {
+            ChangeEvent::Inserted { start, end, .. } => {
+                if pos < start || pos == start && !stick_right {
+                    pos
+                } else if pos.0 == start.0 {
+                    (end.0, end.1 + pos.1 - start.1)
+                } else {
+                    (pos.0 + end.0 - start.0, pos.1)
+                }
+            }
+            ChangeEvent::Deleted { start, end, .. } => {
+                if pos <= start {
+                    pos
+                } else if pos < end {
+                    start
+                } else if pos.0 == end.0 {
+                    (start.0, start.1 + pos.1 - end.1)
+                } else {
+                    (pos.0 - (end.0 - start.0), pos.1)
+                }
+            }
+            _ => pos,
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use crate::cursor::CursorMove;
+use crate::textarea::TextArea;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+type CommandFn = dyn Fn(&mut TextArea<'_>, &[&str]) -> Result<(), String> + Send + Sync;
+
+#[derive(Clone)]
+struct Command {
+    description: Cow<'static, str>,
+    run: Arc<CommandFn>,
+}
+
+/// Registry of named commands which operate on [`TextArea`]. Host applications register their own commands with
+/// callbacks and invoke them by name with arguments. This is useful to implement a command palette, Ex commands like
+/// `:sort` in Vim, or a simple scripting layer over the editor.
+///
+/// [`CommandRegistry::default`] creates an empty registry and [`CommandRegistry::with_builtins`] creates a registry
+/// with the built-in commands which call the methods of [`TextArea`].
+/// ```
+/// use tui_textarea::{CommandRegistry, TextArea};
+///
+/// let mut registry = CommandRegistry::with_builtins();
+/// registry.register("upcase-line", |textarea, _args| {
+///     let (row, _) = textarea.cursor();
+///     let upcased = textarea.lines()[row].to_uppercase();
+///     textarea.move_cursor(tui_textarea::CursorMove::Head);
+///     textarea.delete_line_by_end();
+///     textarea.insert_str(upcased);
+///     Ok(())
+/// });
+///
+/// let mut textarea = TextArea::from(["hello", "world"]);
+/// registry.execute(&mut textarea, "upcase-line", &[]).unwrap();
+/// registry.execute_line(&mut textarea, "goto 2").unwrap();
+/// registry.execute_line(&mut textarea, r#"insert "big ""#).unwrap();
+/// assert_eq!(textarea.lines(), ["HELLO", "big world"]);
+/// ```
+#[derive(Clone, Default)]
+pub struct CommandRegistry {
+    commands: BTreeMap<String, Command>,
+}
+
+impl fmt::Debug for CommandRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandRegistry")
+            .field("commands", &self.commands.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl CommandRegistry {
+    /// Create a registry with the built-in commands. See [`CommandRegistry::builtin_names`] for the list.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        for &(name, description, run) in BUILTINS {
+            registry.register_with_description(name, description, move |t, args| {
+                if run(t, args) {
+                    Ok(())
+                } else {
+                    Err(format!("invalid arguments: {}", args.join(" ")))
+                }
+            });
+        }
+        registry
+    }
+
+    /// Names of the built-in commands registered by [`CommandRegistry::with_builtins`].
+    /// ```
+    /// use tui_textarea::CommandRegistry;
+    ///
+    /// assert!(CommandRegistry::builtin_names().any(|name| name == "undo"));
+    /// ```
+    pub fn builtin_names() -> impl Iterator<Item = &'static str> {
+        BUILTINS.iter().map(|(name, _, _)| *name)
+    }
+
+    /// Register the command with the name. The callback receives the textarea and the arguments, and returns an error
+    /// message on failure. When a command with the same name is already registered, it is replaced.
+    pub fn register<F>(&mut self, name: impl Into<String>, command: F)
+    where
+        F: Fn(&mut TextArea<'_>, &[&str]) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.register_with_description(name, "", command);
+    }
+
+    /// Register the command with the name and a human-readable description. The description is shown in UIs such as
+    /// a command palette. See [`CommandRegistry::register`] for the callback.
+    pub fn register_with_description<F>(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<Cow<'static, str>>,
+        command: F,
+    ) where
+        F: Fn(&mut TextArea<'_>, &[&str]) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let command = Command {
+            description: description.into(),
+            run: Arc::new(command),
+        };
+        self.commands.insert(name.into(), command);
+    }
+
+    /// Remove the command with the name. This method returns if the command was registered or not.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.commands.remove(name).is_some()
+    }
+
+    /// Get if the command with the name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    /// Iterate the names of the registered commands in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.commands.keys().map(String::as_str)
+    }
+
+    /// Get the description of the command. An empty string is returned for a command registered without
+    /// description.
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.commands.get(name).map(|c| c.description.as_ref())
+    }
+
+    /// Execute the command with the arguments.
+    /// ```
+    /// use tui_textarea::{CommandError, CommandRegistry, TextArea};
+    ///
+    /// let registry = CommandRegistry::with_builtins();
+    /// let mut textarea = TextArea::from(["b", "a"]);
+    ///
+    /// registry.execute(&mut textarea, "select-all", &[]).unwrap();
+    /// registry.execute(&mut textarea, "sort-lines", &[]).unwrap();
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    ///
+    /// let err = registry.execute(&mut textarea, "no-such-command", &[]).unwrap_err();
+    /// assert_eq!(err, CommandError::NotFound("no-such-command".into()));
+    /// ```
+    pub fn execute(
+        &self,
+        textarea: &mut TextArea<'_>,
+        name: &str,
+        args: &[&str],
+    ) -> Result<(), CommandError> {
+        let command = self
+            .commands
+            .get(name)
+            .ok_or_else(|| CommandError::NotFound(name.to_string()))?;
+        (command.run)(textarea, args).map_err(|message| CommandError::Failed {
+            name: name.to_string(),
+            message,
+        })
+    }
+
+    /// Parse the command line and execute the command. The first word is the name of the command and the rest are
+    /// its arguments. Words are separated by whitespaces. A word can be quoted with `"` to contain whitespaces, and
+    /// `\` escapes the next character in a quoted word.
+    pub fn execute_line(
+        &self,
+        textarea: &mut TextArea<'_>,
+        line: &str,
+    ) -> Result<(), CommandError> {
+        let words = split_words(line)?;
+        let (name, args) = match words.split_first() {
+            Some(split) => split,
+            None => return Err(CommandError::Parse("empty command line".into())),
+        };
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.execute(textarea, name, &args)
+    }
+}
+
+/// Split the command line into words. See [`CommandRegistry::execute_line`].
+pub(crate) fn split_words(line: &str) -> Result<Vec<String>, CommandError> {
+    let mut words = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let c = match chars.next() {
+            Some(c) => c,
+            None => return Ok(words),
+        };
+        let mut word = String::new();
+        if c == '"' {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => word.extend(chars.next()),
+                    Some(c) => word.push(c),
+                    None => return Err(CommandError::Parse("unterminated quote".into())),
+                }
+            }
+        } else {
+            word.push(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                word.push(c);
+            }
+        }
+        words.push(word);
+    }
+}
+
+/// Parse a 1-based number argument into a 0-based index.
+fn index_arg(arg: &str) -> Option<u16> {
+    arg.parse::<u16>().ok()?.checked_sub(1)
+}
+
+type Builtin = (
+    &'static str,
+    &'static str,
+    fn(&mut TextArea<'_>, &[&str]) -> bool,
+);
+
+/// Built-in commands. The callbacks return `false` only when the arguments are invalid since commands which did
+/// nothing, such as undo without history, are not errors.
+const BUILTINS: &[Builtin] = &[
+    ("cancel-selection", "Cancel text selection", |t, _| {
+        t.cancel_selection();
+        true
+    }),
+    ("copy", "Copy selected text", |t, _| {
+        t.copy();
+        true
+    }),
+    ("cut", "Cut selected text", |t, _| {
+        t.cut();
+        true
+    }),
+    ("dedent", "Dedent selected lines", |t, _| {
+        t.dedent_selection();
+        true
+    }),
+    ("delete-char", "Delete one character before cursor", |t, _| {
+        t.delete_char();
+        true
+    }),
+    ("delete-line-by-end", "Delete until the end of line", |t, _| {
+        t.delete_line_by_end();
+        true
+    }),
+    ("delete-line-by-head", "Delete until the head of line", |t, _| {
+        t.delete_line_by_head();
+        true
+    }),
+    ("delete-next-char", "Delete one character next to cursor", |t, _| {
+        t.delete_next_char();
+        true
+    }),
+    ("delete-next-word", "Delete one word next to cursor", |t, _| {
+        t.delete_next_word();
+        true
+    }),
+    ("delete-whole-line", "Delete the whole line at cursor", |t, _| {
+        t.delete_whole_line();
+        true
+    }),
+    ("delete-word", "Delete one word before cursor", |t, _| {
+        t.delete_word();
+        true
+    }),
+    (
+        "duplicate-lines",
+        "Duplicate selected lines below them",
+        |t, _| {
+            t.duplicate_lines();
+            true
+        },
+    ),
+    (
+        "goto",
+        "Move cursor to the 1-based line and column: goto LINE [COL]",
+        |t, args| {
+            let row = match args.first().and_then(|a| index_arg(a)) {
+                Some(row) => row,
+                None => return false,
+            };
+            let col = match args.get(1) {
+                Some(a) => match index_arg(a) {
+                    Some(col) => col,
+                    None => return false,
+                },
+                None => 0,
+            };
+            t.move_cursor(CursorMove::Jump(row, col));
+            true
+        },
+    ),
+    ("indent", "Indent selected lines", |t, _| {
+        t.indent_selection();
+        true
+    }),
+    ("insert", "Insert the arguments joined with spaces", |t, args| {
+        t.insert_str(args.join(" "));
+        true
+    }),
+    ("insert-newline", "Insert newline", |t, _| {
+        t.insert_newline();
+        true
+    }),
+    ("join-lines", "Join selected lines into one line", |t, _| {
+        t.join_lines();
+        true
+    }),
+    ("move-lines-down", "Move selected lines down", |t, _| {
+        t.move_lines_down();
+        true
+    }),
+    ("move-lines-up", "Move selected lines up", |t, _| {
+        t.move_lines_up();
+        true
+    }),
+    ("paste", "Paste yanked text", |t, _| {
+        t.paste();
+        true
+    }),
+    ("redo", "Redo", |t, _| {
+        t.redo();
+        true
+    }),
+    ("select-all", "Select entire text", |t, _| {
+        t.select_all();
+        true
+    }),
+    ("sort-lines", "Sort selected lines", |t, _| {
+        t.sort_lines();
+        true
+    }),
+    ("start-selection", "Start text selection", |t, _| {
+        t.start_selection();
+        true
+    }),
+    (
+        "toggle-comment",
+        "Toggle line comments with the prefix or the comment prefix of the textarea: toggle-comment [PREFIX]",
+        |t, args| {
+            let prefix = match args.first() {
+                Some(p) => p.to_string(),
+                None => match t.comment_prefix() {
+                    Some(p) => p.to_string(),
+                    None => return false,
+                },
+            };
+            t.toggle_comment(&prefix);
+            true
+        },
+    ),
+    ("undo", "Undo", |t, _| {
+        t.undo();
+        true
+    }),
+    ("yank-pop", "Replace pasted text with older yank", |t, _| {
+        t.yank_pop();
+        true
+    }),
+];
+
+/// An error returned from [`CommandRegistry::execute`] and [`CommandRegistry::execute_line`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandError {
+    /// No command is registered with the name.
+    NotFound(String),
+    /// The command returned the error message.
+    Failed { name: String, message: String },
+    /// The command line could not be parsed.
+    Parse(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "command not found: {}", name),
+            Self::Failed { name, message } => write!(f, "command {} failed: {}", name, message),
+            Self::Parse(message) => write!(f, "could not parse command line: {}", message),
+        }
+    }
+}
+
+impl Error for CommandError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_line() {
+        assert_eq!(split_words("  goto  1 2 ").unwrap(), ["goto", "1", "2"]);
+        assert_eq!(
+            split_words(r#"insert "a \"b\" c" d"#).unwrap(),
+            ["insert", r#"a "b" c"#, "d"],
+        );
+        assert_eq!(split_words("").unwrap(), [] as [&str; 0]);
+        assert_eq!(
+            split_words(r#"insert "a"#),
+            Err(CommandError::Parse("unterminated quote".into())),
+        );
+    }
+
+    #[test]
+    fn builtin_names_sorted() {
+        let names: Vec<_> = CommandRegistry::builtin_names().collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+    }
+}
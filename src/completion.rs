@@ -0,0 +1,177 @@
+use crate::word::is_word_char;
+
+/// An item of the completion menu opened by [`TextArea::open_completion`](crate::TextArea::open_completion).
+///
+/// ```
+/// use tui_textarea::CompletionItem;
+///
+/// let item = CompletionItem::new("println").insert_text("println!()").detail("macro");
+/// assert_eq!(item.label(), "println");
+/// assert_eq!(item.text(), "println!()");
+/// assert_eq!(item.detail_text(), Some("macro"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletionItem {
+    label: String,
+    insert_text: Option<String>,
+    detail: Option<String>,
+}
+
+impl CompletionItem {
+    /// Create a new item with the label. The label is shown in the menu, used to filter items by the typed word, and
+    /// inserted on accepting the item.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            insert_text: None,
+            detail: None,
+        }
+    }
+
+    /// Set the text inserted on accepting the item instead of the label.
+    pub fn insert_text(mut self, text: impl Into<String>) -> Self {
+        self.insert_text = Some(text.into());
+        self
+    }
+
+    /// Set the additional text shown next to the label such as the kind or the type of the item.
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Get the label of the item.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the text inserted on accepting the item.
+    pub fn text(&self) -> &str {
+        self.insert_text.as_deref().unwrap_or(&self.label)
+    }
+
+    /// Get the detail of the item.
+    pub fn detail_text(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+}
+
+/// Column of the start of the word ending at the column.
+pub fn word_start(line: &str, col: usize) -> usize {
+    let before: Vec<char> = line.chars().take(col).collect();
+    before
+        .iter()
+        .rposition(|&c| !is_word_char(c))
+        .map_or(0, |i| i + 1)
+}
+
+/// Column of the end of the word which contains the column.
+pub fn word_end(line: &str, col: usize) -> usize {
+    col + line
+        .chars()
+        .skip(col)
+        .take_while(|&c| is_word_char(c))
+        .count()
+}
+
+/// State of the completion menu.
+#[derive(Clone, Debug)]
+pub struct Completion {
+    items: Vec<CompletionItem>,
+    /// Indices of the items matching the typed word
+    matches: Vec<usize>,
+    /// Index in `matches`
+    selected: usize,
+    /// (row, col) position of the start of the completed word
+    pub start: (usize, usize),
+}
+
+impl Completion {
+    pub fn new(items: Vec<CompletionItem>, start: (usize, usize)) -> Self {
+        let matches = (0..items.len()).collect();
+        Self {
+            items,
+            matches,
+            selected: 0,
+            start,
+        }
+    }
+
+    /// Narrow the items down to the ones whose labels start with the prefix, ignoring case. The selection is reset to
+    /// the first item.
+    pub fn filter(&mut self, prefix: &str) {
+        let prefix = prefix.to_lowercase();
+        self.matches = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.label.to_lowercase().starts_with(&prefix))
+            .map(|(i, _)| i)
+            .collect();
+        self.selected = 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+
+    /// Items matching the typed word.
+    pub fn items(&self) -> impl Iterator<Item = &CompletionItem> {
+        self.matches.iter().map(move |&i| &self.items[i])
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected(&self) -> Option<&CompletionItem> {
+        self.matches.get(self.selected).map(|&i| &self.items[i])
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_range() {
+        assert_eq!(word_start("foo.bar_baz qux", 8), 4);
+        assert_eq!(word_start("foo.bar_baz qux", 4), 4);
+        assert_eq!(word_start("foo", 3), 0);
+        assert_eq!(word_end("foo.bar_baz qux", 8), 11);
+        assert_eq!(word_end("foo", 3), 3);
+    }
+
+    #[test]
+    fn filter_and_select() {
+        let items = ["print", "println", "Parse", "eprint"]
+            .into_iter()
+            .map(CompletionItem::new)
+            .collect();
+        let mut c = Completion::new(items, (0, 0));
+        c.filter("p");
+        let labels: Vec<_> = c.items().map(|i| i.label()).collect();
+        assert_eq!(labels, ["print", "println", "Parse"]);
+        c.select_prev();
+        assert_eq!(c.selected().unwrap().label(), "Parse");
+        c.select_next();
+        assert_eq!(c.selected().unwrap().label(), "print");
+        c.filter("printl");
+        assert_eq!(c.selected().unwrap().label(), "println");
+        c.filter("x");
+        assert!(c.is_empty());
+        assert_eq!(c.selected(), None);
+    }
+}
@@ -0,0 +1,63 @@
+/// Find the ranges of the line which are concealed by the rules. Each rule is a pair of a pattern and its replacement.
+/// Each element of the returned vector is a tuple of the start byte offset, the end byte offset and the replacement.
+/// When matches overlap, the leftmost match wins. When multiple rules match at the same position, the first rule
+/// wins. Empty patterns never match.
+pub fn find_conceals<'r>(
+    line: &str,
+    rules: &'r [(String, String)],
+) -> Vec<(usize, usize, &'r str)> {
+    let mut conceals = vec![];
+    let mut i = 0;
+    'outer: while i < line.len() {
+        let rest = &line[i..];
+        for (pattern, replacement) in rules {
+            if !pattern.is_empty() && rest.starts_with(pattern.as_str()) {
+                conceals.push((i, i + pattern.len(), replacement.as_str()));
+                i += pattern.len();
+                continue 'outer;
+            }
+        }
+        i += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+    }
+    conceals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::col_at_concealed_width;
+
+    fn rules(rules: &[(&str, &str)]) -> Vec<(String, String)> {
+        rules
+            .iter()
+            .map(|(p, r)| (p.to_string(), r.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn find() {
+        let r = rules(&[("\\lambda", "λ"), ("**", ""), ("*", "・"), ("", "x")]);
+        for (line, want) in [
+            ("", vec![]),
+            ("abc", vec![]),
+            ("\\lambda x", vec![(0, 7, "λ")]),
+            ("**bold**", vec![(0, 2, ""), (6, 8, "")]),
+            ("***", vec![(0, 2, ""), (2, 3, "・")]),
+            ("あ*い", vec![(3, 4, "・")]),
+        ] {
+            assert_eq!(find_conceals(line, &r), want, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn col_at_width() {
+        let line = "a\\lambda b**c**";
+        let r = rules(&[("\\lambda", "λ"), ("**", "")]);
+        let conceals = find_conceals(line, &r);
+        // Displayed as "aλ bc"
+        for (width, want) in [(0, 0), (1, 1), (2, 8), (3, 9), (4, 12), (5, 15), (10, 15)] {
+            let have = col_at_concealed_width(line, width, 4, &conceals);
+            assert_eq!(have, want, "width={}", width);
+        }
+    }
+}
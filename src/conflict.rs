@@ -0,0 +1,170 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// A region of a merge conflict in the text, which is surrounded by conflict markers inserted by version control
+/// systems such as Git. Each field is a 0-based row of the marker line.
+///
+/// ```text
+/// <<<<<<< HEAD         <- start
+/// our change
+/// ||||||| base         <- base (only in diff3 conflict style)
+/// original text
+/// =======              <- separator
+/// their change
+/// >>>>>>> branch       <- end
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    /// Row of the `<<<<<<<` marker line.
+    pub start: usize,
+    /// Row of the `|||||||` marker line of the common ancestor. It exists only in diff3 conflict style.
+    pub base: Option<usize>,
+    /// Row of the `=======` marker line.
+    pub separator: usize,
+    /// Row of the `>>>>>>>` marker line.
+    pub end: usize,
+}
+
+impl Conflict {
+    /// Range of rows of our side of the conflict.
+    pub fn ours(&self) -> Range<usize> {
+        self.start + 1..self.base.unwrap_or(self.separator)
+    }
+
+    /// Range of rows of their side of the conflict.
+    pub fn theirs(&self) -> Range<usize> {
+        self.separator + 1..self.end
+    }
+
+    /// Get if the row is in this conflict region including the marker lines.
+    pub fn contains(&self, row: usize) -> bool {
+        self.start <= row && row <= self.end
+    }
+}
+
+/// Specify how to resolve a merge conflict. See also: [`TextArea::resolve_conflict`](crate::TextArea::resolve_conflict)
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum ConflictResolution {
+    /// Accept our side and discard their side.
+    Ours,
+    /// Accept their side and discard our side.
+    Theirs,
+    /// Accept both sides. Our side is followed by their side.
+    Both,
+}
+
+fn is_marker(line: &str, c: char) -> bool {
+    let mut chars = line.chars();
+    chars.by_ref().take(7).filter(|&x| x == c).count() == 7
+        && matches!(chars.next(), None | Some(' '))
+}
+
+/// Find all conflict regions in the lines. Broken or nested markers are ignored.
+pub fn find_conflicts(lines: &[String]) -> Vec<Conflict> {
+    let mut conflicts = vec![];
+    let mut start = None;
+    let mut base = None;
+    let mut separator = None;
+    for (row, line) in lines.iter().enumerate() {
+        if is_marker(line, '<') {
+            start = Some(row);
+            base = None;
+            separator = None;
+        } else if start.is_none() {
+            continue;
+        } else if is_marker(line, '|') && base.is_none() && separator.is_none() {
+            base = Some(row);
+        } else if is_marker(line, '=') && separator.is_none() {
+            separator = Some(row);
+        } else if is_marker(line, '>') {
+            if let (Some(start), Some(separator)) = (start, separator) {
+                conflicts.push(Conflict {
+                    start,
+                    base,
+                    separator,
+                    end: row,
+                });
+            }
+            start = None;
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker() {
+        for (line, c, want) in [
+            ("<<<<<<<", '<', true),
+            ("<<<<<<< HEAD", '<', true),
+            ("<<<<<<<<", '<', false),
+            ("<<<<<<", '<', false),
+            ("<<<<<<<HEAD", '<', false),
+            ("=======", '=', true),
+            (">>>>>>> feature/foo", '>', true),
+            ("||||||| merged common ancestors", '|', true),
+            (" <<<<<<<", '<', false),
+        ] {
+            assert_eq!(is_marker(line, c), want, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn find() {
+        let lines: Vec<String> = [
+            "a",
+            "<<<<<<< HEAD",
+            "b",
+            "=======",
+            "c",
+            ">>>>>>> topic",
+            "=======",
+            "<<<<<<< HEAD",
+            "||||||| base",
+            "d",
+            "=======",
+            "e",
+            "=======",
+            ">>>>>>> topic",
+            "<<<<<<< broken",
+            ">>>>>>> broken",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let conflicts = find_conflicts(&lines);
+        assert_eq!(
+            conflicts,
+            [
+                Conflict {
+                    start: 1,
+                    base: None,
+                    separator: 3,
+                    end: 5,
+                },
+                Conflict {
+                    start: 7,
+                    base: Some(8),
+                    separator: 10,
+                    end: 13,
+                },
+            ],
+        );
+        assert_eq!(conflicts[0].ours(), 2..3);
+        assert_eq!(conflicts[0].theirs(), 4..5);
+        assert_eq!(conflicts[1].ours(), 8..8);
+        assert_eq!(conflicts[1].theirs(), 11..13);
+    }
+}
@@ -0,0 +1,36 @@
+/// View of the text content of [`TextArea`] returned from [`TextArea::content`]. It implements [`PartialEq`], [`Eq`]
+/// and [`Hash`] by the lines of the text, so the contents of textareas can be compared with `==` or put in a
+/// `HashSet`. Other states such as the cursor position, styles and the undo history are not a part of the content.
+/// The line endings are not compared either.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use tui_textarea::TextArea;
+///
+/// let a = TextArea::from(["hello", "world"]);
+/// let mut b = TextArea::from(["hello", "world"]);
+/// b.move_cursor(tui_textarea::CursorMove::Bottom);
+/// assert_eq!(a.content(), b.content());
+///
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(a.content()));
+/// assert!(!seen.insert(b.content()));
+/// ```
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::content`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.content
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Content<'a> {
+    lines: &'a [String],
+}
+
+impl<'a> Content<'a> {
+    pub(crate) fn new(lines: &'a [String]) -> Self {
+        Self { lines }
+    }
+
+    /// Get the lines of the content.
+    pub fn lines(&self) -> &'a [String] {
+        self.lines
+    }
+}
@@ -1,6 +1,11 @@
+use crate::bracket::find_matching_bracket;
+use crate::conflict::find_conflicts;
+use crate::sentence::sentence_starts;
+use crate::util::{next_grapheme_col, prev_grapheme_col};
 use crate::widget::Viewport;
 use crate::word::{
-    find_word_inclusive_end_forward, find_word_start_backward, find_word_start_forward,
+    find_word_end_backward, find_word_inclusive_end_forward, find_word_start_backward,
+    find_word_start_forward, WordClass,
 };
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
@@ -17,6 +22,7 @@ use std::cmp;
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CursorMove {
     /// Move cursor forward by one character. When the cursor is at the end of line, it moves to the head of next line.
+    /// A grapheme cluster such as an emoji joined with ZWJ is treated as one character.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -29,7 +35,7 @@ pub enum CursorMove {
     /// ```
     Forward,
     /// Move cursor backward by one character. When the cursor is at the head of line, it moves to the end of previous
-    /// line.
+    /// line. A grapheme cluster such as an emoji joined with ZWJ is treated as one character.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -87,6 +93,57 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 3));
     /// ```
     End,
+    /// Move cursor to the head of the display row when the line is wrapped (see [`TextArea::set_wrap`]). When the
+    /// cursor is already at the head of the display row, it moves to the head of the line. When the line is not
+    /// wrapped, this is the same as [`CursorMove::Head`]. The display rows are based on the last rendering.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// textarea.set_wrap(true);
+    ///
+    /// // Displayed as "aaa bbb " and "ccc"
+    /// let r = Rect::new(0, 0, 8, 2);
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::VisualHead);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// textarea.move_cursor(CursorMove::VisualHead);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    ///
+    /// [`TextArea::set_wrap`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_wrap
+    VisualHead,
+    /// Move cursor to the end of the display row when the line is wrapped (see [`TextArea::set_wrap`]). When the cursor
+    /// is already at the end of the display row, it moves to the end of the line. When the line is not wrapped, this is
+    /// the same as [`CursorMove::End`]. The display rows are based on the last rendering.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// textarea.set_wrap(true);
+    ///
+    /// // Displayed as "aaa bbb " and "ccc"
+    /// let r = Rect::new(0, 0, 8, 2);
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::VisualEnd);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.move_cursor(CursorMove::VisualEnd);
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    /// ```
+    ///
+    /// [`TextArea::set_wrap`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_wrap
+    VisualEnd,
     /// Move cursor to the top of lines.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
@@ -167,6 +224,87 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     WordBack,
+    /// Move cursor backward to the previous end of word. Word boundary is the same as [`CursorMove::WordEnd`]. An empty
+    /// line is also a stop. This is similar to the 'ge' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "aaa bbb",
+    ///     "",
+    ///     "ccc(ddd)",
+    /// ]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 7));      // At ')'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 6));      // At the end of 'ddd'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 3));      // At '('
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (2, 2));      // At the end of 'ccc'
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (1, 0));      // At the empty line
+    /// textarea.move_cursor(CursorMove::WordEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 6));      // At the end of 'bbb'
+    /// ```
+    WordEndBack,
+    /// Move cursor forward by one WORD. WORD is a sequence of non-whitespace characters. For example `fn foo(a)`
+    /// consists of WORDs `fn` and `foo(a)`. This is similar to the 'W' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn foo(a) {}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::BigWordForward);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// textarea.move_cursor(CursorMove::BigWordForward);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// ```
+    BigWordForward,
+    /// Move cursor forward to the next end of WORD. See [`CursorMove::BigWordForward`] for WORD. This is similar to the
+    /// 'E' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn foo(a) {}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::BigWordEnd);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::BigWordEnd);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// ```
+    BigWordEnd,
+    /// Move cursor backward by one WORD. See [`CursorMove::BigWordForward`] for WORD. This is similar to the 'B' mapping
+    /// of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn foo(a) {}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::BigWordBack);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// textarea.move_cursor(CursorMove::BigWordBack);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    BigWordBack,
+    /// Move cursor backward to the previous end of WORD. See [`CursorMove::BigWordForward`] for WORD. This is similar to
+    /// the 'gE' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn foo(a) {}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::BigWordEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    /// textarea.move_cursor(CursorMove::BigWordEndBack);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// ```
+    BigWordEndBack,
     /// Move cursor down by one paragraph. Paragraph is a chunk of non-empty lines. Cursor moves to the first line of paragraph.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
@@ -206,6 +344,102 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     ParagraphBack,
+    /// Move cursor forward to the start of the next sentence. A sentence ends at `.`, `!` or `?` followed by a space or
+    /// the end of line. Closing brackets and quotes such as `)` and `"` can be put between them. An empty line is also a
+    /// sentence boundary. When no sentence is found after the cursor, the cursor moves to the end of the text. This is
+    /// similar to the ')' mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello, world. How are", "you? (Fine.) Good", "", "Bye"]);
+    ///
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (0, 14));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (1, 5));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (1, 13));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// textarea.move_cursor(CursorMove::SentenceForward);
+    /// assert_eq!(textarea.cursor(), (3, 3));
+    /// ```
+    SentenceForward,
+    /// Move cursor backward to the start of the sentence. When the cursor is at the start of a sentence, it moves to the
+    /// start of the previous sentence. See [`CursorMove::SentenceForward`] for sentences. This is similar to the '('
+    /// mapping of Vim in normal mode.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["One. Two.", "Three"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::SentenceBack);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.move_cursor(CursorMove::SentenceBack);
+    /// assert_eq!(textarea.cursor(), (0, 5));
+    /// textarea.move_cursor(CursorMove::SentenceBack);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    SentenceBack,
+    /// Move cursor to the head of the first line of the next merge conflict region. The region starts with a `<<<<<<<`
+    /// marker line. When no conflict is found after the cursor, the cursor does not move.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "<<<<<<< HEAD", "a", "=======", "b", ">>>>>>> topic",
+    ///     "<<<<<<< HEAD", "c", "=======", "d", ">>>>>>> topic",
+    /// ]);
+    ///
+    /// textarea.move_cursor(CursorMove::ConflictForward);
+    /// assert_eq!(textarea.cursor(), (5, 0));
+    /// textarea.move_cursor(CursorMove::ConflictForward);
+    /// assert_eq!(textarea.cursor(), (5, 0));
+    /// ```
+    ConflictForward,
+    /// Move cursor to the head of the first line of the previous merge conflict region. When the cursor is in a
+    /// conflict region, it moves to the start of the region. When no conflict is found before the cursor, the cursor
+    /// does not move.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "<<<<<<< HEAD", "a", "=======", "b", ">>>>>>> topic",
+    ///     "<<<<<<< HEAD", "c", "=======", "d", ">>>>>>> topic",
+    /// ]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::ConflictBack);
+    /// assert_eq!(textarea.cursor(), (5, 0));
+    /// textarea.move_cursor(CursorMove::ConflictBack);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    ConflictBack,
+    /// Move cursor to the bracket which matches to the bracket under the cursor, like `%` in Vim. Nested brackets are
+    /// skipped. When the cursor is not on a bracket or no matching bracket is found, the cursor does not move. The pairs
+    /// of brackets can be configured with [`TextArea::set_bracket_pairs`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["f(a, (b))", "{", "}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 1));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 0));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// ```
+    ///
+    /// [`TextArea::set_bracket_pairs`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_bracket_pairs
+    MatchingBracket,
     /// Move cursor to (row, col) position. When the position points outside the text, the cursor position is made fit
     /// within the text. Note that row and col are 0-based. (0, 0) means the first character of the first line.
     ///
@@ -257,29 +491,96 @@ pub enum CursorMove {
     InViewport,
 }
 
+/// Fit the column to the line on moving the cursor vertically.
+pub(crate) fn fit_col(col: usize, line: &str) -> usize {
+    // Snap to the start of the grapheme cluster so that the cursor is not placed in the middle of it
+    prev_grapheme_col(line, cmp::min(col, line.chars().count()) + 1)
+}
+
+fn word_end(lines: &[String], (row, col): (usize, usize), class: WordClass<'_>) -> (usize, usize) {
+    // `+ 1` for not accepting the current cursor position
+    if let Some(col) = find_word_inclusive_end_forward(&lines[row], col + 1, class) {
+        return (row, col);
+    }
+    let mut row = row;
+    loop {
+        if row == lines.len() - 1 {
+            break (row, lines[row].chars().count());
+        }
+        row += 1;
+        if let Some(col) = find_word_inclusive_end_forward(&lines[row], 0, class) {
+            break (row, col);
+        }
+    }
+}
+
+fn word_forward(
+    lines: &[String],
+    (row, col): (usize, usize),
+    class: WordClass<'_>,
+) -> (usize, usize) {
+    if let Some(col) = find_word_start_forward(&lines[row], col, class) {
+        (row, col)
+    } else if row + 1 < lines.len() {
+        (row + 1, 0)
+    } else {
+        (row, lines[row].chars().count())
+    }
+}
+
+fn word_back(lines: &[String], (row, col): (usize, usize), class: WordClass<'_>) -> (usize, usize) {
+    if let Some(col) = find_word_start_backward(&lines[row], col, class) {
+        (row, col)
+    } else if row > 0 {
+        (row - 1, lines[row - 1].chars().count())
+    } else {
+        (row, 0)
+    }
+}
+
+fn word_end_back(
+    lines: &[String],
+    (row, col): (usize, usize),
+    class: WordClass<'_>,
+) -> (usize, usize) {
+    if let Some(col) = find_word_end_backward(&lines[row], col, class) {
+        return (row, col);
+    }
+    for row in (0..row).rev() {
+        let line = &lines[row];
+        // Like Vim, an empty line is a stop of the motion
+        if line.is_empty() {
+            return (row, 0);
+        }
+        if let Some(col) = find_word_end_backward(line, line.chars().count(), class) {
+            return (row, col);
+        }
+    }
+    (0, 0)
+}
+
 impl CursorMove {
     pub(crate) fn next_cursor(
         &self,
         (row, col): (usize, usize),
         lines: &[String],
         viewport: &Viewport,
+        bracket_pairs: &[(char, char)],
+        row_starts: &[usize],
+        word_chars: &str,
     ) -> Option<(usize, usize)> {
         use CursorMove::*;
 
-        fn fit_col(col: usize, line: &str) -> usize {
-            cmp::min(col, line.chars().count())
-        }
-
         match self {
             Forward if col >= lines[row].chars().count() => {
                 (row + 1 < lines.len()).then(|| (row + 1, 0))
             }
-            Forward => Some((row, col + 1)),
+            Forward => Some((row, next_grapheme_col(&lines[row], col))),
             Back if col == 0 => {
                 let row = row.checked_sub(1)?;
                 Some((row, lines[row].chars().count()))
             }
-            Back => Some((row, col - 1)),
+            Back => Some((row, prev_grapheme_col(&lines[row], col))),
             Up => {
                 let row = row.checked_sub(1)?;
                 Some((row, fit_col(col, &lines[row])))
@@ -287,46 +588,48 @@ impl CursorMove {
             Down => Some((row + 1, fit_col(col, lines.get(row + 1)?))),
             Head => Some((row, 0)),
             End => Some((row, lines[row].chars().count())),
+            // `row_starts` are the columns where the display rows of the cursor line start
+            VisualHead => {
+                let idx = row_starts.partition_point(|&s| s <= col).saturating_sub(1);
+                let head = row_starts.get(idx).copied().unwrap_or(0);
+                Some((row, if head == col { 0 } else { head }))
+            }
+            VisualEnd => {
+                let len = lines[row].chars().count();
+                let idx = row_starts.partition_point(|&s| s <= col);
+                // The last character of the display row. The column at the next row start is displayed on the next row
+                let end = row_starts.get(idx).map_or(len, |&s| s.saturating_sub(1));
+                Some((row, if end == col { len } else { end }))
+            }
             Top => Some((0, fit_col(col, &lines[0]))),
             Bottom => {
                 let row = lines.len() - 1;
                 Some((row, fit_col(col, &lines[row])))
             }
-            WordEnd => {
-                // `+ 1` for not accepting the current cursor position
-                if let Some(col) = find_word_inclusive_end_forward(&lines[row], col + 1) {
-                    Some((row, col))
-                } else {
-                    let mut row = row;
-                    loop {
-                        if row == lines.len() - 1 {
-                            break Some((row, lines[row].chars().count()));
-                        }
-                        row += 1;
-                        if let Some(col) = find_word_inclusive_end_forward(&lines[row], 0) {
-                            break Some((row, col));
-                        }
-                    }
-                }
-            }
-            WordForward => {
-                if let Some(col) = find_word_start_forward(&lines[row], col) {
-                    Some((row, col))
-                } else if row + 1 < lines.len() {
-                    Some((row + 1, 0))
-                } else {
-                    Some((row, lines[row].chars().count()))
-                }
-            }
-            WordBack => {
-                if let Some(col) = find_word_start_backward(&lines[row], col) {
-                    Some((row, col))
-                } else if row > 0 {
-                    Some((row - 1, lines[row - 1].chars().count()))
-                } else {
-                    Some((row, 0))
-                }
-            }
+            WordEnd => Some(word_end(
+                lines,
+                (row, col),
+                WordClass::new(word_chars, false),
+            )),
+            WordForward => Some(word_forward(
+                lines,
+                (row, col),
+                WordClass::new(word_chars, false),
+            )),
+            WordBack => Some(word_back(
+                lines,
+                (row, col),
+                WordClass::new(word_chars, false),
+            )),
+            WordEndBack => Some(word_end_back(
+                lines,
+                (row, col),
+                WordClass::new(word_chars, false),
+            )),
+            BigWordForward => Some(word_forward(lines, (row, col), WordClass::new("", true))),
+            BigWordEnd => Some(word_end(lines, (row, col), WordClass::new("", true))),
+            BigWordBack => Some(word_back(lines, (row, col), WordClass::new("", true))),
+            BigWordEndBack => Some(word_end_back(lines, (row, col), WordClass::new("", true))),
             ParagraphForward => {
                 let mut prev_is_empty = lines[row].is_empty();
                 for row in row + 1..lines.len() {
@@ -352,6 +655,32 @@ impl CursorMove {
                 }
                 Some((0, fit_col(col, &lines[0])))
             }
+            SentenceForward => {
+                let next = sentence_starts(lines)
+                    .into_iter()
+                    .find(|&pos| pos > (row, col));
+                next.or_else(|| {
+                    let row = lines.len() - 1;
+                    Some((row, lines[row].chars().count()))
+                })
+            }
+            SentenceBack => {
+                let prev = sentence_starts(lines)
+                    .into_iter()
+                    .rev()
+                    .find(|&pos| pos < (row, col));
+                Some(prev.unwrap_or((0, 0)))
+            }
+            ConflictForward => find_conflicts(lines)
+                .into_iter()
+                .find(|c| c.start > row)
+                .map(|c| (c.start, 0)),
+            ConflictBack => find_conflicts(lines)
+                .into_iter()
+                .rev()
+                .find(|c| c.start < row)
+                .map(|c| (c.start, 0)),
+            MatchingBracket => find_matching_bracket(lines, (row, col), bracket_pairs),
             Jump(row, col) => {
                 let row = cmp::min(*row as usize, lines.len() - 1);
                 let col = fit_col(*col as usize, &lines[row]);
@@ -397,4 +726,39 @@ mod tests {
         textarea.move_cursor(CursorMove::InViewport);
         assert_eq!(textarea.cursor(), (7, 0));
     }
+
+    #[test]
+    fn visual_head_end() {
+        use crate::ratatui::buffer::Buffer;
+        use crate::ratatui::layout::Rect;
+        use crate::ratatui::widgets::Widget as _;
+        use crate::{CursorMove, TextArea};
+
+        // Displayed as "aaa ", "bbb " and "cc"
+        let mut textarea = TextArea::from(["aaa bbb cc", "d"]);
+        textarea.set_wrap(true);
+        let r = Rect::new(0, 0, 4, 4);
+        let mut b = Buffer::empty(r);
+        textarea.render(r, &mut b);
+
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        textarea.move_cursor(CursorMove::VisualEnd);
+        assert_eq!(textarea.cursor(), (0, 7));
+        textarea.move_cursor(CursorMove::VisualEnd);
+        assert_eq!(textarea.cursor(), (0, 10));
+        textarea.move_cursor(CursorMove::VisualEnd);
+        assert_eq!(textarea.cursor(), (0, 10));
+        textarea.move_cursor(CursorMove::VisualHead);
+        assert_eq!(textarea.cursor(), (0, 8));
+        textarea.move_cursor(CursorMove::VisualHead);
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        // Same as Head and End when the line is not wrapped
+        textarea.set_wrap(false);
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        textarea.move_cursor(CursorMove::VisualEnd);
+        assert_eq!(textarea.cursor(), (0, 10));
+        textarea.move_cursor(CursorMove::VisualHead);
+        assert_eq!(textarea.cursor(), (0, 0));
+    }
 }
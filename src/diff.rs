@@ -0,0 +1,169 @@
+use std::cmp;
+
+// Fall back to replacing the whole changed region when the LCS table would be larger than this
+const MAX_TABLE_SIZE: usize = 4_000_000;
+
+/// Lines `old[old_start..old_start + old_len]` are replaced with `new[new_start..new_start + new_len]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineChange {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+}
+
+impl LineChange {
+    pub fn old_end(&self) -> usize {
+        self.old_start + self.old_len
+    }
+
+    pub fn new_end(&self) -> usize {
+        self.new_start + self.new_len
+    }
+
+    /// Map a row in the old lines to the nearest row in the new lines, assuming this change is applied.
+    fn map_row(&self, row: usize) -> usize {
+        if row < self.old_start {
+            row
+        } else if row < self.old_end() {
+            let offset = cmp::min(row - self.old_start, self.new_len.saturating_sub(1));
+            self.new_start + offset
+        } else {
+            row - self.old_end() + self.new_end()
+        }
+    }
+}
+
+/// Calculate line-wise changes to convert `old` into `new`. The changes are sorted by their positions and do not
+/// overlap with each other.
+pub fn diff_lines<S: AsRef<str>, T: AsRef<str>>(old: &[S], new: &[T]) -> Vec<LineChange> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(o, n)| o.as_ref() == n.as_ref())
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(o, n)| o.as_ref() == n.as_ref())
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    if old_mid.is_empty() && new_mid.is_empty() {
+        return vec![];
+    }
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n == 0 || m == 0 || (n + 1) * (m + 1) > MAX_TABLE_SIZE {
+        return vec![LineChange {
+            old_start: prefix,
+            old_len: n,
+            new_start: prefix,
+            new_len: m,
+        }];
+    }
+
+    // lcs[i][j] is the length of the longest common subsequence of old_mid[i..] and new_mid[j..]
+    let width = m + 1;
+    let mut lcs = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * width + j] = if old_mid[i].as_ref() == new_mid[j].as_ref() {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                cmp::max(lcs[(i + 1) * width + j], lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut changes = vec![];
+    let mut pending: Option<LineChange> = None;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i].as_ref() == new_mid[j].as_ref() {
+            changes.extend(pending.take());
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let change = pending.get_or_insert(LineChange {
+            old_start: prefix + i,
+            old_len: 0,
+            new_start: prefix + j,
+            new_len: 0,
+        });
+        if j == m || (i < n && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+            change.old_len += 1;
+            i += 1;
+        } else {
+            change.new_len += 1;
+            j += 1;
+        }
+    }
+    changes.extend(pending);
+    changes
+}
+
+/// Map a (row, col) position in the old lines to the nearest position after applying the changes. The column is not
+/// fitted to the new line.
+pub fn map_position(changes: &[LineChange], (row, col): (usize, usize)) -> (usize, usize) {
+    // Only the nearest change before the row matters since its `new_start` already includes shifts by the former ones
+    let row = changes
+        .iter()
+        .rev()
+        .find(|c| c.old_start <= row)
+        .map(|c| c.map_row(row))
+        .unwrap_or(row);
+    (row, col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(old_start: usize, old_len: usize, new_start: usize, new_len: usize) -> LineChange {
+        LineChange {
+            old_start,
+            old_len,
+            new_start,
+            new_len,
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let tests: &[(&[&str], &[&str], &[LineChange])] = &[
+            (&["a", "b"], &["a", "b"], &[]),
+            (&["a", "b", "c"], &["a", "x", "c"], &[change(1, 1, 1, 1)]),
+            (&["a", "c"], &["a", "b", "c"], &[change(1, 0, 1, 1)]),
+            (&["a", "b", "c"], &["a", "c"], &[change(1, 1, 1, 0)]),
+            (
+                &["a", "b", "c", "d", "e"],
+                &["x", "b", "c", "y", "z", "e"],
+                &[change(0, 1, 0, 1), change(3, 1, 3, 2)],
+            ),
+            (&[""], &["a", "b"], &[change(0, 1, 0, 2)]),
+        ];
+        for (old, new, want) in tests {
+            let have = diff_lines(old, new);
+            assert_eq!(&have, want, "{:?} -> {:?}", old, new);
+        }
+    }
+
+    #[test]
+    fn map() {
+        let changes = [change(0, 1, 0, 2), change(3, 2, 4, 1)];
+        for (from, to) in [
+            ((0, 3), (0, 3)),
+            ((1, 1), (2, 1)),
+            ((2, 0), (3, 0)),
+            ((3, 5), (4, 5)),
+            ((4, 0), (4, 0)),
+            ((5, 0), (5, 0)),
+        ] {
+            assert_eq!(map_position(&changes, from), to, "{:?}", from);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+/// Lightweight UX event emitted by [`TextArea`] so that the host application can give feedback to users consistently,
+/// such as ringing a bell, flashing the block border, or showing a transient message. Register a callback to receive
+/// the events with [`TextArea::set_event_hook`].
+///
+/// This type is marked as `#[non_exhaustive]` since more events may be emitted in the future.
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::set_event_hook`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_event_hook
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UxEvent {
+    /// The cursor could not move by the cursor movement. For example, moving to the matching bracket when no bracket
+    /// is at the cursor.
+    MotionFailed,
+    /// The cursor could not move because it is at the start or the end of the text.
+    BufferEdge,
+    /// Text search wrapped around the text to find the next match.
+    SearchWrapped,
+    /// Text search found no match.
+    SearchFailed,
+    /// Text modification was rejected since the textarea is read-only, the text is being edited by an external editor
+    /// or the input was not accepted by the max length or the input filter.
+    EditRejected,
+    /// The file attached with `TextArea::attach_file` was changed on disk. This event is emitted once per change by
+    /// `TextArea::poll_file`. This event is only emitted when `watch` feature is enabled.
+    FileChanged,
+}
@@ -0,0 +1,260 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::ratatui::text::Span;
+#[cfg(feature = "ratatui")]
+use ratatui::text::Line;
+use std::fmt::Write as _;
+#[cfg(feature = "tuirs")]
+use tui::text::Spans as Line;
+
+/// Format of the text exported by [`TextArea::export`].
+///
+/// This type is marked as `#[non_exhaustive]` since more formats may be supported in the future.
+///
+/// [`TextArea::export`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.export
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// Plain text where each line is prefixed with its 1-based line number. No style is exported.
+    PlainWithLineNumbers,
+    /// Text styled with ANSI escape sequences (SGR) to print the text to terminals.
+    Ansi,
+    /// HTML `<pre>` element whose text is styled with inline CSS, for copying the text as HTML or generating reports.
+    Html,
+}
+
+fn line_parts<'l, 'a>(line: &'l Line<'a>) -> (&'l [Span<'a>], Style) {
+    #[cfg(feature = "ratatui")]
+    {
+        (&line.spans, line.style)
+    }
+    #[cfg(feature = "tuirs")]
+    {
+        (&line.0, Style::default())
+    }
+}
+
+/// Convert the rendered lines into the styled text of the format. `PlainWithLineNumbers` is not handled here since it
+/// does not need rendering.
+pub fn styled_text(lines: &[Line<'_>], format: ExportFormat) -> String {
+    let mut out = String::new();
+    if format == ExportFormat::Html {
+        out.push_str("<pre class=\"tui-textarea\">");
+    }
+    for line in lines {
+        let (spans, line_style) = line_parts(line);
+        for span in spans {
+            let style = line_style.patch(span.style);
+            match format {
+                ExportFormat::Html => push_html(&mut out, &span.content, style),
+                _ => push_ansi(&mut out, &span.content, style),
+            }
+        }
+        out.push('\n');
+    }
+    if format == ExportFormat::Html {
+        out.push_str("</pre>\n");
+    }
+    out
+}
+
+const MODIFIER_SGR: [(Modifier, u8); 9] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+
+/// Parameters of SGR for the color. `base` is 30 for foreground and 40 for background.
+fn color_sgr(color: Color, base: u8) -> String {
+    let named = |i: u8| if i < 8 { base + i } else { base + 60 + i - 8 };
+    match color {
+        Color::Reset => (base + 9).to_string(),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        Color::Indexed(i) => format!("{};5;{}", base + 8, i),
+        c => named(named_index(c)).to_string(),
+    }
+}
+
+/// Index of the named color in the 16 colors palette.
+fn named_index(color: Color) -> u8 {
+    match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        _ => 15, // White
+    }
+}
+
+fn push_ansi(out: &mut String, text: &str, style: Style) {
+    let mut params = vec![];
+    for (m, p) in MODIFIER_SGR {
+        if style.add_modifier.contains(m) {
+            params.push(p.to_string());
+        }
+    }
+    if let Some(fg) = style.fg {
+        params.push(color_sgr(fg, 30));
+    }
+    if let Some(bg) = style.bg {
+        params.push(color_sgr(bg, 40));
+    }
+    if params.is_empty() {
+        out.push_str(text);
+    } else {
+        let _ = write!(out, "\x1b[{}m{}\x1b[0m", params.join(";"), text);
+    }
+}
+
+/// RGB value of the color in the xterm palette. `None` for [`Color::Reset`].
+fn color_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const NAMED: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    let rgb = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if i < 16 => NAMED[i as usize],
+        Color::Indexed(i) if i < 232 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        Color::Indexed(i) => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+        c => NAMED[named_index(c) as usize],
+    };
+    Some(rgb)
+}
+
+fn push_html(out: &mut String, text: &str, style: Style) {
+    let mut fg = style.fg.and_then(color_rgb);
+    let mut bg = style.bg.and_then(color_rgb);
+    let m = style.add_modifier;
+    if m.contains(Modifier::REVERSED) {
+        // Assume the default colors are black text on white background
+        let (f, b) = (fg.unwrap_or((0, 0, 0)), bg.unwrap_or((255, 255, 255)));
+        fg = Some(b);
+        bg = Some(f);
+    }
+
+    let mut css = vec![];
+    if let Some((r, g, b)) = fg {
+        css.push(format!("color:#{:02x}{:02x}{:02x}", r, g, b));
+    }
+    if let Some((r, g, b)) = bg {
+        css.push(format!("background-color:#{:02x}{:02x}{:02x}", r, g, b));
+    }
+    if m.contains(Modifier::BOLD) {
+        css.push("font-weight:bold".into());
+    }
+    if m.contains(Modifier::DIM) {
+        css.push("opacity:0.5".into());
+    }
+    if m.contains(Modifier::ITALIC) {
+        css.push("font-style:italic".into());
+    }
+    let decorations: Vec<_> = [
+        (Modifier::UNDERLINED, "underline"),
+        (Modifier::CROSSED_OUT, "line-through"),
+    ]
+    .iter()
+    .filter(|(d, _)| m.contains(*d))
+    .map(|(_, v)| *v)
+    .collect();
+    if !decorations.is_empty() {
+        css.push(format!("text-decoration:{}", decorations.join(" ")));
+    }
+    if m.contains(Modifier::HIDDEN) {
+        css.push("visibility:hidden".into());
+    }
+
+    if !css.is_empty() {
+        let _ = write!(out, "<span style=\"{}\">", css.join(";"));
+    }
+    for c in text.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    if !css.is_empty() {
+        out.push_str("</span>");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi_styles() {
+        let mut out = String::new();
+        push_ansi(&mut out, "a", Style::default());
+        let style = Style::default()
+            .fg(Color::LightRed)
+            .bg(Color::Indexed(200))
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        push_ansi(&mut out, "b", style);
+        push_ansi(&mut out, "c", Style::default().fg(Color::Rgb(1, 2, 3)));
+        assert_eq!(
+            out,
+            "a\x1b[1;4;91;48;5;200mb\x1b[0m\x1b[38;2;1;2;3mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn html_styles() {
+        let mut out = String::new();
+        push_html(&mut out, "<a & b>", Style::default());
+        let style = Style::default()
+            .fg(Color::Indexed(21))
+            .add_modifier(Modifier::REVERSED | Modifier::ITALIC | Modifier::CROSSED_OUT);
+        push_html(&mut out, "x", style);
+        assert_eq!(
+            out,
+            concat!(
+                "&lt;a &amp; b&gt;",
+                "<span style=\"color:#ffffff;background-color:#0000ff;font-style:italic;text-decoration:line-through\">x</span>",
+            ),
+        );
+        assert_eq!(color_rgb(Color::Indexed(244)), Some((128, 128, 128)));
+        assert_eq!(color_rgb(Color::Reset), None);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::limit::SoftLimit;
+use crate::options::Options;
+use std::path::Path;
+
+/// Built-in settings for a file type. Only the settings which depend on the file type are included so that switching
+/// the file type keeps the other settings such as the read-only mode.
+pub struct Profile {
+    /// The first name is the file type name and the rest are its aliases, extensions and file names
+    pub names: &'static [&'static str],
+    pub tab_length: u8,
+    pub hard_tab_indent: bool,
+    pub auto_indent: bool,
+    pub wrap: bool,
+    pub trim_trailing_whitespace: bool,
+    pub soft_limit: Option<SoftLimit>,
+    pub color_column: Option<usize>,
+    pub comment_prefix: Option<&'static str>,
+}
+
+impl Profile {
+    pub fn name(&self) -> &'static str {
+        self.names[0]
+    }
+
+    pub fn apply(&self, options: &mut Options) {
+        options.tab_length = self.tab_length;
+        options.tab_width = None;
+        options.hard_tab_indent = self.hard_tab_indent;
+        options.auto_indent = self.auto_indent;
+        options.wrap = self.wrap;
+        options.trim_trailing_whitespace = self.trim_trailing_whitespace;
+        options.soft_limit = self.soft_limit;
+        options.color_column = self.color_column;
+        options.comment_prefix = self.comment_prefix.map(String::from);
+    }
+}
+
+const fn code(names: &'static [&'static str], tab_length: u8) -> Profile {
+    Profile {
+        names,
+        tab_length,
+        hard_tab_indent: false,
+        auto_indent: true,
+        wrap: false,
+        trim_trailing_whitespace: true,
+        soft_limit: None,
+        color_column: None,
+        comment_prefix: None,
+    }
+}
+
+const PROFILES: &[Profile] = &[
+    Profile {
+        names: &["text", "txt"],
+        tab_length: 4,
+        hard_tab_indent: false,
+        auto_indent: false,
+        wrap: true,
+        trim_trailing_whitespace: false,
+        soft_limit: None,
+        color_column: None,
+        comment_prefix: None,
+    },
+    // Two trailing spaces are a hard line break in Markdown
+    Profile {
+        names: &["markdown", "md", "mkd"],
+        tab_length: 4,
+        hard_tab_indent: false,
+        auto_indent: true,
+        wrap: true,
+        trim_trailing_whitespace: false,
+        soft_limit: None,
+        color_column: None,
+        comment_prefix: None,
+    },
+    // Recipes must be indented with hard tabs
+    Profile {
+        names: &["make", "makefile", "gnumakefile", "mk", "mak"],
+        tab_length: 8,
+        hard_tab_indent: true,
+        auto_indent: true,
+        wrap: false,
+        trim_trailing_whitespace: true,
+        soft_limit: None,
+        color_column: None,
+        comment_prefix: None,
+    },
+    Profile {
+        names: &["go"],
+        tab_length: 4,
+        hard_tab_indent: true,
+        auto_indent: true,
+        wrap: false,
+        trim_trailing_whitespace: true,
+        soft_limit: None,
+        color_column: None,
+        comment_prefix: None,
+    },
+    // Subject line within 50 columns and body lines within 72 columns
+    Profile {
+        names: &["gitcommit", "commit_editmsg", "merge_msg", "tag_editmsg"],
+        tab_length: 4,
+        hard_tab_indent: false,
+        auto_indent: false,
+        wrap: false,
+        trim_trailing_whitespace: true,
+        soft_limit: Some(SoftLimit::FirstLineColumns {
+            first: 50,
+            rest: 72,
+        }),
+        color_column: Some(72),
+        comment_prefix: Some("#"),
+    },
+    code(&["yaml", "yml"], 2),
+    code(&["json", "jsonc"], 2),
+    code(&["toml"], 4),
+    code(&["rust", "rs"], 4),
+    code(&["python", "py"], 4),
+    code(&["javascript", "js", "mjs", "cjs"], 2),
+    code(&["typescript", "ts", "tsx"], 2),
+    code(&["c", "h"], 4),
+    code(&["cpp", "cc", "cxx", "hpp"], 4),
+    code(&["java"], 4),
+    code(&["sh", "bash", "zsh"], 2),
+];
+
+/// Find the profile by a file type name, an alias or an extension. Letter case is ignored.
+pub fn find(filetype: &str) -> Option<&'static Profile> {
+    PROFILES
+        .iter()
+        .find(|p| p.names.iter().any(|n| n.eq_ignore_ascii_case(filetype)))
+}
+
+/// Find the profile by the file name (e.g. `Makefile`) or the extension of the path.
+pub fn detect(path: &Path) -> Option<&'static Profile> {
+    let name = path.file_name()?.to_str()?;
+    if let Some(p) = find(name) {
+        return Some(p);
+    }
+    find(path.extension()?.to_str()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_profiles() {
+        assert_eq!(find("yaml").map(Profile::name), Some("yaml"));
+        assert_eq!(find("YML").map(Profile::name), Some("yaml"));
+        assert_eq!(find("rs").map(Profile::name), Some("rust"));
+        assert!(find("unknown").is_none());
+        assert!(find("").is_none());
+
+        assert_eq!(
+            detect(Path::new("Makefile")).map(Profile::name),
+            Some("make")
+        );
+        assert_eq!(
+            detect(Path::new("a/b.md")).map(Profile::name),
+            Some("markdown")
+        );
+        assert_eq!(
+            detect(Path::new(".git/COMMIT_EDITMSG")).map(Profile::name),
+            Some("gitcommit")
+        );
+        assert_eq!(
+            detect(Path::new("rules.mk")).map(Profile::name),
+            Some("make")
+        );
+        assert!(detect(Path::new("LICENSE")).is_none());
+        assert!(detect(Path::new("a/")).is_none());
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut names: Vec<_> = PROFILES.iter().flat_map(|p| p.names.iter()).collect();
+        let len = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), len);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::change::ChangeEvent;
+use std::ops::Range;
+
+/// Folded region of lines. A hidden fold is rendered without the placeholder line, which is used to hide the lines not
+/// matching the line filter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fold {
+    pub rows: Range<usize>,
+    pub hidden: bool,
+}
+
+/// Folded regions of lines. Each region is a non-empty range of rows and the regions never overlap.
+#[derive(Clone, Debug, Default)]
+pub struct Folds(Vec<Fold>);
+
+impl Folds {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Fold> {
+        self.0.iter()
+    }
+
+    /// Fold the range of rows. Existing folds overlapping with the range are merged into one fold. The merged fold is
+    /// hidden only when all of the merged folds are hidden.
+    pub fn add(&mut self, range: Range<usize>, hidden: bool) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+        let mut merged = Fold {
+            rows: range,
+            hidden,
+        };
+        self.0.retain(|f| {
+            let (r, m) = (&f.rows, &mut merged.rows);
+            if r.start < m.end && m.start < r.end {
+                *m = m.start.min(r.start)..m.end.max(r.end);
+                merged.hidden &= f.hidden;
+                false
+            } else {
+                true
+            }
+        });
+        let i = self.0.partition_point(|f| f.rows.start < merged.rows.start);
+        self.0.insert(i, merged);
+        true
+    }
+
+    /// Find the fold which contains the row.
+    pub fn at(&self, row: usize) -> Option<&Fold> {
+        let i = self.0.partition_point(|f| f.rows.end <= row);
+        self.0.get(i).filter(|f| f.rows.start <= row)
+    }
+
+    /// Remove the fold which contains the row.
+    pub fn remove_at(&mut self, row: usize) -> bool {
+        let len = self.0.len();
+        self.0.retain(|f| !f.rows.contains(&row));
+        self.0.len() != len
+    }
+
+    /// Remove the folds which are hidden or not.
+    #[cfg(feature = "search")]
+    pub fn remove_if(&mut self, hidden: bool) {
+        self.0.retain(|f| f.hidden != hidden);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Follow the text change. A fold grows when lines are inserted in it and shrinks when its lines are deleted. Lines
+    /// inserted right before or after a fold are not folded.
+    pub fn apply(&mut self, change: &ChangeEvent) {
+        for f in self.0.iter_mut() {
+            let start = change.adjust((f.rows.start, 0), true).0;
+            let end = match change.adjust((f.rows.end, 0), false) {
+                (row, 0) => row,
+                (row, _) => row + 1, // The line after the fold was joined with the last line of the fold
+            };
+            f.rows = start..end;
+        }
+        self.0.retain(|f| !f.rows.is_empty());
+    }
+
+    /// Remove the rows which are out of the text after the whole text was replaced.
+    pub fn clamp(&mut self, num_lines: usize) {
+        for f in self.0.iter_mut() {
+            f.rows.end = f.rows.end.min(num_lines);
+        }
+        self.0.retain(|f| !f.rows.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(f: &Folds) -> Vec<(usize, usize)> {
+        f.iter().map(|f| (f.rows.start, f.rows.end)).collect()
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut f = Folds::default();
+        assert!(f.add(4..6, false));
+        assert!(f.add(1..3, false));
+        assert!(!f.add(3..3, false));
+        assert_eq!(ranges(&f), [(1, 3), (4, 6)]);
+        assert_eq!(f.at(2).map(|f| &f.rows), Some(&(1..3)));
+        assert_eq!(f.at(3), None);
+        assert_eq!(f.at(4).map(|f| &f.rows), Some(&(4..6)));
+
+        // Overlapping folds are merged but adjacent folds are not
+        assert!(f.add(2..5, false));
+        assert!(f.add(6..8, false));
+        assert_eq!(ranges(&f), [(1, 6), (6, 8)]);
+
+        assert!(f.remove_at(7));
+        assert!(!f.remove_at(7));
+        f.clamp(3);
+        assert_eq!(ranges(&f), [(1, 3)]);
+    }
+
+    #[test]
+    fn hidden_folds() {
+        let mut f = Folds::default();
+        f.add(0..2, true);
+        f.add(3..5, true);
+        f.add(4..7, false);
+        assert_eq!(ranges(&f), [(0, 2), (3, 7)]);
+        assert!(f.at(1).unwrap().hidden);
+        // Merged with a visible fold
+        assert!(!f.at(3).unwrap().hidden);
+
+        #[cfg(feature = "search")]
+        {
+            f.remove_if(true);
+            assert_eq!(ranges(&f), [(3, 7)]);
+        }
+    }
+
+    #[test]
+    fn follow_changes() {
+        let mut f = Folds::default();
+        f.add(2..4, false);
+
+        // New line before the fold
+        f.apply(&ChangeEvent::Inserted {
+            start: (2, 0),
+            end: (3, 0),
+            text: "\n".into(),
+        });
+        assert_eq!(f.at(3).map(|f| &f.rows), Some(&(3..5)));
+
+        // New line after the fold
+        f.apply(&ChangeEvent::Inserted {
+            start: (5, 0),
+            end: (6, 0),
+            text: "\n".into(),
+        });
+        assert_eq!(f.at(3).map(|f| &f.rows), Some(&(3..5)));
+
+        // Line is inserted in the fold
+        f.apply(&ChangeEvent::Inserted {
+            start: (4, 2),
+            end: (5, 0),
+            text: "\n".into(),
+        });
+        assert_eq!(f.at(3).map(|f| &f.rows), Some(&(3..6)));
+
+        // Join the last line of the fold and the next line
+        f.apply(&ChangeEvent::Deleted {
+            start: (5, 3),
+            end: (6, 0),
+            text: "\n".into(),
+        });
+        assert_eq!(f.at(3).map(|f| &f.rows), Some(&(3..6)));
+
+        // All lines of the fold are deleted
+        f.apply(&ChangeEvent::Deleted {
+            start: (2, 0),
+            end: (6, 0),
+            text: "...".into(),
+        });
+        assert!(f.is_empty());
+    }
+}
@@ -0,0 +1,652 @@
+use crate::cursor::CursorMove;
+use crate::input::Input;
+use crate::macros::{EditMacro, MacroOp};
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::widgets::Widget as _;
+use crate::textarea::TextArea;
+use arbitrary::Arbitrary;
+use std::any::Any;
+use std::cell::Cell;
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError, TryLockError};
+use std::thread;
+
+/// One operation on [`TextArea`] in an [`EditSequence`]. Random operations are generated with the [`Arbitrary`]
+/// trait.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Arbitrary)]
+pub enum EditOp {
+    /// Handle the key input with [`TextArea::input`].
+    Input(Input),
+    /// Move the cursor with [`TextArea::move_cursor`].
+    Move(CursorMove),
+    /// Insert the string with [`TextArea::insert_str`].
+    InsertStr(String),
+    /// Delete characters with [`TextArea::delete_str`].
+    DeleteStr(usize),
+    /// Call [`TextArea::undo`].
+    Undo,
+    /// Call [`TextArea::redo`].
+    Redo,
+    /// Call [`TextArea::start_selection`].
+    StartSelection,
+    /// Call [`TextArea::cancel_selection`].
+    CancelSelection,
+    /// Call [`TextArea::indent_selection`].
+    Indent,
+    /// Call [`TextArea::dedent_selection`].
+    Dedent,
+    /// Call [`TextArea::move_lines_up`].
+    MoveLinesUp,
+    /// Call [`TextArea::move_lines_down`].
+    MoveLinesDown,
+    /// Call [`TextArea::duplicate_lines`].
+    DuplicateLines,
+    /// Call [`TextArea::join_lines`].
+    JoinLines,
+    /// Call [`TextArea::toggle_comment`] with `//`.
+    ToggleComment,
+    /// Enable or disable wrapping with [`TextArea::set_wrap`].
+    SetWrap(bool),
+    /// Change the size of the area where the textarea is rendered. The width and height are at least 1.
+    Resize(u8, u8),
+    /// Replay the macro once with [`TextArea::replay`].
+    Replay(EditMacro),
+}
+
+impl EditOp {
+    /// Apply the operation to the textarea.
+    pub fn apply(&self, textarea: &mut TextArea<'_>) {
+        match self {
+            Self::Input(input) => {
+                textarea.input(input.clone());
+            }
+            Self::Move(m) => textarea.move_cursor(*m),
+            Self::InsertStr(s) => {
+                textarea.insert_str(s);
+            }
+            Self::DeleteStr(n) => {
+                textarea.delete_str(*n);
+            }
+            Self::Undo => {
+                textarea.undo();
+            }
+            Self::Redo => {
+                textarea.redo();
+            }
+            Self::StartSelection => textarea.start_selection(),
+            Self::CancelSelection => textarea.cancel_selection(),
+            Self::Indent => {
+                textarea.indent_selection();
+            }
+            Self::Dedent => {
+                textarea.dedent_selection();
+            }
+            Self::MoveLinesUp => {
+                textarea.move_lines_up();
+            }
+            Self::MoveLinesDown => {
+                textarea.move_lines_down();
+            }
+            Self::DuplicateLines => {
+                textarea.duplicate_lines();
+            }
+            Self::JoinLines => {
+                textarea.join_lines();
+            }
+            Self::ToggleComment => {
+                textarea.toggle_comment("//");
+            }
+            Self::SetWrap(wrap) => textarea.set_wrap(*wrap),
+            Self::Resize(..) => {}
+            Self::Replay(m) => {
+                textarea.replay(m, 1);
+            }
+        }
+    }
+}
+
+/// Sequence of random operations applied to a textarea with the initial text. [`EditSequence::run`] checks the
+/// invariants of the textarea after each operation:
+///
+/// - The text has at least one line and no line contains a newline
+/// - The cursor and the selection are in the text
+/// - After rendering, the cursor is on the screen and converting the screen position of the cursor into a text
+///   position and back again results in the same screen position
+/// - Undoing all operations restores the initial text and redoing them restores the last text
+/// - No operation panics
+///
+/// Downstream applications can reuse the generator to test their own operations with `cargo fuzz` or property-based
+/// testing tools which support [`Arbitrary`].
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use tui_textarea::EditSequence;
+///
+/// let data: Vec<u8> = (0..1024).map(|i| (i * 31 % 251) as u8).collect();
+/// let seq = EditSequence::arbitrary(&mut Unstructured::new(&data)).unwrap();
+/// let textarea = seq.run().unwrap();
+/// assert!(textarea.cursor().0 < textarea.lines().len());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Arbitrary)]
+pub struct EditSequence {
+    /// Initial text. It is split into lines with [`str::lines`].
+    pub text: String,
+    /// Operations applied in order.
+    pub ops: Vec<EditOp>,
+}
+
+impl EditSequence {
+    /// Create a sequence which replays the editing operations recorded by [`TextArea::start_recording`] on the text.
+    /// Each recorded operation becomes one [`EditOp::Replay`] so that [`EditSequence::minimize`] can remove them one by
+    /// one. Note that undo, redo, scrolling and mouse inputs are not recorded.
+    ///
+    /// To report a bug which happens in an application, save the text and start recording when the text is loaded, and
+    /// create the sequence from the recorded macro when the bug is found.
+    /// ```
+    /// use tui_textarea::{EditSequence, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// let text = textarea.text();
+    /// textarea.start_recording();
+    ///
+    /// textarea.insert_str("well, ");
+    /// textarea.delete_next_word();
+    ///
+    /// let seq = EditSequence::from_recording(text, &textarea.stop_recording());
+    /// assert_eq!(seq.ops.len(), 2);
+    /// assert_eq!(seq.run().unwrap().lines(), textarea.lines());
+    /// ```
+    pub fn from_recording(text: impl Into<String>, recorded: &EditMacro) -> Self {
+        let ops = recorded
+            .ops()
+            .iter()
+            .map(|op| {
+                let mut m = EditMacro::default();
+                m.push(op.clone());
+                EditOp::Replay(m)
+            })
+            .collect();
+        Self {
+            text: text.into(),
+            ops,
+        }
+    }
+
+    /// Apply the operations to a new textarea with the initial text and check the invariants. The textarea after the
+    /// operations is returned on success. The size of the history is not limited so that all operations can be undone.
+    /// A panic on applying an operation is caught and returned as an error.
+    pub fn run(&self) -> Result<TextArea<'static>, InvariantViolation> {
+        let mut textarea = TextArea::from(self.text.lines());
+        textarea.set_max_histories(usize::MAX);
+        let initial = textarea.lines().to_vec();
+        let mut area = default_area();
+
+        catch(None, || check(&textarea, area, None))?;
+        for (step, op) in self.ops.iter().enumerate() {
+            if let EditOp::Resize(w, h) = op {
+                area.width = (*w).max(1) as u16;
+                area.height = (*h).max(1) as u16;
+            }
+            catch(Some(step), || {
+                op.apply(&mut textarea);
+                check(&textarea, area, Some(step))
+            })?;
+        }
+
+        catch(None, || {
+            let mut t = textarea.clone();
+            while t.undo() {}
+            if t.lines() != initial.as_slice() {
+                return Err(violation(
+                    None,
+                    "undoing all operations did not restore the initial text",
+                ));
+            }
+            // Trailing undos in the operations are also redone
+            let mut last = textarea.clone();
+            while last.redo() {}
+            while t.redo() {}
+            if t.lines() != last.lines() {
+                return Err(violation(
+                    None,
+                    "redoing all operations did not restore the last text",
+                ));
+            }
+            Ok(())
+        })?;
+        Ok(textarea)
+    }
+
+    /// Find a smaller sequence which still breaks some invariant. Operations and lines of the initial text are removed
+    /// while [`EditSequence::run`] keeps failing. The minimized sequence and its error are returned. `None` is
+    /// returned when this sequence breaks no invariant.
+    ///
+    /// The [`fmt::Display`] output of the minimized sequence can be attached to a bug report to reproduce the bug
+    /// deterministically.
+    /// ```
+    /// use tui_textarea::{EditOp, EditSequence};
+    ///
+    /// let seq = EditSequence {
+    ///     text: "hello".into(),
+    ///     ops: vec![EditOp::InsertStr("world".into()), EditOp::Undo],
+    /// };
+    /// assert!(seq.minimize().is_none());
+    /// ```
+    pub fn minimize(&self) -> Option<(EditSequence, InvariantViolation)> {
+        self.minimize_by(|seq| seq.run().err())
+    }
+
+    fn minimize_by<F>(&self, fails: F) -> Option<(EditSequence, InvariantViolation)>
+    where
+        F: Fn(&EditSequence) -> Option<InvariantViolation>,
+    {
+        let mut err = fails(self)?;
+        let mut seq = self.clone();
+
+        // Remove chunks of operations, halving the chunk size until single operations cannot be removed
+        let mut chunk = cmp::max(seq.ops.len() / 2, 1);
+        loop {
+            let mut removed = false;
+            let mut i = 0;
+            while i < seq.ops.len() {
+                let mut s = seq.clone();
+                s.ops.drain(i..cmp::min(i + chunk, s.ops.len()));
+                match fails(&s) {
+                    Some(e) => {
+                        seq = s;
+                        err = e;
+                        removed = true;
+                    }
+                    None => i += chunk,
+                }
+            }
+            if !removed {
+                if chunk == 1 {
+                    break;
+                }
+                chunk /= 2;
+            }
+        }
+
+        // Remove lines of the initial text one by one
+        let mut row = 0;
+        loop {
+            let lines: Vec<&str> = seq.text.lines().collect();
+            if row >= lines.len() {
+                break;
+            }
+            let mut s = seq.clone();
+            s.text = lines
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != row)
+                .map(|(_, l)| *l)
+                .collect::<Vec<_>>()
+                .join("\n");
+            match fails(&s) {
+                Some(e) => {
+                    seq = s;
+                    err = e;
+                }
+                None => row += 1,
+            }
+        }
+
+        Some((seq, err))
+    }
+}
+
+/// Format the sequence as a report to reproduce a bug. It consists of the initial text and the numbered operations.
+impl fmt::Display for EditSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "initial text: {:?}", self.text)?;
+        writeln!(f, "operations:")?;
+        for (i, op) in self.ops.iter().enumerate() {
+            writeln!(f, "  #{}: {:?}", i + 1, op)?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    // Set on the thread minimizing a replay report to silence the panics caught while running sequences
+    static MINIMIZING: Cell<bool> = Cell::new(false);
+}
+
+/// Operations recorded for the report enabled by [`TextArea::enable_replay_report`].
+#[derive(Clone, Debug)]
+struct ReplayLog {
+    path: PathBuf,
+    text: String,
+    recorded: EditMacro,
+    reported: bool,
+}
+
+impl ReplayLog {
+    /// Minimize the recorded operations and write the report to the file. The report is written only once.
+    fn report(&mut self, err: InvariantViolation) {
+        if self.reported {
+            return;
+        }
+        self.reported = true;
+        let seq = EditSequence::from_recording(self.text.clone(), &self.recorded);
+        let fallback = (seq.clone(), err);
+        // Run on another thread since the sequences may panic while the current thread is panicking
+        let minimized = thread::spawn(move || {
+            MINIMIZING.with(|m| m.set(true));
+            seq.minimize()
+        })
+        .join();
+        let (seq, err) = match minimized {
+            Ok(Some(minimized)) => minimized,
+            _ => fallback,
+        };
+        let _ = fs::write(&self.path, format!("error: {}\n{}", err, seq));
+    }
+}
+
+/// Recorder of the editing operations for [`TextArea::enable_replay_report`]. The operations are shared with the panic
+/// hook so that the report can be written when applying an operation panics.
+#[derive(Debug)]
+pub(crate) struct ReplayReport(Arc<Mutex<ReplayLog>>);
+
+impl ReplayReport {
+    pub fn new(path: PathBuf, text: String) -> Self {
+        Self::install(ReplayLog {
+            path,
+            text,
+            recorded: EditMacro::default(),
+            reported: false,
+        })
+    }
+
+    fn install(log: ReplayLog) -> Self {
+        let log = Arc::new(Mutex::new(log));
+        let weak = Arc::downgrade(&log);
+        let prev = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if MINIMIZING.with(|m| m.get()) {
+                return;
+            }
+            prev(info);
+            // The hook does nothing after the textarea is dropped
+            let log = match weak.upgrade() {
+                Some(log) => log,
+                None => return,
+            };
+            let mut log = match log.try_lock() {
+                Ok(log) => log,
+                Err(TryLockError::Poisoned(err)) => err.into_inner(),
+                Err(TryLockError::WouldBlock) => return,
+            };
+            let step = log.recorded.len().checked_sub(1);
+            let message = format!("panicked: {}", panic_message(info.payload()));
+            log.report(violation(step, message));
+        }));
+        Self(log)
+    }
+
+    /// Record the operation before applying it so that the operation is in the report when it panics.
+    pub fn record(&self, op: &MacroOp) {
+        let mut log = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        log.recorded.push(op.clone());
+    }
+
+    /// Check the invariants of the textarea after applying the last recorded operation. When some invariant is broken,
+    /// the report is written and `false` is returned.
+    pub fn check(&self, textarea: &TextArea<'_>) -> bool {
+        let mut log = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let step = log.recorded.len().checked_sub(1);
+        // Render a clone not to change the viewport of the textarea
+        let result = catch(step, || check(&textarea.clone(), default_area(), step));
+        match result {
+            Ok(()) => true,
+            Err(err) => {
+                log.report(err);
+                false
+            }
+        }
+    }
+}
+
+/// The recorded operations are copied into a new report which has its own panic hook.
+impl Clone for ReplayReport {
+    fn clone(&self) -> Self {
+        let log = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::install(log.clone())
+    }
+}
+
+fn default_area() -> Rect {
+    Rect::new(0, 0, 40, 8)
+}
+
+/// Run the function and convert a panic in it into an error.
+fn catch<F>(step: Option<usize>, f: F) -> Result<(), InvariantViolation>
+where
+    F: FnOnce() -> Result<(), InvariantViolation>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(res) => res,
+        Err(payload) => Err(violation(
+            step,
+            format!("panicked: {}", panic_message(&*payload)),
+        )),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+fn violation(step: Option<usize>, message: impl Into<String>) -> InvariantViolation {
+    InvariantViolation {
+        step,
+        message: message.into(),
+    }
+}
+
+fn check(
+    textarea: &TextArea<'_>,
+    area: Rect,
+    step: Option<usize>,
+) -> Result<(), InvariantViolation> {
+    let lines = textarea.lines();
+    if lines.is_empty() {
+        return Err(violation(step, "text has no line"));
+    }
+    if let Some(row) = lines.iter().position(|l| l.contains('\n')) {
+        return Err(violation(
+            step,
+            format!("line {} contains a newline", row + 1),
+        ));
+    }
+    let in_text =
+        |(row, col): (usize, usize)| lines.get(row).map_or(false, |l| col <= l.chars().count());
+    let cursor = textarea.cursor();
+    if !in_text(cursor) {
+        return Err(violation(
+            step,
+            format!("cursor {:?} is out of the text", cursor),
+        ));
+    }
+    if let Some((start, end)) = textarea.selection_range() {
+        if !in_text(start) || !in_text(end) {
+            return Err(violation(
+                step,
+                format!("selection {:?} is out of the text", (start, end)),
+            ));
+        }
+    }
+
+    let mut buf = Buffer::empty(area);
+    textarea.render(area, &mut buf);
+    let (x, y) = match textarea.text_to_screen(cursor.0, cursor.1) {
+        Some(pos) => pos,
+        // The cursor may not fit in a very narrow area
+        None if area.width <= textarea.gutter_width() as u16 + 2 => return Ok(()),
+        None => {
+            return Err(violation(
+                step,
+                format!("cursor {:?} is not on the screen", cursor),
+            ))
+        }
+    };
+    let pos = textarea.screen_to_text(x, y);
+    let back = pos.and_then(|(r, c)| textarea.text_to_screen(r, c));
+    if back != Some((x, y)) {
+        return Err(violation(
+            step,
+            format!(
+                "cursor {:?} is at {:?} on the screen but it is mapped to {:?} at {:?}",
+                cursor,
+                (x, y),
+                pos,
+                back,
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// An error returned from [`EditSequence::run`] when an invariant of the textarea is broken.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvariantViolation {
+    /// Index of the operation after which the invariant was broken. `None` means that the invariant was broken on
+    /// creating the textarea or on undoing and redoing the operations.
+    pub step: Option<usize>,
+    /// Description of the broken invariant.
+    pub message: String,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.step {
+            Some(step) => write!(f, "{} after operation #{}", self.message, step + 1),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for InvariantViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrary::Unstructured;
+
+    #[test]
+    fn random_sequences() {
+        // Generate random bytes with a linear congruential generator to keep the test deterministic
+        let mut seed = 1u64;
+        let mut data = vec![0u8; 4096];
+        for _ in 0..200 {
+            for b in data.iter_mut() {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                *b = (seed >> 56) as u8;
+            }
+            let seq = EditSequence::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            if let Err(err) = seq.run() {
+                panic!("{} in {:?}", err, seq);
+            }
+        }
+    }
+
+    #[test]
+    fn broken_invariant() {
+        let seq = EditSequence {
+            text: "abc".into(),
+            ops: vec![EditOp::Move(CursorMove::End), EditOp::InsertStr("d".into())],
+        };
+        assert_eq!(seq.run().unwrap().lines(), ["abcd"]);
+
+        let err = violation(Some(1), "cursor is out of the text");
+        assert_eq!(
+            err.to_string(),
+            "cursor is out of the text after operation #2"
+        );
+    }
+
+    #[test]
+    fn minimize_sequence() {
+        let seq = EditSequence {
+            text: "a\nb\nc".into(),
+            ops: vec![
+                EditOp::Move(CursorMove::Bottom),
+                EditOp::InsertStr("x".into()),
+                EditOp::Move(CursorMove::Top),
+                EditOp::InsertStr("y".into()),
+                EditOp::Undo,
+                EditOp::Redo,
+            ],
+        };
+        // Pretend that inserting `y` into a text containing `b` is a bug
+        let (min, err) = seq
+            .minimize_by(|s| {
+                let t = s.run().unwrap();
+                let buggy = t.lines().iter().any(|l| l.contains('y'))
+                    && t.lines().iter().any(|l| l.contains('b'));
+                buggy.then(|| violation(None, "bug"))
+            })
+            .unwrap();
+        assert_eq!(err.message, "bug");
+        assert_eq!(min.text, "b");
+        assert_eq!(min.ops, [EditOp::InsertStr("y".into())]);
+        assert_eq!(
+            min.to_string(),
+            "initial text: \"b\"\noperations:\n  #1: InsertStr(\"y\")\n",
+        );
+    }
+
+    #[test]
+    fn replay_report() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("tui-textarea-replay-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        // Reporting continues while no invariant is broken. Use another file since panics in other tests are reported
+        let mut textarea = TextArea::from(["hello"]);
+        textarea.enable_replay_report(
+            dir.join(format!("tui-textarea-replay-{}-ok.txt", std::process::id())),
+        );
+        textarea.move_cursor(CursorMove::End);
+        textarea.insert_str(", world");
+        assert_eq!(textarea.lines(), ["hello, world"]);
+        assert!(textarea.is_replay_report_enabled());
+        drop(textarea);
+
+        // A panic is reported with the recorded operations
+        let report = ReplayReport::new(path.clone(), "abc".into());
+        report.record(&MacroOp::InsertStr("x".into()));
+        let _ = panic::catch_unwind(|| panic!("boom"));
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("error: panicked: "), "{}", written);
+        assert!(written.contains("initial text: \"abc\"\n"), "{}", written);
+        assert!(written.contains("InsertStr(\"x\")"), "{}", written);
+
+        // The report is written only once
+        fs::remove_file(&path).unwrap();
+        let _ = panic::catch_unwind(|| panic!("boom"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn catch_panic() {
+        let err = catch(Some(0), || panic!("oops")).unwrap_err();
+        assert_eq!(err.to_string(), "panicked: oops after operation #1");
+        let err = catch(None, || panic!("{}", 42)).unwrap_err();
+        assert_eq!(err.message, "panicked: 42");
+    }
+}
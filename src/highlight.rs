@@ -1,52 +1,42 @@
 use crate::ratatui::style::Style;
 use crate::ratatui::text::Span;
 use crate::util::{num_digits, spaces};
+use crate::whitespace::WhitespaceConfig;
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
 use std::borrow::Cow;
-use std::cmp::Ordering;
+use std::cmp;
 use std::iter;
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
 use unicode_width::UnicodeWidthChar as _;
 
-enum Boundary {
-    Cursor(Style),
-    Select(Style),
-    #[cfg(feature = "search")]
-    Search(Style),
-    End,
-}
+// Priorities of the built-in highlights. Highlights added by `TextArea::add_highlight` are merged with them
+pub const PRIORITY_WHITESPACE: u8 = 10;
+pub const PRIORITY_FIELD: u8 = 20;
+pub const PRIORITY_SELECTION: u8 = 30;
+#[cfg(feature = "search")]
+pub const PRIORITY_SEARCH: u8 = 40;
+pub const PRIORITY_BRACKET: u8 = 50;
+pub const PRIORITY_CURSOR: u8 = 60;
+pub const PRIORITY_LABEL: u8 = 70;
 
-impl Boundary {
-    fn cmp(&self, other: &Boundary) -> Ordering {
-        fn rank(b: &Boundary) -> u8 {
-            match b {
-                Boundary::Cursor(_) => 3,
-                #[cfg(feature = "search")]
-                Boundary::Search(_) => 2,
-                Boundary::Select(_) => 1,
-                Boundary::End => 0,
-            }
-        }
-        rank(self).cmp(&rank(other))
-    }
-
-    fn style(&self) -> Option<Style> {
-        match self {
-            Boundary::Cursor(s) => Some(*s),
-            Boundary::Select(s) => Some(*s),
-            #[cfg(feature = "search")]
-            Boundary::Search(s) => Some(*s),
-            Boundary::End => None,
-        }
-    }
+/// Range of the line highlighted with a style. Opaque layers replace the styles of the layers under them and the other
+/// layers are patched onto them.
+struct Layer {
+    start: usize, // Byte offset
+    end: usize,
+    priority: u8,
+    opaque: bool,
+    style: Style,
 }
 
 struct DisplayTextBuilder {
     tab_len: u8,
     width: usize,
     mask: Option<char>,
+    whitespace: Option<WhitespaceConfig>,
+    trailing_start: usize, // Byte offset where trailing spaces start
 }
 
 impl DisplayTextBuilder {
@@ -55,10 +45,18 @@ impl DisplayTextBuilder {
             tab_len,
             width: 0,
             mask,
+            whitespace: None,
+            trailing_start: usize::MAX,
         }
     }
 
     fn build<'s>(&mut self, s: &'s str) -> Cow<'s, str> {
+        self.build_at(s, None)
+    }
+
+    /// Build the display text of `s`. `offset` is the byte offset of `s` in the line, or `None` when `s` is not a part
+    /// of the line such as a replacement of concealed text.
+    fn build_at<'s>(&mut self, s: &'s str, offset: Option<usize>) -> Cow<'s, str> {
         if let Some(ch) = self.mask {
             // Note: We don't need to track width on masking text since width of tab character is fixed
             let masked = iter::repeat(ch).take(s.chars().count()).collect();
@@ -66,8 +64,16 @@ impl DisplayTextBuilder {
         }
 
         let tab = spaces(self.tab_len);
+        let ws = self.whitespace.filter(|_| offset.is_some());
         let mut buf = String::new();
         for (i, c) in s.char_indices() {
+            let glyph = match (c, ws) {
+                ('\u{a0}', Some(ws)) => ws.nbsp.map(|(g, _)| g),
+                (' ', Some(ws)) if offset.unwrap_or(0) + i >= self.trailing_start => {
+                    ws.trailing_space.map(|(g, _)| g)
+                }
+                _ => None,
+            };
             if c == '\t' {
                 if buf.is_empty() {
                     buf.reserve(s.len());
@@ -75,9 +81,21 @@ impl DisplayTextBuilder {
                 }
                 if self.tab_len > 0 {
                     let len = self.tab_len as usize - (self.width % self.tab_len as usize);
-                    buf.push_str(&tab[..len]);
+                    if let Some((g, _)) = ws.and_then(|ws| ws.tab) {
+                        buf.push(g);
+                        buf.push_str(&tab[..len - 1]);
+                    } else {
+                        buf.push_str(&tab[..len]);
+                    }
                     self.width += len;
                 }
+            } else if let Some(g) = glyph {
+                if buf.is_empty() {
+                    buf.reserve(s.len());
+                    buf.push_str(&s[..i]);
+                }
+                buf.push(g);
+                self.width += c.width().unwrap_or(0);
             } else {
                 if !buf.is_empty() {
                     buf.push(c);
@@ -92,12 +110,72 @@ impl DisplayTextBuilder {
             Cow::Borrowed(s)
         }
     }
+
+    /// Build the display text of `line[start..end]` where the concealed ranges are replaced. A replacement is put in
+    /// the text which contains the start of its concealed range.
+    fn build_concealed<'s>(
+        &mut self,
+        line: &'s str,
+        start: usize,
+        end: usize,
+        conceals: &[(usize, usize, &'s str)],
+    ) -> Cow<'s, str> {
+        let mut overlapped = conceals
+            .iter()
+            .filter(|&&(s, e, _)| start < e && s < end)
+            .peekable();
+        if overlapped.peek().is_none() {
+            return self.build_at(&line[start..end], Some(start));
+        }
+
+        let mut buf = String::new();
+        let mut i = start;
+        for &(s, e, replacement) in overlapped {
+            if i < s {
+                buf.push_str(&self.build_at(&line[i..s], Some(i)));
+            }
+            if start <= s {
+                buf.push_str(&self.build(replacement));
+            }
+            i = cmp::min(e, end);
+        }
+        if i < end {
+            buf.push_str(&self.build_at(&line[i..end], Some(i)));
+        }
+        Cow::Owned(buf)
+    }
+}
+
+/// Range of text highlighted by `TextArea::add_highlight`. The range is not moved by edits.
+#[derive(Clone, Debug)]
+pub struct TextHighlight {
+    pub tag: String,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub style: Style,
+    pub priority: u8,
+}
+
+impl TextHighlight {
+    /// Character columns of the highlighted part of the line at the row.
+    pub fn cols_at(&self, row: usize, line_len: usize) -> Option<(usize, usize)> {
+        if row < self.start.0 || self.end.0 < row {
+            return None;
+        }
+        let start = if row == self.start.0 { self.start.1 } else { 0 };
+        let end = if row == self.end.0 {
+            self.end.1
+        } else {
+            line_len
+        };
+        Some((start, end))
+    }
 }
 
 pub struct LineHighlighter<'a> {
     line: &'a str,
     spans: Vec<Span<'a>>,
-    boundaries: Vec<(Boundary, usize)>, // TODO: Consider smallvec
+    layers: Vec<Layer>,
     style_begin: Style,
     cursor_at_end: bool,
     cursor_style: Style,
@@ -105,6 +183,13 @@ pub struct LineHighlighter<'a> {
     mask: Option<char>,
     select_at_end: bool,
     select_style: Style,
+    conceals: Vec<(usize, usize, &'a str)>,
+    whitespace: Option<WhitespaceConfig>,
+    trailing_start: usize,
+    eol: Option<(char, Style)>,
+    annotation: Option<Span<'a>>,
+    preview: Option<(usize, String, Style)>,
+    virtual_cells: Vec<Style>,
 }
 
 impl<'a> LineHighlighter<'a> {
@@ -118,7 +203,7 @@ impl<'a> LineHighlighter<'a> {
         Self {
             line,
             spans: vec![],
-            boundaries: vec![],
+            layers: vec![],
             style_begin: Style::default(),
             cursor_at_end: false,
             cursor_style,
@@ -126,6 +211,51 @@ impl<'a> LineHighlighter<'a> {
             mask,
             select_at_end: false,
             select_style,
+            conceals: vec![],
+            whitespace: None,
+            trailing_start: usize::MAX,
+            eol: None,
+            annotation: None,
+            preview: None,
+            virtual_cells: vec![],
+        }
+    }
+
+    /// Put the opaque layer of a built-in highlight on the byte offsets.
+    fn push_layer(&mut self, start: usize, end: usize, priority: u8, style: Style) {
+        if start < end {
+            self.layers.push(Layer {
+                start,
+                end,
+                priority,
+                opaque: true,
+                style,
+            });
+        }
+    }
+
+    /// Byte offsets of the character columns. Columns beyond the end of line are clamped.
+    fn col_offsets(&self, start_col: usize, end_col: usize) -> (usize, usize) {
+        let mut offsets = self
+            .line
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(iter::once(self.line.len()));
+        let start = offsets.nth(start_col).unwrap_or(self.line.len());
+        let end = match end_col.checked_sub(start_col + 1) {
+            Some(n) => offsets.nth(n).unwrap_or(self.line.len()),
+            None => start,
+        };
+        (start, end)
+    }
+
+    pub fn gutter_sign(&mut self, sign: Option<Span<'a>>, width: usize) {
+        let pad = width - sign.as_ref().map(|s| s.width()).unwrap_or(0);
+        if let Some(sign) = sign {
+            self.spans.push(sign);
+        }
+        if pad > 0 {
+            self.spans.push(Span::raw(" ".repeat(pad)));
         }
     }
 
@@ -135,27 +265,97 @@ impl<'a> LineHighlighter<'a> {
             .push(Span::styled(format!("{}{} ", pad, row + 1), style));
     }
 
+    pub fn prompt(&mut self, prompt: &'a str, width: usize, style: Style) {
+        let pad = width - Span::raw(prompt).width();
+        self.spans.push(Span::styled(prompt, style));
+        if pad > 0 {
+            self.spans.push(Span::raw(" ".repeat(pad)));
+        }
+    }
+
+    /// Put the annotation after the end of line. One cell is put between the line and the annotation.
+    pub fn eol_annotation(&mut self, annotation: Span<'a>) {
+        self.annotation = Some(annotation);
+    }
+
     pub fn cursor_line(&mut self, cursor_col: usize, style: Style) {
         if let Some((start, c)) = self.line.char_indices().nth(cursor_col) {
-            self.boundaries
-                .push((Boundary::Cursor(self.cursor_style), start));
-            self.boundaries.push((Boundary::End, start + c.len_utf8()));
+            let style = self.cursor_style;
+            self.push_layer(start, start + c.len_utf8(), PRIORITY_CURSOR, style);
         } else {
             self.cursor_at_end = true;
         }
         self.style_begin = style;
     }
 
+    /// Visualize whitespaces in the line. `eol` is a glyph put at the end of line.
+    pub fn whitespace(&mut self, config: WhitespaceConfig, eol: Option<(char, Style)>) {
+        if self.mask.is_some() {
+            return;
+        }
+        let trailing_start = self.line.trim_end_matches(' ').len();
+        for (i, c) in self.line.char_indices() {
+            let style = match c {
+                '\t' => config.tab,
+                '\u{a0}' => config.nbsp,
+                ' ' if i >= trailing_start => config.trailing_space,
+                _ => None,
+            };
+            if let Some((_, style)) = style {
+                self.push_layer(i, i + c.len_utf8(), PRIORITY_WHITESPACE, style);
+            }
+        }
+        self.whitespace = Some(config);
+        self.trailing_start = trailing_start;
+        self.eol = eol;
+    }
+
+    pub fn conceal(&mut self, conceals: Vec<(usize, usize, &'a str)>) {
+        self.conceals = conceals;
+    }
+
+    pub fn jump_label(&mut self, start: usize, end: usize, style: Style) {
+        self.push_layer(start, end, PRIORITY_LABEL, style);
+    }
+
+    pub fn bracket(&mut self, col: usize, style: Style) {
+        if let Some((start, c)) = self.line.char_indices().nth(col) {
+            self.push_layer(start, start + c.len_utf8(), PRIORITY_BRACKET, style);
+        }
+    }
+
+    pub fn field(&mut self, start_col: usize, end_col: usize, style: Style) {
+        let (start, end) = self.col_offsets(start_col, end_col);
+        self.push_layer(start, end, PRIORITY_FIELD, style);
+    }
+
     #[cfg(feature = "search")]
     pub fn search(&mut self, matches: impl Iterator<Item = (usize, usize)>, style: Style) {
         for (start, end) in matches {
-            if start != end {
-                self.boundaries.push((Boundary::Search(style), start));
-                self.boundaries.push((Boundary::End, end));
-            }
+            self.push_layer(start, end, PRIORITY_SEARCH, style);
         }
     }
 
+    /// Highlight the character columns with the style patched onto the styles of the highlights with lower priorities.
+    pub fn highlight(&mut self, start_col: usize, end_col: usize, priority: u8, style: Style) {
+        let (start, end) = self.col_offsets(start_col, end_col);
+        if start < end {
+            self.layers.push(Layer {
+                start,
+                end,
+                priority,
+                opaque: false,
+                style,
+            });
+        }
+    }
+
+    /// Put the preview text at the byte offset. The text is not a part of the line.
+    #[cfg(feature = "search")]
+    pub fn replace_preview(&mut self, offset: usize, text: String, style: Style) {
+        self.preview = Some((offset, text, style));
+    }
+
     pub fn selection(
         &mut self,
         current_row: usize,
@@ -179,18 +379,37 @@ impl<'a> LineHighlighter<'a> {
         } else {
             return;
         };
-        if start != end {
-            self.boundaries
-                .push((Boundary::Select(self.select_style), start));
-            self.boundaries.push((Boundary::End, end));
-        }
+        let style = self.select_style;
+        self.push_layer(start, end, PRIORITY_SELECTION, style);
+    }
+
+    /// Highlight the columns of the block selection. Columns past the end of line are rendered as cells in virtual
+    /// space. `cursor_col` is the column of the cursor, which can also be in virtual space.
+    pub fn block_selection(&mut self, start_col: usize, end_col: usize, cursor_col: Option<usize>) {
+        let (start, end) = self.col_offsets(start_col, end_col);
+        let style = self.select_style;
+        self.push_layer(start, end, PRIORITY_SELECTION, style);
+
+        let len = self.line.chars().count();
+        let cells_end = cmp::max(end_col, cursor_col.map_or(0, |c| c + 1));
+        self.virtual_cells = (len..cells_end)
+            .map(|col| {
+                if cursor_col == Some(col) {
+                    self.cursor_style
+                } else if start_col <= col && col < end_col {
+                    style
+                } else {
+                    Style::default()
+                }
+            })
+            .collect();
     }
 
     pub fn into_spans(self) -> Line<'a> {
         let Self {
             line,
             mut spans,
-            mut boundaries,
+            mut layers,
             tab_len,
             style_begin,
             cursor_style,
@@ -198,54 +417,124 @@ impl<'a> LineHighlighter<'a> {
             mask,
             select_at_end,
             select_style,
+            conceals,
+            whitespace,
+            trailing_start,
+            eol,
+            annotation,
+            mut preview,
+            virtual_cells,
         } = self;
         let mut builder = DisplayTextBuilder::new(tab_len, mask);
+        builder.whitespace = whitespace;
+        builder.trailing_start = trailing_start;
 
-        if boundaries.is_empty() {
-            let built = builder.build(line);
-            if !built.is_empty() {
-                spans.push(Span::styled(built, style_begin));
-            }
-            if cursor_at_end {
-                spans.push(Span::styled(" ", cursor_style));
+        // The cell at the end of line shows the EOL glyph if any. The cell separates the annotation from the line
+        let end_of_line = |spans: &mut Vec<Span<'a>>| {
+            let glyph: Cow<'a, str> = match eol {
+                Some((g, _)) => Cow::Owned(g.to_string()),
+                None => Cow::Borrowed(" "),
+            };
+            if !virtual_cells.is_empty() {
+                // Cells in virtual space continue from the cell at the end of line
+                for (i, &style) in virtual_cells.iter().enumerate() {
+                    let (cell, style) = match eol {
+                        _ if i > 0 => (Cow::Borrowed(" "), style),
+                        Some((_, eol_style)) if style == Style::default() => {
+                            (glyph.clone(), eol_style)
+                        }
+                        _ => (glyph.clone(), style),
+                    };
+                    spans.push(Span::styled(cell, style));
+                }
+            } else if cursor_at_end {
+                spans.push(Span::styled(glyph, cursor_style));
             } else if select_at_end {
-                spans.push(Span::styled(" ", select_style));
+                spans.push(Span::styled(glyph, select_style));
+            } else if let Some((_, style)) = eol {
+                spans.push(Span::styled(glyph, style));
+            } else if annotation.is_some() {
+                spans.push(Span::raw(glyph));
+            }
+            if let Some(annotation) = &annotation {
+                spans.push(annotation.clone());
             }
+        };
+
+        // Push the text of `line[start..end]`. The preview is put in the text which contains its offset
+        let mut push_text =
+            |spans: &mut Vec<Span<'a>>, mut start: usize, end: usize, style: Style| {
+                if let Some((at, text, preview_style)) = preview.take() {
+                    if start <= at && at <= end {
+                        if start < at {
+                            let built = builder.build_concealed(line, start, at, &conceals);
+                            spans.push(Span::styled(built, style));
+                        }
+                        let built = builder.build(&text).into_owned();
+                        spans.push(Span::styled(built, preview_style));
+                        start = at;
+                    } else {
+                        preview = Some((at, text, preview_style));
+                    }
+                }
+                if start < end {
+                    let built = builder.build_concealed(line, start, end, &conceals);
+                    if !built.is_empty() {
+                        spans.push(Span::styled(built, style));
+                    }
+                }
+            };
+
+        if layers.is_empty() {
+            push_text(&mut spans, 0, line.len(), style_begin);
+            end_of_line(&mut spans);
             return Line::from(spans);
         }
 
-        boundaries.sort_unstable_by(|(l, i), (r, j)| match i.cmp(j) {
-            Ordering::Equal => l.cmp(r),
-            o => o,
-        });
+        // Layers with the same priority are stacked in the order of addition
+        layers.sort_by_key(|l| l.priority);
+        let mut events: Vec<(usize, usize)> = layers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, l)| [(l.start, i), (l.end, i)])
+            .collect();
+        events.sort_unstable();
 
-        let mut style = style_begin;
-        let mut start = 0;
-        let mut stack = vec![];
+        // Indices of the layers covering the current offset in the ascending order of the priorities
+        let mut active: Vec<usize> = vec![];
+        let layered_style = |active: &[usize]| {
+            active.iter().fold(style_begin, |style, &i| {
+                let layer = &layers[i];
+                if layer.opaque {
+                    layer.style
+                } else {
+                    style.patch(layer.style)
+                }
+            })
+        };
 
-        for (next_boundary, end) in boundaries {
-            if start < end {
-                spans.push(Span::styled(builder.build(&line[start..end]), style));
+        let mut start = 0;
+        let mut style = style_begin;
+        for (offset, i) in events {
+            match active.binary_search(&i) {
+                Ok(pos) => {
+                    active.remove(pos);
+                }
+                Err(pos) => active.insert(pos, i),
+            }
+            // Adjacent ranges with the same style are rendered as one span
+            let next = layered_style(&active);
+            if next != style {
+                if start < offset {
+                    push_text(&mut spans, start, offset, style);
+                }
+                start = offset;
+                style = next;
             }
-
-            style = if let Some(s) = next_boundary.style() {
-                stack.push(style);
-                s
-            } else {
-                stack.pop().unwrap_or(style_begin)
-            };
-            start = end;
-        }
-
-        if start != line.len() {
-            spans.push(Span::styled(builder.build(&line[start..]), style));
         }
 
-        if cursor_at_end {
-            spans.push(Span::styled(" ", cursor_style));
-        } else if select_at_end {
-            spans.push(Span::styled(" ", select_style));
-        }
+        push_text(&mut spans, start, line.len(), style);
+        end_of_line(&mut spans);
 
         Line::from(spans)
     }
@@ -255,7 +544,7 @@ impl<'a> LineHighlighter<'a> {
 #[cfg(all(test, feature = "ratatui"))]
 mod tests {
     use super::*;
-    use crate::ratatui::style::Color;
+    use crate::ratatui::style::{Color, Modifier};
     use std::fmt::Debug;
     use unicode_width::UnicodeWidthStr as _;
 
@@ -482,6 +771,66 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "search")]
+    fn into_spans_replace_preview() {
+        const PREVIEW: Style = Style::new().bg(Color::Magenta);
+        let tests = [
+            // (line, cursor, match, preview offset, preview, want)
+            (
+                "foo bar",
+                0,
+                (0, 3),
+                3,
+                "baz",
+                &[
+                    ("f", CUR),
+                    ("oo", SEARCH),
+                    ("baz", PREVIEW),
+                    (" bar", DEFAULT),
+                ][..],
+            ),
+            (
+                "a foo",
+                2,
+                (2, 5),
+                5,
+                "x",
+                &[("a ", DEFAULT), ("f", CUR), ("oo", SEARCH), ("x", PREVIEW)][..],
+            ),
+            (
+                "ab	c",
+                0,
+                (0, 2),
+                2,
+                "xyz",
+                &[
+                    ("a", CUR),
+                    ("b", SEARCH),
+                    ("xyz", PREVIEW),
+                    ("   c", DEFAULT),
+                ][..],
+            ),
+            (
+                "abc",
+                1,
+                (1, 1),
+                1,
+                "x",
+                &[("a", DEFAULT), ("x", PREVIEW), ("b", CUR), ("c", DEFAULT)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, cursor, (start, end), offset, preview, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.cursor_line(cursor, DEFAULT);
+            lh.search([(start, end)].into_iter(), SEARCH);
+            lh.replace_preview(offset, preview.to_string(), PREVIEW);
+            assert_spans(lh, want, test);
+        }
+    }
+
     #[test]
     fn into_spans_selection() {
         let tests = [
@@ -522,6 +871,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn into_spans_conceal() {
+        let tests = [
+            // (line, conceals, selection, want)
+            ("abc", vec![], None, &[("abc", DEFAULT)][..]),
+            ("a\\lb", vec![(1, 3, "λ")], None, &[("aλb", DEFAULT)][..]),
+            (
+                "**a**",
+                vec![(0, 2, ""), (3, 5, "")],
+                None,
+                &[("a", DEFAULT)][..],
+            ),
+            ("->\t", vec![(0, 2, "→")], None, &[("→   ", DEFAULT)][..]),
+            (
+                "x->y",
+                vec![(1, 3, "→")],
+                Some((0, 2)),
+                &[("x→", SEL), ("y", DEFAULT)][..],
+            ),
+            (
+                "x->y",
+                vec![(1, 3, "→")],
+                Some((2, 4)),
+                &[("x→", DEFAULT), ("y", SEL)][..],
+            ),
+        ];
+        for test in tests {
+            let (line, conceals, sel, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.conceal(conceals);
+            if let Some((start, end)) = sel {
+                lh.selection(0, 0, start, 0, end);
+            }
+            assert_spans(lh, want, (line, sel));
+        }
+    }
+
+    #[test]
+    fn into_spans_layers() {
+        let yellow = LINE.fg(Color::Yellow);
+        let underline = Modifier::UNDERLINED;
+        let tests = [
+            #[cfg(feature = "search")]
+            (
+                "search match partially overlapping selection",
+                {
+                    let mut lh = LineHighlighter::new("abcdef", CUR, 4, None, SEL);
+                    lh.selection(0, 0, 1, 0, 4);
+                    lh.search([(3, 5)].into_iter(), SEARCH);
+                    lh
+                },
+                &[("a", DEFAULT), ("bc", SEL), ("de", SEARCH), ("f", DEFAULT)][..],
+            ),
+            (
+                "highlights under and over selection",
+                {
+                    let mut lh = LineHighlighter::new("abcdef", CUR, 4, None, SEL);
+                    lh.cursor_line(6, LINE);
+                    lh.highlight(0, 3, 5, Style::default().fg(Color::Yellow));
+                    lh.selection(0, 0, 2, 0, 4);
+                    lh.highlight(3, 5, 35, Style::default().add_modifier(underline));
+                    lh
+                },
+                &[
+                    ("ab", yellow),
+                    ("c", SEL),
+                    ("d", SEL.add_modifier(underline)),
+                    ("e", LINE.add_modifier(underline)),
+                    ("f", LINE),
+                    (" ", CUR),
+                ][..],
+            ),
+            (
+                "same priority",
+                {
+                    let mut lh = LineHighlighter::new("abc", CUR, 4, None, SEL);
+                    lh.highlight(0, 2, 1, Style::default().fg(Color::Yellow));
+                    lh.highlight(1, 3, 1, Style::default().fg(Color::Green));
+                    lh.highlight(5, 9, 1, Style::default().fg(Color::Green));
+                    lh
+                },
+                &[
+                    ("a", DEFAULT.fg(Color::Yellow)),
+                    ("bc", DEFAULT.fg(Color::Green)),
+                ][..],
+            ),
+        ];
+
+        for (test, lh, want) in tests {
+            assert_spans(lh, want, test);
+        }
+    }
+
     #[test]
     fn into_spans_mixed_highlights() {
         let tests = [
@@ -601,4 +1043,62 @@ mod tests {
             assert_spans(lh, want, what);
         }
     }
+
+    #[test]
+    fn into_spans_whitespace() {
+        const WS: Style = Style::new().bg(Color::DarkGray);
+        let config = WhitespaceConfig {
+            tab: Some(('→', WS)),
+            trailing_space: Some(('·', WS)),
+            nbsp: Some(('⍽', WS)),
+            newline: Some(('↵', WS)),
+            eof: None,
+        };
+        let tests = [
+            // (line, cursor, eol, want)
+            ("", None, None, &[][..]),
+            ("a b", None, None, &[("a b", DEFAULT)][..]),
+            (
+                "a\tb",
+                None,
+                None,
+                &[("a", DEFAULT), ("→  ", WS), ("b", DEFAULT)][..],
+            ),
+            ("\tb", None, None, &[("→   ", WS), ("b", DEFAULT)][..]),
+            (
+                "a\u{a0}b",
+                None,
+                None,
+                &[("a", DEFAULT), ("⍽", WS), ("b", DEFAULT)][..],
+            ),
+            (
+                "a  ",
+                None,
+                None,
+                &[("a", DEFAULT), ("·", WS), ("·", WS)][..],
+            ),
+            ("a", None, Some(('↵', WS)), &[("a", DEFAULT), ("↵", WS)][..]),
+            (
+                "a",
+                Some(1),
+                Some(('↵', WS)),
+                &[("a", LINE), ("↵", CUR)][..],
+            ),
+            ("a ", Some(1), None, &[("a", LINE), ("·", CUR)][..]),
+        ];
+        for test in tests {
+            let (line, cursor, eol, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.whitespace(config, eol);
+            if let Some(col) = cursor {
+                lh.cursor_line(col, LINE);
+            }
+            assert_spans(lh, want, test);
+        }
+
+        // Whitespaces are not visualized in masked text
+        let mut lh = LineHighlighter::new("a\t ", CUR, 4, Some('*'), SEL);
+        lh.whitespace(config, Some(('↵', WS)));
+        assert_spans(lh, &[("***", DEFAULT)], "masked");
+    }
 }
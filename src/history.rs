@@ -1,7 +1,12 @@
+use crate::change::ChangeEvent;
 use crate::util::Pos;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EditKind {
     InsertChar(char),
     DeleteChar(char),
@@ -11,6 +16,8 @@ pub enum EditKind {
     DeleteStr(String),
     InsertChunk(Vec<String>),
     DeleteChunk(Vec<String>),
+    /// Checkpoint of the cursor position which does not modify the text. See `TextArea::push_cursor_checkpoint`
+    Cursor,
 }
 
 impl EditKind {
@@ -61,7 +68,7 @@ impl EditKind {
                 // Remove middle lines of chunk
                 let mut last_line = lines
                     .drain(after.row + 1..after.row + c.len())
-                    .last()
+                    .next_back()
                     .unwrap();
                 // Remove last line of chunk
                 last_line.drain(..c[c.len() - 1].len());
@@ -71,9 +78,71 @@ impl EditKind {
                 first_line.truncate(after.offset);
                 first_line.push_str(&last_line);
             }
+            EditKind::Cursor => {}
         }
     }
 
+    /// Check if [`EditKind::apply`] can apply the edit to the lines. This is used for validating the edits which were
+    /// not made on the lines, such as deserialized ones.
+    #[cfg(feature = "serde")]
+    fn can_apply(&self, lines: &[String], before: &Pos, after: &Pos) -> bool {
+        let at = |pos: &Pos| {
+            lines
+                .get(pos.row)
+                .filter(|line| line.is_char_boundary(pos.offset))
+                .map(|line| &line[pos.offset..])
+        };
+        match self {
+            EditKind::InsertChar(_) | EditKind::InsertNewline | EditKind::InsertStr(_) => {
+                at(before).is_some()
+            }
+            EditKind::InsertChunk(c) => c.len() > 1 && at(before).is_some(),
+            EditKind::DeleteChar(c) => {
+                before.row == after.row && at(after).map_or(false, |s| s.starts_with(*c))
+            }
+            EditKind::DeleteNewline => before.row > 0 && before.row < lines.len(),
+            EditKind::DeleteStr(s) => at(after).map_or(false, |t| t.starts_with(s.as_str())),
+            EditKind::DeleteChunk(c) if c.len() > 1 => {
+                let last = after.row + c.len() - 1;
+                last < lines.len()
+                    && at(after) == Some(c[0].as_str())
+                    && lines[after.row + 1..last] == c[1..c.len() - 1]
+                    && lines[last].starts_with(c[c.len() - 1].as_str())
+            }
+            EditKind::DeleteChunk(_) => false,
+            EditKind::Cursor => true,
+        }
+    }
+
+    /// Describe the edit as a change event. `before` and `after` are the same as ones passed to [`EditKind::apply`].
+    /// `None` is returned when the edit does not change the text.
+    fn change(&self, before: &Pos, after: &Pos) -> Option<ChangeEvent> {
+        use EditKind::*;
+        let text = match self {
+            InsertChar(c) | DeleteChar(c) => c.to_string(),
+            InsertNewline | DeleteNewline => "\n".to_string(),
+            InsertStr(s) | DeleteStr(s) => s.clone(),
+            InsertChunk(c) | DeleteChunk(c) => c.join("\n"),
+            Cursor => return None,
+        };
+        let before = (before.row, before.col);
+        let after = (after.row, after.col);
+        let change = match self {
+            // On deletion, the cursor moves from the end of the deleted text to its start
+            DeleteChar(_) | DeleteNewline | DeleteStr(_) | DeleteChunk(_) => ChangeEvent::Deleted {
+                start: after,
+                end: before,
+                text,
+            },
+            _ => ChangeEvent::Inserted {
+                start: before,
+                end: after,
+                text,
+            },
+        };
+        Some(change)
+    }
+
     fn invert(&self) -> Self {
         use EditKind::*;
         match self.clone() {
@@ -85,11 +154,13 @@ impl EditKind {
             DeleteStr(s) => InsertStr(s),
             InsertChunk(c) => DeleteChunk(c),
             DeleteChunk(c) => InsertChunk(c),
+            Cursor => Cursor,
         }
     }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Edit {
     kind: EditKind,
     before: Pos,
@@ -113,6 +184,26 @@ impl Edit {
         self.kind.invert().apply(lines, &self.after, &self.before); // Undo is redo of inverted edit
     }
 
+    pub fn change(&self) -> Option<ChangeEvent> {
+        self.kind.change(&self.before, &self.after)
+    }
+
+    #[cfg(feature = "serde")]
+    fn can_redo(&self, lines: &[String]) -> bool {
+        self.kind.can_apply(lines, &self.before, &self.after)
+    }
+
+    #[cfg(feature = "serde")]
+    fn can_undo(&self, lines: &[String]) -> bool {
+        self.kind
+            .invert()
+            .can_apply(lines, &self.after, &self.before)
+    }
+
+    pub fn undo_change(&self) -> Option<ChangeEvent> {
+        self.kind.invert().change(&self.after, &self.before)
+    }
+
     pub fn cursor_before(&self) -> (usize, usize) {
         (self.before.row, self.before.col)
     }
@@ -122,11 +213,66 @@ impl Edit {
     }
 }
 
+/// Specify how consecutive edits are merged into one undo step. See [`TextArea::set_undo_coalescing`] for the usage.
+///
+/// Only consecutive insertions or deletions of a single character at the cursor position (in other words, typing
+/// characters or hitting backspace repeatedly) are merged. Other edits such as pasting text or moving the cursor
+/// between edits always start a new undo step.
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+///
+/// [`TextArea::set_undo_coalescing`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_undo_coalescing
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UndoCoalescing {
+    /// Every edit is a separate undo step. This is the default.
+    Never,
+    /// Consecutive edits are merged into one undo step while the interval between them is within the duration.
+    Time(Duration),
+    /// Consecutive edits are merged into one undo step until a word boundary. A word and the whitespaces following it
+    /// are undone at once.
+    Word,
+}
+
+impl Default for UndoCoalescing {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+impl UndoCoalescing {
+    fn should_merge(&self, prev: &Edit, next: &Edit, elapsed: Duration) -> bool {
+        let (prev_char, next_char) = match (&prev.kind, &next.kind) {
+            (EditKind::InsertChar(p), EditKind::InsertChar(n))
+            | (EditKind::DeleteChar(p), EditKind::DeleteChar(n)) => (*p, *n),
+            _ => return false,
+        };
+        if prev.cursor_after() != next.cursor_before() {
+            return false;
+        }
+        match self {
+            Self::Never => false,
+            Self::Time(d) => elapsed <= *d,
+            Self::Word => !prev_char.is_whitespace() || next_char.is_whitespace(),
+        }
+    }
+}
+
+// Groups in progress are not serialized since they are closed before the text is saved
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct History {
     index: usize,
     max_items: usize,
-    edits: VecDeque<Edit>,
+    edits: VecDeque<Vec<Edit>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    group_depth: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    group_started: bool,
+    coalescing: UndoCoalescing,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_pushed: Option<Instant>,
 }
 
 impl History {
@@ -135,6 +281,10 @@ impl History {
             index: 0,
             max_items,
             edits: VecDeque::new(),
+            group_depth: 0,
+            group_started: false,
+            coalescing: UndoCoalescing::default(),
+            last_pushed: None,
         }
     }
 
@@ -143,6 +293,29 @@ impl History {
             return;
         }
 
+        let now = Instant::now();
+        let elapsed = self
+            .last_pushed
+            .map(|t| now.duration_since(t))
+            .unwrap_or(Duration::MAX);
+        self.last_pushed = Some(now);
+
+        if self.index == self.edits.len() {
+            if let Some(group) = self.edits.back_mut() {
+                // The first edit in a group always starts a new step
+                let merge = if self.group_depth > 0 {
+                    self.group_started
+                } else {
+                    self.coalescing
+                        .should_merge(group.last().unwrap(), &edit, elapsed)
+                };
+                if merge {
+                    group.push(edit);
+                    return;
+                }
+            }
+        }
+
         if self.edits.len() == self.max_items {
             self.edits.pop_front();
             self.index = self.index.saturating_sub(1);
@@ -153,29 +326,145 @@ impl History {
         }
 
         self.index += 1;
-        self.edits.push_back(edit);
+        self.edits.push_back(vec![edit]);
+        self.group_started = self.group_depth > 0;
+    }
+
+    /// Push the cursor position as an undo step. Nothing is pushed when the last undo step is a checkpoint at the same
+    /// position.
+    pub fn push_checkpoint(&mut self, (row, col): (usize, usize)) {
+        if self.index == self.edits.len() {
+            if let Some([edit]) = self.edits.back().map(Vec::as_slice) {
+                if matches!(edit.kind, EditKind::Cursor) && edit.cursor_before() == (row, col) {
+                    return;
+                }
+            }
+        }
+        let pos = Pos::new(row, col, 0);
+        self.push(Edit::new(EditKind::Cursor, pos.clone(), pos));
+    }
+
+    pub fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        if self.group_depth == 0 {
+            self.close_group();
+        }
+    }
+
+    fn close_group(&mut self) {
+        self.group_depth = 0;
+        self.group_started = false;
+        self.last_pushed = None; // Don't merge the next edit into the group
     }
 
     pub fn redo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+        self.close_group();
         if self.index == self.edits.len() {
             return None;
         }
-        let edit = &self.edits[self.index];
-        edit.redo(lines);
+        let group = &self.edits[self.index];
+        for edit in group {
+            edit.redo(lines);
+        }
         self.index += 1;
-        Some(edit.cursor_after())
+        group.last().map(Edit::cursor_after)
     }
 
-    pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+    /// Undo the last undo step. `cursor` is the current cursor position, where redo of a checkpoint moves the cursor.
+    pub fn undo(
+        &mut self,
+        lines: &mut Vec<String>,
+        (row, col): (usize, usize),
+    ) -> Option<(usize, usize)> {
+        self.close_group();
         self.index = self.index.checked_sub(1)?;
-        let edit = &self.edits[self.index];
-        edit.undo(lines);
-        Some(edit.cursor_before())
+        if let [edit] = self.edits[self.index].as_mut_slice() {
+            if matches!(edit.kind, EditKind::Cursor) {
+                edit.after = Pos::new(row, col, 0);
+            }
+        }
+        let group = &self.edits[self.index];
+        for edit in group.iter().rev() {
+            edit.undo(lines);
+        }
+        group.first().map(Edit::cursor_before)
+    }
+
+    /// Changes applied by the last [`History::undo`] call in the applied order.
+    pub fn undone_changes(&self) -> Vec<ChangeEvent> {
+        self.edits.get(self.index).map_or(vec![], |group| {
+            group.iter().rev().filter_map(Edit::undo_change).collect()
+        })
+    }
+
+    /// Changes applied by the last [`History::redo`] call in the applied order.
+    pub fn redone_changes(&self) -> Vec<ChangeEvent> {
+        self.index
+            .checked_sub(1)
+            .and_then(|i| self.edits.get(i))
+            .map_or(vec![], |group| {
+                group.iter().filter_map(Edit::change).collect()
+            })
+    }
+
+    /// Check if all undo steps can be undone and redone on the lines without breaking the text or the cursor. `lines`
+    /// must be the text at the current position in the history. This is used for validating the deserialized history.
+    #[cfg(feature = "serde")]
+    pub fn is_consistent_with(&self, lines: &[String]) -> bool {
+        if self.index > self.edits.len() || self.edits.iter().any(Vec::is_empty) {
+            return false;
+        }
+        let valid = |lines: &[String], (row, col): (usize, usize)| {
+            lines.get(row).map_or(false, |l| col <= l.chars().count())
+        };
+
+        let mut text = lines.to_vec();
+        for group in self.edits.range(..self.index).rev() {
+            for edit in group.iter().rev() {
+                if !edit.can_undo(&text) {
+                    return false;
+                }
+                edit.undo(&mut text);
+            }
+            if !valid(&text, group[0].cursor_before()) {
+                return false;
+            }
+        }
+        for group in &self.edits {
+            for edit in group {
+                if !edit.can_redo(&text) {
+                    return false;
+                }
+                edit.redo(&mut text);
+            }
+            if !valid(&text, group[group.len() - 1].cursor_after()) {
+                return false;
+            }
+        }
+        // Undo the steps after the current position to go back to the given lines
+        for group in self.edits.range(self.index..).rev() {
+            for edit in group.iter().rev() {
+                edit.undo(&mut text);
+            }
+        }
+        text == lines
     }
 
     pub fn max_items(&self) -> usize {
         self.max_items
     }
+
+    pub fn coalescing(&self) -> UndoCoalescing {
+        self.coalescing
+    }
+
+    pub fn set_coalescing(&mut self, coalescing: UndoCoalescing) {
+        self.coalescing = coalescing;
+    }
 }
 
 #[cfg(test)]
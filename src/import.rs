@@ -0,0 +1,626 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+
+/// Format of the rich text imported by [`TextArea::insert_rich_text`].
+///
+/// This type is marked as `#[non_exhaustive]` since more formats may be supported in the future.
+///
+/// [`TextArea::insert_rich_text`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.insert_rich_text
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RichTextFormat {
+    /// Simple HTML such as a fragment copied from a browser. Whitespaces are collapsed except in `<pre>`, and block
+    /// elements like `<p>` and `<div>` and `<br>` break lines. Emphasis elements (`<b>`, `<i>`, `<u>`, `<s>`, ...) and
+    /// `style` attributes with colors, `font-weight`, `font-style` and `text-decoration` are converted into styles.
+    Html,
+    /// Markdown with emphasis (`**bold**`, `*italic*`, `~~strike~~`) and inline HTML elements described in
+    /// [`RichTextFormat::Html`]. Newlines are kept as they are, and the text of code spans is not interpreted.
+    Markdown,
+}
+
+/// Styled range of `(start, end, style)`. Positions are `(row, col)` in the text.
+type StyledRange = ((usize, usize), (usize, usize), Style);
+
+/// Plain text converted from rich text with its styled ranges.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RichText {
+    pub text: String,
+    pub styles: Vec<StyledRange>,
+}
+
+#[derive(Default)]
+struct Builder {
+    out: RichText,
+    row: usize,
+    col: usize,
+    run: Option<((usize, usize), Style)>,
+    pending_breaks: usize,
+    pending_space: Option<Style>,
+}
+
+impl Builder {
+    fn end_run(&mut self) {
+        if let Some((start, style)) = self.run.take() {
+            if style != Style::default() {
+                self.out.styles.push((start, (self.row, self.col), style));
+            }
+        }
+    }
+
+    fn push_char(&mut self, c: char, style: Style) {
+        if c == '\n' {
+            self.end_run();
+            self.out.text.push('\n');
+            self.row += 1;
+            self.col = 0;
+            return;
+        }
+        if self.run.map_or(true, |(_, s)| s != style) {
+            self.end_run();
+            self.run = Some(((self.row, self.col), style));
+        }
+        self.out.text.push(c);
+        self.col += 1;
+    }
+
+    /// Push the text collapsing whitespaces into one space, for HTML outside `<pre>`.
+    fn push_collapsed(&mut self, c: char, style: Style) {
+        if c.is_whitespace() {
+            if self.col > 0 {
+                self.pending_space.get_or_insert(style);
+            }
+            return;
+        }
+        self.flush_pending();
+        self.push_char(c, style);
+    }
+
+    /// Push the line breaks and the space deferred until the next character so that no empty line or space trails.
+    fn flush_pending(&mut self) {
+        if self.pending_breaks > 0 {
+            for _ in 0..self.pending_breaks {
+                self.push_char('\n', Style::default());
+            }
+            self.pending_breaks = 0;
+            self.pending_space = None; // Leading spaces of lines are not shown
+        }
+        if let Some(style) = self.pending_space.take() {
+            self.push_char(' ', style);
+        }
+    }
+
+    /// Break the line at the boundary of a block element unless the line is empty.
+    fn block_break(&mut self) {
+        if self.col > 0 && self.pending_breaks == 0 {
+            self.pending_breaks = 1;
+        }
+        self.pending_space = None;
+    }
+
+    fn finish(mut self) -> RichText {
+        self.end_run();
+        self.out
+    }
+}
+
+/// Stack of the open elements and their styles. The current style is all the styles patched in order.
+#[derive(Default)]
+struct StyleStack(Vec<(String, Style)>);
+
+impl StyleStack {
+    fn push(&mut self, name: &str, style: Style) {
+        self.0.push((name.to_string(), style));
+    }
+
+    /// Close the innermost element of the name. Elements which are not closed explicitly are closed together.
+    fn pop(&mut self, name: &str) {
+        if let Some(i) = self.0.iter().rposition(|(n, _)| n == name) {
+            self.0.truncate(i);
+        }
+    }
+
+    fn current(&self) -> Style {
+        self.0
+            .iter()
+            .fold(Style::default(), |acc, (_, s)| acc.patch(*s))
+    }
+}
+
+struct Tag<'a> {
+    name: String,
+    closing: bool,
+    attrs: &'a str,
+}
+
+/// Parse the tag after `<`. Returns the tag and the length of the source consumed including the closing `>`. Comments
+/// and declarations like `<!DOCTYPE html>` are returned as a tag without name.
+fn parse_tag(src: &str) -> Option<(Tag<'_>, usize)> {
+    if let Some(rest) = src.strip_prefix("!--") {
+        let len = rest.find("-->").map_or(src.len(), |i| i + 6);
+        let tag = Tag {
+            name: String::new(),
+            closing: false,
+            attrs: "",
+        };
+        return Some((tag, len));
+    }
+
+    let (closing, body) = match src.strip_prefix('/') {
+        Some(body) => (true, body),
+        None => (false, src),
+    };
+    let first = body.chars().next()?;
+    if !first.is_ascii_alphabetic() && first != '!' {
+        return None; // Not a tag such as `a < b`
+    }
+
+    let mut quote = None;
+    let mut end = None;
+    for (i, c) in src.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => {
+                end = Some(i);
+                break;
+            }
+            (None, _) => {}
+        }
+    }
+    let end = end?;
+
+    let inner = &body[..end - (src.len() - body.len())];
+    let name_len = inner
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(inner.len());
+    let tag = Tag {
+        name: inner[..name_len].to_ascii_lowercase(),
+        closing,
+        attrs: inner[name_len..].trim_end_matches('/'),
+    };
+    Some((tag, end + 1))
+}
+
+/// Get the value of the attribute from the attributes of a tag.
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start();
+        let key_len = rest.find(|c: char| c == '=' || c.is_whitespace())?;
+        let key = &rest[..key_len];
+        rest = rest[key_len..].trim_start();
+        rest = match rest.strip_prefix('=') {
+            Some(after_eq) => after_eq.trim_start(),
+            None => continue, // Attribute without value
+        };
+        let value = match rest.chars().next() {
+            Some(q @ ('"' | '\'')) => {
+                let end = rest[1..].find(q).map_or(rest.len(), |i| i + 1);
+                let value = &rest[1..end];
+                rest = rest.get(end + 1..).unwrap_or("");
+                value
+            }
+            _ => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let value = &rest[..end];
+                rest = &rest[end..];
+                value
+            }
+        };
+        if key.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_ascii_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        let digit = |i: usize, n: usize| u8::from_str_radix(hex.get(i..i + n)?, 16).ok();
+        return match hex.len() {
+            3 => Some(Color::Rgb(
+                digit(0, 1)? * 17,
+                digit(1, 1)? * 17,
+                digit(2, 1)? * 17,
+            )),
+            6 => Some(Color::Rgb(digit(0, 2)?, digit(2, 2)?, digit(4, 2)?)),
+            _ => None,
+        };
+    }
+    if let Some(args) = value
+        .strip_prefix("rgb(")
+        .or_else(|| value.strip_prefix("rgba("))
+    {
+        let mut args = args.trim_end_matches(')').split(',');
+        let mut next = || args.next()?.trim().parse::<u8>().ok();
+        return Some(Color::Rgb(next()?, next()?, next()?));
+    }
+    let color = match value.as_str() {
+        "black" => Color::Black,
+        "red" | "maroon" => Color::Red,
+        "green" | "lime" => Color::Green,
+        "yellow" | "olive" => Color::Yellow,
+        "blue" | "navy" => Color::Blue,
+        "magenta" | "fuchsia" | "purple" => Color::Magenta,
+        "cyan" | "aqua" | "teal" => Color::Cyan,
+        "gray" | "grey" | "silver" => Color::Gray,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Convert the inline CSS of `style` attribute into a style. Unsupported properties are ignored.
+fn parse_css(css: &str) -> Style {
+    let mut style = Style::default();
+    for decl in css.split(';') {
+        let (prop, value) = match decl.split_once(':') {
+            Some(kv) => kv,
+            None => continue,
+        };
+        let value = value.trim().to_ascii_lowercase();
+        match prop.trim().to_ascii_lowercase().as_str() {
+            "color" => {
+                if let Some(c) = parse_color(&value) {
+                    style = style.fg(c);
+                }
+            }
+            "background-color" | "background" => {
+                if let Some(c) = parse_color(&value) {
+                    style = style.bg(c);
+                }
+            }
+            "font-weight" => {
+                let bold = value == "bold"
+                    || value == "bolder"
+                    || value.parse::<u16>().map_or(false, |w| w >= 600);
+                if bold {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+            }
+            "font-style" if value == "italic" || value == "oblique" => {
+                style = style.add_modifier(Modifier::ITALIC);
+            }
+            "text-decoration" | "text-decoration-line" => {
+                if value.contains("underline") {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                if value.contains("line-through") {
+                    style = style.add_modifier(Modifier::CROSSED_OUT);
+                }
+            }
+            _ => {}
+        }
+    }
+    style
+}
+
+/// Style of the element. `None` for elements which are not styled.
+fn element_style(tag: &Tag<'_>) -> Option<Style> {
+    let modifier = match tag.name.as_str() {
+        "b" | "strong" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => Modifier::BOLD,
+        "i" | "em" | "cite" | "var" => Modifier::ITALIC,
+        "u" | "ins" => Modifier::UNDERLINED,
+        "s" | "strike" | "del" => Modifier::CROSSED_OUT,
+        "mark" => Modifier::REVERSED,
+        _ => Modifier::empty(),
+    };
+    let mut style = Style::default().add_modifier(modifier);
+    if tag.name == "font" {
+        if let Some(c) = attr(tag.attrs, "color").and_then(parse_color) {
+            style = style.fg(c);
+        }
+    }
+    if let Some(css) = attr(tag.attrs, "style") {
+        style = style.patch(parse_css(css));
+    }
+    let styled = style != Style::default()
+        || matches!(
+            tag.name.as_str(),
+            "span" | "font" | "code" | "a" | "small" | "sub" | "sup"
+        );
+    styled.then(|| style)
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div"
+            | "pre"
+            | "li"
+            | "ul"
+            | "ol"
+            | "tr"
+            | "table"
+            | "blockquote"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "hr"
+            | "section"
+            | "article"
+            | "header"
+            | "footer"
+    )
+}
+
+/// Decode the character reference after `&`. Returns the character and the length consumed including `;`.
+fn parse_entity(src: &str) -> Option<(char, usize)> {
+    let end = src.find(';').filter(|&i| i <= 10)?;
+    let name = &src[..end];
+    let c = match name {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((c, end + 1))
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    markdown: bool,
+    builder: Builder,
+    stack: StyleStack,
+    pre: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn push(&mut self, c: char) {
+        let style = self.stack.current();
+        if self.markdown {
+            self.builder.push_char(c, style);
+        } else if self.pre > 0 {
+            if c == '\n' {
+                self.builder.pending_breaks += 1;
+            } else {
+                self.builder.flush_pending();
+                self.builder.push_char(c, style);
+            }
+        } else {
+            self.builder.push_collapsed(c, style);
+        }
+    }
+
+    fn handle_tag(&mut self, tag: Tag<'_>) {
+        if tag.name.is_empty() {
+            return;
+        }
+        if tag.name == "script" || tag.name == "style" {
+            if !tag.closing {
+                // Skip the content
+                let close = format!("</{}", tag.name);
+                let rest = self.rest().to_ascii_lowercase();
+                let end = rest.find(&close).unwrap_or(rest.len());
+                self.pos += end;
+            }
+            return;
+        }
+        if tag.name == "br" {
+            if self.markdown {
+                self.push('\n');
+            } else {
+                self.builder.pending_breaks += 1;
+            }
+            return;
+        }
+        if !self.markdown && is_block(&tag.name) {
+            self.builder.block_break();
+            if tag.name == "pre" {
+                if tag.closing {
+                    self.pre = self.pre.saturating_sub(1);
+                } else {
+                    self.pre += 1;
+                }
+            }
+        }
+        if tag.closing {
+            self.stack.pop(&tag.name);
+            return;
+        }
+        if tag.name == "li" {
+            self.builder.flush_pending();
+            self.builder.push_char('-', Style::default());
+            self.builder.pending_space = Some(Style::default());
+        }
+        if let Some(style) = element_style(&tag) {
+            self.stack.push(&tag.name, style);
+        }
+    }
+
+    /// Toggle the Markdown emphasis of the delimiter. The emphasis is opened only when it is closed on the same line.
+    fn handle_emphasis(&mut self, delim: &str, modifier: Modifier) -> bool {
+        let name = format!("md{}", delim);
+        let open = self.stack.0.iter().any(|(n, _)| *n == name);
+        let after = &self.rest()[delim.len()..];
+        if open {
+            self.stack.pop(&name);
+        } else {
+            let line = &after[..after.find('\n').unwrap_or(after.len())];
+            let closed = !after.starts_with(char::is_whitespace)
+                && line.find(delim).map_or(false, |i| i > 0);
+            if !closed {
+                return false;
+            }
+            self.stack
+                .push(&name, Style::default().add_modifier(modifier));
+        }
+        self.pos += delim.len();
+        true
+    }
+
+    fn handle_markdown(&mut self) -> bool {
+        let rest = self.rest();
+        if let Some(after) = rest.strip_prefix('\\') {
+            if let Some(c) = after.chars().next().filter(|c| c.is_ascii_punctuation()) {
+                self.pos += 1 + c.len_utf8();
+                self.push(c);
+                return true;
+            }
+            return false;
+        }
+        if let Some(after) = rest.strip_prefix('`') {
+            let end = match after.find('`') {
+                Some(end) => end,
+                None => return false,
+            };
+            self.pos += end + 2;
+            for c in after[..end].chars() {
+                self.push(c);
+            }
+            return true;
+        }
+        let prev_alnum = self.src[..self.pos]
+            .chars()
+            .next_back()
+            .map_or(false, char::is_alphanumeric);
+        for (delim, modifier) in [
+            ("**", Modifier::BOLD),
+            ("__", Modifier::BOLD),
+            ("~~", Modifier::CROSSED_OUT),
+            ("*", Modifier::ITALIC),
+            ("_", Modifier::ITALIC),
+        ] {
+            if !rest.starts_with(delim) {
+                continue;
+            }
+            // Underscores in the middle of words like `snake_case` are not emphasis
+            if delim.starts_with('_') && prev_alnum {
+                return false;
+            }
+            return self.handle_emphasis(delim, modifier);
+        }
+        false
+    }
+
+    fn parse(mut self) -> RichText {
+        while let Some(c) = self.rest().chars().next() {
+            if self.markdown && self.handle_markdown() {
+                continue;
+            }
+            match c {
+                '<' => {
+                    if let Some((tag, len)) = parse_tag(&self.rest()[1..]) {
+                        self.pos += len + 1;
+                        self.handle_tag(tag);
+                        continue;
+                    }
+                }
+                '&' => {
+                    if let Some((c, len)) = parse_entity(&self.rest()[1..]) {
+                        self.pos += len + 1;
+                        self.push(c);
+                        continue;
+                    }
+                }
+                '\r' if self.rest().starts_with("\r\n") => {
+                    self.pos += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            self.pos += c.len_utf8();
+            self.push(c);
+        }
+        self.builder.finish()
+    }
+}
+
+/// Convert the rich text into plain text and the styles of its ranges.
+pub fn parse_rich_text(src: &str, format: RichTextFormat) -> RichText {
+    Parser {
+        src,
+        pos: 0,
+        markdown: format == RichTextFormat::Markdown,
+        builder: Builder::default(),
+        stack: StyleStack::default(),
+        pre: 0,
+    }
+    .parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bold() -> Style {
+        Style::default().add_modifier(Modifier::BOLD)
+    }
+
+    fn italic() -> Style {
+        Style::default().add_modifier(Modifier::ITALIC)
+    }
+
+    #[test]
+    fn html() {
+        let src = "<p>Hello,\n  <b>bold <i>and</i></b> &lt;plain&gt;</p><!-- comment --><div>x<br>y</div>";
+        let rich = parse_rich_text(src, RichTextFormat::Html);
+        assert_eq!(rich.text, "Hello, bold and <plain>\nx\ny");
+        assert_eq!(
+            rich.styles,
+            [
+                ((0, 7), (0, 12), bold()),
+                ((0, 12), (0, 15), bold().patch(italic())),
+            ],
+        );
+
+        let src = "<pre class=\"tui-textarea\"><span style=\"color:#ff0000;font-weight:bold\">a  b</span>\n\nc\n</pre>";
+        let rich = parse_rich_text(src, RichTextFormat::Html);
+        assert_eq!(rich.text, "a  b\n\nc");
+        let style = Style::default()
+            .fg(Color::Rgb(255, 0, 0))
+            .add_modifier(Modifier::BOLD);
+        assert_eq!(rich.styles, [((0, 0), (0, 4), style)]);
+
+        let src =
+            "<ul><li>one</li><li><font color='blue'>two</font></li></ul><style>p {}</style>a < b";
+        let rich = parse_rich_text(src, RichTextFormat::Html);
+        assert_eq!(rich.text, "- one\n- two\na < b");
+        let style = Style::default().fg(Color::Blue);
+        assert_eq!(rich.styles, [((1, 2), (1, 5), style)]);
+    }
+
+    #[test]
+    fn markdown() {
+        let src = "**bold** and *italic*\nsnake_case `*code*` \\*2 * 3\n<u>~~x~~</u>";
+        let rich = parse_rich_text(src, RichTextFormat::Markdown);
+        assert_eq!(rich.text, "bold and italic\nsnake_case *code* *2 * 3\nx");
+        let underline = Style::default().add_modifier(Modifier::UNDERLINED);
+        let strike = underline.add_modifier(Modifier::CROSSED_OUT);
+        assert_eq!(
+            rich.styles,
+            [
+                ((0, 0), (0, 4), bold()),
+                ((0, 9), (0, 15), italic()),
+                ((2, 0), (2, 1), strike),
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_attrs() {
+        assert_eq!(attr(" a=1 style=\"x: y\" b", "style"), Some("x: y"));
+        assert_eq!(attr(" a b=c", "b"), Some("c"));
+        assert_eq!(attr(" a", "a"), None);
+        assert_eq!(parse_color("#0f0"), Some(Color::Rgb(0, 255, 0)));
+        assert_eq!(parse_color("rgb(1, 2, 3)"), Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(parse_color("transparent"), None);
+    }
+}
@@ -1,6 +1,6 @@
 use super::{Input, Key};
 use crate::crossterm::event::{
-    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 
 impl From<Event> for Input {
@@ -27,6 +27,7 @@ impl From<KeyCode> for Key {
             KeyCode::Down => Key::Down,
             KeyCode::Tab => Key::Tab,
             KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
             KeyCode::Home => Key::Home,
             KeyCode::End => Key::End,
             KeyCode::PageUp => Key::PageUp,
@@ -75,7 +76,11 @@ impl From<MouseEventKind> for Key {
 impl From<MouseEvent> for Input {
     /// Convert [`crossterm::event::MouseEvent`] into [`Input`].
     fn from(mouse: MouseEvent) -> Self {
-        let key = Key::from(mouse.kind);
+        let key = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => Key::MouseClick(mouse.column, mouse.row),
+            MouseEventKind::Drag(MouseButton::Left) => Key::MouseDrag(mouse.column, mouse.row),
+            kind => Key::from(kind),
+        };
         let ctrl = mouse.modifiers.contains(KeyModifiers::CONTROL);
         let alt = mouse.modifiers.contains(KeyModifiers::ALT);
         let shift = mouse.modifiers.contains(KeyModifiers::SHIFT);
@@ -181,6 +186,24 @@ mod tests {
                 mouse_event(MouseEventKind::Moved, KeyModifiers::CONTROL),
                 input(Key::Null, true, false, false),
             ),
+            (
+                mouse_event(
+                    MouseEventKind::Down(MouseButton::Left),
+                    KeyModifiers::empty(),
+                ),
+                input(Key::MouseClick(1, 1), false, false, false),
+            ),
+            (
+                mouse_event(MouseEventKind::Drag(MouseButton::Left), KeyModifiers::SHIFT),
+                input(Key::MouseDrag(1, 1), false, false, true),
+            ),
+            (
+                mouse_event(
+                    MouseEventKind::Down(MouseButton::Right),
+                    KeyModifiers::empty(),
+                ),
+                input(Key::Null, false, false, false),
+            ),
         ] {
             assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
         }
@@ -9,6 +9,7 @@ mod termwiz;
 use arbitrary::Arbitrary;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Backend-agnostic key input kind.
 ///
@@ -38,6 +39,8 @@ pub enum Key {
     Tab,
     /// Delete key
     Delete,
+    /// Insert key
+    Insert,
     /// Home key
     Home,
     /// End key
@@ -58,6 +61,12 @@ pub enum Key {
     MouseScrollDown,
     /// Virtual key to scroll up by mouse
     MouseScrollUp,
+    /// Virtual key to press the left mouse button at the 0-based (column, row) cell on the screen. This key is
+    /// supported by crossterm and termion only
+    MouseClick(u16, u16),
+    /// Virtual key to drag the mouse with the left button pressed to the 0-based (column, row) cell on the screen.
+    /// This key is supported by crossterm and termion only
+    MouseDrag(u16, u16),
     /// An invalid key input (this key is always ignored by [`TextArea`](crate::TextArea))
     Null,
 }
@@ -117,6 +126,38 @@ pub struct Input {
     pub shift: bool,
 }
 
+/// Format the input in the notation like `C-x`, `M-Enter` or `S-Tab`, which is useful to show key bindings to users.
+/// Shift is omitted for characters since the character is already shifted.
+/// ```
+/// use tui_textarea::{Input, Key};
+///
+/// let input = Input { key: Key::Char('x'), ctrl: true, ..Default::default() };
+/// assert_eq!(input.to_string(), "C-x");
+/// let input = Input { key: Key::Tab, shift: true, ..Default::default() };
+/// assert_eq!(input.to_string(), "S-Tab");
+/// ```
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("C-")?;
+        }
+        if self.alt {
+            f.write_str("M-")?;
+        }
+        if self.shift && !matches!(self.key, Key::Char(_)) {
+            f.write_str("S-")?;
+        }
+        match self.key {
+            Key::Char(' ') => f.write_str("Space"),
+            Key::Char(c) => write!(f, "{}", c),
+            Key::F(n) => write!(f, "F{}", n),
+            Key::MouseClick(x, y) => write!(f, "MouseClick({},{})", x, y),
+            Key::MouseDrag(x, y) => write!(f, "MouseDrag({},{})", x, y),
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +185,18 @@ mod tests {
         let mut u = arbitrary::Unstructured::new(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
         Key::arbitrary(&mut u).unwrap();
     }
+
+    #[test]
+    fn display_input() {
+        for (i, expected) in [
+            (input(Key::Char('a'), false, false, false), "a"),
+            (input(Key::Char('A'), false, false, true), "A"),
+            (input(Key::Char(' '), true, false, false), "C-Space"),
+            (input(Key::Enter, false, true, false), "M-Enter"),
+            (input(Key::F(5), true, true, true), "C-M-S-F5"),
+            (input(Key::PageDown, false, false, false), "PageDown"),
+        ] {
+            assert_eq!(i.to_string(), expected, "{:?}", i);
+        }
+    }
 }
@@ -71,6 +71,7 @@ impl From<KeyEvent> for Input {
             KeyEvent::PageDown => Key::PageDown,
             KeyEvent::BackTab => Key::Tab,
             KeyEvent::Delete => Key::Delete,
+            KeyEvent::Insert => Key::Insert,
             KeyEvent::Esc => Key::Esc,
             KeyEvent::F(x) => Key::F(x),
             _ => Key::Null,
@@ -91,6 +92,7 @@ impl From<KeyEvent> for Input {
             KeyEvent::PageDown => Key::PageDown,
             KeyEvent::BackTab => Key::Tab,
             KeyEvent::Delete => Key::Delete,
+            KeyEvent::Insert => Key::Insert,
             KeyEvent::Esc => Key::Esc,
             KeyEvent::F(x) => Key::F(x),
             _ => Key::Null,
@@ -119,10 +121,14 @@ impl From<MouseButton> for Key {
 impl From<MouseEvent> for Input {
     /// Convert [`termion::event::MouseEvent`] into [`Input`].
     fn from(mouse: MouseEvent) -> Self {
-        let key = if let MouseEvent::Press(button, ..) = mouse {
-            Key::from(button)
-        } else {
-            Key::Null
+        // Note: Positions reported by termion are 1-based
+        let key = match mouse {
+            MouseEvent::Press(MouseButton::Left, x, y) => {
+                Key::MouseClick(x.saturating_sub(1), y.saturating_sub(1))
+            }
+            MouseEvent::Press(button, ..) => Key::from(button),
+            MouseEvent::Hold(x, y) => Key::MouseDrag(x.saturating_sub(1), y.saturating_sub(1)),
+            MouseEvent::Release(..) => Key::Null,
         };
         Self {
             key,
@@ -184,6 +190,10 @@ mod tests {
             ),
             (
                 MouseEvent::Press(MouseButton::Left, 1, 1),
+                input(Key::MouseClick(0, 0), false, false, false),
+            ),
+            (
+                MouseEvent::Press(MouseButton::Right, 1, 1),
                 input(Key::Null, false, false, false),
             ),
             (
@@ -191,8 +201,8 @@ mod tests {
                 input(Key::Null, false, false, false),
             ),
             (
-                MouseEvent::Hold(1, 1),
-                input(Key::Null, false, false, false),
+                MouseEvent::Hold(3, 2),
+                input(Key::MouseDrag(2, 1), false, false, false),
             ),
         ] {
             assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
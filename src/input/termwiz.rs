@@ -33,6 +33,7 @@ impl From<KeyCode> for Key {
             KeyCode::UpArrow => Key::Up,
             KeyCode::DownArrow => Key::Down,
             KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
             KeyCode::Function(x) => Key::F(x),
             KeyCode::Copy => Key::Copy,
             KeyCode::Cut => Key::Cut,
@@ -0,0 +1,135 @@
+use crate::word::{find_word_start_forward, WordClass};
+
+const LABEL_CHARS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// Result of typing a character of a jump label.
+#[derive(Debug, PartialEq, Eq)]
+pub enum JumpInput {
+    /// The label was completed. The cursor should jump to the position.
+    Jump((usize, usize)),
+    /// More characters are necessary to identify the label.
+    Pending,
+    /// No label starts with the typed characters.
+    NoMatch,
+}
+
+/// Labels put on positions in the text to jump the cursor by typing the label like easymotion of Vim.
+#[derive(Clone, Debug)]
+pub struct JumpLabels {
+    targets: Vec<(String, (usize, usize))>,
+    typed: String,
+}
+
+impl JumpLabels {
+    /// Assign labels to the positions. When there are more positions than available labels, the rest of positions
+    /// are ignored.
+    pub fn new(positions: Vec<(usize, usize)>) -> Self {
+        let labels = generate_labels(positions.len());
+        let targets = labels.into_iter().zip(positions).collect();
+        Self {
+            targets,
+            typed: String::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Labels which start with the typed characters. Each element is a pair of the rest of the label which is not
+    /// typed yet and the position.
+    pub fn remaining(&self) -> impl Iterator<Item = (&str, (usize, usize))> {
+        self.targets.iter().filter_map(move |(label, pos)| {
+            label
+                .strip_prefix(self.typed.as_str())
+                .map(|rest| (rest, *pos))
+        })
+    }
+
+    pub fn type_char(&mut self, c: char) -> JumpInput {
+        self.typed.push(c);
+        // All labels have the same length so a completed label is unique
+        match self.remaining().next() {
+            Some(("", pos)) => JumpInput::Jump(pos),
+            Some(_) => JumpInput::Pending,
+            None => JumpInput::NoMatch,
+        }
+    }
+}
+
+/// Generate `n` labels at most. When `n` is larger than the number of label characters, all labels have two
+/// characters.
+fn generate_labels(n: usize) -> Vec<String> {
+    let chars = LABEL_CHARS.chars();
+    if n <= LABEL_CHARS.len() {
+        return chars.take(n).map(String::from).collect();
+    }
+    chars
+        .flat_map(|a| LABEL_CHARS.chars().map(move |b| [a, b].iter().collect()))
+        .take(n)
+        .collect()
+}
+
+/// Columns of the starts of words in the line.
+pub fn word_starts(line: &str) -> Vec<usize> {
+    let mut starts = vec![];
+    let mut col = match line.chars().next() {
+        Some(c) if !c.is_whitespace() => 0,
+        Some(_) => match find_word_start_forward(line, 0, WordClass::default()) {
+            Some(col) => col,
+            None => return starts,
+        },
+        None => return starts,
+    };
+    loop {
+        starts.push(col);
+        col = match find_word_start_forward(line, col, WordClass::default()) {
+            Some(col) => col,
+            None => return starts,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels() {
+        assert_eq!(generate_labels(0), Vec::<String>::new());
+        assert_eq!(generate_labels(3), ["a", "s", "d"]);
+        let labels = generate_labels(30);
+        assert_eq!(labels.len(), 30);
+        assert_eq!(&labels[..3], ["aa", "as", "ad"]);
+        assert_eq!(labels[26], "sa");
+        assert_eq!(generate_labels(1000).len(), 26 * 26);
+    }
+
+    #[test]
+    fn word_start_columns() {
+        for (line, want) in [
+            ("", vec![]),
+            ("   ", vec![]),
+            ("foo", vec![0]),
+            ("foo bar", vec![0, 4]),
+            ("  foo.bar(1)", vec![2, 5, 6, 9, 10, 11]),
+        ] {
+            assert_eq!(word_starts(line), want, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn type_label() {
+        let positions = (0..30).map(|i| (i, 0)).collect();
+        let mut labels = JumpLabels::new(positions);
+        assert_eq!(labels.type_char('s'), JumpInput::Pending);
+        let remaining: Vec<_> = labels.remaining().take(2).collect();
+        assert_eq!(remaining, [("a", (26, 0)), ("s", (27, 0))]);
+        assert_eq!(labels.type_char('s'), JumpInput::Jump((27, 0)));
+
+        let mut labels = JumpLabels::new(vec![(0, 0), (0, 4)]);
+        assert_eq!(labels.type_char('x'), JumpInput::NoMatch);
+        let mut labels = JumpLabels::new(vec![(0, 0), (0, 4)]);
+        assert_eq!(labels.type_char('s'), JumpInput::Jump((0, 4)));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::input::{Input, Key};
+
+/// Result of handling a key input with [`Keymap::input`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeymapInput {
+    /// A key sequence was completed. The command line of the binding is contained, which can be executed with
+    /// [`CommandRegistry::execute_line`](crate::CommandRegistry::execute_line).
+    Matched(String),
+    /// The keys typed so far are a prefix of some bindings. More keys are necessary to identify the binding. The
+    /// possible continuations are available from [`Keymap::hints`].
+    Pending,
+    /// The pending key sequence was broken by the input. The pending keys are discarded.
+    NoMatch,
+    /// No key sequence is pending and no binding starts with the input. The input should be handled by others such as
+    /// [`TextArea::input`](crate::TextArea::input).
+    Unbound,
+}
+
+/// A possible continuation of the pending key sequence returned from [`Keymap::hints`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyHint {
+    key: Input,
+    description: String,
+    prefix: bool,
+}
+
+impl KeyHint {
+    /// Get the next key to type.
+    pub fn key(&self) -> &Input {
+        &self.key
+    }
+
+    /// Get the human-readable name of the action bound to the key. When the key is a prefix of longer sequences, the
+    /// name of the group set by [`Keymap::set_group`] is returned.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get if the key is a prefix of longer sequences rather than completing a binding.
+    pub fn is_prefix(&self) -> bool {
+        self.prefix
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Binding {
+    keys: Vec<Input>,
+    command: String,
+    description: String,
+}
+
+/// Key bindings of multi-key sequences (chords) to command lines with human-readable action names, like Emacs's
+/// `C-x C-s` or Vim's leader mappings.
+///
+/// The keymap tracks the keys typed so far. While a prefix of some bindings is pending, [`Keymap::hints`] returns the
+/// possible continuations with their action names so that the application can render a which-key style popup. The
+/// matched command line is returned to the application, which usually executes it with [`CommandRegistry`].
+///
+/// Shift modifier is ignored on matching characters since the character is already shifted. When the keys of a
+/// binding are a prefix of another binding, the shorter binding is matched first and the longer one is unreachable.
+///
+/// ```
+/// use tui_textarea::{Input, Key, Keymap, KeymapInput};
+///
+/// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+/// let key = |c| Input { key: Key::Char(c), ..Default::default() };
+///
+/// let mut keymap = Keymap::default();
+/// keymap.bind([ctrl('x'), ctrl('u')], "undo", "Undo");
+/// keymap.bind([ctrl('x'), key('l'), key('s')], "sort-lines", "Sort lines");
+/// keymap.bind([ctrl('x'), key('l'), key('j')], "join-lines", "Join lines");
+/// keymap.set_group([ctrl('x'), key('l')], "+lines");
+///
+/// assert_eq!(keymap.input(ctrl('x')), KeymapInput::Pending);
+/// let hints: Vec<_> = keymap
+///     .hints()
+///     .iter()
+///     .map(|h| format!("{} {}", h.key(), h.description()))
+///     .collect();
+/// assert_eq!(hints, ["C-u Undo", "l +lines"]);
+///
+/// assert_eq!(keymap.input(key('l')), KeymapInput::Pending);
+/// assert_eq!(keymap.input(key('s')), KeymapInput::Matched("sort-lines".into()));
+///
+/// // Keys which are not bound are handled by others
+/// assert_eq!(keymap.input(key('a')), KeymapInput::Unbound);
+/// ```
+///
+/// [`CommandRegistry`]: crate::CommandRegistry
+#[derive(Clone, Debug, Default)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    groups: Vec<(Vec<Input>, String)>,
+    pending: Vec<Input>,
+}
+
+impl Keymap {
+    /// Bind the key sequence to the command line with the human-readable name of the action. Binding the same key
+    /// sequence again replaces the previous binding. An empty key sequence is ignored.
+    pub fn bind<I>(&mut self, keys: I, command: impl Into<String>, description: impl Into<String>)
+    where
+        I: IntoIterator,
+        I::Item: Into<Input>,
+    {
+        let keys = normalize_keys(keys);
+        if keys.is_empty() {
+            return;
+        }
+        let binding = Binding {
+            keys,
+            command: command.into(),
+            description: description.into(),
+        };
+        match self.bindings.iter_mut().find(|b| b.keys == binding.keys) {
+            Some(b) => *b = binding,
+            None => self.bindings.push(binding),
+        }
+    }
+
+    /// Remove the binding of the key sequence. This method returns if the binding existed or not.
+    pub fn unbind<I>(&mut self, keys: I) -> bool
+    where
+        I: IntoIterator,
+        I::Item: Into<Input>,
+    {
+        let keys = normalize_keys(keys);
+        let len = self.bindings.len();
+        self.bindings.retain(|b| b.keys != keys);
+        self.bindings.len() != len
+    }
+
+    /// Set the name of the group of the bindings starting with the prefix, like `+window`. The name is shown as the
+    /// description of the prefix key in [`Keymap::hints`]. The default name is `+prefix`.
+    pub fn set_group<I>(&mut self, prefix: I, name: impl Into<String>)
+    where
+        I: IntoIterator,
+        I::Item: Into<Input>,
+    {
+        let prefix = normalize_keys(prefix);
+        let name = name.into();
+        match self.groups.iter_mut().find(|(p, _)| *p == prefix) {
+            Some((_, n)) => *n = name,
+            None => self.groups.push((prefix, name)),
+        }
+    }
+
+    /// Get the command line and the action name bound to the key sequence.
+    pub fn binding<I>(&self, keys: I) -> Option<(&str, &str)>
+    where
+        I: IntoIterator,
+        I::Item: Into<Input>,
+    {
+        let keys = normalize_keys(keys);
+        self.bindings
+            .iter()
+            .find(|b| b.keys == keys)
+            .map(|b| (b.command.as_str(), b.description.as_str()))
+    }
+
+    /// Get the keys typed so far which are a prefix of some bindings.
+    pub fn pending(&self) -> &[Input] {
+        &self.pending
+    }
+
+    /// Get if a key sequence is pending. The application should show the hints while this method returns `true`.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Discard the pending key sequence.
+    pub fn cancel(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Handle a key input. See [`KeymapInput`] for the results.
+    pub fn input(&mut self, input: impl Into<Input>) -> KeymapInput {
+        let was_pending = self.is_pending();
+        self.pending.push(normalize(input.into()));
+
+        let mut prefix_of_binding = false;
+        for binding in &self.bindings {
+            if !binding.keys.starts_with(&self.pending) {
+                continue;
+            }
+            if binding.keys.len() == self.pending.len() {
+                self.pending.clear();
+                return KeymapInput::Matched(binding.command.clone());
+            }
+            prefix_of_binding = true;
+        }
+
+        if prefix_of_binding {
+            return KeymapInput::Pending;
+        }
+        self.pending.clear();
+        if was_pending {
+            KeymapInput::NoMatch
+        } else {
+            KeymapInput::Unbound
+        }
+    }
+
+    /// Get the possible continuations of the pending key sequence with the names of their actions, in the order of
+    /// the bindings. When no key is pending, the first keys of all bindings are returned.
+    pub fn hints(&self) -> Vec<KeyHint> {
+        let len = self.pending.len();
+        let mut hints: Vec<KeyHint> = vec![];
+        for binding in &self.bindings {
+            if binding.keys.len() <= len || !binding.keys.starts_with(&self.pending) {
+                continue;
+            }
+            let key = &binding.keys[len];
+            if hints.iter().any(|h| h.key == *key) {
+                continue;
+            }
+            let prefix = binding.keys.len() > len + 1;
+            let description = if prefix {
+                let keys = &binding.keys[..=len];
+                self.groups
+                    .iter()
+                    .find(|(p, _)| p == keys)
+                    .map_or("+prefix", |(_, n)| n.as_str())
+            } else {
+                &binding.description
+            };
+            hints.push(KeyHint {
+                key: key.clone(),
+                description: description.to_string(),
+                prefix,
+            });
+        }
+        hints
+    }
+}
+
+/// Ignore Shift modifier of characters since some backends report Shift with uppercase characters and others don't.
+fn normalize(mut input: Input) -> Input {
+    if let Key::Char(_) = input.key {
+        input.shift = false;
+    }
+    input
+}
+
+fn normalize_keys<I>(keys: I) -> Vec<Input>
+where
+    I: IntoIterator,
+    I::Item: Into<Input>,
+{
+    keys.into_iter().map(|k| normalize(k.into())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> Input {
+        Input {
+            key: Key::Char(c),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pending_sequence() {
+        let mut keymap = Keymap::default();
+        keymap.bind([key('g'), key('g')], "goto 1", "Go to top");
+        keymap.bind(
+            [key('g'), key('c'), key('c')],
+            "toggle-comment",
+            "Comment line",
+        );
+        keymap.bind([key('g'), key('c'), key('u')], "undo", "Undo");
+
+        assert_eq!(keymap.input(key('g')), KeymapInput::Pending);
+        assert_eq!(keymap.pending(), [key('g')]);
+        let hints = keymap.hints();
+        assert_eq!(hints.len(), 2);
+        assert_eq!(hints[0].key(), &key('g'));
+        assert_eq!(hints[0].description(), "Go to top");
+        assert!(!hints[0].is_prefix());
+        assert_eq!(hints[1].description(), "+prefix");
+        assert!(hints[1].is_prefix());
+
+        // Broken sequence discards the pending keys
+        assert_eq!(keymap.input(key('x')), KeymapInput::NoMatch);
+        assert!(!keymap.is_pending());
+        assert_eq!(keymap.input(key('x')), KeymapInput::Unbound);
+
+        // Shift of characters is ignored
+        let shifted = Input {
+            shift: true,
+            ..key('g')
+        };
+        assert_eq!(keymap.input(shifted), KeymapInput::Pending);
+        assert_eq!(keymap.input(key('c')), KeymapInput::Pending);
+        keymap.cancel();
+        assert!(!keymap.is_pending());
+
+        // Rebinding replaces the command and unbinding removes it
+        keymap.bind([key('g'), key('g')], "goto 2", "Go to line 2");
+        assert_eq!(
+            keymap.binding([key('g'), key('g')]),
+            Some(("goto 2", "Go to line 2"))
+        );
+        assert_eq!(keymap.input(key('g')), KeymapInput::Pending);
+        assert_eq!(
+            keymap.input(key('g')),
+            KeymapInput::Matched("goto 2".into())
+        );
+        assert!(keymap.unbind([key('g'), key('g')]));
+        assert!(!keymap.unbind([key('g'), key('g')]));
+        assert_eq!(keymap.hints().len(), 1);
+    }
+}
@@ -7,15 +7,57 @@
 #[cfg(all(feature = "ratatui", feature = "tuirs"))]
 compile_error!("ratatui support and tui-rs support are exclusive. only one of them can be enabled at the same time. see https://github.com/rhysd/tui-textarea#installation");
 
+mod block;
+mod bookmark;
+mod bracket;
+mod buffers;
+mod change;
+mod command;
+mod completion;
+mod conceal;
+mod conflict;
+mod content;
 mod cursor;
+mod diff;
+mod event;
+mod export;
+mod filetype;
+mod fold;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
 mod highlight;
 mod history;
+#[cfg(feature = "rich-text")]
+mod import;
 mod input;
+mod jump;
+mod keymap;
+mod limit;
+mod line_ending;
+mod macros;
+mod marker;
+mod message;
+mod operator;
+mod options;
+mod palette;
+mod patch;
+mod profile;
+mod render_cache;
+mod save;
 mod scroll;
 #[cfg(feature = "search")]
 mod search;
+mod sentence;
+mod snippet;
+mod split;
+mod swap;
+mod template;
 mod textarea;
 mod util;
+mod visit;
+#[cfg(feature = "watch")]
+mod watch;
+mod whitespace;
 mod widget;
 mod word;
 
@@ -37,7 +79,38 @@ use termion;
 #[cfg(feature = "tuirs-termion")]
 use termion_15 as termion;
 
+pub use bookmark::BookmarkList;
+pub use buffers::BufferSet;
+pub use change::ChangeEvent;
+pub use command::{CommandError, CommandRegistry};
+pub use completion::CompletionItem;
+pub use conflict::{Conflict, ConflictResolution};
+pub use content::Content;
 pub use cursor::CursorMove;
+pub use event::UxEvent;
+pub use export::ExportFormat;
+#[cfg(feature = "arbitrary")]
+pub use fuzz::{EditOp, EditSequence, InvariantViolation};
+pub use history::UndoCoalescing;
+#[cfg(feature = "rich-text")]
+pub use import::RichTextFormat;
 pub use input::{Input, Key};
+pub use keymap::{KeyHint, Keymap, KeymapInput};
+pub use limit::SoftLimit;
+pub use line_ending::LineEnding;
+pub use macros::EditMacro;
+pub use marker::Marker;
+pub use message::Messages;
+pub use operator::{MotionKind, Operator, TextObject};
+pub use options::Options;
+pub use palette::{CommandPalette, PaletteInput};
+pub use patch::{Hunk, PatchError, TextEdit};
+pub use profile::ColorProfile;
+pub use save::SaveOptions;
 pub use scroll::Scrolling;
+#[cfg(feature = "search")]
+pub use search::{IncrementalSearch, SearchStatus};
+pub use split::SplitEditor;
+pub use template::{Template, TemplateField};
 pub use textarea::TextArea;
+pub use whitespace::WhitespaceConfig;
@@ -0,0 +1,116 @@
+use crate::util::col_at_display_width;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Length limit of the text which is not enforced. Unlike [`TextArea::set_max_length`], the text beyond the limit can
+/// be inserted and it is rendered with the warning style instead. This is useful for editors of commit messages or
+/// short posts where the length is a recommendation. See [`TextArea::set_soft_limit`].
+///
+/// This type is marked as `#[non_exhaustive]` since more kinds of limits may be supported in the future.
+///
+/// [`TextArea::set_max_length`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_max_length
+/// [`TextArea::set_soft_limit`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_soft_limit
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SoftLimit {
+    /// Number of characters in the whole text. Newlines are counted as characters in the same manner as
+    /// [`TextArea::set_max_length`].
+    ///
+    /// [`TextArea::set_max_length`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_max_length
+    Chars(usize),
+    /// Display width of each line. Tabs are expanded to the next tab stop and wide characters are counted as 2
+    /// columns.
+    Columns(usize),
+    /// Display width of the first line and the other lines. This is useful for commit messages where the subject line
+    /// should be shorter than the lines of the body.
+    FirstLineColumns {
+        /// Limit of the first line
+        first: usize,
+        /// Limit of the other lines
+        rest: usize,
+    },
+}
+
+impl SoftLimit {
+    /// Character column in the line at the row where the text beyond the limit starts. `None` is returned when the line
+    /// is within the limit.
+    pub(crate) fn exceeding_col(self, lines: &[String], row: usize, tab_len: u8) -> Option<usize> {
+        let line = &lines[row];
+        let col = match self {
+            Self::Chars(max) => {
+                let mut remaining = max;
+                for l in &lines[..row] {
+                    // `+ 1` for the newline
+                    match remaining.checked_sub(l.chars().count() + 1) {
+                        Some(r) => remaining = r,
+                        None => return (!line.is_empty()).then(|| 0),
+                    }
+                }
+                remaining
+            }
+            Self::Columns(max) => col_at_display_width(line, max, tab_len),
+            Self::FirstLineColumns { first, rest } => {
+                let max = if row == 0 { first } else { rest };
+                col_at_display_width(line, max, tab_len)
+            }
+        };
+        (col < line.chars().count()).then(|| col)
+    }
+
+    /// Check if the text exceeds the limit.
+    pub(crate) fn is_exceeded(self, lines: &[String], tab_len: u8) -> bool {
+        match self {
+            Self::Chars(max) => {
+                let mut len = lines.len() - 1;
+                lines.iter().any(|l| {
+                    len += l.chars().count();
+                    len > max
+                })
+            }
+            Self::Columns(_) | Self::FirstLineColumns { .. } => {
+                (0..lines.len()).any(|row| self.exceeding_col(lines, row, tab_len).is_some())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exceeding_col() {
+        let lines: Vec<_> = ["abcd", "", "\tef", "ｇh"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let cols = |limit: SoftLimit| -> Vec<_> {
+            (0..lines.len())
+                .map(|row| limit.exceeding_col(&lines, row, 4))
+                .collect()
+        };
+        assert_eq!(cols(SoftLimit::Chars(100)), [None, None, None, None]);
+        assert_eq!(cols(SoftLimit::Chars(2)), [Some(2), None, Some(0), Some(0)]);
+        assert_eq!(cols(SoftLimit::Chars(7)), [None, None, Some(1), Some(0)]);
+        assert_eq!(cols(SoftLimit::Chars(0)), [Some(0), None, Some(0), Some(0)]);
+        assert_eq!(cols(SoftLimit::Columns(4)), [None, None, Some(1), None]);
+        assert_eq!(
+            cols(SoftLimit::Columns(1)),
+            [Some(1), None, Some(0), Some(0)]
+        );
+        assert_eq!(
+            cols(SoftLimit::Columns(2)),
+            [Some(2), None, Some(0), Some(1)]
+        );
+        let limit = SoftLimit::FirstLineColumns { first: 2, rest: 4 };
+        assert_eq!(cols(limit), [Some(2), None, Some(1), None]);
+
+        assert!(SoftLimit::Chars(11).is_exceeded(&lines, 4));
+        assert!(!SoftLimit::Chars(12).is_exceeded(&lines, 4));
+        assert!(SoftLimit::Columns(5).is_exceeded(&lines, 4));
+        assert!(!SoftLimit::Columns(6).is_exceeded(&lines, 4));
+        let limit = SoftLimit::FirstLineColumns { first: 4, rest: 6 };
+        assert!(!limit.is_exceeded(&lines, 4));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::change::ChangeEvent;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Line ending used to join the lines of [`TextArea`] into a text with [`TextArea::text`]. It is detected from the
+/// lines on creating a textarea and can be changed with [`TextArea::set_line_ending`].
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::text`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.text
+/// [`TextArea::set_line_ending`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_line_ending
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LineEnding {
+    /// `\n` used on Unix-like systems. This is the default line ending.
+    Lf,
+    /// `\r\n` used on Windows.
+    CrLf,
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        Self::Lf
+    }
+}
+
+impl LineEnding {
+    /// Get the line ending as string.
+    /// ```
+    /// use tui_textarea::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::Lf.as_str(), "\n");
+    /// assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    /// Remove `\r` at the end of each line and return the dominant line ending of the lines with the rows of the lines
+    /// ending with the other one. Lines are expected to be split by `\n` so the `\r` before it remains at the end of
+    /// line. When the line endings are mixed, the one used by more lines wins. The last line is not followed by a
+    /// newline, so its `\r` is kept as a part of the text and is not counted.
+    pub(crate) fn strip(lines: &mut [String]) -> (Self, MixedEndings) {
+        let newlines = lines.len().saturating_sub(1);
+        let mut crlf = vec![];
+        for (row, line) in lines.iter_mut().take(newlines).enumerate() {
+            if line.ends_with('\r') {
+                line.pop();
+                crlf.push(row);
+            }
+        }
+        if !crlf.is_empty() && crlf.len() * 2 >= newlines {
+            let lf = (0..newlines)
+                .filter(|r| crlf.binary_search(r).is_err())
+                .collect();
+            (Self::CrLf, MixedEndings(lf))
+        } else {
+            (Self::Lf, MixedEndings(crlf))
+        }
+    }
+}
+
+/// Rows of the lines which end with the other line ending than the dominant one in a text with mixed line endings.
+/// The rows follow the text changes so that [`TextArea::text`] keeps the line ending of each line. Newlines inserted
+/// by edits use the dominant line ending.
+///
+/// [`TextArea::text`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.text
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MixedEndings(Vec<usize>);
+
+impl MixedEndings {
+    /// Create from the rows restored from the serialized state. Rows are sorted and the ones without a newline after
+    /// them are removed.
+    #[cfg(feature = "serde")]
+    pub fn from_rows(mut rows: Vec<usize>, num_lines: usize) -> Self {
+        rows.sort_unstable();
+        rows.dedup();
+        rows.retain(|&r| r + 1 < num_lines);
+        Self(rows)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rows(&self) -> &[usize] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Get the line ending of the newline after the line at the row.
+    pub fn ending(&self, row: usize, dominant: LineEnding) -> LineEnding {
+        match (self.0.binary_search(&row).is_ok(), dominant) {
+            (false, e) => e,
+            (true, LineEnding::Lf) => LineEnding::CrLf,
+            (true, LineEnding::CrLf) => LineEnding::Lf,
+        }
+    }
+
+    /// Follow the text change. The newline at the end of the line where text is inserted moves to the end of the
+    /// inserted text. Newlines in deleted text are removed and the newline after the deletion is kept.
+    pub fn apply(&mut self, change: &ChangeEvent) {
+        match *change {
+            ChangeEvent::Inserted { start, end, .. } => {
+                let lines = end.0 - start.0;
+                for r in self.0.iter_mut().filter(|r| **r >= start.0) {
+                    *r += lines;
+                }
+            }
+            ChangeEvent::Deleted { start, end, .. } => {
+                let lines = end.0 - start.0;
+                self.0.retain(|r| !(start.0..end.0).contains(r));
+                for r in self.0.iter_mut().filter(|r| **r >= end.0) {
+                    *r -= lines;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(lines: &[&str]) -> (Vec<String>, LineEnding, Vec<usize>) {
+        let mut lines: Vec<_> = lines.iter().map(|s| s.to_string()).collect();
+        let (ending, mixed) = LineEnding::strip(&mut lines);
+        (lines, ending, mixed.0)
+    }
+
+    #[test]
+    fn strip_mixed() {
+        let (lines, ending, mixed) = strip(&["a\r", "b", "c\r", "d"]);
+        assert_eq!(lines, ["a", "b", "c", "d"]);
+        assert_eq!(ending, LineEnding::CrLf);
+        assert_eq!(mixed, [1]);
+
+        let (lines, ending, mixed) = strip(&["a\r", "b", "c", "d\r"]);
+        assert_eq!(lines, ["a", "b", "c", "d\r"]);
+        assert_eq!(ending, LineEnding::Lf);
+        assert_eq!(mixed, [0]);
+    }
+
+    #[test]
+    fn strip_last_line() {
+        let (lines, ending, mixed) = strip(&["a\r"]);
+        assert_eq!(lines, ["a\r"]);
+        assert_eq!(ending, LineEnding::Lf);
+        assert!(mixed.is_empty());
+    }
+
+    #[test]
+    fn follow_changes() {
+        let mut m = MixedEndings(vec![1, 3]);
+        m.apply(&ChangeEvent::Inserted {
+            start: (1, 2),
+            end: (2, 0),
+            text: "\n".into(),
+        });
+        assert_eq!(m.0, [2, 4]);
+        m.apply(&ChangeEvent::Deleted {
+            start: (1, 0),
+            end: (3, 0),
+            text: String::new(),
+        });
+        assert_eq!(m.0, [2]);
+    }
+}
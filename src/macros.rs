@@ -0,0 +1,107 @@
+use crate::conflict::ConflictResolution;
+use crate::cursor::CursorMove;
+use crate::operator::{MotionKind, Operator, TextObject};
+use crate::patch::{Hunk, TextEdit};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+
+/// Editing operation recorded in [`EditMacro`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum MacroOp {
+    InsertChar(char),
+    InsertNewline,
+    InsertTab,
+    InsertStr(String),
+    DeleteChar,
+    DeleteNextChar,
+    DeleteNewline,
+    DeleteStr(usize),
+    DeleteLineByEnd,
+    DeleteLineByHead,
+    DeleteWord,
+    DeleteNextWord,
+    DeleteWholeLine,
+    DeleteTo(CursorMove),
+    Operator(Operator, CursorMove, MotionKind),
+    OperatorObject(Operator, TextObject),
+    /// Cursor move and if the selection is extended by the move or not
+    Move(CursorMove, bool),
+    StartSelection,
+    CancelSelection,
+    SelectAll,
+    Copy,
+    Cut,
+    Paste,
+    YankPop,
+    YankToRegister(char),
+    PasteFromRegister(char),
+    IndentSelection,
+    DedentSelection,
+    MoveLinesUp,
+    MoveLinesDown,
+    DuplicateLines,
+    SortLines,
+    JoinLines,
+    ToggleComment(String),
+    ApplyEdits(Vec<TextEdit>),
+    ApplyPatch(Vec<Hunk>),
+    ResolveConflict(ConflictResolution),
+    /// Whole text replaced with minimal edits, such as the result of [`TextArea::format_with`]
+    ///
+    /// [`TextArea::format_with`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.format_with
+    UpdateLines(Vec<String>),
+}
+
+/// Sequence of editing operations recorded by [`TextArea::start_recording`] and [`TextArea::stop_recording`]. The
+/// recorded operations are not key inputs but the operations such as inserting a character or moving the cursor by a
+/// word, so the macro can be replayed with [`TextArea::replay`] regardless of key mappings.
+///
+/// ```
+/// use tui_textarea::{TextArea, CursorMove};
+///
+/// let mut textarea = TextArea::from(["a", "b", "c"]);
+///
+/// textarea.start_recording();
+/// textarea.insert_str("- ");
+/// textarea.move_cursor(CursorMove::Down);
+/// textarea.move_cursor(CursorMove::Head);
+/// let m = textarea.stop_recording();
+/// assert_eq!(m.len(), 3);
+///
+/// textarea.replay(&m, 2);
+/// assert_eq!(textarea.lines(), ["- a", "- b", "- c"]);
+/// ```
+///
+/// [`TextArea::start_recording`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.start_recording
+/// [`TextArea::stop_recording`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.stop_recording
+/// [`TextArea::replay`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.replay
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct EditMacro {
+    ops: Vec<MacroOp>,
+}
+
+impl EditMacro {
+    /// Number of the recorded operations.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Check if no operation was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, op: MacroOp) {
+        self.ops.push(op);
+    }
+
+    pub(crate) fn extend(&mut self, other: &EditMacro) {
+        self.ops.extend(other.ops.iter().cloned());
+    }
+
+    pub(crate) fn ops(&self) -> &[MacroOp] {
+        &self.ops
+    }
+}
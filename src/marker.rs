@@ -0,0 +1,133 @@
+use crate::change::ChangeEvent;
+use std::collections::BTreeMap;
+
+/// Handle of a position marker added by [`TextArea::add_marker`]. The position of the marker follows edits of the text
+/// and can be retrieved with [`TextArea::marker_pos`].
+///
+/// [`TextArea::add_marker`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.add_marker
+/// [`TextArea::marker_pos`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.marker_pos
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Marker(u64);
+
+#[derive(Clone, Debug, Default)]
+pub struct Markers {
+    next_id: u64,
+    positions: BTreeMap<Marker, (usize, usize)>,
+    stick_right: bool,
+}
+
+impl Markers {
+    /// Markers which move after the text inserted at them. This is useful to keep markers at the heads of lines.
+    pub fn stick_right() -> Self {
+        Self {
+            stick_right: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn add(&mut self, pos: (usize, usize)) -> Marker {
+        let marker = Marker(self.next_id);
+        self.next_id += 1;
+        self.positions.insert(marker, pos);
+        marker
+    }
+
+    pub fn get(&self, marker: Marker) -> Option<(usize, usize)> {
+        self.positions.get(&marker).copied()
+    }
+
+    pub fn remove(&mut self, marker: Marker) -> bool {
+        self.positions.remove(&marker).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.positions.values().copied()
+    }
+
+    /// Keep only the markers whose positions satisfy the predicate. This method returns if some marker was removed.
+    pub fn retain(&mut self, mut f: impl FnMut((usize, usize)) -> bool) -> bool {
+        let len = self.positions.len();
+        self.positions.retain(|_, pos| f(*pos));
+        self.positions.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+
+    /// Follow the text change. Text inserted at a marker is put after the marker unless the markers stick right.
+    pub fn apply(&mut self, change: &ChangeEvent) {
+        for pos in self.positions.values_mut() {
+            *pos = change.adjust(*pos, self.stick_right);
+        }
+    }
+
+    /// Move the markers with the function. This is used when the whole text is replaced.
+    pub fn map(&mut self, mut f: impl FnMut((usize, usize)) -> (usize, usize)) {
+        for pos in self.positions.values_mut() {
+            *pos = f(*pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove() {
+        let mut m = Markers::default();
+        let a = m.add((0, 1));
+        let b = m.add((2, 3));
+        assert_ne!(a, b);
+        assert_eq!(m.get(b), Some((2, 3)));
+        assert!(m.remove(a));
+        assert!(!m.remove(a));
+        assert_eq!(m.get(a), None);
+        assert!(m.remove(b));
+        assert!(m.is_empty());
+        // IDs are not reused
+        assert_ne!(m.add((0, 0)), a);
+    }
+
+    #[test]
+    fn follow_changes() {
+        let mut m = Markers::default();
+        let a = m.add((0, 2));
+        let b = m.add((1, 4));
+        m.apply(&ChangeEvent::Inserted {
+            start: (0, 2),
+            end: (1, 1),
+            text: "x\ny".into(),
+        });
+        assert_eq!(m.get(a), Some((0, 2)));
+        assert_eq!(m.get(b), Some((2, 4)));
+        m.apply(&ChangeEvent::Deleted {
+            start: (0, 1),
+            end: (2, 2),
+            text: "...".into(),
+        });
+        assert_eq!(m.get(a), Some((0, 1)));
+        assert_eq!(m.get(b), Some((0, 3)));
+    }
+
+    #[test]
+    fn stick_right() {
+        let mut m = Markers::stick_right();
+        m.add((1, 0));
+        m.add((2, 0));
+        m.apply(&ChangeEvent::Inserted {
+            start: (1, 0),
+            end: (2, 0),
+            text: "\n".into(),
+        });
+        assert_eq!(m.positions().collect::<Vec<_>>(), [(2, 0), (3, 0)]);
+        assert!(m.retain(|(r, _)| r != 2));
+        assert!(!m.retain(|(r, _)| r != 2));
+        assert_eq!(m.positions().collect::<Vec<_>>(), [(3, 0)]);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::event::UxEvent;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Table of user-facing strings produced by [`TextArea`]. Replace the strings to localize them and set the table with
+/// [`TextArea::set_messages`]. The default strings are in English.
+///
+/// This type is marked as `#[non_exhaustive]` since more strings may be added in the future. Create an instance with
+/// [`Messages::default`] and update its fields.
+/// ```
+/// use tui_textarea::{Messages, TextArea, UxEvent};
+///
+/// let mut messages = Messages::default();
+/// messages.search_failed = "パターンが見つかりません".into();
+///
+/// let mut textarea = TextArea::default();
+/// textarea.set_messages(messages);
+/// assert_eq!(textarea.messages().event(UxEvent::SearchFailed), "パターンが見つかりません");
+/// ```
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::set_messages`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_messages
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Messages {
+    /// Placeholder text shown when the textarea is empty and no placeholder text is set by
+    /// [`TextArea::set_placeholder_text`]. The default value is an empty string, meaning no placeholder.
+    ///
+    /// [`TextArea::set_placeholder_text`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_placeholder_text
+    pub placeholder: Cow<'static, str>,
+    /// Message for [`UxEvent::MotionFailed`].
+    pub motion_failed: Cow<'static, str>,
+    /// Message for [`UxEvent::BufferEdge`].
+    pub buffer_edge: Cow<'static, str>,
+    /// Message for [`UxEvent::SearchWrapped`].
+    pub search_wrapped: Cow<'static, str>,
+    /// Message for [`UxEvent::SearchFailed`].
+    pub search_failed: Cow<'static, str>,
+    /// Message for [`UxEvent::EditRejected`].
+    pub edit_rejected: Cow<'static, str>,
+    /// Message for [`UxEvent::FileChanged`].
+    pub file_changed: Cow<'static, str>,
+    /// Placeholder line of folded lines. `{lines}` is replaced with the number of the folded lines. See
+    /// [`TextArea::fold`].
+    ///
+    /// [`TextArea::fold`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.fold
+    pub folded_lines: Cow<'static, str>,
+    /// Cursor position in [`TextArea::accessible_description`]. `{row}`, `{lines}` and `{col}` are replaced with the
+    /// 1-based cursor row, the number of lines and the 1-based cursor column.
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_cursor: Cow<'static, str>,
+    /// Visible lines in [`TextArea::accessible_description`]. `{start}` and `{end}` are replaced with the 1-based first
+    /// and last visible lines.
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_visible: Cow<'static, str>,
+    /// Selection in [`TextArea::accessible_description`]. `{len}` is replaced with the number of selected characters
+    /// and `{start_row}`, `{start_col}`, `{end_row}`, `{end_col}` are replaced with the 1-based selection range.
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_selection: Cow<'static, str>,
+    /// Cursor line in [`TextArea::accessible_description`]. `{text}` is replaced with the text of the line.
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_line: Cow<'static, str>,
+    /// Text of an empty cursor line in [`TextArea::accessible_description`].
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_blank: Cow<'static, str>,
+    /// Text of a masked cursor line in [`TextArea::accessible_description`]. `{len}` is replaced with the number of
+    /// characters in the line.
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_hidden: Cow<'static, str>,
+    /// Read-only state in [`TextArea::accessible_description`].
+    ///
+    /// [`TextArea::accessible_description`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.accessible_description
+    pub a11y_read_only: Cow<'static, str>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            placeholder: Cow::Borrowed(""),
+            motion_failed: Cow::Borrowed("Cannot move the cursor"),
+            buffer_edge: Cow::Borrowed("Reached the edge of the text"),
+            search_wrapped: Cow::Borrowed("Search wrapped around"),
+            search_failed: Cow::Borrowed("Pattern not found"),
+            edit_rejected: Cow::Borrowed("Cannot modify the text"),
+            file_changed: Cow::Borrowed("File was changed on disk"),
+            folded_lines: Cow::Borrowed("⋯ {lines} lines"),
+            a11y_cursor: Cow::Borrowed("Line {row} of {lines}, column {col}"),
+            a11y_visible: Cow::Borrowed("Showing lines {start} to {end}"),
+            a11y_selection: Cow::Borrowed(
+                "Selected {len} characters from line {start_row}, column {start_col} to line {end_row}, column {end_col}",
+            ),
+            a11y_line: Cow::Borrowed("Current line: {text}"),
+            a11y_blank: Cow::Borrowed("blank"),
+            a11y_hidden: Cow::Borrowed("{len} hidden characters"),
+            a11y_read_only: Cow::Borrowed("Read-only"),
+        }
+    }
+}
+
+impl Messages {
+    /// Get the message for the UX event. This is useful to show a transient status message in the event hook set by
+    /// [`TextArea::set_event_hook`].
+    /// ```
+    /// use tui_textarea::{Messages, UxEvent};
+    ///
+    /// let messages = Messages::default();
+    /// assert_eq!(messages.event(UxEvent::SearchFailed), "Pattern not found");
+    /// ```
+    ///
+    /// [`TextArea::set_event_hook`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_event_hook
+    pub fn event(&self, event: UxEvent) -> &str {
+        match event {
+            UxEvent::MotionFailed => &self.motion_failed,
+            UxEvent::BufferEdge => &self.buffer_edge,
+            UxEvent::SearchWrapped => &self.search_wrapped,
+            UxEvent::SearchFailed => &self.search_failed,
+            UxEvent::EditRejected => &self.edit_rejected,
+            UxEvent::FileChanged => &self.file_changed,
+        }
+    }
+
+    /// Replace `{name}` placeholders in the message with the arguments.
+    pub(crate) fn format(message: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut formatted = message.to_string();
+        for (name, value) in args {
+            formatted = formatted.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        formatted
+    }
+}
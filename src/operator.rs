@@ -0,0 +1,180 @@
+use crate::cursor::CursorMove;
+use crate::swap::ColRange;
+use crate::word::is_word_char;
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Operation applied to the range of text decided by a cursor move or a text object. This is the operator of Vim's
+/// operator-pending mode such as `d`, `c`, `y`, `>` and `<`. See [`TextArea::apply_operator`].
+///
+/// This type is marked as `#[non_exhaustive]` since more operators may be supported in the future.
+///
+/// [`TextArea::apply_operator`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.apply_operator
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operator {
+    /// Delete the text and put it in the yank buffer.
+    Delete,
+    /// Delete the text and put it in the yank buffer to replace it with new text. Linewise change keeps one empty line
+    /// and changing a word does not delete the spaces after it.
+    Change,
+    /// Copy the text to the yank buffer. The cursor moves to the start of the text.
+    Yank,
+    /// Indent the lines of the text.
+    Indent,
+    /// Dedent the lines of the text.
+    Dedent,
+}
+
+/// How the range between the cursor and the destination of a cursor move is decided. See [`MotionKind::of`] for the
+/// kind of each cursor move.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MotionKind {
+    /// The character at the end of the range is not included. When the range ends at the head of a line, it ends at
+    /// the end of the previous line instead, and the whole lines are the range when it also starts before the first
+    /// non-blank character of a line.
+    Exclusive,
+    /// The character at the end of the range is included.
+    Inclusive,
+    /// All lines between the cursor and the destination are included.
+    Linewise,
+}
+
+impl MotionKind {
+    /// Get the kind of the cursor move in the same manner as Vim. Vertical moves and moves to the top or the bottom are
+    /// linewise, moves to the end of a word and to the matching bracket are inclusive, and the others are exclusive.
+    /// ```
+    /// use tui_textarea::{CursorMove, MotionKind};
+    ///
+    /// assert_eq!(MotionKind::of(CursorMove::Down), MotionKind::Linewise);
+    /// assert_eq!(MotionKind::of(CursorMove::WordEnd), MotionKind::Inclusive);
+    /// assert_eq!(MotionKind::of(CursorMove::WordForward), MotionKind::Exclusive);
+    /// ```
+    pub fn of(m: CursorMove) -> Self {
+        match m {
+            CursorMove::Up | CursorMove::Down | CursorMove::Top | CursorMove::Bottom => {
+                Self::Linewise
+            }
+            CursorMove::WordEnd
+            | CursorMove::WordEndBack
+            | CursorMove::BigWordEnd
+            | CursorMove::BigWordEndBack
+            | CursorMove::MatchingBracket => Self::Inclusive,
+            _ => Self::Exclusive,
+        }
+    }
+}
+
+/// Range of text which is not a destination of a cursor move but is selected around the cursor. This is Vim's text
+/// object such as `iw` and `a(`. See [`TextArea::apply_operator_to_object`].
+///
+/// This type is marked as `#[non_exhaustive]` since more text objects may be supported in the future.
+///
+/// [`TextArea::apply_operator_to_object`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.apply_operator_to_object
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextObject {
+    /// Word under the cursor. A sequence of spaces or punctuations is also a word.
+    InnerWord,
+    /// Word under the cursor with the spaces after it. When no space follows the word, the spaces before it are
+    /// included instead.
+    AroundWord,
+    /// Text in the innermost brackets enclosing the cursor, not including the brackets.
+    InnerBracket,
+    /// Text in the innermost brackets enclosing the cursor, including the brackets.
+    AroundBracket,
+    /// The cursor line. This is linewise like `dd` or `yy` in Vim.
+    Line,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Other,
+}
+
+fn char_class(c: char, word_chars: &str) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::Space
+    } else if is_word_char(c) || word_chars.contains(c) {
+        CharClass::Word
+    } else {
+        CharClass::Other
+    }
+}
+
+/// Range of the sequence of characters of the same class containing the column. `word_chars` are punctuations
+/// which are treated as word characters.
+pub fn char_run(line: &str, col: usize, word_chars: &str) -> Option<ColRange> {
+    let chars: Vec<char> = line.chars().collect();
+    let class = char_class(*chars.get(col)?, word_chars);
+    let start = chars[..col]
+        .iter()
+        .rposition(|&c| char_class(c, word_chars) != class)
+        .map_or(0, |i| i + 1);
+    let end = chars[col..]
+        .iter()
+        .position(|&c| char_class(c, word_chars) != class)
+        .map_or(chars.len(), |i| col + i);
+    Some((start, end))
+}
+
+/// Range of the word text object at the column in the line.
+pub fn word_object(line: &str, col: usize, around: bool, word_chars: &str) -> Option<ColRange> {
+    let (start, end) = char_run(line, col, word_chars)?;
+    if !around {
+        return Some((start, end));
+    }
+    let is_space = |col: usize| {
+        line.chars()
+            .nth(col)
+            .map_or(false, |c| char_class(c, word_chars) == CharClass::Space)
+    };
+    if is_space(start) {
+        // Spaces followed by the next word
+        return Some(char_run(line, end, word_chars).map_or((start, end), |(_, e)| (start, e)));
+    }
+    if is_space(end) {
+        return Some((start, char_run(line, end, word_chars).unwrap().1));
+    }
+    if start > 0 && is_space(start - 1) {
+        return Some((char_run(line, start - 1, word_chars).unwrap().0, end));
+    }
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_objects() {
+        let line = "foo(bar)  baz";
+        assert_eq!(char_run(line, 1, ""), Some((0, 3)));
+        assert_eq!(char_run(line, 3, ""), Some((3, 4)));
+        assert_eq!(char_run(line, 9, ""), Some((8, 10)));
+        assert_eq!(char_run(line, 13, ""), None);
+
+        assert_eq!(word_object(line, 5, false, ""), Some((4, 7)));
+        assert_eq!(word_object(line, 5, true, ""), Some((4, 7)));
+        assert_eq!(word_object(line, 7, true, ""), Some((7, 10)));
+        assert_eq!(word_object(line, 8, true, ""), Some((8, 13)));
+        assert_eq!(word_object(line, 11, true, ""), Some((8, 13)));
+        assert_eq!(word_object("a  ", 1, true, ""), Some((1, 3)));
+
+        let line = "foo-bar baz";
+        assert_eq!(char_run(line, 1, ""), Some((0, 3)));
+        assert_eq!(char_run(line, 1, "-"), Some((0, 7)));
+        assert_eq!(word_object(line, 4, true, "-"), Some((0, 8)));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::filetype;
+use crate::history::UndoCoalescing;
+use crate::limit::SoftLimit;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Behavioral settings of [`TextArea`] such as tab width, wrapping and auto-indent. Get the current settings with
+/// [`TextArea::options`] and set them at once with [`TextArea::apply_options`]. This is useful for applications which
+/// manage many buffers and switch or template the settings per file type. Styles are not a part of the options.
+///
+/// This type is marked as `#[non_exhaustive]` since more options may be added in the future. Create an instance with
+/// [`Options::default`] or [`TextArea::options`] and update its fields.
+/// ```
+/// use tui_textarea::{Options, TextArea};
+///
+/// let mut makefile = Options::default();
+/// makefile.hard_tab_indent = true;
+/// makefile.tab_length = 8;
+///
+/// let mut textarea = TextArea::default();
+/// textarea.apply_options(&makefile);
+/// assert!(textarea.hard_tab_indent());
+/// assert_eq!(textarea.options(), makefile);
+/// ```
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::options`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.options
+/// [`TextArea::apply_options`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.apply_options
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Options {
+    /// Number of spaces inserted by a tab key. See `TextArea::set_tab_length`. The default value is 4.
+    pub tab_length: u8,
+    /// Display width of tab characters. `None` follows `tab_length`. See `TextArea::set_tab_width`. The default value
+    /// is `None`.
+    pub tab_width: Option<u8>,
+    /// Insert a hard tab by a tab key. See `TextArea::set_hard_tab_indent`. The default value is `false`.
+    pub hard_tab_indent: bool,
+    /// Keep the indentation of the previous line on a newline. See `TextArea::set_auto_indent`. The default value is
+    /// `false`.
+    pub auto_indent: bool,
+    /// Wrap long lines. See `TextArea::set_wrap`. The default value is `false`.
+    pub wrap: bool,
+    /// Reject edits. See `TextArea::set_read_only`. The default value is `false`.
+    pub read_only: bool,
+    /// Overwrite characters on typing. See `TextArea::set_overwrite`. The default value is `false`.
+    pub overwrite: bool,
+    /// Max number of characters. See `TextArea::set_max_length`. The default value is `None`.
+    pub max_length: Option<usize>,
+    /// Length limit which is not enforced but warned with a style. See `TextArea::set_soft_limit`. The default value is
+    /// `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub soft_limit: Option<SoftLimit>,
+    /// Max number of undo steps. See `TextArea::set_max_histories`. The default value is 50.
+    pub max_histories: usize,
+    /// How edits are merged into one undo step. See `TextArea::set_undo_coalescing`. The default value is
+    /// [`UndoCoalescing::Never`].
+    pub undo_coalescing: UndoCoalescing,
+    /// Max number of yanked texts kept in the yank history. See `TextArea::set_max_yank_history`. The default value
+    /// is 10.
+    pub max_yank_history: usize,
+    /// Join texts deleted by consecutive kill operations into one yanked text. See `TextArea::set_kill_accumulation`.
+    /// The default value is `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kill_accumulation: bool,
+    /// Pairs of brackets for matching brackets and text objects. See `TextArea::set_bracket_pairs`. The default pairs
+    /// are `()`, `[]` and `{}`.
+    pub bracket_pairs: Vec<(char, char)>,
+    /// Punctuations which are treated as a part of word. See `TextArea::set_word_chars`. The default value is empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub word_chars: String,
+    /// Delete trailing whitespace of each line on saving the text. See `TextArea::set_trim_trailing_whitespace`. The
+    /// default value is `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub trim_trailing_whitespace: bool,
+    /// Display column highlighted as a guide of the line length. See `TextArea::set_color_column`. The default value
+    /// is `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color_column: Option<usize>,
+    /// Prefix of comment lines. See `TextArea::set_comment_prefix`. The default value is `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comment_prefix: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            tab_length: 4,
+            tab_width: None,
+            hard_tab_indent: false,
+            auto_indent: false,
+            wrap: false,
+            read_only: false,
+            overwrite: false,
+            max_length: None,
+            soft_limit: None,
+            max_histories: 50,
+            undo_coalescing: UndoCoalescing::default(),
+            max_yank_history: 10,
+            kill_accumulation: false,
+            bracket_pairs: vec![('(', ')'), ('[', ']'), ('{', '}')],
+            word_chars: String::new(),
+            trim_trailing_whitespace: false,
+            color_column: None,
+            comment_prefix: None,
+        }
+    }
+}
+
+impl Options {
+    /// Get the built-in options for the file type. The file type can be a name such as `"yaml"` and `"make"`, an
+    /// extension such as `"yml"` and `"rs"`, or a file name such as `"Makefile"`. Letter case is ignored. The options
+    /// related to the file type (tab length, hard tab indent, auto-indent, wrapping, trimming trailing whitespace, soft
+    /// limit, color column and comment prefix) are set on the default options. `None` is returned when no built-in
+    /// profile is found.
+    ///
+    /// | File type                  | Indent   | Wrap | Trim trailing whitespace |
+    /// |----------------------------|----------|------|--------------------------|
+    /// | `text`                     | 4 spaces | Yes  | No                       |
+    /// | `markdown`                 | 4 spaces | Yes  | No                       |
+    /// | `make`                     | Hard tab | No   | Yes                      |
+    /// | `go`                       | Hard tab | No   | Yes                      |
+    /// | `yaml`, `json`             | 2 spaces | No   | Yes                      |
+    /// | `javascript`, `typescript` | 2 spaces | No   | Yes                      |
+    /// | `sh`                       | 2 spaces | No   | Yes                      |
+    /// | `toml`, `rust`, `python`   | 4 spaces | No   | Yes                      |
+    /// | `c`, `cpp`, `java`         | 4 spaces | No   | Yes                      |
+    /// | `gitcommit`                | 4 spaces | No   | Yes                      |
+    ///
+    /// Auto-indent is enabled for all file types except for `text` and `gitcommit`. `gitcommit` is the preset for commit
+    /// messages of Git (`COMMIT_EDITMSG`). The subject line is limited to 50 columns and the body lines are limited to
+    /// 72 columns by [`SoftLimit::FirstLineColumns`], the column after the 72nd column is highlighted, and lines starting
+    /// with `#` are comments.
+    /// ```
+    /// use tui_textarea::Options;
+    ///
+    /// let yaml = Options::for_filetype("yml").unwrap();
+    /// assert_eq!(yaml.tab_length, 2);
+    ///
+    /// let makefile = Options::for_filetype("Makefile").unwrap();
+    /// assert!(makefile.hard_tab_indent);
+    ///
+    /// let markdown = Options::for_filetype("markdown").unwrap();
+    /// assert!(!markdown.trim_trailing_whitespace);
+    ///
+    /// assert_eq!(Options::for_filetype("unknown"), None);
+    /// ```
+    pub fn for_filetype(filetype: &str) -> Option<Self> {
+        let profile = filetype::find(filetype)?;
+        let mut options = Self::default();
+        profile.apply(&mut options);
+        Some(options)
+    }
+}
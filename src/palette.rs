@@ -0,0 +1,462 @@
+use crate::command::{split_words, CommandError, CommandRegistry};
+use crate::input::{Input, Key};
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::ratatui::widgets::{Block, Widget};
+use crate::textarea::TextArea;
+use std::cmp;
+
+/// Result of handling a key input with [`CommandPalette::input`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaletteInput {
+    /// The query or the selection was updated.
+    Handled,
+    /// The input was not handled by the palette.
+    Ignored,
+    /// The selected command was executed. The name of the command is contained.
+    Executed(String),
+    /// Executing the command failed, or no command matched the query on Enter.
+    Failed(CommandError),
+    /// The palette was cancelled with Esc.
+    Cancelled,
+}
+
+/// Companion widget of [`CommandRegistry`] to search commands with fuzzy matching and execute the selected one against
+/// a [`TextArea`], like the command palette of VS Code.
+///
+/// The first word of the query is matched against the command names. The characters of the word must appear in the
+/// name in the same order, and matches at the start of words and consecutive matches are ranked higher. The rest of
+/// the query is passed to the command as arguments in the same syntax as [`CommandRegistry::execute_line`].
+///
+/// ```
+/// use tui_textarea::{CommandPalette, CommandRegistry, Input, Key, PaletteInput, TextArea};
+///
+/// let mut textarea = TextArea::from(["b", "a"]);
+/// let mut palette = CommandPalette::new(CommandRegistry::with_builtins());
+///
+/// for c in "sa".chars() {
+///     palette.input(Input { key: Key::Char(c), ..Default::default() }, &mut textarea);
+/// }
+/// assert_eq!(palette.matches().next(), Some("select-all"));
+///
+/// let enter = Input { key: Key::Enter, ..Default::default() };
+/// assert_eq!(palette.input(enter.clone(), &mut textarea), PaletteInput::Executed("select-all".into()));
+///
+/// palette.set_query("sortl");
+/// assert_eq!(palette.input(enter, &mut textarea), PaletteInput::Executed("sort-lines".into()));
+/// assert_eq!(textarea.lines(), ["a", "b"]);
+///
+/// // Render the palette with `frame.render_widget(palette.widget(&textarea), area)`
+/// ```
+#[derive(Clone, Debug)]
+pub struct CommandPalette<'a> {
+    registry: CommandRegistry,
+    query: String,
+    matches: Vec<String>,
+    selected: usize,
+    block: Option<Block<'a>>,
+    style: Style,
+    selected_style: Style,
+    description_style: Style,
+}
+
+impl<'a> CommandPalette<'a> {
+    /// Create a palette listing the commands of the registry. All commands are listed while the query is empty.
+    pub fn new(registry: CommandRegistry) -> Self {
+        let mut palette = Self {
+            registry,
+            query: String::new(),
+            matches: vec![],
+            selected: 0,
+            block: None,
+            style: Style::default(),
+            selected_style: Style::default().add_modifier(Modifier::REVERSED),
+            description_style: Style::default().fg(Color::DarkGray),
+        };
+        palette.update();
+        palette
+    }
+
+    /// Get the registry of the commands listed in the palette.
+    pub fn registry(&self) -> &CommandRegistry {
+        &self.registry
+    }
+
+    /// Get the mutable registry of the commands. Call [`CommandPalette::reset`] after modifying the registry to update
+    /// the list.
+    pub fn registry_mut(&mut self) -> &mut CommandRegistry {
+        &mut self.registry
+    }
+
+    /// Get the current query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Replace the query and select the best match.
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.update();
+    }
+
+    /// Clear the query and the selection.
+    pub fn reset(&mut self) {
+        self.set_query(String::new());
+    }
+
+    /// Get the names of the commands matching the query, from the best match.
+    pub fn matches(&self) -> impl Iterator<Item = &str> {
+        self.matches.iter().map(String::as_str)
+    }
+
+    /// Get the index of the selected command in [`CommandPalette::matches`].
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Get the name of the selected command. `None` when no command matches the query.
+    pub fn selected_name(&self) -> Option<&str> {
+        self.matches.get(self.selected).map(String::as_str)
+    }
+
+    fn update(&mut self) {
+        let word = self.query.split_whitespace().next().unwrap_or("");
+        let mut scored: Vec<_> = self
+            .registry
+            .names()
+            .filter_map(|name| fuzzy_score(word, name).map(|score| (score, name)))
+            .collect();
+        // The names are sorted alphabetically and the stable sort keeps the order of the names with the same score
+        scored.sort_by_key(|&(score, _)| cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(_, n)| n.to_string()).collect();
+        self.selected = 0;
+    }
+
+    /// Execute the selected command against the textarea with the arguments in the query. The query is cleared when
+    /// the command is executed successfully.
+    pub fn execute(&mut self, textarea: &mut TextArea<'_>) -> Result<String, CommandError> {
+        let name = match self.selected_name() {
+            Some(name) => name.to_string(),
+            None => return Err(CommandError::NotFound(self.query.trim().to_string())),
+        };
+        let words = split_words(&self.query)?;
+        let args: Vec<&str> = words.iter().skip(1).map(String::as_str).collect();
+        self.registry.execute(textarea, &name, &args)?;
+        self.reset();
+        Ok(name)
+    }
+
+    /// Handle a key input for the palette. Characters and Backspace edit the query, Ctrl+U clears it, Up and Down (or
+    /// Ctrl+P and Ctrl+N) move the selection, Enter executes the selected command and Esc cancels the palette
+    /// clearing the query.
+    pub fn input(&mut self, input: impl Into<Input>, textarea: &mut TextArea<'_>) -> PaletteInput {
+        let len = self.matches.len();
+        match input.into() {
+            Input {
+                key: Key::Char('u'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.reset(),
+            Input { key: Key::Up, .. }
+            | Input {
+                key: Key::Char('p'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.selected = self.selected.saturating_sub(1),
+            Input { key: Key::Down, .. }
+            | Input {
+                key: Key::Char('n'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.selected = cmp::min(self.selected + 1, len.saturating_sub(1)),
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } => {
+                self.query.push(c);
+                self.update();
+            }
+            Input {
+                key: Key::Backspace,
+                ..
+            } => {
+                if self.query.pop().is_none() {
+                    return PaletteInput::Ignored;
+                }
+                self.update();
+            }
+            Input {
+                key: Key::Enter, ..
+            } => {
+                return match self.execute(textarea) {
+                    Ok(name) => PaletteInput::Executed(name),
+                    Err(err) => PaletteInput::Failed(err),
+                };
+            }
+            Input { key: Key::Esc, .. } => {
+                self.reset();
+                return PaletteInput::Cancelled;
+            }
+            _ => return PaletteInput::Ignored,
+        }
+        PaletteInput::Handled
+    }
+
+    /// Set the block of the palette.
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of the palette which was set by [`CommandPalette::set_block`].
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of the palette if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Set the style of the palette. The default style is empty.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the style of the palette.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Set the style of the selected command. The default style is reversed.
+    pub fn set_selected_style(&mut self, style: Style) {
+        self.selected_style = style;
+    }
+
+    /// Get the style of the selected command.
+    pub fn selected_style(&self) -> Style {
+        self.selected_style
+    }
+
+    /// Set the style of the descriptions of the commands. The default style is dark gray foreground.
+    pub fn set_description_style(&mut self, style: Style) {
+        self.description_style = style;
+    }
+
+    /// Get the style of the descriptions of the commands.
+    pub fn description_style(&self) -> Style {
+        self.description_style
+    }
+
+    /// Build a widget to render the palette. The first row shows the query and the following rows list the matched
+    /// commands with their descriptions. The textarea is used to apply its color profile to the styles.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::{CommandPalette, CommandRegistry, TextArea};
+    ///
+    /// let textarea = TextArea::default();
+    /// let mut palette = CommandPalette::new(CommandRegistry::with_builtins());
+    /// palette.set_query("redo");
+    ///
+    /// let area = Rect::new(0, 0, 12, 2);
+    /// let mut buf = Buffer::empty(area);
+    /// palette.widget(&textarea).render(area, &mut buf);
+    /// let row = |y: usize| buf.content[y * 12..(y + 1) * 12].iter().map(|c| c.symbol()).collect::<String>();
+    /// assert_eq!(row(0), "> redo      ");
+    /// assert_eq!(row(1), "redo  Redo  ");
+    /// ```
+    pub fn widget<'w>(&'w self, textarea: &'w TextArea<'w>) -> impl Widget + 'w {
+        CommandPaletteWidget {
+            palette: self,
+            textarea,
+        }
+    }
+}
+
+/// Score of the fuzzy match of the pattern in the name. `None` when the name does not contain the characters of the
+/// pattern in order. Characters are compared case-insensitively. When the characters can be matched in several ways,
+/// the best score is taken so that "dl" matches the "l" of "lines" rather than the "l" of "duplicate".
+fn fuzzy_score(pattern: &str, name: &str) -> Option<u32> {
+    const WORD_START: u32 = 8;
+    const CONSECUTIVE: u32 = 4;
+
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let name: Vec<char> = name.chars().collect();
+    if pattern.is_empty() {
+        return Some(0);
+    }
+    let eq = |p: char, c: char| c.to_lowercase().eq(std::iter::once(p));
+    let word_start = |j: usize| j == 0 || !name[j - 1].is_alphanumeric();
+
+    // scores[j] is the best score of matching the pattern so far where the last character is matched at name[j]
+    let mut scores: Vec<Option<u32>> = name
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| eq(pattern[0], c).then(|| 1 + if word_start(j) { WORD_START } else { 0 }))
+        .collect();
+    for &p in &pattern[1..] {
+        let mut next = vec![None; name.len()];
+        let mut best_before: Option<u32> = None; // Best score matched before name[j - 1]
+        for j in 1..name.len() {
+            if j >= 2 {
+                best_before = cmp::max(best_before, scores[j - 2]);
+            }
+            if !eq(p, name[j]) {
+                continue;
+            }
+            let bonus = if word_start(j) { WORD_START } else { 0 };
+            let gap = best_before.map(|s| s + 1 + bonus);
+            let consecutive = scores[j - 1].map(|s| s + 1 + cmp::max(bonus, CONSECUTIVE));
+            next[j] = cmp::max(gap, consecutive);
+        }
+        scores = next;
+    }
+    scores.into_iter().flatten().max()
+}
+
+struct CommandPaletteWidget<'w> {
+    palette: &'w CommandPalette<'w>,
+    textarea: &'w TextArea<'w>,
+}
+
+impl Widget for CommandPaletteWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Self { palette, textarea } = self;
+        let inner = if let Some(b) = palette.block() {
+            let inner = b.inner(area);
+            #[cfg(feature = "tuirs")]
+            let b = b.clone();
+            b.render(area, buf);
+            inner
+        } else {
+            area
+        };
+
+        let style = textarea.cue_style(palette.style, Modifier::empty());
+        buf.set_style(inner, style);
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+        let width = inner.width as usize;
+        buf.set_stringn(
+            inner.x,
+            inner.y,
+            format!("> {}", palette.query),
+            width,
+            style,
+        );
+
+        let selected_style = textarea.cue_style(palette.selected_style, Modifier::REVERSED);
+        let desc_style = textarea.cue_style(palette.description_style, Modifier::empty());
+        let height = inner.height as usize - 1;
+        let name_len = palette
+            .matches
+            .iter()
+            .map(|n| n.chars().count())
+            .max()
+            .unwrap_or(0);
+        // Scroll the items so that the selected item is visible
+        let offset = (palette.selected + 1).saturating_sub(height);
+
+        for (i, name) in palette.matches.iter().enumerate().skip(offset).take(height) {
+            let y = inner.y + 1 + (i - offset) as u16;
+            let style = if i == palette.selected {
+                style.patch(selected_style)
+            } else {
+                style
+            };
+            buf.set_stringn(inner.x, y, " ".repeat(width), width, style);
+            let name = format!("{:<1$}  ", name, name_len);
+            let (x, _) = buf.set_stringn(inner.x, y, name, width, style);
+            let desc = palette.registry.description(&palette.matches[i]);
+            let rest = inner.right().saturating_sub(x) as usize;
+            buf.set_stringn(x, y, desc.unwrap_or(""), rest, style.patch(desc_style));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match() {
+        assert_eq!(fuzzy_score("", "undo"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "undo"), None);
+        assert_eq!(fuzzy_score("ud", "undo"), Some(10));
+        assert_eq!(fuzzy_score("UN", "undo"), Some(14));
+        assert_eq!(fuzzy_score("dl", "duplicate-lines"), Some(18));
+        assert_eq!(fuzzy_score("ml", "move-lines-up"), Some(18));
+        // Matches at word starts are ranked higher than matches in the middle of words
+        assert!(fuzzy_score("dc", "delete-char") > fuzzy_score("dc", "dedicated"));
+        assert!(fuzzy_score("dl", "delete-line-by-end") > fuzzy_score("dl", "dedent"));
+    }
+
+    #[test]
+    fn input_and_execute() {
+        let mut registry = CommandRegistry::default();
+        registry.register_with_description("fail", "Always fail", |_, _| Err("oops".into()));
+        registry.register("append", |t, args| {
+            t.insert_str(args.join(","));
+            Ok(())
+        });
+        let mut palette = CommandPalette::new(registry);
+        assert_eq!(palette.matches().collect::<Vec<_>>(), ["append", "fail"]);
+
+        let mut t = TextArea::default();
+        let key = |key| Input {
+            key,
+            ..Default::default()
+        };
+        assert_eq!(palette.input(key(Key::Down), &mut t), PaletteInput::Handled);
+        assert_eq!(palette.selected_name(), Some("fail"));
+        assert_eq!(palette.input(key(Key::Down), &mut t), PaletteInput::Handled);
+        assert_eq!(palette.selected(), 1);
+        assert_eq!(
+            palette.input(key(Key::Enter), &mut t),
+            PaletteInput::Failed(CommandError::Failed {
+                name: "fail".into(),
+                message: "oops".into(),
+            }),
+        );
+        // The query is kept on failure
+        assert_eq!(palette.selected(), 1);
+
+        palette.set_query(r#"ap a "b c""#);
+        assert_eq!(
+            palette.input(key(Key::Enter), &mut t),
+            PaletteInput::Executed("append".into()),
+        );
+        assert_eq!(t.lines(), ["a,b c"]);
+        assert_eq!(palette.query(), "");
+
+        palette.set_query("zz");
+        assert_eq!(palette.selected_name(), None);
+        assert_eq!(
+            palette.input(key(Key::Enter), &mut t),
+            PaletteInput::Failed(CommandError::NotFound("zz".into())),
+        );
+        assert_eq!(
+            palette.input(key(Key::Backspace), &mut t),
+            PaletteInput::Handled
+        );
+        assert_eq!(palette.query(), "z");
+        assert_eq!(
+            palette.input(key(Key::Esc), &mut t),
+            PaletteInput::Cancelled
+        );
+        assert_eq!(palette.query(), "");
+        assert_eq!(
+            palette.input(key(Key::Backspace), &mut t),
+            PaletteInput::Ignored
+        );
+        assert_eq!(palette.input(key(Key::Tab), &mut t), PaletteInput::Ignored);
+    }
+}
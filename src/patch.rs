@@ -0,0 +1,260 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+use std::error::Error;
+use std::fmt;
+
+/// A hunk of a patch which replaces some lines with other lines. See
+/// [`TextArea::apply_patch`](crate::TextArea::apply_patch) for the usage.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct Hunk {
+    /// 0-based row where the hunk starts in the text before applying the patch.
+    pub start: usize,
+    /// Lines which are expected at the `start` row. They are replaced with [`Hunk::new_lines`]. Context lines of a
+    /// unified diff are contained in both this and [`Hunk::new_lines`].
+    pub old_lines: Vec<String>,
+    /// Lines which replace [`Hunk::old_lines`].
+    pub new_lines: Vec<String>,
+}
+
+impl Hunk {
+    /// Create a new hunk replacing `old_lines` at the `start` row with `new_lines`.
+    /// ```
+    /// use tui_textarea::Hunk;
+    ///
+    /// let hunk = Hunk::new(1, ["b"], ["B", "C"]);
+    /// assert_eq!(hunk.start, 1);
+    /// assert_eq!(hunk.old_lines, ["b"]);
+    /// assert_eq!(hunk.new_lines, ["B", "C"]);
+    /// ```
+    pub fn new<O, N>(start: usize, old_lines: O, new_lines: N) -> Self
+    where
+        O: IntoIterator,
+        O::Item: Into<String>,
+        N: IntoIterator,
+        N::Item: Into<String>,
+    {
+        Self {
+            start,
+            old_lines: old_lines.into_iter().map(Into::into).collect(),
+            new_lines: new_lines.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parse hunks in a unified diff text. File headers such as `---`, `+++`, `diff` and `index` lines are ignored.
+    /// When the text contains a diff of multiple files, all hunks are returned in order.
+    /// ```
+    /// use tui_textarea::Hunk;
+    ///
+    /// let diff = "\
+    /// --- a/hello.txt
+    /// +++ b/hello.txt
+    /// @@ -1,3 +1,3 @@
+    ///  hello
+    /// -world
+    /// +patched
+    ///  goodbye
+    /// ";
+    /// let hunks = Hunk::parse_unified(diff).unwrap();
+    /// assert_eq!(hunks, [Hunk::new(0, ["hello", "world", "goodbye"], ["hello", "patched", "goodbye"])]);
+    /// ```
+    pub fn parse_unified(diff: &str) -> Result<Vec<Self>, PatchError> {
+        fn parse_range(s: &str) -> Option<(usize, usize)> {
+            let mut it = s.splitn(2, ',');
+            let start = it.next()?.parse().ok()?;
+            let len = match it.next() {
+                Some(l) => l.parse().ok()?,
+                None => 1,
+            };
+            Some((start, len))
+        }
+
+        let mut hunks = vec![];
+        let mut lines = diff.lines().enumerate();
+        while let Some((idx, line)) = lines.next() {
+            if !line.starts_with("@@") {
+                continue; // Skip file headers
+            }
+            let parse_error = |message: &str| PatchError::Parse {
+                line: idx + 1,
+                message: message.to_string(),
+            };
+
+            let mut ranges = line.split(' ').skip(1);
+            let old = ranges
+                .next()
+                .and_then(|r| r.strip_prefix('-'))
+                .and_then(parse_range);
+            let new = ranges
+                .next()
+                .and_then(|r| r.strip_prefix('+'))
+                .and_then(parse_range);
+            let ((old_start, mut old_len), (_, mut new_len)) = match (old, new) {
+                (Some(o), Some(n)) => (o, n),
+                _ => return Err(parse_error("invalid hunk header")),
+            };
+
+            let mut hunk = Hunk {
+                // When no line is removed, the start line number points the line before the hunk
+                start: if old_len == 0 {
+                    old_start
+                } else {
+                    old_start.saturating_sub(1)
+                },
+                ..Default::default()
+            };
+            while old_len > 0 || new_len > 0 {
+                let (idx, line) = match lines.next() {
+                    Some(l) => l,
+                    None => return Err(parse_error("unexpected end of hunk")),
+                };
+                let (marker, text) = line.split_at(line.len().min(1));
+                match marker {
+                    " " | "" if old_len > 0 && new_len > 0 => {
+                        hunk.old_lines.push(text.to_string());
+                        hunk.new_lines.push(text.to_string());
+                        old_len -= 1;
+                        new_len -= 1;
+                    }
+                    "-" if old_len > 0 => {
+                        hunk.old_lines.push(text.to_string());
+                        old_len -= 1;
+                    }
+                    "+" if new_len > 0 => {
+                        hunk.new_lines.push(text.to_string());
+                        new_len -= 1;
+                    }
+                    "\\" => {} // "\ No newline at end of file"
+                    _ => {
+                        return Err(PatchError::Parse {
+                            line: idx + 1,
+                            message: format!("unexpected line in hunk: {:?}", line),
+                        })
+                    }
+                }
+            }
+            hunks.push(hunk);
+        }
+
+        Ok(hunks)
+    }
+}
+
+/// An edit which replaces the text in a range with a new text, like `TextEdit` of the Language Server Protocol. See
+/// [`TextArea::apply_edits`](crate::TextArea::apply_edits) for the usage.
+///
+/// Positions are 0-based (row, column) pairs where the column is counted in characters. Columns counted in UTF-16
+/// code units, as the Language Server Protocol does by default, need to be converted by the caller.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct TextEdit {
+    /// Start and end positions of the replaced text. When both are the same, the edit inserts the text.
+    pub range: ((usize, usize), (usize, usize)),
+    /// Text which replaces the range. Newlines are `\n`. When it is empty, the edit deletes the text in the range.
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Create a new edit replacing the text between `start` and `end` with `new_text`.
+    /// ```
+    /// use tui_textarea::TextEdit;
+    ///
+    /// let edit = TextEdit::new((0, 1), (2, 0), "x");
+    /// assert_eq!(edit.range, ((0, 1), (2, 0)));
+    /// assert_eq!(edit.new_text, "x");
+    /// ```
+    pub fn new(start: (usize, usize), end: (usize, usize), new_text: impl Into<String>) -> Self {
+        Self {
+            range: (start, end),
+            new_text: new_text.into(),
+        }
+    }
+}
+
+/// An error returned from [`TextArea::apply_patch`](crate::TextArea::apply_patch),
+/// [`TextArea::apply_edits`](crate::TextArea::apply_edits) and [`Hunk::parse_unified`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatchError {
+    /// The lines in the text don't match to the old lines of the hunk, or the hunk overlaps with the previous hunk.
+    /// `hunk` is an index of the hunk and `row` is a 0-based row where the hunk was expected to be applied.
+    Conflict { hunk: usize, row: usize },
+    /// The unified diff text is broken at the 1-based `line`.
+    Parse { line: usize, message: String },
+    /// The range of the [`TextEdit`] at the index `edit` is out of the text, its start is after its end, or it overlaps
+    /// with the range of another edit.
+    InvalidRange { edit: usize },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conflict { hunk, row } => {
+                write!(
+                    f,
+                    "hunk #{} conflicts with the text at line {}",
+                    hunk + 1,
+                    row + 1
+                )
+            }
+            Self::Parse { line, message } => {
+                write!(f, "could not parse diff at line {}: {}", line, message)
+            }
+            Self::InvalidRange { edit } => {
+                write!(f, "range of edit #{} is invalid or overlapping", edit + 1)
+            }
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unified_diff() {
+        let diff = "\
+diff --git a/a.txt b/a.txt
+index 1234567..89abcde 100644
+--- a/a.txt
++++ b/a.txt
+@@ -1,2 +1,2 @@
+-a
++A
+ b
+@@ -10,0 +11,2 @@ fn foo() {
++x
++y
+@@ -20 +21,0 @@
+-z
+\\ No newline at end of file
+";
+        let hunks = Hunk::parse_unified(diff).unwrap();
+        assert_eq!(
+            hunks,
+            [
+                Hunk::new(0, ["a", "b"], ["A", "b"]),
+                Hunk::new(10, [""; 0], ["x", "y"]),
+                Hunk::new(19, ["z"], [""; 0]),
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_broken_unified_diff() {
+        for (diff, line) in [
+            ("@@ -1,2 @@\n", 1),
+            ("@@ -a,2 +1,2 @@\n", 1),
+            ("@@ -1,2 +1,2 @@\n a\n", 1),
+            ("--- a\n+++ b\n@@ -1,2 +1,2 @@\n a\n?b\n", 5),
+            ("@@ -1 +1 @@\n+a\n+b\n", 3),
+        ] {
+            match Hunk::parse_unified(diff) {
+                Err(PatchError::Parse { line: l, .. }) => assert_eq!(l, line, "{:?}", diff),
+                res => panic!("unexpected result {:?} for {:?}", res, diff),
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::ratatui::style::{Modifier, Style};
+
+/// Specify how [`TextArea`] renders visual cues such as the cursor, the selection and search matches. Set the profile
+/// with [`TextArea::set_color_profile`].
+///
+/// This type is marked as `#[non_exhaustive]` since more profiles may be supported in the future.
+///
+/// [`TextArea`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html
+/// [`TextArea::set_color_profile`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_color_profile
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorProfile {
+    /// Render the styles as they are set. This is the default profile.
+    Full,
+    /// Render without colors for monochrome terminals. Foreground and background colors of all styles are ignored and
+    /// the visual cues are rendered with text attributes instead:
+    ///
+    /// | Cue                | Attributes            |
+    /// |--------------------|-----------------------|
+    /// | Cursor             | Reversed, bold        |
+    /// | Selection          | Reversed              |
+    /// | Search match       | Underlined, bold      |
+    /// | Matching bracket   | Underlined, bold      |
+    /// | Jump label         | Reversed, bold        |
+    /// | Template field     | Underlined            |
+    /// | Invalid field      | Underlined, italic    |
+    /// | Cursor column      | Bold                  |
+    /// | Soft limit         | Underlined            |
+    /// | Color column       | Reversed              |
+    /// | Comment line       | Dim                   |
+    /// | EOL annotation     | Dim                   |
+    /// | Placeholder        | Dim                   |
+    ///
+    /// Modifiers set in the styles such as the underline of the cursor line are kept.
+    Monochrome,
+}
+
+impl Default for ColorProfile {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl ColorProfile {
+    /// Convert the style of a visual cue for this profile. `cue` is the text attributes to show the cue without colors.
+    pub(crate) fn apply(self, style: Style, cue: Modifier) -> Style {
+        match self {
+            Self::Full => style,
+            Self::Monochrome => Style::default().add_modifier(style.add_modifier | cue),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::ratatui::text::Span;
+#[cfg(feature = "ratatui")]
+use ratatui::text::Line;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Write as _};
+use std::hash::Hasher;
+use std::ops::Range;
+use std::sync::Mutex;
+#[cfg(feature = "tuirs")]
+use tui::text::Spans as Line;
+
+/// Line rendered at the last frame with the state it was rendered with.
+struct CachedLine {
+    /// Text generation at which `text` was known to be the text of the line
+    generation: u64,
+    text: String,
+    /// Hash of the other state which affects the rendering. See `state_hash`
+    state: u64,
+    line: Line<'static>,
+}
+
+/// Cache of the rendered lines keyed by their rows. Rendering only takes `&TextArea` so the cache is mutated through
+/// `Mutex`. Cloning the cache does not copy the cached lines.
+pub struct RenderCache {
+    enabled: bool,
+    lines: Mutex<BTreeMap<usize, CachedLine>>,
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lines: Mutex::default(),
+        }
+    }
+}
+
+impl Clone for RenderCache {
+    fn clone(&self) -> Self {
+        Self {
+            enabled: self.enabled,
+            lines: Mutex::default(),
+        }
+    }
+}
+
+impl Debug for RenderCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.lines.lock().map_or(0, |l| l.len());
+        f.debug_struct("RenderCache")
+            .field("enabled", &self.enabled)
+            .field("lines", &len)
+            .finish()
+    }
+}
+
+impl RenderCache {
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.clear();
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.clear();
+        }
+    }
+
+    /// Get the line rendered at the row when its text and state are not changed. The text is compared only when the
+    /// text was modified since the line was cached.
+    pub fn get(
+        &self,
+        row: usize,
+        generation: u64,
+        text: &str,
+        state: u64,
+    ) -> Option<Line<'static>> {
+        let mut lines = self.lines.lock().ok()?;
+        let cached = lines.get_mut(&row)?;
+        if cached.state != state {
+            return None;
+        }
+        if cached.generation != generation {
+            if cached.text != text {
+                return None;
+            }
+            cached.generation = generation;
+        }
+        Some(cached.line.clone())
+    }
+
+    pub fn insert(&self, row: usize, generation: u64, text: &str, state: u64, line: &Line<'_>) {
+        if let Ok(mut lines) = self.lines.lock() {
+            let cached = CachedLine {
+                generation,
+                text: text.to_string(),
+                state,
+                line: into_owned(line),
+            };
+            lines.insert(row, cached);
+        }
+    }
+
+    /// Drop the lines out of the rows so that the cache does not grow beyond the viewport.
+    pub fn retain(&self, rows: Range<usize>) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.retain(|row, _| rows.contains(row));
+        }
+    }
+
+    #[cfg(test)]
+    pub fn rows(&self) -> Vec<usize> {
+        self.lines.lock().unwrap().keys().copied().collect()
+    }
+}
+
+fn into_owned(line: &Line<'_>) -> Line<'static> {
+    let owned = |s: &Span<'_>| Span::styled(s.content.to_string(), s.style);
+    #[cfg(feature = "ratatui")]
+    {
+        Line {
+            spans: line.spans.iter().map(owned).collect(),
+            style: line.style,
+            alignment: line.alignment,
+        }
+    }
+    #[cfg(feature = "tuirs")]
+    {
+        Line(line.0.iter().map(owned).collect())
+    }
+}
+
+struct HashWriter(DefaultHasher);
+
+impl fmt::Write for HashWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Hash the state which affects the rendering. The `Debug` representation is hashed since styles of tui-rs do not
+/// implement `Hash`.
+pub fn state_hash(state: &impl Debug) -> u64 {
+    let mut w = HashWriter(DefaultHasher::new());
+    let _ = write!(w, "{:?}", state);
+    w.0.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_cached_line() {
+        let cache = RenderCache::default();
+        let line = Line::from("abc");
+        cache.insert(0, 1, "abc", 42, &line);
+
+        assert_eq!(cache.get(0, 1, "abc", 42), Some(line.clone()));
+        assert_eq!(cache.get(1, 1, "abc", 42), None);
+        assert_eq!(cache.get(0, 1, "abc", 0), None);
+        // The text is compared after the text was modified
+        assert_eq!(cache.get(0, 2, "abd", 42), None);
+        assert_eq!(cache.get(0, 2, "abc", 42), Some(line.clone()));
+
+        cache.insert(3, 1, "x", 42, &line);
+        cache.retain(1..5);
+        assert_eq!(cache.rows(), [3]);
+        assert_eq!(cache.clone().rows(), []);
+    }
+
+    #[test]
+    fn hash_state() {
+        assert_eq!(state_hash(&(1, "a")), state_hash(&(1, "a")));
+        assert_ne!(state_hash(&(1, "a")), state_hash(&(1, "b")));
+    }
+}
@@ -0,0 +1,100 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+
+/// Options to write the text to a file with [`TextArea::save_to_path`].
+///
+/// This type is marked as `#[non_exhaustive]` since more options may be added in the future. Create an instance with
+/// [`SaveOptions::default`] and update its fields.
+/// ```
+/// use tui_textarea::SaveOptions;
+///
+/// let mut options = SaveOptions::default();
+/// options.backup = true;
+/// assert!(options.atomic_rename);
+/// assert!(options.fsync);
+/// ```
+///
+/// [`TextArea::save_to_path`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.save_to_path
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Copy the existing file to the path with `~` suffix (e.g. `foo.txt~`) before writing the file. The default value
+    /// is `false`.
+    pub backup: bool,
+    /// Write the text to a temporary file in the same directory and rename it to the path. The file is replaced at
+    /// once so it is never truncated or partially written even if the writing fails. Permissions of the existing file
+    /// are kept. The default value is `true`.
+    pub atomic_rename: bool,
+    /// Flush the written data to the storage device before returning. The default value is `true`.
+    pub fsync: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            backup: false,
+            atomic_rename: true,
+            fsync: true,
+        }
+    }
+}
+
+fn with_suffix(path: &Path, prefix: &str, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(prefix);
+    name.push(path.file_name().unwrap_or_default());
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn write_to(path: &Path, content: &str, fsync: bool) -> io::Result<fs::File> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    if fsync {
+        file.sync_all()?;
+    }
+    Ok(file)
+}
+
+/// Write the content to the file at the path following the options.
+pub fn save(path: &Path, content: &str, options: &SaveOptions) -> io::Result<()> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => Some(meta),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+
+    if options.backup && meta.is_some() {
+        fs::copy(path, with_suffix(path, "", "~"))?;
+    }
+
+    if !options.atomic_rename {
+        write_to(path, content, options.fsync)?;
+        return Ok(());
+    }
+
+    let tmp = with_suffix(path, ".", &format!(".{}.tmp", std::process::id()));
+    let written = write_to(&tmp, content, options.fsync).and_then(|file| {
+        if let Some(meta) = &meta {
+            file.set_permissions(meta.permissions())?;
+        }
+        fs::rename(&tmp, path)
+    });
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(err);
+    }
+
+    if options.fsync {
+        // Persist the rename. Opening a directory is not supported on some platforms such as Windows
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        if let Ok(dir) = fs::File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
@@ -1,10 +1,15 @@
-use crate::ratatui::style::{Color, Style};
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::textarea::TextArea;
 use regex::Regex;
 
 #[derive(Clone, Debug)]
 pub struct Search {
     pub pat: Option<Regex>,
     pub style: Style,
+    pub replacement: Option<String>,
+    pub preview_style: Style,
+    /// Pattern of the line filter. See `TextArea::set_line_filter`
+    pub filter: Option<Regex>,
 }
 
 impl Default for Search {
@@ -12,6 +17,9 @@ impl Default for Search {
         Self {
             pat: None,
             style: Style::default().bg(Color::Blue),
+            replacement: None,
+            preview_style: Style::default().add_modifier(Modifier::DIM),
+            filter: None,
         }
     }
 }
@@ -26,6 +34,37 @@ impl Search {
         Some(matches)
     }
 
+    /// Find the match starting at the byte offset in the line and expand the replacement text for it. The result is
+    /// the byte range of the match and the expanded replacement.
+    pub fn replacement_at(&self, line: &str, offset: usize) -> Option<(usize, usize, String)> {
+        let pat = self.pat.as_ref()?;
+        let replacement = self.replacement.as_ref()?;
+        let caps = pat
+            .captures_iter(line)
+            .take_while(|c| c.get(0).map_or(false, |m| m.start() <= offset))
+            .find(|c| c.get(0).map_or(false, |m| m.start() == offset))?;
+        let m = caps.get(0)?;
+        let mut expanded = String::new();
+        caps.expand(replacement, &mut expanded);
+        Some((m.start(), m.end(), expanded))
+    }
+
+    /// All matches in the line with their expanded replacement texts.
+    pub fn replacements(&self, line: &str) -> Vec<(usize, usize, String)> {
+        let (pat, replacement) = match (&self.pat, &self.replacement) {
+            (Some(p), Some(r)) => (p, r),
+            _ => return vec![],
+        };
+        pat.captures_iter(line)
+            .filter_map(|caps| {
+                let m = caps.get(0)?;
+                let mut expanded = String::new();
+                caps.expand(replacement, &mut expanded);
+                Some((m.start(), m.end(), expanded))
+            })
+            .collect()
+    }
+
     pub fn set_pattern(&mut self, query: &str) -> Result<(), regex::Error> {
         match &self.pat {
             Some(r) if r.as_str() == query => {}
@@ -152,6 +191,235 @@ impl Search {
     }
 }
 
+/// Result of moving to a match in [`IncrementalSearch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SearchStatus {
+    /// A match was found.
+    Found,
+    /// A match was found after wrapping around the end of the text (or the start of the text on backward search).
+    Wrapped,
+    /// No match was found or the pattern is empty.
+    NotFound,
+}
+
+/// Session of incremental text search, which is useful to implement a search prompt like `/` in Vim.
+///
+/// A session remembers the cursor position when it started. Every time the pattern is updated, the nearest match from
+/// the position is selected and the cursor moves to the start of the match, so the match is scrolled into view on the
+/// next render. All matches in the text are highlighted with [`TextArea::search_style`]. The text of the textarea
+/// should not be modified while the session is ongoing except by [`IncrementalSearch::replace_match`].
+///
+/// ```
+/// use tui_textarea::{TextArea, IncrementalSearch, SearchStatus};
+///
+/// let mut textarea = TextArea::from(["hello", "world", "help"]);
+///
+/// let mut search = IncrementalSearch::new(&mut textarea);
+/// for c in "hel".chars() {
+///     search.push_char(&mut textarea, c).unwrap();
+/// }
+/// // The nearest match is selected
+/// assert_eq!(textarea.cursor(), (0, 0));
+/// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 3))));
+///
+/// // Cycle matches
+/// assert_eq!(search.next_match(&mut textarea), SearchStatus::Found);
+/// assert_eq!(textarea.cursor(), (2, 0));
+/// assert_eq!(search.next_match(&mut textarea), SearchStatus::Wrapped);
+/// assert_eq!(textarea.cursor(), (0, 0));
+///
+/// // Narrow the pattern
+/// assert_eq!(search.push_char(&mut textarea, 'p'), Ok(SearchStatus::Found));
+/// assert_eq!(textarea.cursor(), (2, 0));
+///
+/// // Finish the search keeping the cursor position. `search.cancel(&mut textarea)` moves the cursor back instead
+/// search.finish(&mut textarea);
+/// assert_eq!(textarea.cursor(), (2, 0));
+/// assert_eq!(textarea.selection_range(), None);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+#[derive(Clone, Debug)]
+pub struct IncrementalSearch {
+    pattern: String,
+    origin: (usize, usize),
+    current: Option<(usize, usize)>,
+    backward: bool,
+}
+
+impl IncrementalSearch {
+    /// Start a new incremental search session searching the text forward from the current cursor position. The
+    /// search pattern of the textarea is cleared.
+    pub fn new(textarea: &mut TextArea<'_>) -> Self {
+        Self::start(textarea, false)
+    }
+
+    /// Start a new incremental search session searching the text backward from the current cursor position, like `?`
+    /// in Vim. The search pattern of the textarea is cleared.
+    pub fn backward(textarea: &mut TextArea<'_>) -> Self {
+        Self::start(textarea, true)
+    }
+
+    fn start(textarea: &mut TextArea<'_>, backward: bool) -> Self {
+        let _ = textarea.set_search_pattern("");
+        Self {
+            pattern: String::new(),
+            origin: textarea.cursor(),
+            current: None,
+            backward,
+        }
+    }
+
+    /// Get the current search pattern.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Get if this session searches the text backward or not.
+    pub fn is_backward(&self) -> bool {
+        self.backward
+    }
+
+    /// Append a character to the search pattern and select the nearest match. While the pattern is an invalid regular
+    /// expression, the error is returned and the previous matches are kept.
+    pub fn push_char(
+        &mut self,
+        textarea: &mut TextArea<'_>,
+        c: char,
+    ) -> Result<SearchStatus, regex::Error> {
+        self.pattern.push(c);
+        self.update(textarea)
+    }
+
+    /// Remove the last character from the search pattern and select the nearest match. When the pattern is already
+    /// empty, this method does nothing and returns [`SearchStatus::NotFound`].
+    pub fn pop_char(&mut self, textarea: &mut TextArea<'_>) -> Result<SearchStatus, regex::Error> {
+        if self.pattern.pop().is_none() {
+            return Ok(SearchStatus::NotFound);
+        }
+        self.update(textarea)
+    }
+
+    /// Replace the whole search pattern and select the nearest match.
+    pub fn set_pattern(
+        &mut self,
+        textarea: &mut TextArea<'_>,
+        pattern: impl Into<String>,
+    ) -> Result<SearchStatus, regex::Error> {
+        self.pattern = pattern.into();
+        self.update(textarea)
+    }
+
+    fn update(&mut self, textarea: &mut TextArea<'_>) -> Result<SearchStatus, regex::Error> {
+        textarea.set_search_pattern(&self.pattern)?;
+        Ok(self.jump(textarea, self.origin, true, self.backward))
+    }
+
+    /// Select the next match in the search direction. When no match is selected yet, search from the position where the
+    /// session started.
+    pub fn next_match(&mut self, textarea: &mut TextArea<'_>) -> SearchStatus {
+        self.jump_from_current(textarea, self.backward)
+    }
+
+    /// Select the previous match in the search direction. When no match is selected yet, search from the position where
+    /// the session started.
+    pub fn prev_match(&mut self, textarea: &mut TextArea<'_>) -> SearchStatus {
+        self.jump_from_current(textarea, !self.backward)
+    }
+
+    fn jump_from_current(&mut self, textarea: &mut TextArea<'_>, backward: bool) -> SearchStatus {
+        match self.current {
+            Some(pos) => self.jump(textarea, pos, false, backward),
+            None => self.jump(textarea, self.origin, true, backward),
+        }
+    }
+
+    fn jump(
+        &mut self,
+        textarea: &mut TextArea<'_>,
+        from: (usize, usize),
+        match_cursor: bool,
+        backward: bool,
+    ) -> SearchStatus {
+        self.current = textarea.select_search_match(from, match_cursor, backward);
+        let found = match self.current {
+            Some(pos) => pos,
+            None => {
+                let origin = textarea.clamp_cursor(self.origin);
+                textarea.set_cursor_with_shift(origin, false);
+                return SearchStatus::NotFound;
+            }
+        };
+        let from = textarea.clamp_cursor(from);
+        let wrapped = if backward {
+            found > from || (found == from && !match_cursor)
+        } else {
+            found < from || (found == from && !match_cursor)
+        };
+        if wrapped {
+            SearchStatus::Wrapped
+        } else {
+            SearchStatus::Found
+        }
+    }
+
+    /// Get the start position of the currently selected match.
+    pub fn current_match(&self) -> Option<(usize, usize)> {
+        self.current
+    }
+
+    /// Replace the currently selected match with the replacement text set by [`TextArea::set_search_replacement`] and
+    /// select the next match in the search direction. This is useful to confirm substitutions one by one while the
+    /// replacement of the selected match is previewed. When no match is selected or no replacement is set, nothing is
+    /// replaced and [`SearchStatus::NotFound`] is returned.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, IncrementalSearch, SearchStatus};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar foo", "foo"]);
+    ///
+    /// let mut search = IncrementalSearch::new(&mut textarea);
+    /// search.set_pattern(&mut textarea, "foo").unwrap();
+    /// textarea.set_search_replacement(Some("baz"));
+    ///
+    /// assert_eq!(search.replace_match(&mut textarea), SearchStatus::Found);
+    /// assert_eq!(search.current_match(), Some((0, 8)));
+    /// // Skip the match
+    /// search.next_match(&mut textarea);
+    /// assert_eq!(search.replace_match(&mut textarea), SearchStatus::Wrapped);
+    /// assert_eq!(search.current_match(), Some((0, 8)));
+    /// assert_eq!(textarea.lines(), ["baz bar foo", "baz"]);
+    /// ```
+    pub fn replace_match(&mut self, textarea: &mut TextArea<'_>) -> SearchStatus {
+        let pos = match self.current {
+            Some(pos) if pos == textarea.cursor() => pos,
+            _ => return SearchStatus::NotFound,
+        };
+        if !textarea.replace_search_match() {
+            return SearchStatus::NotFound;
+        }
+        if self.backward {
+            self.jump(textarea, pos, false, true)
+        } else {
+            let end = textarea.cursor();
+            self.jump(textarea, end, true, false)
+        }
+    }
+
+    /// Finish the session keeping the cursor at the selected match. The selection is cancelled and the search pattern
+    /// is kept so that [`TextArea::search_forward`] and [`TextArea::search_back`] can continue the search.
+    pub fn finish(self, textarea: &mut TextArea<'_>) {
+        textarea.cancel_selection();
+    }
+
+    /// Cancel the session. The cursor moves back to the position where the session started and the search pattern is
+    /// cleared.
+    pub fn cancel(self, textarea: &mut TextArea<'_>) {
+        let _ = textarea.set_search_pattern("");
+        let origin = textarea.clamp_cursor(self.origin);
+        textarea.set_cursor_with_shift(origin, false);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,69 @@
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// In the middle of a sentence
+    Text,
+    /// After `.`, `!` or `?` and closing brackets or quotes
+    End,
+    /// After the end of a sentence and spaces. The next non-space character starts a sentence
+    Start,
+}
+
+/// Find the positions where sentences start in the text. A sentence ends at `.`, `!` or `?` followed by a space or the
+/// end of line. Closing brackets and quotes may be put between them. An empty line is also a position of a sentence
+/// start since it separates paragraphs.
+pub fn sentence_starts(lines: &[String]) -> Vec<(usize, usize)> {
+    let mut starts = vec![];
+    let mut state = State::Start;
+    for (row, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            starts.push((row, 0));
+            state = State::Start;
+            continue;
+        }
+        for (col, c) in line.chars().enumerate() {
+            state = match (state, c) {
+                (State::Start, c) if c.is_whitespace() => State::Start,
+                (State::Start, _) => {
+                    starts.push((row, col));
+                    if matches!(c, '.' | '!' | '?') {
+                        State::End
+                    } else {
+                        State::Text
+                    }
+                }
+                (_, '.' | '!' | '?') => State::End,
+                (State::End, ')' | ']' | '"' | '\'') => State::End,
+                (State::End, c) if c.is_whitespace() => State::Start,
+                _ => State::Text,
+            };
+        }
+        if state == State::End {
+            state = State::Start;
+        }
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starts(lines: &[&str]) -> Vec<(usize, usize)> {
+        let lines: Vec<_> = lines.iter().map(|l| l.to_string()).collect();
+        sentence_starts(&lines)
+    }
+
+    #[test]
+    fn find_sentence_starts() {
+        assert_eq!(starts(&[""]), [(0, 0)]);
+        assert_eq!(starts(&["  Foo. Bar!  Baz"]), [(0, 2), (0, 7), (0, 13)]);
+        assert_eq!(starts(&["e.g. foo", "1.5 bar"]), [(0, 0), (0, 5)]);
+        assert_eq!(starts(&["(Foo.) \"Bar?\" Baz"]), [(0, 0), (0, 7), (0, 14)]);
+        assert_eq!(starts(&["Foo", "bar.", "  Baz"]), [(0, 0), (2, 2)]);
+        assert_eq!(
+            starts(&["Foo", "", "", "Bar"]),
+            [(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+        assert_eq!(starts(&["Foo.)x Bar"]), [(0, 0)]);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::change::ChangeEvent;
+
+/// Tab stop parsed from a snippet. `start` and `end` are character offsets in the expanded text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParsedStop {
+    pub number: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Parse the snippet text and expand its placeholders into their default values. The following syntax is supported.
+///
+/// - `$1`, `${1}`: Empty tab stop
+/// - `${1:default}`: Tab stop with the default text
+/// - `$0`: Final cursor position
+/// - `\$`, `\}`, `\\`: Literal characters
+///
+/// Only the first tab stop is kept when the same number appears more than once. The other ones are expanded to their
+/// default text. Nested placeholders are not supported. Malformed placeholders are kept as literal text. The returned
+/// tab stops are sorted by their numbers.
+pub fn parse(snippet: &str) -> (String, Vec<ParsedStop>) {
+    let chars: Vec<char> = snippet.chars().collect();
+    let mut text = String::new();
+    let mut len = 0;
+    let mut stops: Vec<ParsedStop> = vec![];
+    let mut i = 0;
+
+    let digits = |from: usize| -> (Option<usize>, usize) {
+        let n = chars[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        let number = chars[from..from + n]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok();
+        (number, from + n)
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        match (c, chars.get(i + 1)) {
+            ('\\', Some(&next @ ('$' | '}' | '\\'))) => {
+                text.push(next);
+                len += 1;
+                i += 2;
+                continue;
+            }
+            ('$', Some(d)) if d.is_ascii_digit() => {
+                let (number, next) = digits(i + 1);
+                if let Some(number) = number {
+                    if stops.iter().all(|s| s.number != number) {
+                        stops.push(ParsedStop {
+                            number,
+                            start: len,
+                            end: len,
+                        });
+                    }
+                    i = next;
+                    continue;
+                }
+            }
+            ('$', Some('{')) => {
+                if let (Some(number), next) = digits(i + 2) {
+                    let mut default = String::new();
+                    let mut j = next;
+                    let closed = match chars.get(j) {
+                        Some('}') => true,
+                        Some(':') => {
+                            j += 1;
+                            loop {
+                                match (chars.get(j), chars.get(j + 1)) {
+                                    (Some('\\'), Some(&next @ ('$' | '}' | '\\'))) => {
+                                        default.push(next);
+                                        j += 1;
+                                    }
+                                    (Some('}'), _) => break true,
+                                    (Some(&c), _) => default.push(c),
+                                    (None, _) => break false,
+                                }
+                                j += 1;
+                            }
+                        }
+                        _ => false,
+                    };
+                    if closed {
+                        let n = default.chars().count();
+                        if stops.iter().all(|s| s.number != number) {
+                            stops.push(ParsedStop {
+                                number,
+                                start: len,
+                                end: len + n,
+                            });
+                        }
+                        text.push_str(&default);
+                        len += n;
+                        i = j + 1;
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        text.push(c);
+        len += 1;
+        i += 1;
+    }
+
+    // `$0` is the last stop
+    stops.sort_by_key(|s| s.number.wrapping_sub(1));
+    (text, stops)
+}
+
+/// Position of the character offset in the text inserted at `origin`. In single-line mode, newlines are replaced with
+/// spaces so they don't move the position to the next row.
+pub fn position(
+    text: &str,
+    offset: usize,
+    origin: (usize, usize),
+    single_line: bool,
+) -> (usize, usize) {
+    let (mut row, mut col) = origin;
+    for c in text.chars().take(offset) {
+        if c == '\n' && !single_line {
+            row += 1;
+            col = 0;
+        } else {
+            col += 1;
+        }
+    }
+    (row, col)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TabStop {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+/// State of the snippet being filled in. The last stop is the final cursor position which ends the session.
+#[derive(Clone, Debug)]
+pub struct SnippetSession {
+    stops: Vec<TabStop>,
+    active: usize,
+}
+
+impl SnippetSession {
+    pub fn new(stops: Vec<TabStop>) -> Self {
+        debug_assert!(!stops.is_empty(), "snippet must have the final position");
+        Self { stops, active: 0 }
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> TabStop {
+        self.stops[self.active]
+    }
+
+    pub fn is_final(&self, index: usize) -> bool {
+        index + 1 == self.stops.len()
+    }
+
+    pub fn stop(&self, index: usize) -> Option<TabStop> {
+        self.stops.get(index).copied()
+    }
+
+    pub fn set_active(&mut self, index: usize) {
+        self.active = index;
+    }
+
+    /// Follow the text change. The active stop grows when text is inserted at its edges so that typed text stays in
+    /// the placeholder. Other stops don't grow.
+    pub fn apply(&mut self, change: &ChangeEvent) {
+        for (i, stop) in self.stops.iter_mut().enumerate() {
+            let active = i == self.active;
+            stop.start = change.adjust(stop.start, !active);
+            stop.end = change.adjust(stop.end, active).max(stop.start);
+        }
+    }
+
+    /// Check if the position is in the active stop, including its edges.
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        let TabStop { start, end } = self.active();
+        start <= pos && pos <= end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop(number: usize, start: usize, end: usize) -> ParsedStop {
+        ParsedStop { number, start, end }
+    }
+
+    #[test]
+    fn parse_placeholders() {
+        let (text, stops) = parse("fn ${1:name}($2) {\n    $0\n}");
+        assert_eq!(text, "fn name() {\n    \n}");
+        assert_eq!(stops, [stop(1, 3, 7), stop(2, 8, 8), stop(0, 16, 16)]);
+
+        let (text, stops) = parse("${2:b} ${1} $1 ${1:x}");
+        assert_eq!(text, "b   x");
+        assert_eq!(stops, [stop(1, 2, 2), stop(2, 0, 1)]);
+
+        let (text, stops) = parse(r"\$1 ${1:a\}b} $ ${x} ${1:open");
+        assert_eq!(text, "$1 a}b $ ${x} ${1:open");
+        assert_eq!(stops, [stop(1, 3, 6)]);
+    }
+
+    #[test]
+    fn position_in_text() {
+        let text = "ab\ncd";
+        assert_eq!(position(text, 1, (2, 3), false), (2, 4));
+        assert_eq!(position(text, 4, (2, 3), false), (3, 1));
+        assert_eq!(position(text, 4, (2, 3), true), (2, 7));
+    }
+
+    #[test]
+    fn follow_changes() {
+        let mut s = SnippetSession::new(vec![
+            TabStop {
+                start: (0, 2),
+                end: (0, 2),
+            },
+            TabStop {
+                start: (0, 2),
+                end: (0, 5),
+            },
+            TabStop {
+                start: (1, 0),
+                end: (1, 0),
+            },
+        ]);
+
+        s.apply(&ChangeEvent::Inserted {
+            start: (0, 2),
+            end: (0, 4),
+            text: "xy".into(),
+        });
+        assert_eq!(s.stop(0).unwrap().end, (0, 4));
+        assert_eq!(s.stop(1).unwrap().start, (0, 4));
+        assert_eq!(s.stop(1).unwrap().end, (0, 7));
+
+        s.apply(&ChangeEvent::Inserted {
+            start: (0, 0),
+            end: (1, 1),
+            text: "\nz".into(),
+        });
+        assert_eq!(s.stop(0).unwrap().start, (1, 3));
+        assert_eq!(s.stop(2).unwrap().start, (2, 0));
+
+        s.apply(&ChangeEvent::Deleted {
+            start: (1, 2),
+            end: (1, 6),
+            text: "xxxx".into(),
+        });
+        assert_eq!(
+            s.stop(0).unwrap(),
+            TabStop {
+                start: (1, 2),
+                end: (1, 2)
+            }
+        );
+        assert_eq!(
+            s.stop(1).unwrap(),
+            TabStop {
+                start: (1, 2),
+                end: (1, 4)
+            }
+        );
+        assert!(s.contains((1, 2)));
+        assert!(!s.contains((1, 3)));
+    }
+}
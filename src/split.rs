@@ -0,0 +1,371 @@
+use crate::input::{Input, Key};
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::{Direction, Rect};
+use crate::ratatui::style::{Color, Style};
+use crate::ratatui::widgets::Widget;
+use crate::textarea::TextArea;
+use std::cmp;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Widget laying out two [`TextArea`] panes side by side or one above the other with a divider between them.
+///
+/// Inputs passed to [`SplitEditor::input`] are routed to the focused pane. Clicking a pane focuses it, and dragging the
+/// divider with the mouse resizes the panes. Style the focused pane with [`SplitEditor::focused`] such as hiding the
+/// cursor of the other pane.
+///
+/// The panes are independent textareas and do not share their text. Even when a pane is created by cloning the other
+/// textarea, edits in one pane are not reflected to the other pane.
+///
+/// ```
+/// use ratatui::layout::Direction;
+/// use tui_textarea::{Input, Key, SplitEditor, TextArea};
+///
+/// let mut split = SplitEditor::new(TextArea::from(["left"]), TextArea::from(["right"]));
+/// assert_eq!(split.direction(), Direction::Horizontal);
+///
+/// split.input(Input { key: Key::Char('x'), ..Default::default() });
+/// assert_eq!(split.pane(0).lines(), ["xleft"]);
+///
+/// split.focus_next();
+/// split.input(Input { key: Key::Char('y'), ..Default::default() });
+/// assert_eq!(split.pane(1).lines(), ["yright"]);
+///
+/// // Render the panes with `frame.render_widget(&split, area)`
+/// ```
+#[derive(Debug)]
+pub struct SplitEditor<'a> {
+    panes: [TextArea<'a>; 2],
+    horizontal: bool,
+    ratio: u16,
+    focused: usize,
+    divider_style: Style,
+    dragging: bool,
+    /// Area of the widget at the last rendering packed into one value. Rendering only takes `&self`
+    area: AtomicU64,
+}
+
+impl Clone for SplitEditor<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            panes: self.panes.clone(),
+            horizontal: self.horizontal,
+            ratio: self.ratio,
+            focused: self.focused,
+            divider_style: self.divider_style,
+            dragging: self.dragging,
+            area: AtomicU64::new(self.area.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<'a> SplitEditor<'a> {
+    /// Create a split editor with the two panes laid out side by side in the same size. The first pane is focused.
+    pub fn new(first: TextArea<'a>, second: TextArea<'a>) -> Self {
+        Self {
+            panes: [first, second],
+            horizontal: true,
+            ratio: 50,
+            focused: 0,
+            divider_style: Style::default().fg(Color::DarkGray),
+            dragging: false,
+            area: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the pane at the index. The index is 0 for the left or top pane and 1 for the other.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the index is not 0 or 1.
+    pub fn pane(&self, index: usize) -> &TextArea<'a> {
+        &self.panes[index]
+    }
+
+    /// Get the mutable pane at the index. See [`SplitEditor::pane`].
+    pub fn pane_mut(&mut self, index: usize) -> &mut TextArea<'a> {
+        &mut self.panes[index]
+    }
+
+    /// Consume the editor and get the two panes.
+    pub fn into_panes(self) -> (TextArea<'a>, TextArea<'a>) {
+        let [first, second] = self.panes;
+        (first, second)
+    }
+
+    /// Get the index of the focused pane.
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Get the focused pane.
+    pub fn focused_pane(&self) -> &TextArea<'a> {
+        &self.panes[self.focused]
+    }
+
+    /// Get the mutable focused pane.
+    pub fn focused_pane_mut(&mut self) -> &mut TextArea<'a> {
+        &mut self.panes[self.focused]
+    }
+
+    /// Focus the pane at the index. An index other than 0 or 1 is ignored.
+    pub fn set_focus(&mut self, index: usize) {
+        if index < 2 {
+            self.focused = index;
+        }
+    }
+
+    /// Move the focus to the other pane and return the index of the newly focused pane.
+    pub fn focus_next(&mut self) -> usize {
+        self.focused = 1 - self.focused;
+        self.focused
+    }
+
+    /// Set the direction of the layout. [`Direction::Horizontal`] lays out the panes side by side and
+    /// [`Direction::Vertical`] lays out them one above the other.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.horizontal = direction == Direction::Horizontal;
+    }
+
+    /// Get the direction of the layout.
+    pub fn direction(&self) -> Direction {
+        if self.horizontal {
+            Direction::Horizontal
+        } else {
+            Direction::Vertical
+        }
+    }
+
+    /// Set the size of the first pane in percentage of the whole size. The value is clamped to 1..=99.
+    pub fn set_ratio(&mut self, percent: u16) {
+        self.ratio = percent.clamp(1, 99);
+    }
+
+    /// Get the size of the first pane in percentage of the whole size.
+    pub fn ratio(&self) -> u16 {
+        self.ratio
+    }
+
+    /// Grow the first pane by the percentage. A negative value shrinks it.
+    pub fn resize(&mut self, delta: i16) {
+        self.set_ratio((self.ratio as i16).saturating_add(delta).max(0) as u16);
+    }
+
+    /// Set the style of the divider. The default style is dark gray foreground.
+    pub fn set_divider_style(&mut self, style: Style) {
+        self.divider_style = style;
+    }
+
+    /// Get the style of the divider.
+    pub fn divider_style(&self) -> Style {
+        self.divider_style
+    }
+
+    /// Split the area into the areas of the first pane, the divider and the second pane.
+    fn layout(&self, area: Rect) -> (Rect, Rect, Rect) {
+        let horizontal = self.horizontal;
+        let total = if horizontal { area.width } else { area.height };
+        let available = total.saturating_sub(1); // One cell for the divider
+        let first = (available as u32 * self.ratio as u32 / 100) as u16;
+        let divider = cmp::min(1, total);
+        let second = available - first;
+        if horizontal {
+            (
+                Rect {
+                    width: first,
+                    ..area
+                },
+                Rect {
+                    x: area.x + first,
+                    width: divider,
+                    ..area
+                },
+                Rect {
+                    x: area.x + first + divider,
+                    width: second,
+                    ..area
+                },
+            )
+        } else {
+            (
+                Rect {
+                    height: first,
+                    ..area
+                },
+                Rect {
+                    y: area.y + first,
+                    height: divider,
+                    ..area
+                },
+                Rect {
+                    y: area.y + first + divider,
+                    height: second,
+                    ..area
+                },
+            )
+        }
+    }
+
+    fn last_area(&self) -> Rect {
+        let u = self.area.load(Ordering::Relaxed);
+        Rect::new(
+            (u >> 48) as u16,
+            (u >> 32) as u16,
+            (u >> 16) as u16,
+            u as u16,
+        )
+    }
+
+    fn store_area(&self, area: Rect) {
+        let u = ((area.x as u64) << 48)
+            | ((area.y as u64) << 32)
+            | ((area.width as u64) << 16)
+            | area.height as u64;
+        self.area.store(u, Ordering::Relaxed);
+    }
+
+    /// Resize the panes so that the divider is at the screen position, based on the last rendering.
+    fn drag_divider(&mut self, x: u16, y: u16) {
+        let area = self.last_area();
+        let (pos, start, total) = if self.horizontal {
+            (x, area.x, area.width)
+        } else {
+            (y, area.y, area.height)
+        };
+        let available = total.saturating_sub(1);
+        if available == 0 {
+            return;
+        }
+        let offset = cmp::min(pos.saturating_sub(start), available);
+        // Round up so that the divider is rendered at the dragged position
+        let percent = (offset as u32 * 100 + available as u32 - 1) / available as u32;
+        self.set_ratio(percent as u16);
+    }
+
+    /// Handle a key input. Mouse clicks focus the clicked pane, and dragging the divider resizes the panes. Other inputs
+    /// are passed to [`TextArea::input`] of the focused pane. This method returns if the input modified the text or
+    /// not.
+    pub fn input(&mut self, input: impl Into<Input>) -> bool {
+        let input = input.into();
+        match input.key {
+            Key::MouseClick(x, y) => {
+                let (first, divider, second) = self.layout(self.last_area());
+                let contains = |r: Rect| r.x <= x && x < r.right() && r.y <= y && y < r.bottom();
+                self.dragging = contains(divider);
+                if self.dragging {
+                    return false;
+                }
+                if contains(first) {
+                    self.focused = 0;
+                } else if contains(second) {
+                    self.focused = 1;
+                }
+            }
+            Key::MouseDrag(x, y) if self.dragging => {
+                self.drag_divider(x, y);
+                return false;
+            }
+            Key::MouseDrag(..) => {}
+            _ => self.dragging = false,
+        }
+        self.panes[self.focused].input(input)
+    }
+}
+
+impl Widget for &SplitEditor<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.store_area(area);
+        let (first, divider, second) = self.layout(area);
+        self.panes[0].render(first, buf);
+        self.panes[1].render(second, buf);
+
+        let symbol = if self.horizontal { "│" } else { "─" };
+        for y in divider.top()..divider.bottom() {
+            for x in divider.left()..divider.right() {
+                buf.set_string(x, y, symbol, self.divider_style);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+
+    fn mouse(key: Key) -> Input {
+        Input {
+            key,
+            ..Default::default()
+        }
+    }
+
+    fn render(split: &SplitEditor<'_>, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        split.render(area, &mut buf);
+        buf.content
+            .chunks(width as usize)
+            .map(|row| row.iter().map(|c| c.symbol()).collect())
+            .collect()
+    }
+
+    fn split() -> SplitEditor<'static> {
+        let mut split = SplitEditor::new(TextArea::from(["abc"]), TextArea::from(["def"]));
+        for pane in &mut split.panes {
+            pane.set_cursor_style(Style::default());
+        }
+        split
+    }
+
+    #[test]
+    fn layout_panes() {
+        let mut split = split();
+        assert_eq!(render(&split, 9, 2), ["abc │def ", "    │    "]);
+
+        split.set_ratio(25);
+        assert_eq!(render(&split, 9, 1), ["ab│def   "]);
+
+        split.set_direction(Direction::Vertical);
+        split.set_ratio(50);
+        assert_eq!(render(&split, 4, 3), ["abc ", "────", "def "]);
+
+        // Too small area
+        assert_eq!(render(&split, 4, 1), ["────"]);
+        split.resize(100);
+        assert_eq!(split.ratio(), 99);
+        split.resize(-200);
+        assert_eq!(split.ratio(), 1);
+    }
+
+    #[test]
+    fn mouse_focus_and_drag() {
+        let mut split = split();
+        render(&split, 11, 2);
+
+        // Click the second pane
+        split.input(mouse(Key::MouseClick(8, 0)));
+        assert_eq!(split.focused(), 1);
+        assert_eq!(split.focused_pane().cursor(), (0, 2));
+
+        // Drag the divider
+        split.input(mouse(Key::MouseClick(5, 1)));
+        assert_eq!(split.focused(), 1);
+        split.input(mouse(Key::MouseDrag(2, 1)));
+        assert_eq!(split.ratio(), 20);
+        assert_eq!(render(&split, 11, 1), ["ab│def     "]);
+        split.input(mouse(Key::MouseDrag(8, 1)));
+        // The second pane is scrolled to show the cursor
+        assert_eq!(render(&split, 11, 1), ["abc     │ef"]);
+
+        // Dragging ends with other inputs
+        split.input(mouse(Key::Null));
+        split.input(mouse(Key::MouseDrag(2, 1)));
+        assert_eq!(render(&split, 11, 1), ["abc     │ef"]);
+
+        split.input(mouse(Key::MouseClick(0, 0)));
+        assert_eq!(split.focused(), 0);
+        assert!(split.input(mouse(Key::Char('x'))));
+        let (first, second) = split.clone().into_panes();
+        assert_eq!(first.lines(), ["xabc"]);
+        assert_eq!(second.lines(), ["def"]);
+    }
+}
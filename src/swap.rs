@@ -0,0 +1,163 @@
+use crate::word::is_word_char;
+
+/// Range of characters in a line as a pair of start and end (exclusive) columns.
+pub type ColRange = (usize, usize);
+
+fn word_at(chars: &[char], col: usize) -> ColRange {
+    let start = chars[..col]
+        .iter()
+        .rposition(|&c| !is_word_char(c))
+        .map_or(0, |i| i + 1);
+    let end = chars[col..]
+        .iter()
+        .position(|&c| !is_word_char(c))
+        .map_or(chars.len(), |i| col + i);
+    (start, end)
+}
+
+fn next_word(chars: &[char], col: usize) -> Option<ColRange> {
+    let start = col + chars[col..].iter().position(|&c| is_word_char(c))?;
+    Some(word_at(chars, start))
+}
+
+/// Find the word under the cursor and the next word in the line. When the cursor is not on a word, the word just before
+/// the cursor is used. When there is no such word, the next word is used.
+pub fn find_word_swap(line: &str, col: usize) -> Option<(ColRange, ColRange)> {
+    let chars: Vec<char> = line.chars().collect();
+    let col = col.min(chars.len());
+    let current = if chars.get(col).map_or(false, |&c| is_word_char(c)) {
+        word_at(&chars, col)
+    } else if col > 0 && is_word_char(chars[col - 1]) {
+        word_at(&chars, col - 1)
+    } else {
+        next_word(&chars, col)?
+    };
+    let next = next_word(&chars, current.1)?;
+    Some((current, next))
+}
+
+/// Find the comma-separated argument under the cursor and its neighbor in the innermost brackets enclosing the cursor.
+/// Commas in nested brackets are not separators. The next argument is the neighbor unless the argument is the last
+/// one. Only the pairs of different open and close brackets are considered.
+pub fn find_argument_swap(
+    line: &str,
+    col: usize,
+    pairs: &[(char, char)],
+) -> Option<(ColRange, ColRange)> {
+    let chars: Vec<char> = line.chars().collect();
+    let col = col.min(chars.len());
+    let pairs: Vec<_> = pairs.iter().filter(|(o, c)| o != c).collect();
+    let is_open = |c: char| pairs.iter().any(|p| p.0 == c);
+    let is_close = |c: char| pairs.iter().any(|p| p.1 == c);
+
+    // Find the open bracket enclosing the cursor
+    let mut depth = 0usize;
+    let mut open = None;
+    for i in (0..col).rev() {
+        let c = chars[i];
+        if is_close(c) {
+            depth += 1;
+        } else if is_open(c) {
+            if depth == 0 {
+                open = Some(i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let open = open?;
+
+    // Split the arguments until the matching close bracket
+    let mut separators = vec![open];
+    let mut close = None;
+    for (i, &c) in chars.iter().enumerate().skip(open + 1) {
+        if is_open(c) {
+            depth += 1;
+        } else if is_close(c) {
+            if depth == 0 {
+                close = Some(i);
+                break;
+            }
+            depth -= 1;
+        } else if c == ',' && depth == 0 {
+            separators.push(i);
+        }
+    }
+    separators.push(close?);
+
+    let args: Vec<ColRange> = separators
+        .windows(2)
+        .map(|w| {
+            let (mut start, mut end) = (w[0] + 1, w[1]);
+            while start < end && chars[start].is_whitespace() {
+                start += 1;
+            }
+            while start < end && chars[end - 1].is_whitespace() {
+                end -= 1;
+            }
+            (start, end)
+        })
+        .collect();
+    if args.len() < 2 || args.iter().any(|(s, e)| s == e) {
+        return None;
+    }
+
+    // The cursor on a comma belongs to the argument before it
+    let index = separators[1..].iter().take_while(|&&i| i < col).count();
+    let index = index.min(args.len() - 1);
+    if index + 1 < args.len() {
+        Some((args[index], args[index + 1]))
+    } else {
+        Some((args[index - 1], args[index]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_swap() {
+        let tests = [
+            ("foo bar baz", 0, Some(((0, 3), (4, 7)))),
+            ("foo bar baz", 2, Some(((0, 3), (4, 7)))),
+            ("foo bar baz", 3, Some(((0, 3), (4, 7)))),
+            ("foo bar baz", 5, Some(((4, 7), (8, 11)))),
+            ("foo bar baz", 9, None),
+            ("  foo_1, bar", 0, Some(((2, 7), (9, 12)))),
+            ("a.b", 1, Some(((0, 1), (2, 3)))),
+            ("", 0, None),
+        ];
+        for (line, col, expected) in tests {
+            assert_eq!(find_word_swap(line, col), expected, "{:?} at {}", line, col);
+        }
+    }
+
+    #[test]
+    fn argument_swap() {
+        let pairs = [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"')];
+        let tests = [
+            ("f(a, b, c)", 2, Some(((2, 3), (5, 6)))),
+            ("f(a, b, c)", 3, Some(((2, 3), (5, 6)))),
+            ("f(a, b, c)", 4, Some(((5, 6), (8, 9)))),
+            ("f(a, b, c)", 8, Some(((5, 6), (8, 9)))),
+            ("f(a, b, c)", 9, Some(((5, 6), (8, 9)))),
+            ("f( x , g(y, z) )", 3, Some(((3, 4), (7, 14)))),
+            ("f( x , g(y, z) )", 10, Some(((9, 10), (12, 13)))),
+            ("f(a)", 2, None),
+            ("f(a, )", 2, None),
+            ("f(a, b", 2, None),
+            ("a, b", 0, None),
+            ("[1, 2]", 1, Some(((1, 2), (4, 5)))),
+        ];
+        for (line, col, expected) in tests {
+            assert_eq!(
+                find_argument_swap(line, col, &pairs),
+                expected,
+                "{:?} at {}",
+                line,
+                col,
+            );
+        }
+    }
+}
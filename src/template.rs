@@ -0,0 +1,251 @@
+use std::fmt;
+use std::sync::Arc;
+
+type Validator = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// An editable field in [`Template`]. A field is a single-line text and cannot contain newlines.
+///
+/// ```
+/// use tui_textarea::TemplateField;
+///
+/// let field = TemplateField::new("port")
+///     .value("8080")
+///     .validator(|s| match s.parse::<u16>() {
+///         Ok(_) => Ok(()),
+///         Err(err) => Err(format!("invalid port number: {}", err)),
+///     });
+/// assert_eq!(field.name(), "port");
+/// assert_eq!(field.validate("8080"), Ok(()));
+/// assert!(field.validate("http").is_err());
+/// ```
+#[derive(Clone)]
+pub struct TemplateField {
+    name: String,
+    value: String,
+    validator: Option<Validator>,
+}
+
+impl TemplateField {
+    /// Create a new empty field with the name. The name is used to get the value of the field.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: String::new(),
+            validator: None,
+        }
+    }
+
+    /// Set the initial value of the field. Newlines in the value are removed.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        let value = value.into();
+        self.value = value.replace(['\r', '\n'], "");
+        self
+    }
+
+    /// Set the function to validate the value of the field. The function returns an error message when the value is
+    /// invalid.
+    pub fn validator<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.validator = Some(Arc::new(validator));
+        self
+    }
+
+    /// Get the name of the field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Validate the value with the validator of the field. When no validator is set, any value is valid.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match &self.validator {
+            Some(v) => v(value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for TemplateField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateField")
+            .field("name", &self.name)
+            .field("value", &self.value)
+            .field("validator", &self.validator.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Text(String),
+    Field(TemplateField),
+}
+
+/// Text with editable fields for fill-in-the-blanks input such as configuration wizards. The text outside the fields
+/// is read-only. See [`TextArea::set_template`](crate::TextArea::set_template) for the usage.
+///
+/// ```
+/// use tui_textarea::{Template, TemplateField};
+///
+/// let template = Template::new()
+///     .text("name: ")
+///     .field(TemplateField::new("name"))
+///     .text("\nport: ")
+///     .field(TemplateField::new("port").value("8080"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Create a new empty template.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a read-only text. The text can contain newlines.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.segments.push(Segment::Text(text.into()));
+        self
+    }
+
+    /// Append an editable field.
+    pub fn field(mut self, field: TemplateField) -> Self {
+        self.segments.push(Segment::Field(field));
+        self
+    }
+
+    /// Build the lines of the template and the fields with their positions.
+    pub(crate) fn build(self) -> (Vec<String>, TemplateState) {
+        let mut lines = vec![String::new()];
+        let mut fields = vec![];
+        for segment in self.segments {
+            match segment {
+                Segment::Text(text) => {
+                    let mut it = text.split('\n').map(|s| s.strip_suffix('\r').unwrap_or(s));
+                    if let Some(first) = it.next() {
+                        lines.last_mut().unwrap().push_str(first);
+                    }
+                    lines.extend(it.map(String::from));
+                }
+                Segment::Field(field) => {
+                    let row = lines.len() - 1;
+                    let line = &mut lines[row];
+                    let col = line.chars().count();
+                    line.push_str(&field.value);
+                    let len = field.value.chars().count();
+                    fields.push(FieldPos {
+                        field,
+                        row,
+                        col,
+                        len,
+                    });
+                }
+            }
+        }
+        (lines, TemplateState { fields, focus: 0 })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct FieldPos {
+    pub field: TemplateField,
+    pub row: usize,
+    pub col: usize,
+    pub len: usize,
+}
+
+impl FieldPos {
+    pub fn end(&self) -> usize {
+        self.col + self.len
+    }
+
+    pub fn value<'a>(&self, lines: &'a [String]) -> &'a str {
+        let line = &lines[self.row];
+        let offset = |col| {
+            line.char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len())
+        };
+        &line[offset(self.col)..offset(self.end())]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct TemplateState {
+    pub fields: Vec<FieldPos>,
+    pub focus: usize,
+}
+
+impl TemplateState {
+    pub fn focused(&self) -> Option<&FieldPos> {
+        self.fields.get(self.focus)
+    }
+
+    /// Update the length of the focused field and shift the following fields in the same row.
+    pub fn resize_focused(&mut self, new_len: usize) {
+        let (row, old_len) = match self.fields.get_mut(self.focus) {
+            Some(f) => (f.row, std::mem::replace(&mut f.len, new_len)),
+            None => return,
+        };
+        for f in self.fields[self.focus + 1..].iter_mut() {
+            if f.row != row {
+                break;
+            }
+            f.col = f.col + new_len - old_len;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let (lines, state) = Template::new()
+            .text("a: ")
+            .field(TemplateField::new("a").value("x\ny"))
+            .text(" b: ")
+            .field(TemplateField::new("b"))
+            .text("\r\n\nc: ")
+            .field(TemplateField::new("c").value("あい"))
+            .build();
+        assert_eq!(lines, ["a: xy b: ", "", "c: あい"]);
+        let positions: Vec<_> = state
+            .fields
+            .iter()
+            .map(|f| (f.field.name(), f.row, f.col, f.len, f.value(&lines)))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                ("a", 0, 3, 2, "xy"),
+                ("b", 0, 9, 0, ""),
+                ("c", 2, 3, 2, "あい"),
+            ],
+        );
+    }
+
+    #[test]
+    fn resize() {
+        let (_, mut state) = Template::new()
+            .field(TemplateField::new("a").value("aa"))
+            .field(TemplateField::new("b").value("bb"))
+            .text("\n")
+            .field(TemplateField::new("c").value("cc"))
+            .build();
+        state.resize_focused(5);
+        let cols: Vec<_> = state.fields.iter().map(|f| (f.col, f.len)).collect();
+        assert_eq!(cols, [(0, 5), (5, 2), (0, 2)]);
+        state.resize_focused(0);
+        let cols: Vec<_> = state.fields.iter().map(|f| (f.col, f.len)).collect();
+        assert_eq!(cols, [(0, 0), (0, 2), (0, 2)]);
+        state.resize_focused(1);
+        let cols: Vec<_> = state.fields.iter().map(|f| (f.col, f.len)).collect();
+        assert_eq!(cols, [(0, 1), (1, 2), (0, 2)]);
+    }
+}
@@ -1,20 +1,67 @@
-use crate::cursor::CursorMove;
-use crate::highlight::LineHighlighter;
-use crate::history::{Edit, EditKind, History};
+use crate::block::{block_text, BlockSelection};
+use crate::bracket::{find_enclosing_brackets, find_matching_bracket};
+use crate::change::ChangeEvent;
+use crate::completion::{word_end, word_start, Completion, CompletionItem};
+use crate::conceal::find_conceals;
+use crate::conflict::{find_conflicts, Conflict, ConflictResolution};
+use crate::content::Content;
+use crate::cursor::{fit_col, CursorMove};
+use crate::diff::{diff_lines, map_position, LineChange};
+use crate::event::UxEvent;
+use crate::export::{styled_text, ExportFormat};
+use crate::filetype::{self, Profile};
+use crate::fold::{Fold, Folds};
+#[cfg(feature = "arbitrary")]
+use crate::fuzz::ReplayReport;
+use crate::highlight::{LineHighlighter, TextHighlight};
+use crate::history::{Edit, EditKind, History, UndoCoalescing};
+#[cfg(feature = "rich-text")]
+use crate::import::{parse_rich_text, RichTextFormat};
 use crate::input::{Input, Key};
-use crate::ratatui::layout::Alignment;
+use crate::jump::{word_starts, JumpInput, JumpLabels};
+use crate::limit::SoftLimit;
+use crate::line_ending::{LineEnding, MixedEndings};
+use crate::macros::{EditMacro, MacroOp};
+use crate::marker::{Marker, Markers};
+use crate::message::Messages;
+use crate::operator::{char_run, word_object, MotionKind, Operator, TextObject};
+use crate::options::Options;
+use crate::patch::{Hunk, PatchError, TextEdit};
+use crate::profile::ColorProfile;
+use crate::ratatui::layout::{Alignment, Rect};
 use crate::ratatui::style::{Color, Modifier, Style};
+use crate::ratatui::text::Span;
 use crate::ratatui::widgets::{Block, Widget};
+use crate::render_cache::{state_hash, RenderCache};
+use crate::save::{save, SaveOptions};
 use crate::scroll::Scrolling;
 #[cfg(feature = "search")]
 use crate::search::Search;
-use crate::util::{spaces, Pos};
+use crate::snippet::{self, SnippetSession, TabStop};
+use crate::swap::{find_argument_swap, find_word_swap, ColRange};
+use crate::template::{Template, TemplateState};
+use crate::util::{display_width, next_grapheme_col, num_digits, prev_grapheme_col, spaces, Pos};
+use crate::visit::Visits;
+#[cfg(feature = "watch")]
+use crate::watch::AttachedFile;
+use crate::whitespace::WhitespaceConfig;
 use crate::widget::Viewport;
-use crate::word::{find_word_exclusive_end_forward, find_word_start_backward};
+use crate::word::{find_word_exclusive_end_forward, find_word_start_backward, WordClass};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
-use std::cmp::Ordering;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::cmp::{self, Ordering};
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt;
+use std::io;
+use std::mem;
+use std::ops::Range;
+use std::path::Path;
+#[cfg(feature = "arbitrary")]
+use std::path::PathBuf;
+use std::sync::Arc;
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
 use unicode_width::UnicodeWidthChar as _;
@@ -46,6 +93,89 @@ impl From<Vec<String>> for YankText {
     }
 }
 
+/// Text inserted by the last paste. It is replaced by [`TextArea::yank_pop`] while the cursor stays at the end.
+#[derive(Clone, Debug)]
+struct LastPaste {
+    start: (usize, usize),
+    end: (usize, usize),
+    text: String,
+    /// Index of the pasted text in the yank history
+    index: Option<usize>,
+}
+
+#[derive(Clone)]
+struct InputFilter(Arc<dyn Fn(char) -> bool + Send + Sync>);
+
+impl InputFilter {
+    fn accepts(&self, c: char) -> bool {
+        (self.0)(c)
+    }
+}
+
+impl fmt::Debug for InputFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("InputFilter(..)")
+    }
+}
+
+/// 64-bit FNV-1a hash, which is used for the content hash since its value is stable.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct EventHook(Arc<dyn Fn(UxEvent) + Send + Sync>);
+
+impl fmt::Debug for EventHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EventHook(..)")
+    }
+}
+
+#[derive(Clone)]
+struct ChangeHook(Arc<dyn Fn(&ChangeEvent) + Send + Sync>);
+
+impl fmt::Debug for ChangeHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChangeHook(..)")
+    }
+}
+
+type BeforeLineFn = dyn Fn(usize, &str) -> Option<Line<'static>> + Send + Sync;
+
+#[derive(Clone)]
+struct BeforeLineHook(Arc<BeforeLineFn>);
+
+impl fmt::Debug for BeforeLineHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BeforeLineHook(..)")
+    }
+}
+
+type AfterLineFn = dyn Fn(usize, &mut Line<'_>) + Send + Sync;
+
+#[derive(Clone)]
+struct AfterLineHook(Arc<AfterLineFn>);
+
+impl fmt::Debug for AfterLineHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AfterLineHook(..)")
+    }
+}
+
 impl fmt::Display for YankText {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -55,6 +185,92 @@ impl fmt::Display for YankText {
     }
 }
 
+impl YankText {
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Piece(s) if s.is_empty())
+    }
+
+    /// One-line preview of the text within the display width. Newlines are shown as `↵` and the truncated text ends
+    /// with `…`.
+    fn preview(&self, max_width: usize) -> String {
+        let text = match self {
+            Self::Piece(s) => Cow::Borrowed(s.as_str()),
+            Self::Chunk(ss) => Cow::Owned(ss.join("↵")),
+        };
+        let mut preview = String::new();
+        let mut width = 0;
+        let mut chars = text
+            .chars()
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .peekable();
+        while let Some(c) = chars.next() {
+            let w = c.width().unwrap_or(0);
+            let reserved = if chars.peek().is_some() { 1 } else { 0 }; // For `…`
+            if width + w + reserved > max_width {
+                if max_width > width {
+                    preview.push('…');
+                }
+                break;
+            }
+            preview.push(c);
+            width += w;
+        }
+        preview
+    }
+}
+
+/// Check if the input modifies the text with the default key mappings of [`TextArea::input`] or
+/// [`TextArea::input_without_shortcuts`]. This must be updated when a new mapping to modify the text is added.
+fn is_edit_input(input: &Input) -> bool {
+    match *input {
+        Input {
+            key: Key::Char(c),
+            ctrl,
+            alt,
+            ..
+        } => match (ctrl, alt) {
+            (false, false) => true,
+            (true, false) => matches!(c, 'm' | 'h' | 'd' | 'k' | 'j' | 'w' | 'u' | 'r' | 'y' | 'x'),
+            (false, true) => matches!(c, 'h' | 'd' | 'y'),
+            (true, true) => false,
+        },
+        Input {
+            key: Key::Enter | Key::Tab | Key::Backspace | Key::Delete | Key::Paste | Key::Cut,
+            ..
+        } => true,
+        _ => false,
+    }
+}
+
+/// Inputs which keep the block selection. Other inputs cancel it.
+fn is_block_input(input: &Input) -> bool {
+    match *input {
+        Input {
+            key: Key::Char(c),
+            ctrl,
+            alt: false,
+            ..
+        } => !ctrl || matches!(c, 'h' | 'd' | 'c' | 'x'),
+        Input {
+            key: Key::Backspace | Key::Delete,
+            ctrl: false,
+            alt: false,
+            ..
+        } => true,
+        Input {
+            key: Key::Up | Key::Down | Key::Left | Key::Right | Key::Home | Key::End,
+            ctrl: false,
+            alt: true,
+            shift: true,
+        } => true,
+        Input {
+            key: Key::Copy | Key::Cut,
+            ..
+        } => true,
+        _ => false,
+    }
+}
+
 /// A type to manage state of textarea. These are some important methods:
 ///
 /// - [`TextArea::default`] creates an empty textarea.
@@ -106,26 +322,107 @@ impl fmt::Display for YankText {
 #[derive(Clone, Debug)]
 pub struct TextArea<'a> {
     lines: Vec<String>,
+    line_ending: LineEnding,
+    mixed_endings: MixedEndings,
     block: Option<Block<'a>>,
     wrap: bool,
+    reserved_rows: BTreeMap<usize, u16>,
+    gutter_signs: BTreeMap<usize, Span<'a>>,
+    prompt: Option<(String, String)>,
+    eol_annotations: BTreeMap<usize, Span<'a>>,
+    eol_annotation_style: Style,
+    prompt_style: Style,
+    highlights: Vec<TextHighlight>,
     style: Style,
     cursor: (usize, usize), // 0-base
     tab_len: u8,
+    tab_width: Option<u8>,
     hard_tab_indent: bool,
+    auto_indent: bool,
+    read_only: bool,
+    external_edit: bool,
+    single_line: bool,
+    submitted: bool,
+    cursor_hidden: bool,
     history: History,
     cursor_line_style: Style,
+    cursor_line_full_width: bool,
+    cursor_column_style: Option<Style>,
+    color_column: Option<usize>,
+    color_column_style: Style,
+    comment_prefix: Option<String>,
+    comment_style: Style,
+    whitespace: Option<WhitespaceConfig>,
+    color_profile: ColorProfile,
     line_number_style: Option<Style>,
+    bracket_pairs: Vec<(char, char)>,
+    word_chars: String,
+    trim_trailing_whitespace: bool,
+    filetype: Option<&'static str>,
+    matched_bracket_style: Option<Style>,
+    conceal_rules: Vec<(String, String)>,
+    ligatures: Vec<(String, String)>,
+    jump_labels: Option<JumpLabels>,
+    completion: Option<Completion>,
+    completion_style: Style,
+    completion_selected_style: Style,
+    jump_label_style: Style,
+    visits: Visits,
+    #[cfg(feature = "ratatui")]
+    scrollbars: (bool, bool),
+    #[cfg(feature = "ratatui")]
+    scrollbar_style: Style,
     pub(crate) viewport: Viewport,
     pub(crate) cursor_style: Style,
+    overwrite: bool,
+    overwrite_cursor_style: Style,
     yank: YankText,
+    yank_history: VecDeque<YankText>,
+    max_yank_history: usize,
+    yank_masked: bool,
+    kill_accumulation: bool,
+    /// Cursor position after the last kill operation to accumulate the next kill
+    last_kill: Option<(usize, usize)>,
+    registers: BTreeMap<char, YankText>,
+    last_paste: Option<LastPaste>,
+    max_length: Option<usize>,
+    soft_limit: Option<SoftLimit>,
+    soft_limit_style: Style,
+    input_filter: Option<InputFilter>,
+    event_hook: Option<EventHook>,
+    change_hook: Option<ChangeHook>,
+    before_line_hook: Option<BeforeLineHook>,
+    after_line_hook: Option<AfterLineHook>,
+    /// Cursor and selection last reported to the change hook
+    notified_cursor: (usize, usize),
+    notified_selection: Option<((usize, usize), (usize, usize))>,
     #[cfg(feature = "search")]
     search: Search,
+    #[cfg(feature = "watch")]
+    file: Option<AttachedFile>,
     alignment: Alignment,
     pub(crate) placeholder: String,
     pub(crate) placeholder_style: Style,
+    messages: Messages,
     mask: Option<char>,
     selection_start: Option<(usize, usize)>,
+    block_selection: Option<BlockSelection>,
     select_style: Style,
+    template: Option<TemplateState>,
+    template_field_style: Style,
+    invalid_field_style: Style,
+    snippet: Option<SnippetSession>,
+    snippet_stop_style: Style,
+    markers: Markers,
+    bookmarks: Markers,
+    folds: Folds,
+    fold_style: Style,
+    recording: Option<EditMacro>,
+    #[cfg(feature = "arbitrary")]
+    replay_report: Option<ReplayReport>,
+    /// Incremented on every modification of the text. The render cache uses it to know if lines may be modified
+    generation: u64,
+    pub(crate) render_cache: RenderCache,
 }
 
 /// Convert any iterator whose elements can be converted into [`String`] into [`TextArea`]. Each [`String`] element is
@@ -195,6 +492,120 @@ impl Default for TextArea<'_> {
     }
 }
 
+/// Persistent state of [`TextArea`] serialized with serde. Styles, hooks and transient states such as an ongoing
+/// completion are not a part of the state.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "TextArea")]
+struct TextAreaState<'s> {
+    lines: Cow<'s, [String]>,
+    line_ending: LineEnding,
+    #[serde(default)]
+    mixed_line_endings: Vec<usize>,
+    cursor: (usize, usize),
+    selection_start: Option<(usize, usize)>,
+    scroll_top: (u16, u16),
+    history: Cow<'s, History>,
+    bookmarks: Vec<usize>,
+    tab_len: u8,
+    tab_width: Option<u8>,
+    hard_tab_indent: bool,
+    auto_indent: bool,
+    wrap: bool,
+}
+
+/// Serialize the persistent state of the textarea with serde. The state consists of the text, the line ending, the
+/// cursor, the selection, the scroll position, the undo history, the bookmarks and the settings of tab, indentation
+/// and wrapping. Styles, blocks, hooks and other settings are not serialized. This is available with `serde` feature.
+/// ```
+/// use tui_textarea::{CursorMove, TextArea};
+///
+/// let mut textarea = TextArea::from(["hello"]);
+/// textarea.set_tab_length(2);
+/// textarea.move_cursor(CursorMove::End);
+/// textarea.insert_str(", world");
+///
+/// let json = serde_json::to_string(&textarea).unwrap();
+/// let mut restored: TextArea = serde_json::from_str(&json).unwrap();
+///
+/// assert_eq!(restored.lines(), ["hello, world"]);
+/// assert_eq!(restored.cursor(), (0, 12));
+/// assert_eq!(restored.tab_length(), 2);
+///
+/// // The undo history is restored as well
+/// restored.undo();
+/// assert_eq!(restored.lines(), ["hello"]);
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for TextArea<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TextAreaState {
+            lines: Cow::Borrowed(&self.lines),
+            line_ending: self.line_ending,
+            mixed_line_endings: self.mixed_endings.rows().to_vec(),
+            cursor: self.cursor,
+            selection_start: self.selection_start,
+            scroll_top: self.viewport.scroll_top(),
+            history: Cow::Borrowed(&self.history),
+            bookmarks: self.bookmarks(),
+            tab_len: self.tab_len,
+            tab_width: self.tab_width,
+            hard_tab_indent: self.hard_tab_indent,
+            auto_indent: self.auto_indent,
+            wrap: self.wrap,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialize the state serialized by the [`Serialize`] implementation into a new textarea. Other settings are the
+/// default ones, so set styles and so on after deserialization. The cursor, the selection and the bookmarks are
+/// clamped to the text. The undo history must be the one serialized with the text. This is available with `serde`
+/// feature.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for TextArea<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = TextAreaState::deserialize(deserializer)?;
+        let mut lines = state.lines.into_owned();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        let mut textarea = Self::new(lines);
+        textarea.line_ending = state.line_ending;
+        textarea.mixed_endings =
+            MixedEndings::from_rows(state.mixed_line_endings, textarea.lines.len());
+        textarea.cursor = textarea.clamp_cursor(state.cursor);
+        textarea.selection_start = state.selection_start.map(|p| textarea.clamp_cursor(p));
+        textarea.notified_cursor = textarea.cursor;
+        textarea.notified_selection = textarea.selection_range();
+        let (row, col) = state.scroll_top;
+        textarea.viewport.set_scroll_top(row, col);
+        let history = state.history.into_owned();
+        // Corrupted or mismatched history would break the text on undo or redo
+        textarea.history = if history.is_consistent_with(&textarea.lines) {
+            history
+        } else {
+            let mut empty = History::new(history.max_items());
+            empty.set_coalescing(history.coalescing());
+            empty
+        };
+        for row in state.bookmarks {
+            if row < textarea.lines.len() {
+                textarea.bookmarks.add((row, 0));
+            }
+        }
+        textarea.tab_len = state.tab_len;
+        textarea.tab_width = state.tab_width;
+        textarea.hard_tab_indent = state.hard_tab_indent;
+        textarea.auto_indent = state.auto_indent;
+        textarea.wrap = state.wrap;
+        Ok(textarea)
+    }
+}
+
 impl<'a> TextArea<'a> {
     /// Create [`TextArea`] instance with given lines. If you have value other than `Vec<String>`, [`TextArea::from`]
     /// may be more useful.
@@ -209,29 +620,108 @@ impl<'a> TextArea<'a> {
         if lines.is_empty() {
             lines.push(String::new());
         }
+        let (line_ending, mixed_endings) = LineEnding::strip(&mut lines);
 
         Self {
             lines,
+            line_ending,
+            mixed_endings,
             block: None,
             wrap: false,
+            reserved_rows: BTreeMap::new(),
+            gutter_signs: BTreeMap::new(),
+            prompt: None,
+            eol_annotations: BTreeMap::new(),
+            eol_annotation_style: Style::default().add_modifier(Modifier::DIM),
+            prompt_style: Style::default(),
+            highlights: vec![],
             style: Style::default(),
             cursor: (0, 0),
             tab_len: 4,
+            tab_width: None,
             hard_tab_indent: false,
+            auto_indent: false,
+            read_only: false,
+            external_edit: false,
+            single_line: false,
+            submitted: false,
+            cursor_hidden: false,
             history: History::new(50),
             cursor_line_style: Style::default().add_modifier(Modifier::UNDERLINED),
+            cursor_line_full_width: false,
+            cursor_column_style: None,
+            color_column: None,
+            color_column_style: Style::default().bg(Color::DarkGray),
+            comment_prefix: None,
+            comment_style: Style::default().add_modifier(Modifier::DIM),
+            whitespace: None,
+            color_profile: ColorProfile::default(),
             line_number_style: None,
+            bracket_pairs: vec![('(', ')'), ('[', ']'), ('{', '}')],
+            word_chars: String::new(),
+            trim_trailing_whitespace: false,
+            filetype: None,
+            matched_bracket_style: None,
+            conceal_rules: vec![],
+            ligatures: vec![],
+            jump_labels: None,
+            completion: None,
+            completion_style: Style::default().bg(Color::DarkGray),
+            completion_selected_style: Style::default().add_modifier(Modifier::REVERSED),
+            jump_label_style: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            visits: Visits::default(),
+            #[cfg(feature = "ratatui")]
+            scrollbars: (false, false),
+            #[cfg(feature = "ratatui")]
+            scrollbar_style: Style::default(),
             viewport: Viewport::default(),
             cursor_style: Style::default().add_modifier(Modifier::REVERSED),
+            overwrite: false,
+            overwrite_cursor_style: Style::default().fg(Color::Black).bg(Color::LightYellow),
             yank: YankText::default(),
+            yank_history: VecDeque::new(),
+            max_yank_history: 10,
+            yank_masked: true,
+            kill_accumulation: false,
+            last_kill: None,
+            registers: BTreeMap::new(),
+            last_paste: None,
+            max_length: None,
+            soft_limit: None,
+            soft_limit_style: Style::default().fg(Color::Red),
+            input_filter: None,
+            event_hook: None,
+            change_hook: None,
+            before_line_hook: None,
+            after_line_hook: None,
+            notified_cursor: (0, 0),
+            notified_selection: None,
             #[cfg(feature = "search")]
             search: Search::default(),
+            #[cfg(feature = "watch")]
+            file: None,
             alignment: Alignment::Left,
             placeholder: String::new(),
             placeholder_style: Style::default().fg(Color::DarkGray),
+            messages: Messages::default(),
             mask: None,
             selection_start: None,
+            block_selection: None,
             select_style: Style::default().bg(Color::LightBlue),
+            template: None,
+            template_field_style: Style::default().bg(Color::DarkGray),
+            invalid_field_style: Style::default().bg(Color::Red),
+            snippet: None,
+            snippet_stop_style: Style::default().bg(Color::DarkGray),
+            markers: Markers::default(),
+            bookmarks: Markers::stick_right(),
+            folds: Folds::default(),
+            fold_style: Style::default().fg(Color::DarkGray),
+            recording: None,
+            #[cfg(feature = "arbitrary")]
+            replay_report: None,
+            generation: 0,
+            render_cache: RenderCache::default(),
         }
     }
 
@@ -273,7 +763,56 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn input(&mut self, input: impl Into<Input>) -> bool {
         let input = input.into();
+        let modified = match self.completion_input(&input) {
+            Some(modified) => modified,
+            None => match self.snippet_input(&input) {
+                Some(modified) => modified,
+                None => {
+                    let modified = self.handle_input(input);
+                    self.update_completion();
+                    self.update_snippet();
+                    modified
+                }
+            },
+        };
+        self.unfold_at_cursor();
+        self.record_visit();
+        self.notify_cursor();
+        modified
+    }
+
+    fn handle_input(&mut self, input: Input) -> bool {
+        if self.jump_labels.is_some() {
+            return self.jump_input(input);
+        }
+        if (self.read_only || self.external_edit) && is_edit_input(&input) {
+            self.emit(UxEvent::EditRejected);
+            return false;
+        }
+        if self.template.is_some() {
+            return self.template_input(input);
+        }
+        if self.block_selection.is_some() && !is_block_input(&input) {
+            self.block_selection = None;
+        }
         let modified = match input {
+            Input {
+                key: key @ (Key::Up | Key::Down | Key::Left | Key::Right | Key::Home | Key::End),
+                ctrl: false,
+                alt: true,
+                shift: true,
+            } => {
+                let m = match key {
+                    Key::Up => CursorMove::Up,
+                    Key::Down => CursorMove::Down,
+                    Key::Left => CursorMove::Back,
+                    Key::Right => CursorMove::Forward,
+                    Key::Home => CursorMove::Head,
+                    _ => CursorMove::End,
+                };
+                self.select_block(m);
+                false
+            }
             Input {
                 key: Key::Char('m'),
                 ctrl: true,
@@ -288,19 +827,13 @@ impl<'a> TextArea<'a> {
             }
             | Input {
                 key: Key::Enter, ..
-            } => {
-                self.insert_newline();
-                true
-            }
+            } => self.insert_newline_checked(),
             Input {
                 key: Key::Char(c),
                 ctrl: false,
                 alt: false,
                 ..
-            } => {
-                self.insert_char(c);
-                true
-            }
+            } => self.insert_char_checked(c),
             Input {
                 key: Key::Tab,
                 ctrl: false,
@@ -331,6 +864,15 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 ..
             } => self.delete_next_char(),
+            Input {
+                key: Key::Insert,
+                ctrl: false,
+                alt: false,
+                ..
+            } => {
+                self.overwrite = !self.overwrite;
+                false
+            }
             Input {
                 key: Key::Char('k'),
                 ctrl: true,
@@ -385,7 +927,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Down, shift);
+                self.move_cursor_by_key(CursorMove::Down, shift);
                 false
             }
             Input {
@@ -400,7 +942,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Up, shift);
+                self.move_cursor_by_key(CursorMove::Up, shift);
                 false
             }
             Input {
@@ -415,7 +957,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Forward, shift);
+                self.move_cursor_by_key(CursorMove::Forward, shift);
                 false
             }
             Input {
@@ -430,7 +972,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Back, shift);
+                self.move_cursor_by_key(CursorMove::Back, shift);
                 false
             }
             Input {
@@ -450,7 +992,7 @@ impl<'a> TextArea<'a> {
                 alt: true,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Head, shift);
+                self.move_cursor_by_key(CursorMove::Head, shift);
                 false
             }
             Input {
@@ -470,7 +1012,7 @@ impl<'a> TextArea<'a> {
                 alt: true,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::End, shift);
+                self.move_cursor_by_key(CursorMove::End, shift);
                 false
             }
             Input {
@@ -485,7 +1027,7 @@ impl<'a> TextArea<'a> {
                 alt: true,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Top, shift);
+                self.move_cursor_by_key(CursorMove::Top, shift);
                 false
             }
             Input {
@@ -500,7 +1042,7 @@ impl<'a> TextArea<'a> {
                 alt: true,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Bottom, shift);
+                self.move_cursor_by_key(CursorMove::Bottom, shift);
                 false
             }
             Input {
@@ -515,7 +1057,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::WordForward, shift);
+                self.move_cursor_by_key(CursorMove::WordForward, shift);
                 false
             }
             Input {
@@ -530,7 +1072,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::WordBack, shift);
+                self.move_cursor_by_key(CursorMove::WordBack, shift);
                 false
             }
             Input {
@@ -551,7 +1093,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::ParagraphForward, shift);
+                self.move_cursor_by_key(CursorMove::ParagraphForward, shift);
                 false
             }
             Input {
@@ -572,7 +1114,7 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::ParagraphBack, shift);
+                self.move_cursor_by_key(CursorMove::ParagraphBack, shift);
                 false
             }
             Input {
@@ -596,6 +1138,12 @@ impl<'a> TextArea<'a> {
             | Input {
                 key: Key::Paste, ..
             } => self.paste(),
+            Input {
+                key: Key::Char('y'),
+                ctrl: false,
+                alt: true,
+                ..
+            } => self.yank_pop(),
             Input {
                 key: Key::Char('x'),
                 ctrl: true,
@@ -649,6 +1197,21 @@ impl<'a> TextArea<'a> {
                 self.scroll_with_shift((1, 0).into(), shift);
                 false
             }
+            Input {
+                key: Key::MouseClick(x, y),
+                shift,
+                ..
+            } => {
+                self.click_at(x, y, shift);
+                false
+            }
+            Input {
+                key: Key::MouseDrag(x, y),
+                ..
+            } => {
+                self.drag_to(x, y);
+                false
+            }
             Input {
                 key: Key::MouseScrollUp,
                 shift,
@@ -695,16 +1258,42 @@ impl<'a> TextArea<'a> {
     /// This method is useful when you want to define your own key mappings and don't want default key mappings.
     /// See 'Define your own key mappings' section in [the module document](./index.html).
     pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> bool {
-        match input.into() {
+        let input = input.into();
+        let modified = match self.completion_input(&input) {
+            Some(modified) => modified,
+            None => match self.snippet_input(&input) {
+                Some(modified) => modified,
+                None => {
+                    let modified = self.handle_input_without_shortcuts(input);
+                    self.update_completion();
+                    self.update_snippet();
+                    modified
+                }
+            },
+        };
+        self.unfold_at_cursor();
+        self.record_visit();
+        modified
+    }
+
+    fn handle_input_without_shortcuts(&mut self, input: Input) -> bool {
+        if self.jump_labels.is_some() {
+            return self.jump_input(input);
+        }
+        if (self.read_only || self.external_edit) && is_edit_input(&input) {
+            self.emit(UxEvent::EditRejected);
+            return false;
+        }
+        if self.template.is_some() {
+            return self.template_input(input);
+        }
+        match input {
             Input {
                 key: Key::Char(c),
                 ctrl: false,
                 alt: false,
                 ..
-            } => {
-                self.insert_char(c);
-                true
-            }
+            } => self.insert_char_checked(c),
             Input {
                 key: Key::Tab,
                 ctrl: false,
@@ -720,10 +1309,7 @@ impl<'a> TextArea<'a> {
             } => self.delete_next_char(),
             Input {
                 key: Key::Enter, ..
-            } => {
-                self.insert_newline();
-                true
-            }
+            } => self.insert_newline_checked(),
             Input {
                 key: Key::MouseScrollDown,
                 ..
@@ -738,85 +1324,437 @@ impl<'a> TextArea<'a> {
                 self.scroll((-1, 0));
                 false
             }
+            Input {
+                key: Key::MouseClick(x, y),
+                shift,
+                ..
+            } => {
+                self.click_at(x, y, shift);
+                false
+            }
+            Input {
+                key: Key::MouseDrag(x, y),
+                ..
+            } => {
+                self.drag_to(x, y);
+                false
+            }
             _ => false,
         }
     }
 
-    fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
-        let (row, col) = self.cursor;
-        let after = Pos::new(row, col, after_offset);
-        let edit = Edit::new(kind, before, after);
-        self.history.push(edit);
+    /// Handle the input for the completion menu. `None` is returned when the input is not for the menu.
+    fn completion_input(&mut self, input: &Input) -> Option<bool> {
+        self.completion.as_ref()?;
+        match input {
+            Input { key: Key::Up, .. }
+            | Input {
+                key: Key::Char('p'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.select_prev_completion(),
+            Input { key: Key::Down, .. }
+            | Input {
+                key: Key::Char('n'),
+                ctrl: true,
+                alt: false,
+                ..
+            } => self.select_next_completion(),
+            Input {
+                key: Key::Tab | Key::Enter,
+                ..
+            } => return Some(self.accept_completion()),
+            Input { key: Key::Esc, .. } => self.close_completion(),
+            _ => return None,
+        }
+        Some(false)
     }
 
-    /// Insert a single character at current cursor position.
-    /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
-    /// ```
-    pub fn insert_char(&mut self, c: char) {
-        if c == '\n' || c == '\r' {
-            self.insert_newline();
+    /// Narrow down the completion items by the word typed after opening the completion menu. The menu is closed when
+    /// the cursor leaves the word or no item matches.
+    fn update_completion(&mut self) {
+        let (row, col) = self.cursor;
+        let completion = match &mut self.completion {
+            Some(c) => c,
+            None => return,
+        };
+        let (start_row, start_col) = completion.start;
+        if row != start_row || col < start_col || word_start(&self.lines[row], col) != start_col {
+            self.completion = None;
             return;
         }
+        let prefix: String = self.lines[row]
+            .chars()
+            .skip(start_col)
+            .take(col - start_col)
+            .collect();
+        completion.filter(&prefix);
+        if completion.is_empty() {
+            self.completion = None;
+        }
+    }
 
-        self.delete_selection(false);
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        line.insert(i, c);
-        self.cursor.1 += 1;
-        self.push_history(
-            EditKind::InsertChar(c),
-            Pos::new(row, col, i),
-            i + c.len_utf8(),
-        );
+    /// Handle the input for the snippet session. `None` is returned when the input is not for the session.
+    fn snippet_input(&mut self, input: &Input) -> Option<bool> {
+        self.snippet.as_ref()?;
+        match input {
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: false,
+            } => self.next_snippet_stop(),
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: true,
+            } => self.prev_snippet_stop(),
+            _ => return None,
+        };
+        Some(false)
     }
 
-    /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
-    /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't.
-    /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.insert_str("hello");
-    /// assert_eq!(textarea.lines(), ["hello"]);
-    ///
-    /// textarea.insert_str(", world\ngoodbye, world");
-    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye, world"]);
-    /// ```
-    pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
-        let modified = self.delete_selection(false);
-        let mut lines: Vec<_> = s
-            .as_ref()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
-        match lines.len() {
-            0 => modified,
-            1 => self.insert_piece(lines.remove(0)),
-            _ => self.insert_chunk(lines),
+    /// End the snippet session when the cursor leaves the active tab stop.
+    fn update_snippet(&mut self) {
+        if let Some(snippet) = &self.snippet {
+            if !snippet.contains(self.cursor) {
+                self.snippet = None;
+            }
         }
     }
 
-    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
-        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
+    fn jump_input(&mut self, input: Input) -> bool {
+        let labels = match &mut self.jump_labels {
+            Some(labels) => labels,
+            None => return false,
+        };
+        match input {
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } => match labels.type_char(c) {
+                JumpInput::Jump((row, col)) => {
+                    self.jump_labels = None;
+                    self.move_cursor(CursorMove::Jump(row as u16, col as u16));
+                }
+                JumpInput::Pending => {}
+                JumpInput::NoMatch => self.jump_labels = None,
+            },
+            _ => self.jump_labels = None,
+        }
+        false
+    }
 
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
+    fn template_input(&mut self, input: Input) -> bool {
+        let (row, start, len) = match self.template.as_ref().and_then(|t| t.focused()) {
+            Some(f) => (f.row, f.col, f.len),
+            None => return false,
+        };
+        let end = start + len;
+        // Keep the cursor in the focused field
+        self.cancel_selection();
+        let col = self.cursor.1.clamp(start, end);
+        self.cursor = (row, col);
+
+        let modified = match input {
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: false,
+            }
+            | Input { key: Key::Down, .. } => {
+                self.next_field();
+                false
+            }
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                shift: true,
+            }
+            | Input { key: Key::Up, .. } => {
+                self.prev_field();
+                false
+            }
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } if c != '\n' && c != '\r' => {
+                if !self.insert_char_checked(c) {
+                    return false;
+                }
+                self.resize_focused_field(len + 1);
+                true
+            }
+            Input {
+                key: Key::Backspace,
+                ..
+            } if col > start => {
+                self.delete_char();
+                self.resize_focused_field(len - 1);
+                true
+            }
+            Input {
+                key: Key::Delete, ..
+            } if col < end => {
+                self.delete_next_char();
+                self.resize_focused_field(len - 1);
+                true
+            }
+            Input { key: Key::Left, .. } if col > start => {
+                self.cursor.1 -= 1;
+                false
+            }
+            Input {
+                key: Key::Right, ..
+            } if col < end => {
+                self.cursor.1 += 1;
+                false
+            }
+            Input { key: Key::Home, .. } => {
+                self.cursor.1 = start;
+                false
+            }
+            Input { key: Key::End, .. } => {
+                self.cursor.1 = end;
+                false
+            }
+            Input {
+                key: Key::MouseClick(x, y),
+                ..
+            } => {
+                if let Some((r, c)) = self.screen_to_text_pos(x, y, false) {
+                    let clicked = self.template.as_ref().and_then(|t| {
+                        t.fields
+                            .iter()
+                            .position(|f| f.row == r && f.col <= c && c <= f.end())
+                    });
+                    if let Some(i) = clicked {
+                        self.focus_field_at(i);
+                        self.cursor.1 = c;
+                    }
+                }
+                false
+            }
+            Input {
+                key: Key::MouseScrollDown,
+                ..
+            } => {
+                self.scroll((1, 0));
+                false
+            }
+            Input {
+                key: Key::MouseScrollUp,
+                ..
+            } => {
+                self.scroll((-1, 0));
+                false
+            }
+            _ => false,
+        };
+
+        modified
+    }
+
+    fn resize_focused_field(&mut self, len: usize) {
+        if let Some(t) = &mut self.template {
+            t.resize_focused(len);
+        }
+    }
+
+    fn click_at(&mut self, x: u16, y: u16, shift: bool) {
+        if let Some(cursor) = self.screen_to_text_pos(x, y, false) {
+            self.set_cursor_with_shift(cursor, shift);
+        }
+    }
+
+    fn drag_to(&mut self, x: u16, y: u16) {
+        // Dragging outside the textarea keeps extending the selection to the nearest edge
+        if let Some(cursor) = self.screen_to_text_pos(x, y, true) {
+            self.set_cursor_with_shift(cursor, true);
+        }
+    }
+
+    /// Check if text changes need to be reported to the change hook or followed by the tracked positions.
+    fn tracks_changes(&self) -> bool {
+        self.change_hook.is_some()
+            || self.snippet.is_some()
+            || !self.markers.is_empty()
+            || !self.bookmarks.is_empty()
+            || !self.folds.is_empty()
+            || !self.mixed_endings.is_empty()
+    }
+
+    /// Update the tracked positions to follow the text change and report it to the change hook.
+    fn track_change(&mut self, change: &ChangeEvent) {
+        if let Some(snippet) = &mut self.snippet {
+            snippet.apply(change);
+        }
+        self.markers.apply(change);
+        self.bookmarks.apply(change);
+        self.folds.apply(change);
+        self.mixed_endings.apply(change);
+        self.notify_change(change);
+    }
+
+    fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
+        let (row, col) = self.cursor;
+        let after = Pos::new(row, col, after_offset);
+        let edit = Edit::new(kind, before, after);
+        self.generation += 1;
+        self.last_paste = None;
+        self.last_kill = None;
+        if self.tracks_changes() {
+            if let Some(change) = edit.change() {
+                self.track_change(&change);
+            }
+            self.unfold_at_cursor();
+            self.notify_cursor();
+        }
+        self.history.push(edit);
+    }
+
+    /// Insert a single character at current cursor position. In overwrite mode, the character under the cursor is
+    /// replaced with the character instead. See [`TextArea::set_overwrite`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    /// ```
+    pub fn insert_char(&mut self, c: char) {
+        self.insert_char_checked(c);
+    }
+
+    fn insert_char_checked(&mut self, c: char) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::InsertChar(c));
+        }
+        if let Some(block) = self.block_selection.take() {
+            if c != '\n' && c != '\r' {
+                return self.insert_block(block, c.encode_utf8(&mut [0; 4]));
+            }
+        }
+        if c == '\n' || c == '\r' {
+            return self.insert_newline_checked();
+        }
+
+        // In overwrite mode, the character under the cursor is selected so that it is replaced with the typed one
+        let (row, col) = self.cursor;
+        let end = next_grapheme_col(&self.lines[row], col);
+        let overwriting = self.overwrite && self.selection_start.is_none() && end > col;
+        if overwriting {
+            self.selection_start = Some((row, col));
+            self.cursor = (row, end);
+        }
+
+        if self.constrain_input(c.encode_utf8(&mut [0; 4])).is_empty() {
+            if overwriting {
+                self.selection_start = None;
+                self.cursor = (row, col);
+            }
+            self.emit(UxEvent::EditRejected);
+            return false;
+        }
+
+        if overwriting {
+            self.history.begin_group();
+        }
+        self.delete_selection(false);
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        line.insert(i, c);
+        self.cursor.1 += 1;
+        self.push_history(
+            EditKind::InsertChar(c),
+            Pos::new(row, col, i),
+            i + c.len_utf8(),
+        );
+        if overwriting {
+            self.history.end_group();
+        }
+        true
+    }
+
+    /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
+    /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't. When the textarea is empty, the line ending of
+    /// the text is detected. See [`TextArea::set_line_ending`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_str("hello");
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    ///
+    /// textarea.insert_str(", world\ngoodbye, world");
+    /// assert_eq!(textarea.lines(), ["hello, world", "goodbye, world"]);
+    /// ```
+    pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
+        let input = s.as_ref();
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::InsertStr(input.to_string()));
+        }
+        if let Some(block) = self.block_selection.take() {
+            if !input.contains('\n') {
+                return self.insert_block(block, input);
+            }
+        }
+        let s = self.constrain_input(input);
+        if s.is_empty() && !input.is_empty() {
+            return false; // All characters were rejected
+        }
+        self.insert_text(&s, true)
+    }
+
+    /// Insert the text at the cursor position without the constraints of [`TextArea::set_input_filter`] and
+    /// [`TextArea::set_max_length`], which only apply to user input. Programmatic edits such as replacing ranges use
+    /// this method. When `detect` is `true` and the textarea is empty, the line endings of the text are detected.
+    fn insert_text(&mut self, s: &str, detect: bool) -> bool {
+        let modified = self.delete_selection(false);
+        let detect = detect && self.is_empty();
+        let mut lines: Vec<_> = s.split('\n').map(|s| s.to_string()).collect();
+        let (line_ending, mixed_endings) = LineEnding::strip(&mut lines);
+        let multi_line = lines.len() > 1;
+        let modified = match lines.len() {
+            0 => modified,
+            1 => self.insert_piece(lines.remove(0)),
+            _ => self.insert_chunk(lines),
+        };
+        if detect && multi_line {
+            self.line_ending = line_ending;
+            self.mixed_endings = mixed_endings;
+        }
+        modified
+    }
+
+    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
+        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
+
+        if self.single_line {
+            return self.insert_piece(chunk.join(" "));
+        }
+
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
             .unwrap_or(line.len());
         let before = Pos::new(row, col, i);
 
@@ -870,7 +1808,7 @@ impl<'a> TextArea<'a> {
                 .as_str()
                 .to_string();
             if should_yank {
-                self.yank = removed.clone().into();
+                self.store_yank(removed.clone().into());
             }
             self.push_history(EditKind::DeleteStr(removed), end, start.offset);
             return;
@@ -889,7 +1827,7 @@ impl<'a> TextArea<'a> {
         }
 
         if should_yank {
-            self.yank = YankText::Chunk(deleted.clone());
+            self.store_yank(YankText::Chunk(deleted.clone()));
         }
 
         let edit = if deleted.len() == 1 {
@@ -919,6 +1857,9 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["🐱", "🐮"]);
     /// ```
     pub fn delete_str(&mut self, chars: usize) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteStr(chars));
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -962,7 +1903,7 @@ impl<'a> TextArea<'a> {
                 .drain(start_offset..end_offset)
                 .as_str()
                 .to_string();
-            self.yank = removed.clone().into();
+            self.store_yank(removed.clone().into());
             self.push_history(
                 EditKind::DeleteStr(removed),
                 Pos::new(start_row, end_col, end_offset),
@@ -984,6 +1925,13 @@ impl<'a> TextArea<'a> {
             }
             r += 1;
         }
+        // Delete until the end of text when the number of characters exceeds it
+        if r == self.lines.len() {
+            r -= 1;
+            let line = &self.lines[r];
+            offset = line.len();
+            col = line.chars().count();
+        }
 
         let start = Pos::new(start_row, start_col, start_offset);
         let end = Pos::new(r, col, offset);
@@ -1021,7 +1969,7 @@ impl<'a> TextArea<'a> {
                 Pos::new(row, col + chars, i + bytes),
                 i,
             );
-            self.yank = removed.into();
+            self.store_yank(removed.into());
             true
         } else {
             false
@@ -1043,27 +1991,28 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["hi      "]);
     /// ```
     pub fn insert_tab(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::InsertTab);
+        }
         let modified = self.delete_selection(false);
         if self.tab_len == 0 {
             return modified;
         }
 
         if self.hard_tab_indent {
-            self.insert_char('\t');
-            return true;
+            return self.insert_char_checked('\t') || modified;
         }
 
         let (row, col) = self.cursor;
-        let width: usize = self.lines[row]
-            .chars()
-            .take(col)
-            .map(|c| c.width().unwrap_or(0))
-            .sum();
+        let width = display_width(&self.lines[row], col, self.tab_width());
         let len = self.tab_len - (width % self.tab_len as usize) as u8;
-        self.insert_piece(spaces(len).to_string())
+        let tab = self.constrain_input(spaces(len)).into_owned();
+        self.insert_piece(tab) || modified
     }
 
-    /// Insert a newline at current cursor position.
+    /// Insert a newline at current cursor position. When auto-indent is enabled by [`TextArea::set_auto_indent`], the
+    /// leading whitespaces of the current line are also inserted to the new line. In single-line mode, this method
+    /// inserts nothing and marks the text as submitted instead. See [`TextArea::set_single_line`].
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -1074,6 +2023,22 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["h", "i"]);
     /// ```
     pub fn insert_newline(&mut self) {
+        self.insert_newline_checked();
+    }
+
+    fn insert_newline_checked(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::InsertNewline);
+        }
+        if self.single_line {
+            self.submitted = true;
+            return false;
+        }
+        if self.constrain_input("\n").is_empty() {
+            self.emit(UxEvent::EditRejected);
+            return false;
+        }
+        self.history.begin_group();
         self.delete_selection(false);
 
         let (row, col) = self.cursor;
@@ -1085,66 +2050,589 @@ impl<'a> TextArea<'a> {
             .unwrap_or(line.len());
         let next_line = line[offset..].to_string();
         line.truncate(offset);
+        let indent = if self.auto_indent {
+            let end = line
+                .find(|c: char| c != ' ' && c != '\t')
+                .unwrap_or(line.len());
+            line[..end].to_string()
+        } else {
+            String::new()
+        };
 
         self.lines.insert(row + 1, next_line);
         self.cursor = (row + 1, 0);
         self.push_history(EditKind::InsertNewline, Pos::new(row, col, offset), 0);
+        let indent = self.constrain_input(&indent).into_owned();
+        self.insert_piece(indent);
+        self.history.end_group();
+        true
     }
 
-    /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
-    /// the textarea. When some text is selected, it is deleted instead.
+    /// Apply the input filter and the max length set by [`TextArea::set_input_filter`] and
+    /// [`TextArea::set_max_length`] to the text which will be inserted at the cursor. Rejected characters are removed
+    /// and the text is truncated so that the text does not exceed the max length. The selected text is not counted
+    /// since it is replaced with the inserted text. `\r` of `\r\n` is treated as a part of the newline.
+    fn constrain_input<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        if self.input_filter.is_none() && self.max_length.is_none() {
+            return Cow::Borrowed(s);
+        }
+
+        let mut remaining = match self.max_length {
+            Some(max) => max.saturating_sub(self.text_len() - self.selected_len()),
+            None => usize::MAX,
+        };
+        let mut constrained = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if remaining == 0 {
+                break;
+            }
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                continue;
+            }
+            if self.input_filter.as_ref().map(|f| f.accepts(c)) == Some(false) {
+                continue;
+            }
+            constrained.push(c);
+            remaining -= 1;
+        }
+        Cow::Owned(constrained)
+    }
+
+    /// Number of characters in the text. Newlines are counted as characters.
+    fn text_len(&self) -> usize {
+        self.lines.iter().map(|l| l.chars().count()).sum::<usize>() + self.lines.len() - 1
+    }
+
+    /// Number of characters in the selected text. Newlines are counted as characters.
+    fn selected_len(&self) -> usize {
+        let (start, end) = match self.selection_positions() {
+            Some(range) => range,
+            None => return 0,
+        };
+        if start.row == end.row {
+            return end.col - start.col;
+        }
+        let first = self.lines[start.row].chars().count() - start.col + 1;
+        let middle: usize = self.lines[start.row + 1..end.row]
+            .iter()
+            .map(|l| l.chars().count() + 1)
+            .sum();
+        first + middle + end.col
+    }
+
+    /// Indent the selected lines by one level with [`TextArea::indent`]. When no text is selected, the cursor line is
+    /// indented. Empty lines are not indented. When the selection ends at the head of a line, the line is not included.
+    /// The selection and the cursor are kept on the same characters. All modifications are undone at once. Note that
+    /// this method does nothing when the tab length is 0. This method returns if some line was indented or not.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// let mut textarea = TextArea::from(["a:", "b: 1", "", "c: 2"]);
     ///
     /// textarea.move_cursor(CursorMove::Down);
-    /// textarea.delete_newline();
-    /// assert_eq!(textarea.lines(), ["helloworld"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.indent_selection();
+    /// assert_eq!(textarea.lines(), ["a:", "    b: 1", "", "    c: 2"]);
+    /// assert_eq!(textarea.selection_range(), Some(((1, 4), (3, 8))));
+    ///
+    /// textarea.dedent_selection();
+    /// assert_eq!(textarea.lines(), ["a:", "b: 1", "", "c: 2"]);
     /// ```
-    pub fn delete_newline(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
+    pub fn indent_selection(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::IndentSelection);
         }
-
-        let (row, _) = self.cursor;
-        if row == 0 {
+        if self.tab_len == 0 {
             return false;
         }
-
-        let line = self.lines.remove(row);
-        let prev_line = &mut self.lines[row - 1];
-        let prev_line_end = prev_line.len();
-
-        self.cursor = (row - 1, prev_line.chars().count());
-        prev_line.push_str(&line);
-        self.push_history(EditKind::DeleteNewline, Pos::new(row, 0, 0), prev_line_end);
-        true
+        let indent = self.indent();
+        let len = indent.chars().count();
+        self.modify_selected_lines(|t, row| {
+            if t.lines[row].is_empty() {
+                return (0, 0);
+            }
+            t.cursor = (row, 0);
+            t.insert_piece(indent.to_string());
+            (0, len as isize)
+        })
     }
 
-    /// Delete one character before cursor. When the cursor is at head of line, the newline before the cursor will be
-    /// removed. This method returns if some text was deleted or not in the textarea. When some text is selected, it is
-    /// deleted instead.
+    /// Exchange the word under the cursor with the next word in the cursor line. Words consist of alphanumeric
+    /// characters and `_`. When the cursor is not on a word, the word just before the cursor is swapped. The cursor
+    /// moves to the head of the swapped word so that calling this method repeatedly moves the word forward. The swap is
+    /// undone at once. This method returns if the words were swapped or not.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc"]);
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// textarea.delete_char();
-    /// assert_eq!(textarea.lines(), ["bc"]);
+    /// assert!(textarea.swap_with_next_word());
+    /// assert_eq!(textarea.lines(), ["bar foo baz"]);
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    ///
+    /// assert!(textarea.swap_with_next_word());
+    /// assert_eq!(textarea.lines(), ["bar baz foo"]);
+    /// assert!(!textarea.swap_with_next_word());
     /// ```
-    pub fn delete_char(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
-        }
-
+    pub fn swap_with_next_word(&mut self) -> bool {
         let (row, col) = self.cursor;
-        if col == 0 {
-            return self.delete_newline();
+        match find_word_swap(&self.lines[row], col) {
+            Some(ranges) => self.swap_ranges(row, ranges),
+            None => false,
+        }
+    }
+
+    /// Exchange the comma-separated argument under the cursor with its neighbor in the innermost brackets enclosing the
+    /// cursor in the cursor line. Brackets are decided by [`TextArea::set_bracket_pairs`] and commas in nested brackets
+    /// are not separators. The argument is swapped with the next one, or with the previous one when it is the last
+    /// argument. The cursor moves to the head of the swapped argument. The swap is undone at once. This method returns
+    /// if the arguments were swapped or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["f(a, g(b, c), d)"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// assert!(textarea.swap_arguments());
+    /// assert_eq!(textarea.lines(), ["f(g(b, c), a, d)"]);
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    ///
+    /// // Swap with the previous argument since `d` is the last one
+    /// textarea.move_cursor(CursorMove::Jump(0, 14));
+    /// assert!(textarea.swap_arguments());
+    /// assert_eq!(textarea.lines(), ["f(g(b, c), d, a)"]);
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    /// ```
+    pub fn swap_arguments(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        let (first, second) = match find_argument_swap(&self.lines[row], col, &self.bracket_pairs) {
+            Some(ranges) => ranges,
+            None => return false,
+        };
+        // Keep the cursor on the argument which was under the cursor
+        let moved_first = col <= first.1;
+        if !self.swap_ranges(row, (first, second)) {
+            return false;
+        }
+        if !moved_first {
+            self.cursor = (row, first.0);
+        }
+        true
+    }
+
+    /// Swap the two non-overlapping character ranges in the line at the row as one undo step. The cursor moves to the
+    /// new head of the first range.
+    fn swap_ranges(&mut self, row: usize, (first, second): (ColRange, ColRange)) -> bool {
+        let chars: Vec<char> = self.lines[row].chars().collect();
+        let text = |(s, e): ColRange| chars[s..e].iter().collect::<String>();
+        let (a, between, b) = (text(first), text((first.1, second.0)), text(second));
+        if a == b {
+            return false;
+        }
+        let swapped = format!("{}{}{}", b, between, a);
+
+        self.history.begin_group();
+        self.replace_range((row, first.0), (row, second.1), &swapped);
+        self.history.end_group();
+        let head = first.0 + (second.1 - second.0) + (second.0 - first.1);
+        self.cursor = (row, head);
+        true
+    }
+
+    /// Remove one level of indent from the selected lines. A hard tab or spaces up to the tab length are removed from
+    /// the head of each line. When no text is selected, the cursor line is dedented. The selected lines are decided
+    /// in the same way as [`TextArea::indent_selection`]. All modifications are undone at once. This method returns
+    /// if some line was dedented or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["      hello"]);
+    ///
+    /// textarea.dedent_selection();
+    /// assert_eq!(textarea.lines(), ["  hello"]);
+    /// textarea.dedent_selection();
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    /// assert!(!textarea.dedent_selection());
+    /// ```
+    pub fn dedent_selection(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DedentSelection);
+        }
+        let tab_len = self.tab_len as usize;
+        self.modify_selected_lines(|t, row| {
+            let line = &t.lines[row];
+            let len = if line.starts_with('\t') {
+                1
+            } else {
+                line.chars().take(tab_len).take_while(|&c| c == ' ').count()
+            };
+            if len == 0 {
+                return (0, 0);
+            }
+            // Both tab and spaces are 1 byte
+            t.delete_range(Pos::new(row, 0, 0), Pos::new(row, len, len), false);
+            (0, -(len as isize))
+        })
+    }
+
+    /// Rows of the selected lines. When the selection ends at the head of a line, the line is not included. When no
+    /// text is selected, the cursor row is returned.
+    fn selected_rows(&self) -> Range<usize> {
+        let (start, end) = self.selection_range().unwrap_or((self.cursor, self.cursor));
+        let end_row = if end.0 > start.0 && end.1 == 0 {
+            end.0 - 1
+        } else {
+            end.0
+        };
+        start.0..end_row + 1
+    }
+
+    /// Apply the function to each selected line (or the cursor line when nothing is selected) as one undo step. The
+    /// function returns the column where the line was modified and how many characters were inserted at the column
+    /// (negative on deletion). The cursor and the selection are moved along with the modified text.
+    fn modify_selected_lines<F>(&mut self, mut modify: F) -> bool
+    where
+        F: FnMut(&mut Self, usize) -> (usize, isize),
+    {
+        let shift = |pos: (usize, usize), row: usize, (col, delta): (usize, isize)| {
+            if pos.0 == row && pos.1 >= col {
+                (pos.0, (pos.1 as isize + delta).max(col as isize) as usize)
+            } else {
+                pos
+            }
+        };
+
+        let mut modified = false;
+        let mut cursor = self.cursor;
+        let mut selection_start = self.selection_start;
+        self.history.begin_group();
+        for row in self.selected_rows() {
+            let change = modify(self, row);
+            if change.1 != 0 {
+                modified = true;
+                cursor = shift(cursor, row, change);
+                selection_start = selection_start.map(|pos| shift(pos, row, change));
+            }
+        }
+        self.history.end_group();
+
+        self.cursor = cursor;
+        self.selection_start = selection_start;
+        modified
+    }
+
+    /// Move the selected lines (or the cursor line when nothing is selected) up by one line. The selected lines are
+    /// decided in the same way as [`TextArea::indent_selection`]. The cursor and the selection move along with the
+    /// lines. The move is undone at once. This method returns if the lines were moved or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.move_cursor(CursorMove::Jump(2, 0));
+    ///
+    /// assert!(textarea.move_lines_up());
+    /// assert_eq!(textarea.lines(), ["a", "c", "b"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// assert!(textarea.move_lines_up());
+    /// assert_eq!(textarea.lines(), ["c", "a", "b"]);
+    /// assert!(!textarea.move_lines_up());
+    /// ```
+    pub fn move_lines_up(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::MoveLinesUp);
+        }
+        let rows = self.selected_rows();
+        if rows.start == 0 {
+            return false;
+        }
+        let mut lines = self.lines[rows.start - 1..rows.end].to_vec();
+        lines.rotate_left(1);
+        self.replace_rows(rows.start - 1..rows.end, &lines, -1)
+    }
+
+    /// Move the selected lines (or the cursor line when nothing is selected) down by one line. See
+    /// [`TextArea::move_lines_up`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    ///
+    /// assert!(textarea.move_lines_down());
+    /// assert_eq!(textarea.lines(), ["b", "a", "c"]);
+    /// assert_eq!(textarea.selection_range(), Some(((1, 0), (2, 0))));
+    /// ```
+    pub fn move_lines_down(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::MoveLinesDown);
+        }
+        let rows = self.selected_rows();
+        if rows.end == self.lines.len() {
+            return false;
+        }
+        let mut lines = self.lines[rows.start..rows.end + 1].to_vec();
+        lines.rotate_right(1);
+        self.replace_rows(rows.start..rows.end + 1, &lines, 1)
+    }
+
+    /// Duplicate the selected lines (or the cursor line when nothing is selected) below them. The cursor and the
+    /// selection move to the copy so that calling this method repeatedly makes more copies. The duplication is undone
+    /// at once.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.duplicate_lines());
+    /// assert_eq!(textarea.lines(), ["a", "a", "b"]);
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn duplicate_lines(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DuplicateLines);
+        }
+        let rows = self.selected_rows();
+        let mut lines = self.lines[rows.clone()].to_vec();
+        lines.extend_from_slice(&self.lines[rows.clone()]);
+        self.replace_rows(rows.clone(), &lines, rows.len() as isize)
+    }
+
+    /// Sort the selected lines in lexicographical order. Lines are decided in the same way as
+    /// [`TextArea::indent_selection`]. The selection is kept on the same rows. The sort is undone at once. This method
+    /// returns if the order of the lines was changed or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["c", "a", "b", "0"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(2, 1));
+    ///
+    /// assert!(textarea.sort_lines());
+    /// assert_eq!(textarea.lines(), ["a", "b", "c", "0"]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 1))));
+    /// ```
+    pub fn sort_lines(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::SortLines);
+        }
+        let rows = self.selected_rows();
+        let mut lines = self.lines[rows.clone()].to_vec();
+        lines.sort();
+        self.replace_rows(rows, &lines, 0)
+    }
+
+    /// Replace the lines in the rows with the given lines as one undo step. The cursor and the selection are moved by
+    /// `offset` rows.
+    fn replace_rows(&mut self, rows: Range<usize>, lines: &[String], offset: isize) -> bool {
+        if self.lines[rows.clone()] == *lines {
+            return false;
+        }
+        let (cursor, selection_start) = (self.cursor, self.selection_start);
+        self.history.begin_group();
+        self.replace_lines(rows.start, rows.end, lines);
+        self.history.end_group();
+        let shift = |t: &Self, (row, col): (usize, usize)| {
+            t.clamp_cursor(((row as isize + offset) as usize, col))
+        };
+        self.cursor = shift(self, cursor);
+        self.selection_start = selection_start.map(|pos| shift(self, pos));
+        true
+    }
+
+    /// Join the selected lines into one line like `J` of Vim. When no text is selected or only one line is selected,
+    /// the line is joined with the next line. Leading whitespaces of the joined lines are removed and one space is
+    /// put between the lines unless either side is empty or the line already ends with a whitespace. The cursor moves
+    /// to the last join point and the selection is cancelled. The join is undone at once. This method returns if some
+    /// lines were joined or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    1", "}"]);
+    ///
+    /// assert!(textarea.join_lines());
+    /// assert_eq!(textarea.lines(), ["fn f() { 1", "}"]);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// ```
+    pub fn join_lines(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::JoinLines);
+        }
+        let rows = self.selected_rows();
+        let end = cmp::min(cmp::max(rows.end, rows.start + 2), self.lines.len());
+        if end < rows.start + 2 {
+            return false;
+        }
+        let row = rows.start;
+        let mut col = 0;
+        self.history.begin_group();
+        for _ in row + 1..end {
+            let line = &self.lines[row];
+            let next = &self.lines[row + 1];
+            let indent = next.chars().take_while(|c| c.is_whitespace()).count();
+            let sep = if line.is_empty()
+                || line.ends_with(char::is_whitespace)
+                || indent == next.chars().count()
+            {
+                ""
+            } else {
+                " "
+            };
+            col = line.chars().count();
+            self.replace_range((row, col), (row + 1, indent), sep);
+        }
+        self.history.end_group();
+        self.cursor = (row, col);
+        true
+    }
+
+    /// Toggle line comments of the selected lines (or the cursor line when nothing is selected) with the comment
+    /// prefix such as `//` or `#`. When all non-blank lines are commented, the prefix and one space after it are
+    /// removed. Otherwise the prefix followed by one space is inserted into the non-blank lines at the smallest
+    /// indentation of them. The cursor and the selection are kept on the same characters. The toggle is undone at
+    /// once. This method returns if some line was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["if x {", "    y();", "", "}"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.toggle_comment("//"));
+    /// assert_eq!(textarea.lines(), ["// if x {", "//     y();", "", "// }"]);
+    ///
+    /// assert!(textarea.toggle_comment("//"));
+    /// assert_eq!(textarea.lines(), ["if x {", "    y();", "", "}"]);
+    /// ```
+    pub fn toggle_comment(&mut self, prefix: &str) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::ToggleComment(prefix.to_string()));
+        }
+        if prefix.is_empty() {
+            return false;
+        }
+        let indent_of = |line: &str| line.chars().take_while(|c| c.is_whitespace()).count();
+        let blank = |line: &str| line.chars().all(char::is_whitespace);
+        let mut commented = true;
+        let mut min_indent = usize::MAX;
+        for line in &self.lines[self.selected_rows()] {
+            if blank(line) {
+                continue;
+            }
+            commented &= line.trim_start().starts_with(prefix);
+            min_indent = cmp::min(min_indent, indent_of(line));
+        }
+        if min_indent == usize::MAX {
+            return false;
+        }
+
+        let prefix_len = prefix.chars().count();
+        self.modify_selected_lines(|t, row| {
+            let line = &t.lines[row];
+            if blank(line) {
+                return (0, 0);
+            }
+            if commented {
+                let col = indent_of(line);
+                let mut len = prefix_len;
+                if line.chars().nth(col + len) == Some(' ') {
+                    len += 1;
+                }
+                let start = Pos::new(row, col, t.line_offset(row, col));
+                let end = Pos::new(row, col + len, t.line_offset(row, col + len));
+                t.delete_range(start, end, false);
+                (col, -(len as isize))
+            } else {
+                t.cursor = (row, min_indent);
+                t.insert_piece(format!("{} ", prefix));
+                (min_indent, prefix_len as isize + 1)
+            }
+        })
+    }
+
+    /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
+    /// the textarea. When some text is selected, it is deleted instead.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.delete_newline();
+    /// assert_eq!(textarea.lines(), ["helloworld"]);
+    /// ```
+    pub fn delete_newline(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteNewline);
+        }
+        if self.delete_selection(false) {
+            return true;
+        }
+
+        let (row, _) = self.cursor;
+        if row == 0 {
+            return false;
+        }
+
+        let line = self.lines.remove(row);
+        let prev_line = &mut self.lines[row - 1];
+        let prev_line_end = prev_line.len();
+
+        self.cursor = (row - 1, prev_line.chars().count());
+        prev_line.push_str(&line);
+        self.push_history(EditKind::DeleteNewline, Pos::new(row, 0, 0), prev_line_end);
+        true
+    }
+
+    /// Delete one character before cursor. When the cursor is at head of line, the newline before the cursor will be
+    /// removed. This method returns if some text was deleted or not in the textarea. When some text is selected, it is
+    /// deleted instead. A grapheme cluster such as a character with combining marks is deleted at once.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["bc"]);
+    /// ```
+    pub fn delete_char(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteChar);
+        }
+        if let Some(block) = self.block_selection.take() {
+            return self.delete_block(block, true);
+        }
+        if self.delete_selection(false) {
+            return true;
+        }
+
+        let (row, col) = self.cursor;
+        if col == 0 {
+            return self.delete_newline();
         }
 
         let line = &mut self.lines[row];
+        let start = prev_grapheme_col(line, col);
+        if col - start > 1 {
+            // Delete the whole grapheme cluster such as an emoji joined with ZWJ or a character with combining marks
+            let begin = line.char_indices().nth(start).map(|(i, _)| i);
+            let end = line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+            if let Some(begin) = begin {
+                let removed = line.drain(begin..end).as_str().to_string();
+                self.cursor.1 = start;
+                self.push_history(EditKind::DeleteStr(removed), Pos::new(row, col, end), begin);
+                return true;
+            }
+            return false;
+        }
+
         if let Some((offset, c)) = line.char_indices().nth(col - 1) {
             line.remove(offset);
             self.cursor.1 -= 1;
@@ -1171,6 +2659,12 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["ac"]);
     /// ```
     pub fn delete_next_char(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteNextChar);
+        }
+        if let Some(block) = self.block_selection.take() {
+            return self.delete_block(block, false);
+        }
         if self.delete_selection(false) {
             return true;
         }
@@ -1199,13 +2693,18 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["ab"]);
     /// ```
     pub fn delete_line_by_end(&mut self) -> bool {
-        if self.delete_selection(false) {
-            return true;
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteLineByEnd);
         }
-        if self.delete_piece(self.cursor.1, usize::MAX) {
+        if self.delete_selection(false) {
             return true;
         }
-        self.delete_next_char() // At the end of the line. Try to delete next line
+        self.kill(false, |t| {
+            if t.delete_piece(t.cursor.1, usize::MAX) {
+                return true;
+            }
+            t.delete_next_char() // At the end of the line. Try to delete next line
+        })
     }
 
     /// Delete string from cursor to head of the line. When the cursor is at head of line, the newline before the cursor
@@ -1223,13 +2722,61 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["cde"]);
     /// ```
     pub fn delete_line_by_head(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteLineByHead);
+        }
         if self.delete_selection(false) {
             return true;
         }
-        if self.delete_piece(0, self.cursor.1) {
-            return true;
+        self.kill(true, |t| {
+            if t.delete_piece(0, t.cursor.1) {
+                return true;
+            }
+            t.delete_newline()
+        })
+    }
+
+    /// Delete the whole line at the cursor including its newline, like `kill-whole-line` of Emacs or `dd` of Vim. The
+    /// deleted text is yanked and the cursor moves to the head of the next line. When the cursor is on the last line,
+    /// the newline before it is deleted and the cursor moves to the end of the previous line. This method returns if
+    /// some text was deleted or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
+    ///
+    /// assert!(textarea.delete_whole_line());
+    /// assert_eq!(textarea.lines(), ["aaa", "ccc"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert_eq!(textarea.yank_text(), "bbb\n");
+    ///
+    /// assert!(textarea.delete_whole_line());
+    /// assert_eq!(textarea.lines(), ["aaa"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// assert_eq!(textarea.yank_text(), "\nccc");
+    /// ```
+    pub fn delete_whole_line(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteWholeLine);
         }
-        self.delete_newline()
+        self.cancel_selection();
+        let row = self.cursor.0;
+        let eol =
+            |t: &Self, row: usize| Pos::new(row, t.lines[row].chars().count(), t.lines[row].len());
+        let (start, end) = if row + 1 < self.lines.len() {
+            (Pos::new(row, 0, 0), Pos::new(row + 1, 0, 0))
+        } else if row > 0 {
+            (eol(self, row - 1), eol(self, row))
+        } else if !self.lines[row].is_empty() {
+            (Pos::new(row, 0, 0), eol(self, row))
+        } else {
+            return false;
+        };
+        self.kill(false, |t| {
+            t.delete_range(start, end, true);
+            true
+        })
     }
 
     /// Delete a word before cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
@@ -1251,17 +2798,23 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["aaa "]);
     /// ```
     pub fn delete_word(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteWord);
+        }
         if self.delete_selection(false) {
             return true;
         }
-        let (r, c) = self.cursor;
-        if let Some(col) = find_word_start_backward(&self.lines[r], c) {
-            self.delete_piece(col, c - col)
-        } else if c > 0 {
-            self.delete_piece(0, c)
-        } else {
-            self.delete_newline()
-        }
+        self.kill(true, |t| {
+            let (r, c) = t.cursor;
+            let class = WordClass::new(&t.word_chars, false);
+            if let Some(col) = find_word_start_backward(&t.lines[r], c, class) {
+                t.delete_piece(col, c - col)
+            } else if c > 0 {
+                t.delete_piece(0, c)
+            } else {
+                t.delete_newline()
+            }
+        })
     }
 
     /// Delete a word next to cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
@@ -1281,24 +2834,79 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" ccc"]);
     /// ```
     pub fn delete_next_word(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteNextWord);
+        }
         if self.delete_selection(false) {
             return true;
         }
-        let (r, c) = self.cursor;
-        let line = &self.lines[r];
-        if let Some(col) = find_word_exclusive_end_forward(line, c) {
-            self.delete_piece(c, col - c)
-        } else {
-            let end_col = line.chars().count();
-            if c < end_col {
-                self.delete_piece(c, end_col - c)
-            } else if r + 1 < self.lines.len() {
-                self.cursor = (r + 1, 0);
-                self.delete_newline()
+        self.kill(false, |t| {
+            let (r, c) = t.cursor;
+            let line = &t.lines[r];
+            let class = WordClass::new(&t.word_chars, false);
+            if let Some(col) = find_word_exclusive_end_forward(line, c, class) {
+                t.delete_piece(c, col - c)
             } else {
-                false
+                let end_col = line.chars().count();
+                if c < end_col {
+                    t.delete_piece(c, end_col - c)
+                } else if r + 1 < t.lines.len() {
+                    t.cursor = (r + 1, 0);
+                    t.delete_newline()
+                } else {
+                    false
+                }
+            }
+        })
+    }
+
+    /// Run the kill operation which deletes text around the cursor. When the kill accumulation is enabled, the deleted
+    /// text including a deleted newline is yanked, and it is joined with the yanked text of the previous kill when the
+    /// cursor has not moved since then. `backward` is `true` when the text before the cursor is deleted.
+    fn kill<F>(&mut self, backward: bool, kill: F) -> bool
+    where
+        F: FnOnce(&mut Self) -> bool,
+    {
+        if !self.kill_accumulation {
+            return kill(self);
+        }
+        let consecutive = self.last_kill == Some(self.cursor);
+        let prev = self.yank.to_string();
+        let history = self.yank_history.clone();
+
+        // A kill deletes text in the cursor line and its adjacent line. Compare the text of the lines before and after
+        // the kill to know the deleted text
+        let num_lines = self.lines.len();
+        let first = self.cursor.0.saturating_sub(1);
+        let last = cmp::min(self.cursor.0 + 1, num_lines - 1);
+        let before: Vec<char> = self.lines[first..=last].join("\n").chars().collect();
+        if !kill(self) {
+            return false;
+        }
+        let last = last + self.lines.len() - num_lines;
+        let after = self.lines[first..=last].join("\n").chars().count();
+        let (row, col) = self.cursor;
+        let start: usize = self.lines[first..row]
+            .iter()
+            .map(|l| l.chars().count() + 1)
+            .sum::<usize>()
+            + col;
+        let killed: String = before[start..start + before.len() - after].iter().collect();
+
+        if self.mask.is_none() || self.yank_masked {
+            let text = match (consecutive, backward) {
+                (false, _) => killed,
+                (true, false) => prev.clone() + &killed,
+                (true, true) => killed + &prev,
+            };
+            self.yank_history = history;
+            if consecutive {
+                self.yank_history.retain(|y| y.to_string() != prev);
             }
+            self.set_yank_text(text);
         }
+        self.last_kill = Some(self.cursor);
+        true
     }
 
     /// Paste a string previously deleted by [`TextArea::delete_line_by_head`], [`TextArea::delete_line_by_end`],
@@ -1315,18 +2923,597 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
     /// ```
     pub fn paste(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::Paste);
+        }
+        let yank = self.yank.clone();
+        let text = yank.to_string();
+        let start = self.selection_range().map_or(self.cursor, |(s, _)| s);
+        if !self.paste_yank(yank) {
+            return false;
+        }
+        self.last_paste = Some(LastPaste {
+            start,
+            end: self.cursor,
+            text: self.text_between(start, self.cursor),
+            index: self.yank_history.iter().position(|y| y.to_string() == text),
+        });
+        true
+    }
+
+    fn paste_yank(&mut self, yank: YankText) -> bool {
+        if self.input_filter.is_some() || self.max_length.is_some() {
+            return self.insert_str(yank.to_string());
+        }
         self.delete_selection(false);
-        match self.yank.clone() {
+        match yank {
             YankText::Piece(s) => self.insert_piece(s),
             YankText::Chunk(c) => self.insert_chunk(c),
         }
     }
 
-    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
+    fn text_between(&self, start: (usize, usize), end: (usize, usize)) -> String {
+        let start_offset = self.line_offset(start.0, start.1);
+        let end_offset = self.line_offset(end.0, end.1);
+        if start.0 == end.0 {
+            return self.lines[start.0][start_offset..end_offset].to_string();
+        }
+        let mut text = self.lines[start.0][start_offset..].to_string();
+        for line in &self.lines[start.0 + 1..end.0] {
+            text.push('\n');
+            text.push_str(line);
+        }
+        text.push('\n');
+        text.push_str(&self.lines[end.0][..end_offset]);
+        text
+    }
+
+    /// Replace the text inserted by the last [`TextArea::paste`] with the previous text in the yank history, like
+    /// `yank-pop` of Emacs. Calling this method repeatedly cycles through the yank history. The replacing text becomes
+    /// the current yanked text but the order of the yank history is kept. The replacement is undone at once.
+    ///
+    /// This method works only while the cursor stays at the end of the pasted text and the text is not modified since
+    /// the paste. Otherwise, or when the yank history has no other text, this method does nothing and returns `false`.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_yank_text("foo");
+    /// textarea.set_yank_text("bar");
+    /// textarea.set_yank_text("baz");
+    ///
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["baz"]);
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), ["bar"]);
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// assert!(textarea.yank_pop()); // Wrap around
+    /// assert_eq!(textarea.lines(), ["baz"]);
+    ///
+    /// // Yank-pop is not available after other edits
+    /// textarea.insert_char('!');
+    /// assert!(!textarea.yank_pop());
+    /// ```
+    pub fn yank_pop(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::YankPop);
+        }
+        let last = match self.last_paste.take() {
+            Some(last) => last,
+            None => return false,
+        };
+        if self.cursor != last.end || self.text_between(last.start, last.end) != last.text {
+            return false;
+        }
+        let len = self.yank_history.len();
+        let index = match last.index {
+            Some(i) if len > 1 => (i + 1) % len,
+            None if len > 0 => 0,
+            _ => return false,
+        };
+        let yank = self.yank_history[index].clone();
+        self.history.begin_group();
+        self.replace_range(last.start, last.end, "");
+        self.paste_yank(yank.clone());
+        self.history.end_group();
+        self.yank = yank;
+        self.last_paste = Some(LastPaste {
+            start: last.start,
+            end: self.cursor,
+            text: self.text_between(last.start, self.cursor),
+            index: Some(index),
+        });
+        true
+    }
+
+    /// Copy the selection text to the register named `register`. Registers are the storage of texts separate from the
+    /// yank buffer and the yank history, like registers of Vim or Emacs. Any character can be a register name. The text
+    /// in the register can be inserted by [`TextArea::paste_from_register`]. When nothing is selected, this method does
+    /// nothing and returns `false`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordEnd);
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert!(textarea.yank_to_register('a'));
+    ///
+    /// assert_eq!(textarea.register_text('a').as_deref(), Some("hello"));
+    /// assert_eq!(textarea.yank_text(), ""); // The yank buffer is not changed
+    /// ```
+    pub fn yank_to_register(&mut self, register: char) -> bool {
+        if self.is_recording_ops() {
+            let selected = self.selection_start.is_some();
+            self.record_op(MacroOp::YankToRegister(register));
+            return selected;
+        }
+        let (start, end) = match self.take_selection_positions() {
+            Some(range) => range,
+            None => return false,
+        };
+        if self.mask.is_none() || self.yank_masked {
+            let text = self.text_between((start.row, start.col), (end.row, end.col));
+            self.set_register_text(register, text);
+        }
+        true
+    }
+
+    /// Paste the text in the register named `register`. See [`TextArea::yank_to_register`] for registers. This method
+    /// returns if some text was inserted or not. When the register is empty, nothing happens and `false` is returned.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_register_text('a', "foo\nbar");
+    /// textarea.set_yank_text("baz");
+    ///
+    /// assert!(textarea.paste_from_register('a'));
+    /// assert_eq!(textarea.lines(), ["foo", "bar"]);
+    /// assert!(!textarea.paste_from_register('b'));
+    /// ```
+    pub fn paste_from_register(&mut self, register: char) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::PasteFromRegister(register));
+        }
+        match self.registers.get(&register) {
+            Some(yank) => {
+                let yank = yank.clone();
+                self.paste_yank(yank)
+            }
+            None => false,
+        }
+    }
+
+    /// Format the entire text with the given function. The function receives the current text joined with `\n` and
+    /// returns the formatted text, or `None` when formatting failed. When the formatted text ends with a newline, the
+    /// last newline is ignored.
+    ///
+    /// Instead of replacing the whole text, only the changed lines are updated as one undo step. The cursor stays at
+    /// the nearest position in the formatted text and the scroll position is kept. This method returns if the text
+    /// was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {", "println!();", "}"]);
+    /// textarea.move_cursor(CursorMove::Jump(2, 0));
+    ///
+    /// let modified = textarea.format_with(|text| {
+    ///     let lines: Vec<_> = text
+    ///         .lines()
+    ///         .map(|l| if l.starts_with("print") { format!("    {}", l) } else { l.to_string() })
+    ///         .collect();
+    ///     Some(lines.join("\n") + "\n")
+    /// });
+    /// assert!(modified);
+    /// assert_eq!(textarea.lines(), ["fn main() {", "    println!();", "}"]);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // Formatting is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["fn main() {", "println!();", "}"]);
+    /// ```
+    pub fn format_with<F>(&mut self, format: F) -> bool
+    where
+        F: FnOnce(&str) -> Option<String>,
+    {
+        let text = self.lines.join("\n");
+        let formatted = match format(&text) {
+            Some(formatted) => formatted,
+            None => return false,
+        };
+        let formatted = match formatted.strip_suffix('\n') {
+            Some(s) if !text.ends_with('\n') => s,
+            _ => formatted.as_str(),
+        };
+        let lines = formatted
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        self.update_lines_recorded(lines)
+    }
+
+    /// Get the text to edit it with an external editor such as `$EDITOR`. The text is joined with the line ending and
+    /// ends with the line ending as editors usually add the newline at the end of file. Write the text to a temporary
+    /// file, run the editor, and pass the edited content to [`TextArea::import_external_edit`].
+    ///
+    /// Until the external edit is imported or cancelled with [`TextArea::cancel_external_edit`], the textarea rejects
+    /// the inputs which modify the text in the same way as [`TextArea::set_read_only`] so that the edits are not lost.
+    /// This allows an application to keep rendering the textarea while the editor runs asynchronously, for example in
+    /// another terminal window.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// let text = textarea.export_for_external_edit();
+    /// assert_eq!(text, "hello\nworld\n");
+    /// assert!(textarea.is_editing_externally());
+    /// ```
+    pub fn export_for_external_edit(&mut self) -> String {
+        self.external_edit = true;
+        let mut text = self.text();
+        text.push_str(self.line_ending.as_str());
+        text
+    }
+
+    /// Import the text edited by an external editor. See [`TextArea::export_for_external_edit`]. One newline at the
+    /// end of the text is removed. `\n` and `\r\n` are recognized as newline and the line ending of the textarea is
+    /// kept.
+    ///
+    /// Instead of replacing the whole text, only the changed lines are updated as one undo step like
+    /// [`TextArea::format_with`], so the edit can be undone at once. The cursor stays at the nearest position in the
+    /// edited text and the scroll position is kept when possible. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 3));
+    ///
+    /// let text = textarea.export_for_external_edit();
+    /// // Run the editor with the text here
+    /// let edited = text.replace("hello", "hi\nthere");
+    ///
+    /// assert!(textarea.import_external_edit(edited));
+    /// assert_eq!(textarea.lines(), ["hi", "there", "world"]);
+    /// assert_eq!(textarea.cursor(), (2, 3));
+    /// assert!(!textarea.is_editing_externally());
+    ///
+    /// // The external edit is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// ```
+    pub fn import_external_edit(&mut self, text: impl AsRef<str>) -> bool {
+        self.external_edit = false;
+        let text = text.as_ref();
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let lines = text
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        self.update_lines_recorded(lines)
+    }
+
+    /// Cancel the external edit started by [`TextArea::export_for_external_edit`] without modifying the text, for
+    /// example when the editor exited with an error.
+    pub fn cancel_external_edit(&mut self) {
+        self.external_edit = false;
+    }
+
+    /// Check if the text is being edited by an external editor. See [`TextArea::export_for_external_edit`].
+    pub fn is_editing_externally(&self) -> bool {
+        self.external_edit
+    }
+
+    /// Apply the hunks of a patch to the text. Each hunk replaces its [`Hunk::old_lines`] at the [`Hunk::start`] row
+    /// with its [`Hunk::new_lines`]. Rows of all hunks point the text before applying the patch, so hunks must be
+    /// sorted by their rows and must not overlap. Hunks can be parsed from a unified diff text with
+    /// [`Hunk::parse_unified`].
+    ///
+    /// All hunks are validated before modifying the text. When the old lines of some hunk don't match to the text,
+    /// this method returns [`PatchError::Conflict`] and the text is not modified at all. Otherwise only the changed
+    /// lines are updated as one undo step and the cursor stays at the nearest position. This method returns if the
+    /// text was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, Hunk, PatchError};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world", "goodbye"]);
+    ///
+    /// let diff = "\
+    /// @@ -1,2 +1,3 @@
+    ///  hello
+    /// -world
+    /// +patched
+    /// +world
+    /// ";
+    /// let hunks = Hunk::parse_unified(diff).unwrap();
+    /// assert_eq!(textarea.apply_patch(&hunks), Ok(true));
+    /// assert_eq!(textarea.lines(), ["hello", "patched", "world", "goodbye"]);
+    ///
+    /// // Conflicting patch is rejected without any modification
+    /// let hunks = [Hunk::new(0, ["hello", "world"], ["bye"])];
+    /// assert_eq!(textarea.apply_patch(&hunks), Err(PatchError::Conflict { hunk: 0, row: 0 }));
+    /// assert_eq!(textarea.lines(), ["hello", "patched", "world", "goodbye"]);
+    ///
+    /// // Patch is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hello", "world", "goodbye"]);
+    /// ```
+    pub fn apply_patch(&mut self, hunks: &[Hunk]) -> Result<bool, PatchError> {
+        if self.is_recording_ops() {
+            let op = MacroOp::ApplyPatch(hunks.to_vec());
+            return self.record_fallible(op, |t| t.apply_patch(hunks));
+        }
+        let mut changes = vec![];
+        let mut lines = Vec::with_capacity(self.lines.len());
+        let mut row = 0; // The first row which is not copied to `lines` yet
+        for (i, hunk) in hunks.iter().enumerate() {
+            let end = hunk.start + hunk.old_lines.len();
+            if hunk.start < row
+                || end > self.lines.len()
+                || self.lines[hunk.start..end] != hunk.old_lines[..]
+            {
+                return Err(PatchError::Conflict {
+                    hunk: i,
+                    row: hunk.start,
+                });
+            }
+            lines.extend_from_slice(&self.lines[row..hunk.start]);
+            // Context lines in the hunk should not be modified
+            for c in diff_lines(&hunk.old_lines, &hunk.new_lines) {
+                changes.push(LineChange {
+                    old_start: hunk.start + c.old_start,
+                    new_start: lines.len() + c.new_start,
+                    ..c
+                });
+            }
+            lines.extend_from_slice(&hunk.new_lines);
+            row = end;
+        }
+        lines.extend_from_slice(&self.lines[row..]);
+        Ok(self.apply_line_changes(&changes, &lines))
+    }
+
+    /// Apply a batch of [`TextEdit`]s atomically, like `WorkspaceEdit` of the Language Server Protocol. Ranges of all
+    /// edits point the text before applying the edits and must not overlap. The order of edits does not matter except
+    /// that texts inserted at the same position are inserted in the order of the edits.
+    ///
+    /// All edits are validated before modifying the text. When some range is invalid, this method returns
+    /// [`PatchError::InvalidRange`] and the text is not modified at all. Otherwise the edits are applied as one undo
+    /// step. Unlike replacing the whole text, the cursor, the selection, markers and bookmarks follow the edits and the
+    /// scroll position is kept.
+    ///
+    /// This method returns the sorted ranges of rows changed by the edits in the text after the edits, so that only
+    /// those lines need to be highlighted again.
+    /// ```
+    /// use tui_textarea::{TextArea, TextEdit, PatchError, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["let a = 1;", "let b = 2;", "let c = 3;"]);
+    /// textarea.move_cursor(CursorMove::Jump(2, 4));
+    ///
+    /// let edits = [
+    ///     TextEdit::new((2, 0), (2, 0), "// comment\n"),
+    ///     TextEdit::new((0, 4), (0, 5), "x"),
+    /// ];
+    /// assert_eq!(textarea.apply_edits(&edits), Ok(vec![0..1, 2..4]));
+    /// assert_eq!(textarea.lines(), ["let x = 1;", "let b = 2;", "// comment", "let c = 3;"]);
+    /// // The cursor follows the edits
+    /// assert_eq!(textarea.cursor(), (3, 4));
+    ///
+    /// // Overlapping edits are rejected without any modification
+    /// let edits = [TextEdit::new((0, 0), (0, 5), ""), TextEdit::new((0, 4), (0, 6), "")];
+    /// assert_eq!(textarea.apply_edits(&edits), Err(PatchError::InvalidRange { edit: 1 }));
+    ///
+    /// // The edits are undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["let a = 1;", "let b = 2;", "let c = 3;"]);
+    /// ```
+    pub fn apply_edits(&mut self, edits: &[TextEdit]) -> Result<Vec<Range<usize>>, PatchError> {
+        if self.is_recording_ops() {
+            let op = MacroOp::ApplyEdits(edits.to_vec());
+            return self.record_fallible(op, |t| t.apply_edits(edits));
+        }
+        let mut order: Vec<usize> = (0..edits.len()).collect();
+        // Insertions come before the replacements starting at the same position. Stable sort keeps the order of
+        // insertions at the same position
+        order.sort_by_key(|&i| edits[i].range);
+
+        let mut prev_end = (0, 0);
+        for &i in &order {
+            let (start, end) = edits[i].range;
+            let valid = |pos| self.clamp_cursor(pos) == pos;
+            if start < prev_end || end < start || !valid(start) || !valid(end) {
+                return Err(PatchError::InvalidRange { edit: i });
+            }
+            prev_end = end;
+        }
+
+        // The cursor and the selection follow the edits as markers
+        let cursor = self.markers.add(self.cursor);
+        let selection = self.selection_start.map(|pos| self.markers.add(pos));
+        self.history.begin_group();
+        // Apply edits from the bottom so that the ranges of the remaining edits are not shifted. The row at the end of
+        // each edited text is remembered since newlines may not be inserted as they are (e.g. in single-line mode)
+        let mut end_rows = vec![0; edits.len()];
+        for &i in order.iter().rev() {
+            let TextEdit {
+                range: (start, end),
+                new_text,
+            } = &edits[i];
+            self.replace_range(*start, *end, new_text);
+            end_rows[i] = self.cursor.0;
+        }
+        self.history.end_group();
+        self.cursor = self.markers.get(cursor).unwrap_or(self.cursor);
+        self.markers.remove(cursor);
+        if let Some(m) = selection {
+            self.selection_start = self.markers.get(m);
+            self.markers.remove(m);
+        }
+        self.notify_cursor();
+
+        // Rows of the edits in the text after the edits
+        let mut changed: Vec<Range<usize>> = vec![];
+        let mut shift = 0isize;
+        for &i in &order {
+            let TextEdit {
+                range: (start, end),
+                new_text,
+            } = &edits[i];
+            if start == end && new_text.is_empty() {
+                continue;
+            }
+            let row = (start.0 as isize + shift) as usize;
+            let new_rows = end_rows[i] - start.0;
+            shift += end_rows[i] as isize - end.0 as isize;
+            let rows = row..row + new_rows + 1;
+            match changed.last_mut() {
+                Some(last) if rows.start <= last.end => last.end = cmp::max(last.end, rows.end),
+                _ => changed.push(rows),
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Get all merge conflict regions in the text. Each region is surrounded by `<<<<<<<` and `>>>>>>>` marker lines.
+    /// To move the cursor between the regions, use [`CursorMove::ConflictForward`] and [`CursorMove::ConflictBack`].
+    /// ```
+    /// use tui_textarea::{TextArea, Conflict};
+    ///
+    /// let textarea = TextArea::from(["<<<<<<< HEAD", "a", "=======", "b", ">>>>>>> topic"]);
+    /// assert_eq!(
+    ///     textarea.conflicts(),
+    ///     [Conflict { start: 0, base: None, separator: 2, end: 4 }],
+    /// );
+    /// ```
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        find_conflicts(&self.lines)
+    }
+
+    /// Resolve the merge conflict region at the cursor. See [`ConflictResolution`] for the argument. The region
+    /// including the marker lines is replaced with the accepted lines as one undo step, and the cursor moves to the
+    /// head of the first accepted line. This method returns if a conflict was resolved or not. When the cursor is not
+    /// in any conflict region, it does nothing.
+    /// ```
+    /// use tui_textarea::{TextArea, ConflictResolution, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "x",
+    ///     "<<<<<<< HEAD", "ours", "=======", "theirs", ">>>>>>> topic",
+    ///     "<<<<<<< HEAD", "ours", "=======", "theirs", ">>>>>>> topic",
+    /// ]);
+    ///
+    /// textarea.move_cursor(CursorMove::ConflictForward);
+    /// assert!(textarea.resolve_conflict(ConflictResolution::Theirs));
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// textarea.move_cursor(CursorMove::ConflictForward);
+    /// assert!(textarea.resolve_conflict(ConflictResolution::Both));
+    /// assert_eq!(textarea.lines(), ["x", "theirs", "ours", "theirs"]);
+    /// ```
+    pub fn resolve_conflict(&mut self, resolution: ConflictResolution) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::ResolveConflict(resolution));
+        }
+        let row = self.cursor.0;
+        let conflict = match self.conflicts().into_iter().find(|c| c.contains(row)) {
+            Some(c) => c,
+            None => return false,
+        };
+
+        let ours = &self.lines[conflict.ours()];
+        let theirs = &self.lines[conflict.theirs()];
+        let accepted = match resolution {
+            ConflictResolution::Ours => ours.to_vec(),
+            ConflictResolution::Theirs => theirs.to_vec(),
+            ConflictResolution::Both => [ours, theirs].concat(),
+        };
+
+        self.history.begin_group();
+        self.replace_lines(conflict.start, conflict.end + 1, &accepted);
+        self.history.end_group();
+        self.cursor = self.clamp_cursor((conflict.start, 0));
+        true
+    }
+
+    /// Same as [`TextArea::update_lines`], but the update is recorded as one operation of the macro.
+    fn update_lines_recorded(&mut self, lines: Vec<String>) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::UpdateLines(lines));
+        }
+        self.update_lines(lines)
+    }
+
+    /// Update the text to the given lines with minimal edits as one undo step, keeping the cursor at the nearest
+    /// position.
+    fn update_lines(&mut self, lines: Vec<String>) -> bool {
+        let changes = diff_lines(&self.lines, &lines);
+        self.apply_line_changes(&changes, &lines)
+    }
+
+    /// Apply the line-wise changes to convert the current text into `lines` as one undo step.
+    fn apply_line_changes(&mut self, changes: &[LineChange], lines: &[String]) -> bool {
+        if changes.is_empty() {
+            return false;
+        }
+
+        let (row, col) = map_position(changes, self.cursor);
+        self.cancel_selection();
+        self.history.begin_group();
+        // Apply changes from the bottom so that positions of the remaining changes are not shifted
+        for c in changes.iter().rev() {
+            self.replace_lines(c.old_start, c.old_end(), &lines[c.new_start..c.new_end()]);
+        }
+        self.history.end_group();
+
+        self.cursor = self.clamp_cursor((row, col));
+        true
+    }
+
+    /// Replace lines in the range of `start..end` rows with the given lines through the edit history.
+    fn replace_lines(&mut self, start: usize, end: usize, lines: &[String]) {
+        let eol = |t: &Self, row: usize| (row, t.lines[row].chars().count());
+        if start < end && !lines.is_empty() {
+            self.replace_range((start, 0), eol(self, end - 1), &lines.join("\n"));
+        } else if !lines.is_empty() {
+            if start < self.lines.len() {
+                let text = lines.join("\n") + "\n";
+                self.replace_range((start, 0), (start, 0), &text);
+            } else {
+                let pos = eol(self, self.lines.len() - 1);
+                self.replace_range(pos, pos, &("\n".to_string() + &lines.join("\n")));
+            }
+        } else if start < end {
+            if end < self.lines.len() {
+                self.replace_range((start, 0), (end, 0), "");
+            } else if start > 0 {
+                self.replace_range(eol(self, start - 1), eol(self, end - 1), "");
+            } else {
+                self.replace_range((0, 0), eol(self, end - 1), "");
+            }
+        }
+    }
+
+    /// Replace the text between the (row, col) positions with the given text through the edit history. The cursor
+    /// moves to the end of the inserted text.
+    fn replace_range(&mut self, start: (usize, usize), end: (usize, usize), text: &str) {
+        self.cancel_selection();
+        if start != end {
+            let s = Pos::new(start.0, start.1, self.line_offset(start.0, start.1));
+            let e = Pos::new(end.0, end.1, self.line_offset(end.0, end.1));
+            self.delete_range(s, e, false);
+        }
+        self.cursor = start;
+        if !text.is_empty() {
+            self.insert_text(text, false);
+        }
+    }
+
+    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
     ///
     /// textarea.start_selection();
     /// textarea.move_cursor(CursorMove::WordForward);
@@ -1334,7 +3521,13 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.yank_text(), "aaa ");
     /// ```
     pub fn start_selection(&mut self) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::StartSelection);
+            return;
+        }
         self.selection_start = Some(self.cursor);
+        self.block_selection = None;
+        self.notify_cursor();
     }
 
     /// Stop the current text selection. This method does nothing if text selection is not ongoing.
@@ -1354,7 +3547,13 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.yank_text(), "");
     /// ```
     pub fn cancel_selection(&mut self) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::CancelSelection);
+            return;
+        }
         self.selection_start = None;
+        self.block_selection = None;
+        self.notify_cursor();
     }
 
     /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
@@ -1373,8 +3572,14 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
     /// ```
     pub fn select_all(&mut self) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::SelectAll);
+            return;
+        }
+        self.block_selection = None;
         self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
         self.selection_start = Some((0, 0));
+        self.notify_cursor();
     }
 
     /// Return if text selection is ongoing or not.
@@ -1393,6 +3598,215 @@ impl<'a> TextArea<'a> {
         self.selection_start.is_some()
     }
 
+    /// Start a block (rectangular) selection at the cursor position. The ongoing text selection is canceled. The block
+    /// selection is extended with [`TextArea::select_block`] or Alt+Shift+arrow keys in [`TextArea::input`], and
+    /// canceled by [`TextArea::cancel_selection`] or other cursor moves.
+    ///
+    /// While a block is selected, typed characters and inserted text without newlines replace the selected columns of
+    /// every row, [`TextArea::delete_char`] and [`TextArea::delete_next_char`] delete the selected columns or the
+    /// character before or after the column when the block is zero-width, and [`TextArea::copy`] and
+    /// [`TextArea::cut`] yank the selected columns as lines. All modifications to the rows are undone at once.
+    ///
+    /// The columns of the block can be past the end of lines (virtual space), which is necessary for editing ragged
+    /// lines in columns. Lines shorter than the block are padded with spaces only when text is inserted there. Columns
+    /// are counted in characters, so the block is not rectangular on screen when the lines contain tabs or wide
+    /// characters.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["ab", "a", "abcd"]);
+    ///
+    /// // Select the zero-width column at the end of the first line in all lines
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.start_block_selection();
+    /// textarea.select_block(CursorMove::Down);
+    /// textarea.select_block(CursorMove::Down);
+    ///
+    /// // The short line is padded
+    /// textarea.insert_str("|");
+    /// assert_eq!(textarea.lines(), ["ab|", "a |", "ab|cd"]);
+    ///
+    /// // Select the inserted column and cut it
+    /// textarea.select_block(CursorMove::Back);
+    /// textarea.cut();
+    /// assert_eq!(textarea.lines(), ["ab", "a ", "abcd"]);
+    /// assert_eq!(textarea.yank_text(), "|\n|\n|");
+    /// ```
+    pub fn start_block_selection(&mut self) {
+        self.selection_start = None;
+        self.block_selection = Some(BlockSelection::new(self.cursor));
+        self.notify_cursor();
+    }
+
+    /// Move the corner of the block selection by the [`CursorMove`] parameter. When no block is selected, the block
+    /// selection starts at the cursor position. [`CursorMove::Forward`], [`CursorMove::Back`], [`CursorMove::Up`],
+    /// [`CursorMove::Down`], [`CursorMove::Top`] and [`CursorMove::Bottom`] keep the column even if it is past the end
+    /// of the line. Other moves put the corner at the cursor position after the move. See
+    /// [`TextArea::start_block_selection`] for more details.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["ab", "abcd"]);
+    ///
+    /// textarea.select_block(CursorMove::End);
+    /// textarea.select_block(CursorMove::Forward);
+    /// textarea.select_block(CursorMove::Down);
+    /// textarea.select_block(CursorMove::Forward);
+    ///
+    /// // The block spans into virtual space
+    /// assert_eq!(textarea.block_selection(), Some(((0, 0), (1, 4))));
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    /// ```
+    pub fn select_block(&mut self, m: CursorMove) {
+        if self.block_selection.is_none() {
+            self.start_block_selection();
+        }
+        let mut block = match self.block_selection {
+            Some(block) => block,
+            None => return,
+        };
+        block.clamp_rows(self.lines.len());
+        let (row, col) = block.corner;
+        let last = self.lines.len() - 1;
+        block.corner = match m {
+            CursorMove::Forward => (row, col + 1),
+            CursorMove::Back => (row, col.saturating_sub(1)),
+            CursorMove::Up => (row.saturating_sub(1), col),
+            CursorMove::Down => (cmp::min(row + 1, last), col),
+            CursorMove::Top => (0, col),
+            CursorMove::Bottom => (last, col),
+            m => {
+                self.cursor = (row, cmp::min(col, self.lines[row].chars().count()));
+                self.move_cursor_with_shift(m, false);
+                self.cursor
+            }
+        };
+        self.set_block_selection(block);
+        self.notify_cursor();
+    }
+
+    /// Get the range of the block selection as the top-left and the bottom-right positions. The bottom row is
+    /// inclusive and the right column is exclusive. The columns can be past the end of lines. This method returns
+    /// `None` when no block is selected.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// assert_eq!(textarea.block_selection(), None);
+    /// textarea.move_cursor(CursorMove::Jump(1, 2));
+    /// textarea.select_block(CursorMove::Up);
+    /// textarea.select_block(CursorMove::Back);
+    /// assert_eq!(textarea.block_selection(), Some(((0, 1), (1, 2))));
+    /// ```
+    pub fn block_selection(&self) -> Option<((usize, usize), (usize, usize))> {
+        let block = self.block_selection?;
+        let (left, right) = block.cols();
+        let rows = block.rows();
+        Some(((*rows.start(), left), (*rows.end(), right)))
+    }
+
+    /// Set the block selection and put the cursor at its corner, clamped to the end of the line.
+    fn set_block_selection(&mut self, block: BlockSelection) {
+        let (row, col) = block.corner;
+        self.cursor = (row, cmp::min(col, self.lines[row].chars().count()));
+        self.block_selection = Some(block);
+    }
+
+    /// Replace the columns of the block in each row with the text as one undo step. Rows shorter than the left edge
+    /// are padded with spaces only when some text is inserted.
+    fn replace_block(&mut self, block: BlockSelection, text: &str) -> bool {
+        let (left, right) = block.cols();
+        let mut modified = false;
+        self.history.begin_group();
+        for row in block.rows() {
+            let len = self.lines[row].chars().count();
+            let (start, end) = (cmp::min(left, len), cmp::min(right, len));
+            if start == end && text.is_empty() {
+                continue; // Nothing to delete in virtual space
+            }
+            let padded = format!("{}{}", " ".repeat(left - start), text);
+            self.replace_range((row, start), (row, end), &padded);
+            modified = true;
+        }
+        self.history.end_group();
+        modified
+    }
+
+    fn insert_block(&mut self, mut block: BlockSelection, s: &str) -> bool {
+        block.clamp_rows(self.lines.len());
+        let accepted = self
+            .input_filter
+            .as_ref()
+            .map_or(true, |f| s.chars().all(|c| f.accepts(c)));
+        if !accepted || !self.block_fits_max_length(block, s) {
+            self.set_block_selection(block);
+            self.emit(UxEvent::EditRejected);
+            return false;
+        }
+        let (left, _) = block.cols();
+        let modified = self.replace_block(block, s);
+        block.collapse(left + s.chars().count());
+        self.set_block_selection(block);
+        modified
+    }
+
+    /// Check if the text inserted into every row of the block fits in [`TextArea::set_max_length`]. Spaces padding
+    /// short lines are counted and the replaced columns are not.
+    fn block_fits_max_length(&self, block: BlockSelection, s: &str) -> bool {
+        let max = match self.max_length {
+            Some(max) => max,
+            None => return true,
+        };
+        let (left, right) = block.cols();
+        let inserted = s.chars().count();
+        let len = block.rows().fold(self.text_len(), |len, row| {
+            let line_len = self.lines[row].chars().count();
+            let (start, end) = (cmp::min(left, line_len), cmp::min(right, line_len));
+            len + (left - start) + inserted - (end - start)
+        });
+        len <= max
+    }
+
+    /// Delete the selected columns. When the block is zero-width, the character before the column (`backward`) or
+    /// after the column is deleted in each row.
+    fn delete_block(&mut self, mut block: BlockSelection, backward: bool) -> bool {
+        block.clamp_rows(self.lines.len());
+        let (left, right) = block.cols();
+        let (deleted, col) = if left < right {
+            (block, left)
+        } else if backward && left > 0 {
+            let mut deleted = block;
+            deleted.anchor.1 = left - 1;
+            (deleted, left - 1)
+        } else if !backward {
+            let mut deleted = block;
+            deleted.anchor.1 = left + 1;
+            (deleted, left)
+        } else {
+            self.set_block_selection(block);
+            return false;
+        };
+        let modified = self.replace_block(deleted, "");
+        block.collapse(col);
+        self.set_block_selection(block);
+        modified
+    }
+
+    fn block_yank(&self, mut block: BlockSelection) -> YankText {
+        block.clamp_rows(self.lines.len());
+        let cols = block.cols();
+        let mut lines: Vec<String> = block
+            .rows()
+            .map(|row| block_text(&self.lines[row], cols))
+            .collect();
+        if lines.len() == 1 {
+            YankText::Piece(lines.remove(0))
+        } else {
+            YankText::Chunk(lines)
+        }
+    }
+
     fn line_offset(&self, row: usize, col: usize) -> usize {
         let line = self
             .lines
@@ -1470,18 +3884,43 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
     /// ```
     pub fn copy(&mut self) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::Copy);
+            return;
+        }
+        if let Some(block) = self.block_selection {
+            self.store_yank(self.block_yank(block));
+            return;
+        }
         if let Some((start, end)) = self.take_selection_positions() {
             if start.row == end.row {
-                self.yank = self.lines[start.row][start.offset..end.offset]
-                    .to_string()
-                    .into();
+                let yank = self.lines[start.row][start.offset..end.offset].to_string();
+                self.store_yank(yank.into());
                 return;
             }
             let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
             chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
             chunk.push(self.lines[end.row][..end.offset].to_string());
-            self.yank = YankText::Chunk(chunk);
+            self.store_yank(YankText::Chunk(chunk));
+        }
+    }
+
+    fn store_yank(&mut self, yank: YankText) {
+        if self.mask.is_none() || self.yank_masked {
+            self.push_yank_history(&yank);
+            self.yank = yank;
+        }
+    }
+
+    fn push_yank_history(&mut self, yank: &YankText) {
+        if self.max_yank_history == 0 || yank.is_empty() {
+            return;
         }
+        let text = yank.to_string();
+        // The same text is moved to the front instead of being duplicated
+        self.yank_history.retain(|y| y.to_string() != text);
+        self.yank_history.push_front(yank.clone());
+        self.yank_history.truncate(self.max_yank_history);
     }
 
     /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
@@ -1504,6 +3943,14 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["Hello "]);
     /// ```
     pub fn cut(&mut self) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::Cut);
+        }
+        if let Some(block) = self.block_selection.take() {
+            self.store_yank(self.block_yank(block));
+            let (left, right) = block.cols();
+            return left < right && self.delete_block(block, false);
+        }
         self.delete_selection(true)
     }
 
@@ -1528,23 +3975,460 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.cursor(), (1, 1));
     /// ```
     pub fn move_cursor(&mut self, m: CursorMove) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::Move(m, self.selection_start.is_some()));
+            return;
+        }
+        self.block_selection = None;
         self.move_cursor_with_shift(m, self.selection_start.is_some());
+        self.record_visit();
     }
 
-    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
-        if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
-            if shift {
-                if self.selection_start.is_none() {
-                    self.start_selection();
-                }
-            } else {
-                self.cancel_selection();
-            }
-            self.cursor = cursor;
+    /// Move the cursor by the [`CursorMove`] parameter while extending the text selection. When no text is selected, the
+    /// selection starts at the current cursor position. This is the same as moving the cursor with a Shift key, so
+    /// Shift-modified key bindings can be defined for any kind of cursor moves.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["(abc def)", "", "ghi"]);
+    ///
+    /// textarea.select_to(CursorMove::WordEnd);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 3))));
+    /// textarea.select_to(CursorMove::ParagraphForward);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 3))));
+    ///
+    /// textarea.cancel_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 0));
+    /// textarea.select_to(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 8))));
+    /// ```
+    pub fn select_to(&mut self, m: CursorMove) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::Move(m, true));
+            return;
         }
+        self.move_cursor_with_shift(m, true);
+        self.record_visit();
     }
 
-    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// Delete the text between the cursor and the position where the [`CursorMove`] parameter would move the cursor.
+    /// The deleted text is yanked and the deletion is one undo step, so deleting by any kind of cursor moves behaves the
+    /// same as the other `delete_*` methods. The current text selection is cancelled before the deletion. This method
+    /// returns if some text was deleted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo(bar, baz) qux", "", "end"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 3));
+    /// assert!(textarea.delete_to(CursorMove::MatchingBracket));
+    /// assert_eq!(textarea.lines(), ["foo) qux", "", "end"]);
+    /// assert_eq!(textarea.yank_text(), "(bar, baz");
+    ///
+    /// assert!(textarea.delete_to(CursorMove::Head));
+    /// assert_eq!(textarea.lines(), [") qux", "", "end"]);
+    /// assert!(!textarea.delete_to(CursorMove::Head));
+    ///
+    /// assert!(textarea.delete_to(CursorMove::ParagraphForward));
+    /// assert_eq!(textarea.lines(), ["end"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [") qux", "", "end"]);
+    /// ```
+    pub fn delete_to(&mut self, m: CursorMove) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::DeleteTo(m));
+        }
+        self.cancel_selection();
+        self.move_cursor_with_shift(m, true);
+        if self.delete_selection(true) {
+            return true;
+        }
+        self.cancel_selection();
+        false
+    }
+
+    /// Apply the [`Operator`] to the text between the cursor and the position where the [`CursorMove`] parameter would
+    /// move the cursor. This is the same as Vim's operator-pending mode such as `dw`, `c$` or `>j`. How the range is
+    /// decided (inclusive, exclusive or linewise) follows the kind of the cursor move decided by [`MotionKind::of`]. To
+    /// force another kind like Vim's `v` and `V` in operator-pending mode, use [`TextArea::apply_operator_as`].
+    ///
+    /// Deleted or yanked text is put in the yank buffer. Linewise text is yanked with the trailing newline so that
+    /// pasting it at the head of a line inserts the lines. The current text selection is cancelled before applying the
+    /// operator and each operation is one undo step. When the cursor cannot move by the cursor move, nothing happens.
+    /// This method returns if the text was modified or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, Operator};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz", "qux"]);
+    ///
+    /// // `dw` is exclusive
+    /// assert!(textarea.apply_operator(Operator::Delete, CursorMove::WordForward));
+    /// assert_eq!(textarea.lines(), ["bar baz", "qux"]);
+    ///
+    /// // `de` is inclusive
+    /// assert!(textarea.apply_operator(Operator::Delete, CursorMove::WordEnd));
+    /// assert_eq!(textarea.lines(), [" baz", "qux"]);
+    ///
+    /// // `yj` is linewise
+    /// assert!(!textarea.apply_operator(Operator::Yank, CursorMove::Down));
+    /// assert_eq!(textarea.yank_text(), " baz\nqux\n");
+    ///
+    /// // `>j` indents the lines
+    /// assert!(textarea.apply_operator(Operator::Indent, CursorMove::Down));
+    /// assert_eq!(textarea.lines(), ["     baz", "    qux"]);
+    /// ```
+    pub fn apply_operator(&mut self, op: Operator, m: CursorMove) -> bool {
+        self.apply_operator_as(op, m, MotionKind::of(m))
+    }
+
+    /// Apply the [`Operator`] to the text between the cursor and the position where the [`CursorMove`] parameter would
+    /// move the cursor, as the range of the [`MotionKind`] parameter. See [`TextArea::apply_operator`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, MotionKind, Operator};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    ///
+    /// // Like `dvw` in Vim, delete the word including the first character of the next word
+    /// textarea.apply_operator_as(Operator::Delete, CursorMove::WordForward, MotionKind::Inclusive);
+    /// assert_eq!(textarea.lines(), ["ar baz"]);
+    ///
+    /// // Like `dVl` in Vim, delete the cursor line
+    /// textarea.apply_operator_as(Operator::Delete, CursorMove::Forward, MotionKind::Linewise);
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn apply_operator_as(&mut self, op: Operator, m: CursorMove, kind: MotionKind) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::Operator(op, m, kind));
+        }
+        self.cancel_selection();
+        let origin = self.cursor;
+
+        // Like Vim, changing a word does not change the spaces after the word
+        if op == Operator::Change
+            && matches!(m, CursorMove::WordForward | CursorMove::BigWordForward)
+            && kind == MotionKind::Exclusive
+            && self.lines[origin.0]
+                .chars()
+                .nth(origin.1)
+                .map_or(false, |c| !c.is_whitespace())
+        {
+            let line = &self.lines[origin.0];
+            let end = if m == CursorMove::WordForward {
+                char_run(line, origin.1, &self.word_chars).map(|(_, end)| end)
+            } else {
+                let len = line.chars().count();
+                let spaces = line.chars().skip(origin.1).position(char::is_whitespace);
+                Some(spaces.map_or(len, |i| origin.1 + i))
+            };
+            if let Some(end) = end {
+                return self.operate(op, origin, (origin.0, end), false);
+            }
+        }
+
+        self.move_cursor_with_shift(m, false);
+        let target = self.cursor;
+        self.cursor = origin;
+        if target == origin {
+            return false;
+        }
+        let (start, end) = if origin < target {
+            (origin, target)
+        } else {
+            (target, origin)
+        };
+
+        match kind {
+            MotionKind::Linewise => self.operate(op, start, end, true),
+            MotionKind::Inclusive => {
+                let col = next_grapheme_col(&self.lines[end.0], end.1);
+                self.operate(op, start, (end.0, col), false)
+            }
+            MotionKind::Exclusive if end.0 > start.0 && end.1 == 0 => {
+                // The range ending at the head of a line ends at the end of the previous line instead. When it starts
+                // before the first non-blank character, it is linewise except for moving by a word
+                let row = end.0 - 1;
+                let by_word = matches!(m, CursorMove::WordForward | CursorMove::BigWordForward);
+                if !by_word && start.1 <= self.first_non_blank_col(start.0) {
+                    self.operate(op, start, (row, 0), true)
+                } else {
+                    let col = self.lines[row].chars().count();
+                    self.operate(op, start, (row, col), false)
+                }
+            }
+            MotionKind::Exclusive => self.operate(op, start, end, false),
+        }
+    }
+
+    /// Apply the [`Operator`] to the [`TextObject`] at the cursor. This is the same as Vim's operator-pending mode with
+    /// text objects such as `diw`, `ca(` or `yy`. See [`TextArea::apply_operator`] for how the operators work. When no
+    /// text object is found at the cursor, nothing happens. This method returns if the text was modified or not in the
+    /// textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, Operator, TextObject};
+    ///
+    /// let mut textarea = TextArea::from(["f(a, b) + g", "foo bar"]);
+    ///
+    /// // `ci(`
+    /// textarea.move_cursor(CursorMove::Jump(0, 3));
+    /// assert!(textarea.apply_operator_to_object(Operator::Change, TextObject::InnerBracket));
+    /// assert_eq!(textarea.lines(), ["f() + g", "foo bar"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    ///
+    /// // `daw`
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
+    /// assert!(textarea.apply_operator_to_object(Operator::Delete, TextObject::AroundWord));
+    /// assert_eq!(textarea.lines(), ["f() + g", "bar"]);
+    ///
+    /// // `dd`
+    /// assert!(textarea.apply_operator_to_object(Operator::Delete, TextObject::Line));
+    /// assert_eq!(textarea.lines(), ["f() + g"]);
+    /// assert_eq!(textarea.yank_text(), "bar\n");
+    /// ```
+    pub fn apply_operator_to_object(&mut self, op: Operator, obj: TextObject) -> bool {
+        if self.is_recording_ops() {
+            return self.record_op(MacroOp::OperatorObject(op, obj));
+        }
+        self.cancel_selection();
+        let (row, col) = self.cursor;
+        match obj {
+            TextObject::InnerWord | TextObject::AroundWord => {
+                let around = obj == TextObject::AroundWord;
+                match word_object(&self.lines[row], col, around, &self.word_chars) {
+                    Some((start, end)) => self.operate(op, (row, start), (row, end), false),
+                    None => false,
+                }
+            }
+            TextObject::InnerBracket | TextObject::AroundBracket => {
+                match find_enclosing_brackets(&self.lines, self.cursor, &self.bracket_pairs) {
+                    Some((open, close)) if obj == TextObject::InnerBracket => {
+                        self.operate(op, (open.0, open.1 + 1), close, false)
+                    }
+                    Some((open, close)) => self.operate(op, open, (close.0, close.1 + 1), false),
+                    None => false,
+                }
+            }
+            TextObject::Line => self.operate(op, self.cursor, self.cursor, true),
+        }
+    }
+
+    fn first_non_blank_col(&self, row: usize) -> usize {
+        self.lines[row]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .count()
+    }
+
+    /// Apply the operator to the range from `start` to `end` (exclusive). When `linewise` is true, all lines from the
+    /// row of `start` to the row of `end` are the range.
+    fn operate(
+        &mut self,
+        op: Operator,
+        start: (usize, usize),
+        end: (usize, usize),
+        linewise: bool,
+    ) -> bool {
+        let (first, last) = (start.0, end.0);
+        match op {
+            Operator::Indent | Operator::Dedent => {
+                self.selection_start = Some((first, 0));
+                self.cursor = (last, self.lines[last].chars().count());
+                let modified = if op == Operator::Indent {
+                    self.indent_selection()
+                } else {
+                    self.dedent_selection()
+                };
+                self.cancel_selection();
+                self.cursor = (first, self.first_non_blank_col(first));
+                modified
+            }
+            Operator::Yank if linewise => {
+                let mut chunk = self.lines[first..=last].to_vec();
+                chunk.push(String::new());
+                self.store_yank(YankText::Chunk(chunk));
+                self.cursor = (first, fit_col(self.cursor.1, &self.lines[first]));
+                false
+            }
+            Operator::Yank => {
+                self.selection_start = Some(start);
+                self.cursor = end;
+                self.copy();
+                self.cursor = start;
+                false
+            }
+            Operator::Delete | Operator::Change if linewise => {
+                let mut chunk = self.lines[first..=last].to_vec();
+                chunk.push(String::new());
+                let last_col = self.lines[last].chars().count();
+                let (s, e) = if op == Operator::Change {
+                    // Keep one line to type the new text. The indentation is kept with auto-indent
+                    let col = if self.auto_indent {
+                        self.first_non_blank_col(first)
+                    } else {
+                        0
+                    };
+                    ((first, col), (last, last_col))
+                } else if last + 1 < self.lines.len() {
+                    ((first, 0), (last + 1, 0))
+                } else if first > 0 {
+                    let col = self.lines[first - 1].chars().count();
+                    ((first - 1, col), (last, last_col))
+                } else {
+                    ((0, 0), (last, last_col))
+                };
+                self.store_yank(YankText::Chunk(chunk));
+                let modified = s != e;
+                if modified {
+                    let start = Pos::new(s.0, s.1, self.line_offset(s.0, s.1));
+                    let end = Pos::new(e.0, e.1, self.line_offset(e.0, e.1));
+                    self.delete_range(start, end, false);
+                }
+                if op == Operator::Change {
+                    self.cursor = s;
+                } else {
+                    let row = first.min(self.lines.len() - 1);
+                    self.cursor = (row, self.first_non_blank_col(row));
+                }
+                modified
+            }
+            Operator::Delete | Operator::Change => {
+                self.selection_start = Some(start);
+                self.cursor = end;
+                if self.delete_selection(true) {
+                    return true;
+                }
+                self.cancel_selection();
+                self.cursor = start;
+                false
+            }
+        }
+    }
+
+    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
+        let row_starts = match m {
+            CursorMove::VisualHead | CursorMove::VisualEnd => self.cursor_row_starts(),
+            _ => vec![0],
+        };
+        let next = m.next_cursor(
+            self.cursor,
+            &self.lines,
+            &self.viewport,
+            &self.bracket_pairs,
+            &row_starts,
+            &self.word_chars,
+        );
+        let next = next.map(|c| self.skip_folds(m, c));
+        if next.map_or(true, |c| c == self.cursor)
+            && !matches!(m, CursorMove::InViewport | CursorMove::Jump(..))
+        {
+            let (row, col) = self.cursor;
+            let last = self.lines.len() - 1;
+            let at_edge = match m {
+                CursorMove::Back
+                | CursorMove::WordBack
+                | CursorMove::WordEndBack
+                | CursorMove::BigWordBack
+                | CursorMove::BigWordEndBack
+                | CursorMove::SentenceBack => (row, col) == (0, 0),
+                CursorMove::Up | CursorMove::Top | CursorMove::ParagraphBack => row == 0,
+                CursorMove::Forward
+                | CursorMove::WordForward
+                | CursorMove::WordEnd
+                | CursorMove::BigWordForward
+                | CursorMove::BigWordEnd
+                | CursorMove::SentenceForward => {
+                    row == last && col == self.lines[last].chars().count()
+                }
+                CursorMove::Down | CursorMove::Bottom | CursorMove::ParagraphForward => row == last,
+                _ => false,
+            };
+            self.emit(if at_edge {
+                UxEvent::BufferEdge
+            } else {
+                UxEvent::MotionFailed
+            });
+        }
+        if let Some(cursor) = next {
+            self.set_cursor_with_shift(cursor, shift);
+            self.unfold_at_cursor();
+        }
+    }
+
+    /// Move the position out of folded lines. Relative cursor moves skip the folds in their direction. Other moves such
+    /// as [`CursorMove::Jump`] unfold the fold instead.
+    fn skip_folds(&self, m: CursorMove, next: (usize, usize)) -> (usize, usize) {
+        use CursorMove::*;
+        if self.folds.is_empty()
+            || matches!(
+                m,
+                Jump(..) | MatchingBracket | ConflictForward | ConflictBack
+            )
+        {
+            return next;
+        }
+        let row = match self.unfolded_row(next.0, next.0 > self.cursor.0) {
+            Some(row) if row != next.0 => row,
+            _ => return next,
+        };
+        let line = &self.lines[row];
+        let col = match m {
+            Up | Down | Top | Bottom | ParagraphForward | ParagraphBack | InViewport => {
+                fit_col(next.1, line)
+            }
+            _ if row > next.0 => 0,
+            _ => line.chars().count(),
+        };
+        (row, col)
+    }
+
+    /// Find the nearest row which is not folded from the row in the direction. When no such row is found in the
+    /// direction, the opposite direction is searched. `None` is returned when all lines are folded.
+    fn unfolded_row(&self, mut row: usize, mut down: bool) -> Option<usize> {
+        let len = self.lines.len();
+        let mut turned = false;
+        while let Some(Fold { rows: fold, .. }) = self.folds.at(row) {
+            if down && fold.end < len {
+                row = fold.end;
+            } else if !down && fold.start > 0 {
+                row = fold.start - 1;
+            } else if turned {
+                return None;
+            } else {
+                down = !down;
+                turned = true;
+            }
+        }
+        Some(row)
+    }
+
+    /// Unfold the fold which hides the cursor line.
+    fn unfold_at_cursor(&mut self) {
+        if !self.folds.is_empty() {
+            self.folds.remove_at(self.cursor.0);
+        }
+    }
+
+    pub(crate) fn set_cursor_with_shift(&mut self, cursor: (usize, usize), shift: bool) {
+        if shift {
+            if self.selection_start.is_none() {
+                self.start_selection();
+            }
+        } else {
+            self.cancel_selection();
+        }
+        self.cursor = cursor;
+        self.notify_cursor();
+    }
+
+    /// Fit the (row, col) position to the current text.
+    pub(crate) fn clamp_cursor(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        let row = row.min(self.lines.len() - 1);
+        let col = col.min(self.lines[row].chars().count());
+        (row, col)
+    }
+
+    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea. Undo
+    /// of a checkpoint pushed by [`TextArea::push_cursor_checkpoint`] only moves the cursor and returns `true`.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -1556,16 +4440,28 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["abc def"]);
     /// ```
     pub fn undo(&mut self) -> bool {
-        if let Some(cursor) = self.history.undo(&mut self.lines) {
+        if self.template.is_some() {
+            return false; // Field positions would be broken
+        }
+        if let Some(cursor) = self.history.undo(&mut self.lines, self.cursor) {
+            self.generation += 1;
+            if self.tracks_changes() {
+                for change in self.history.undone_changes() {
+                    self.track_change(&change);
+                }
+            }
             self.cancel_selection();
             self.cursor = cursor;
+            self.unfold_at_cursor();
+            self.notify_cursor();
             true
         } else {
             false
         }
     }
 
-    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea. Redo
+    /// of a checkpoint pushed by [`TextArea::push_cursor_checkpoint`] only moves the cursor and returns `true`.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -1579,41 +4475,394 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" def"]);
     /// ```
     pub fn redo(&mut self) -> bool {
+        if self.template.is_some() {
+            return false; // Field positions would be broken
+        }
         if let Some(cursor) = self.history.redo(&mut self.lines) {
+            self.generation += 1;
+            if self.tracks_changes() {
+                for change in self.history.redone_changes() {
+                    self.track_change(&change);
+                }
+            }
             self.cancel_selection();
             self.cursor = cursor;
+            self.unfold_at_cursor();
+            self.notify_cursor();
             true
         } else {
             false
         }
     }
 
-    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize, lnum_len: u8) -> Line<'b> {
+    /// Positions of the bracket under the cursor and its matching bracket when they should be highlighted.
+    pub(crate) fn matched_brackets(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.matched_bracket_style?;
+        let matched = find_matching_bracket(&self.lines, self.cursor, &self.bracket_pairs)?;
+        Some((self.cursor, matched))
+    }
+
+    /// Find the byte ranges of the line at the row which are covered by the jump labels. Each label covers the same
+    /// number of characters as its length from the target position.
+    fn jump_label_ranges<'b>(&'b self, row: usize, line: &str) -> Vec<(usize, usize, &'b str)> {
+        let labels = match &self.jump_labels {
+            Some(labels) => labels,
+            None => return vec![],
+        };
+        let mut ranges: Vec<_> = labels
+            .remaining()
+            .filter(|&(_, (r, _))| r == row)
+            .filter_map(|(label, (_, col))| {
+                let mut offsets = line.char_indices().map(|(i, _)| i).skip(col);
+                let start = offsets.next()?;
+                let end = offsets
+                    .nth(label.chars().count().saturating_sub(1))
+                    .unwrap_or(line.len());
+                Some((start, end, label))
+            })
+            .collect();
+        ranges.sort_unstable_by_key(|&(s, _, _)| s);
+        // When a label is longer than the text until the next label, only the characters before the next label are
+        // covered
+        let mut i = 1;
+        while i < ranges.len() {
+            if ranges[i].0 < ranges[i - 1].1 {
+                ranges[i - 1].1 = ranges[i].0;
+            }
+            i += 1;
+        }
+        ranges.retain(|&(s, e, _)| s < e);
+        ranges
+    }
+
+    /// Find the ranges of the line at the row which are displayed as other text by jump labels, conceal rules and
+    /// ligatures. The cursor line is not concealed and the ligature under the cursor is not substituted. Masked text
+    /// is displayed as-is.
+    pub(crate) fn conceals<'b>(&'b self, row: usize, line: &str) -> Vec<(usize, usize, &'b str)> {
+        if self.mask.is_some() {
+            return vec![];
+        }
+        let labels = self.jump_label_ranges(row, line);
+        let mut conceals = if row == self.cursor.0 || self.conceal_rules.is_empty() {
+            vec![]
+        } else {
+            find_conceals(line, &self.conceal_rules)
+        };
+        if !labels.is_empty() {
+            conceals.retain(|&(s, e, _)| labels.iter().all(|&(ls, le, _)| e <= ls || le <= s));
+            conceals.extend(labels);
+            conceals.sort_unstable_by_key(|&(s, _, _)| s);
+        }
+        if self.ligatures.is_empty() {
+            return conceals;
+        }
+
+        let cursor = if row == self.cursor.0 {
+            line.char_indices().nth(self.cursor.1).map(|(i, _)| i)
+        } else {
+            None
+        };
+        let ligatures = find_conceals(line, &self.ligatures)
+            .into_iter()
+            .filter(|&(s, e, _)| !matches!(cursor, Some(i) if s <= i && i < e))
+            .filter(|&(s, e, _)| conceals.iter().all(|&(cs, ce, _)| e <= cs || ce <= s))
+            .collect::<Vec<_>>();
+        if !ligatures.is_empty() {
+            conceals.extend(ligatures);
+            conceals.sort_unstable_by_key(|&(s, _, _)| s);
+        }
+        conceals
+    }
+
+    /// Render the line at the row with the hooks set by [`TextArea::set_before_line_hook`] and
+    /// [`TextArea::set_after_line_hook`].
+    pub(crate) fn render_line<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        brackets: Option<((usize, usize), (usize, usize))>,
+        settings: u64,
+    ) -> Line<'b> {
+        let replaced = self
+            .before_line_hook
+            .as_ref()
+            .and_then(|h| (h.0)(row, line));
+        let mut spans = match replaced {
+            Some(spans) => spans,
+            None => self.cached_line_spans(line, row, lnum_len, brackets, settings),
+        };
+        if let Some(hook) = &self.after_line_hook {
+            (hook.0)(row, &mut spans);
+        }
+        spans
+    }
+
+    /// Same as [`TextArea::line_spans`] but reuses the line rendered at the last frame when neither its text nor the
+    /// state affecting it is changed. `settings` is the hash calculated by [`TextArea::render_settings_hash`].
+    pub(crate) fn cached_line_spans<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        brackets: Option<((usize, usize), (usize, usize))>,
+        settings: u64,
+    ) -> Line<'b> {
+        if !self.render_cache.enabled() {
+            return self.line_spans(line, row, lnum_len, brackets);
+        }
+        let state = state_hash(&(
+            settings,
+            self.row_render_state(row, line, lnum_len, brackets),
+        ));
+        if let Some(cached) = self.render_cache.get(row, self.generation, line, state) {
+            return cached;
+        }
+        let spans = self.line_spans(line, row, lnum_len, brackets);
+        self.render_cache
+            .insert(row, self.generation, line, state, &spans);
+        spans
+    }
+
+    /// Hash of the settings which affect the rendering of every line. Keep this in sync with [`TextArea::line_spans`].
+    pub(crate) fn render_settings_hash(&self) -> u64 {
+        #[cfg(feature = "search")]
+        let search = &self.search;
+        #[cfg(not(feature = "search"))]
+        let search = ();
+        state_hash(&(
+            (self.current_cursor_style(), self.tab_width(), self.mask),
+            (
+                self.select_style,
+                self.gutter_sign_width(),
+                self.line_number_style,
+            ),
+            (&self.prompt, self.prompt_style, self.eol_annotation_style),
+            (self.whitespace, &self.conceal_rules, &self.ligatures),
+            (&self.jump_labels, self.jump_label_style, self.cursor_hidden),
+            (
+                self.cursor_line_style,
+                self.template_field_style,
+                self.invalid_field_style,
+            ),
+            (
+                &self.comment_prefix,
+                self.comment_style,
+                self.soft_limit_style,
+            ),
+            (
+                &self.snippet,
+                self.snippet_stop_style,
+                self.matched_bracket_style,
+            ),
+            (search, self.color_profile),
+        ))
+    }
+
+    /// State which affects the rendering of the line at the row except for its text and the settings. Keep this in sync
+    /// with [`TextArea::line_spans`].
+    fn row_render_state(
+        &self,
+        row: usize,
+        line: &str,
+        lnum_len: u8,
+        brackets: Option<((usize, usize), (usize, usize))>,
+    ) -> impl fmt::Debug + '_ {
+        let cursor = (row == self.cursor.0).then(|| self.cursor.1);
+        let selection = self
+            .selection_positions()
+            .filter(|(start, end)| start.row <= row && row <= end.row);
+        let block = self.block_selection.filter(|b| b.rows().contains(&row));
+        let brackets = brackets.filter(|(cursor, matched)| cursor.0 == row || matched.0 == row);
+        let len = line.chars().count();
+        let highlights: Vec<_> = self
+            .highlights
+            .iter()
+            .filter_map(|h| Some((h.cols_at(row, len)?, h.priority, h.style)))
+            .collect();
+        let fields: Vec<_> = self
+            .template
+            .iter()
+            .flat_map(|t| t.fields.iter().filter(|f| f.row == row))
+            .map(|f| {
+                (
+                    f.col,
+                    f.end(),
+                    f.field.validate(f.value(&self.lines)).is_ok(),
+                )
+            })
+            .collect();
+        let soft_limit = self
+            .soft_limit
+            .and_then(|l| l.exceeding_col(&self.lines, row, self.tab_width()));
+        (
+            (row, lnum_len, row + 1 == self.lines.len()),
+            (cursor, selection, block, brackets),
+            (self.gutter_signs.get(&row), self.eol_annotations.get(&row)),
+            (highlights, fields, soft_limit),
+        )
+    }
+
+    pub(crate) fn line_spans<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        brackets: Option<((usize, usize), (usize, usize))>,
+    ) -> Line<'b> {
         let mut hl = LineHighlighter::new(
             line,
-            self.cursor_style,
-            self.tab_len,
+            self.cue_style(
+                self.current_cursor_style(),
+                Modifier::REVERSED | Modifier::BOLD,
+            ),
+            self.tab_width(),
             self.mask,
-            self.select_style,
+            self.cue_style(self.select_style, Modifier::REVERSED),
         );
 
+        let sign_width = self.gutter_sign_width();
+        if sign_width > 0 {
+            let sign = self.gutter_signs.get(&row).map(|s| {
+                Span::styled(
+                    s.content.clone(),
+                    self.cue_style(s.style, Modifier::empty()),
+                )
+            });
+            hl.gutter_sign(sign, sign_width);
+        }
         if let Some(style) = self.line_number_style {
-            hl.line_number(row, lnum_len, style);
+            hl.line_number(row, lnum_len, self.cue_style(style, Modifier::empty()));
+        }
+        if let Some((first, continuation)) = &self.prompt {
+            let prompt = if row == 0 { first } else { continuation };
+            let style = self.cue_style(self.prompt_style, Modifier::empty());
+            hl.prompt(prompt, self.prompt_width(), style);
+        }
+        if let Some(annotation) = self.eol_annotations.get(&row) {
+            let style = self.eol_annotation_style.patch(annotation.style);
+            let style = self.cue_style(style, Modifier::DIM);
+            hl.eol_annotation(Span::styled(annotation.content.clone(), style));
+        }
+
+        if let Some(mut config) = self.whitespace {
+            for (_, style) in [
+                &mut config.tab,
+                &mut config.trailing_space,
+                &mut config.nbsp,
+                &mut config.newline,
+                &mut config.eof,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *style = self.cue_style(*style, Modifier::empty());
+            }
+            let eol = if row + 1 == self.lines.len() {
+                config.eof
+            } else {
+                config.newline
+            };
+            hl.whitespace(config, eol);
+        }
+        hl.conceal(self.conceals(row, line));
+        for (start, end, _) in self.jump_label_ranges(row, line) {
+            let style = self.cue_style(self.jump_label_style, Modifier::REVERSED | Modifier::BOLD);
+            hl.jump_label(start, end, style);
+        }
+
+        if row == self.cursor.0 && !self.cursor_hidden {
+            let style = self.cue_style(self.cursor_line_style, Modifier::empty());
+            hl.cursor_line(self.cursor.1, style);
+        }
+
+        if let Some(t) = &self.template {
+            for f in t.fields.iter().filter(|f| f.row == row) {
+                let style = if f.field.validate(f.value(&self.lines)).is_ok() {
+                    self.cue_style(self.template_field_style, Modifier::UNDERLINED)
+                } else {
+                    let cue = Modifier::UNDERLINED | Modifier::ITALIC;
+                    self.cue_style(self.invalid_field_style, cue)
+                };
+                hl.field(f.col, f.end(), style);
+            }
+        }
+
+        if self.is_comment_line(line) {
+            let style = self.cue_style(self.comment_style, Modifier::DIM);
+            hl.highlight(0, line.chars().count(), 0, style);
+        }
+
+        if !self.highlights.is_empty() {
+            let len = line.chars().count();
+            for h in &self.highlights {
+                if let Some((start, end)) = h.cols_at(row, len) {
+                    let style = self.cue_style(h.style, Modifier::empty());
+                    hl.highlight(start, end, h.priority, style);
+                }
+            }
+        }
+
+        if let Some(col) = self
+            .soft_limit
+            .and_then(|l| l.exceeding_col(&self.lines, row, self.tab_width()))
+        {
+            let style = self.cue_style(self.soft_limit_style, Modifier::UNDERLINED);
+            hl.field(col, line.chars().count(), style);
+        }
+
+        if let Some(snippet) = &self.snippet {
+            let TabStop { start, end } = snippet.active();
+            if start.0 <= row && row <= end.0 {
+                let start_col = if start.0 == row { start.1 } else { 0 };
+                let end_col = if end.0 == row {
+                    end.1
+                } else {
+                    line.chars().count()
+                };
+                let style = self.cue_style(self.snippet_stop_style, Modifier::UNDERLINED);
+                hl.field(start_col, end_col, style);
+            }
         }
 
-        if row == self.cursor.0 {
-            hl.cursor_line(self.cursor.1, self.cursor_line_style);
+        if let (Some(style), Some((cursor, matched))) = (self.matched_bracket_style, brackets) {
+            let style = self.cue_style(style, Modifier::UNDERLINED | Modifier::BOLD);
+            for (r, c) in [cursor, matched] {
+                if r == row {
+                    hl.bracket(c, style);
+                }
+            }
         }
 
         #[cfg(feature = "search")]
         if let Some(matches) = self.search.matches(line) {
-            hl.search(matches, self.search.style);
+            let style = self.cue_style(self.search.style, Modifier::UNDERLINED | Modifier::BOLD);
+            hl.search(matches, style);
+        }
+
+        #[cfg(feature = "search")]
+        if row == self.cursor.0 && self.mask.is_none() {
+            let offset = line
+                .char_indices()
+                .nth(self.cursor.1)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len());
+            if let Some((_, end, text)) = self.search.replacement_at(line, offset) {
+                let style = self.cue_style(self.search.preview_style, Modifier::ITALIC);
+                hl.replace_preview(end, text, style);
+            }
         }
 
         if let Some((start, end)) = self.selection_positions() {
             hl.selection(row, start.row, start.offset, end.row, end.offset);
         }
 
+        if let Some(block) = self.block_selection.filter(|b| b.rows().contains(&row)) {
+            let (left, right) = block.cols();
+            let cursor = (block.corner.0 == row && !self.cursor_hidden).then(|| block.corner.1);
+            hl.block_selection(left, right, cursor);
+        }
+
         hl.into_spans()
     }
 
@@ -1685,86 +4934,612 @@ impl<'a> TextArea<'a> {
         self.wrap = wrap
     }
 
-    /// Set the block of textarea. By default, no block is set.
+    /// Enable or disable the render cache. Rendering reuses the lines rendered at the last frame when neither their
+    /// text nor the state affecting them such as the cursor, the selection and the highlights is changed. This saves CPU
+    /// time when the textarea is rendered at a high frame rate. By default, the render cache is enabled. Disabling it
+    /// also drops the cached lines.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
     /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// assert!(textarea.block().is_some());
+    /// assert!(textarea.render_cache_enabled());
+    ///
+    /// textarea.set_render_cache(false);
+    /// assert!(!textarea.render_cache_enabled());
     /// ```
-    pub fn set_block(&mut self, block: Block<'a>) {
-        self.block = Some(block);
+    pub fn set_render_cache(&mut self, enabled: bool) {
+        self.render_cache.set_enabled(enabled);
     }
 
-    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// Get if the render cache is enabled. See [`TextArea::set_render_cache`].
+    pub fn render_cache_enabled(&self) -> bool {
+        self.render_cache.enabled()
+    }
+
+    /// Reserve blank display rows after the line at the 0-based `row`. The reserved rows are rendered as empty rows and
+    /// the host application can render arbitrary content there such as images, charts or expanded diffs. Scrolling
+    /// takes the reserved rows into account. Setting `0` removes the reservation.
+    ///
+    /// The row is not adjusted when lines are inserted or removed by editing. Update the reservation as needed. Use
+    /// [`TextArea::reserved_areas`] to know where to render the content after rendering the textarea.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// textarea.remove_block();
-    /// assert!(textarea.block().is_none());
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.reserve_rows_after(1, 3);
+    /// assert_eq!(textarea.reserved_rows_after(1), 3);
+    /// assert_eq!(textarea.reserved_rows_after(0), 0);
+    ///
+    /// textarea.reserve_rows_after(1, 0);
+    /// assert_eq!(textarea.reserved_rows_after(1), 0);
     /// ```
-    pub fn remove_block(&mut self) {
-        self.block = None;
+    pub fn reserve_rows_after(&mut self, row: usize, rows: u16) {
+        if rows == 0 {
+            self.reserved_rows.remove(&row);
+        } else {
+            self.reserved_rows.insert(row, rows);
+        }
     }
 
-    /// Get the block of textarea if exists.
-    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
-        self.block.as_ref()
+    /// Get the number of display rows reserved after the line at the 0-based `row` by
+    /// [`TextArea::reserve_rows_after`].
+    pub fn reserved_rows_after(&self, row: usize) -> u16 {
+        self.reserved_rows.get(&row).copied().unwrap_or(0)
     }
 
-    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// Remove all reservations of display rows set by [`TextArea::reserve_rows_after`].
     /// ```
-    /// use tui_textarea::{TextArea, Input, Key};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    /// textarea.reserve_rows_after(0, 2);
+    /// textarea.reserve_rows_after(1, 2);
+    /// textarea.clear_reserved_rows();
+    /// assert_eq!(textarea.reserved_rows_after(0), 0);
+    /// assert_eq!(textarea.reserved_rows_after(1), 0);
+    /// ```
+    pub fn clear_reserved_rows(&mut self) {
+        self.reserved_rows.clear();
+    }
+
+    /// Set a sign shown in the gutter at the left of the line at the 0-based `row`, such as a breakpoint (`●`), a
+    /// diagnostic, or a git sign (`+`, `-`, `▎`). The gutter is shown before line numbers (see
+    /// [`TextArea::set_line_number_style`]) or alone when line numbers are not shown. Its width is the widest sign and
+    /// lines without a sign are padded with spaces. The sign is rendered with its own style.
     ///
-    /// textarea.set_tab_length(8);
-    /// textarea.input(tab_input.clone());
-    /// assert_eq!(textarea.lines(), ["        "]);
+    /// The row is not adjusted when lines are inserted or removed by editing. Update the signs as needed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Color, Style};
+    /// use ratatui::text::Span;
     ///
-    /// textarea.set_tab_length(2);
-    /// textarea.input(tab_input);
-    /// assert_eq!(textarea.lines(), ["          "]);
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.set_gutter_sign(1, Span::styled("●", Style::default().fg(Color::Red)));
+    /// assert_eq!(textarea.gutter_sign(1).unwrap().content, "●");
+    /// assert!(textarea.gutter_sign(0).is_none());
+    ///
+    /// textarea.remove_gutter_sign(1);
+    /// assert!(textarea.gutter_sign(1).is_none());
     /// ```
-    pub fn set_tab_length(&mut self, len: u8) {
-        self.tab_len = len;
+    pub fn set_gutter_sign(&mut self, row: usize, sign: impl Into<Span<'a>>) {
+        self.gutter_signs.insert(row, sign.into());
     }
 
-    /// Get how many spaces are used for representing tab character. The default value is 4.
-    pub fn tab_length(&self) -> u8 {
-        self.tab_len
+    /// Get the gutter sign of the line at the row. See [`TextArea::set_gutter_sign`].
+    pub fn gutter_sign(&self, row: usize) -> Option<&Span<'a>> {
+        self.gutter_signs.get(&row)
     }
 
-    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
-    /// spaces. By default, hard tab is disabled.
+    /// Remove the gutter sign of the line at the row. See [`TextArea::set_gutter_sign`].
+    pub fn remove_gutter_sign(&mut self, row: usize) {
+        self.gutter_signs.remove(&row);
+    }
+
+    /// Remove all gutter signs. The gutter is no longer shown.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.set_hard_tab_indent(true);
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    /// textarea.set_gutter_sign(0, "+");
+    /// textarea.set_gutter_sign(1, "-");
+    /// textarea.clear_gutter_signs();
+    /// assert!(textarea.gutter_sign(0).is_none());
+    /// assert!(textarea.gutter_sign(1).is_none());
     /// ```
-    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
-        self.hard_tab_indent = enabled;
+    pub fn clear_gutter_signs(&mut self) {
+        self.gutter_signs.clear();
     }
 
-    /// Get if a hard tab is used for indent or not.
+    /// Set an annotation shown after the end of the line at the 0-based `row`, such as an inlay hint or a summary of
+    /// diagnostics like `// 3 errors`. One cell is put between the line and the annotation. The annotation is rendered
+    /// with its own style patched onto [`TextArea::eol_annotation_style`].
+    ///
+    /// The annotation is not a part of the text. The cursor cannot move onto it, it cannot be selected or edited, and it
+    /// is not included in the content such as [`TextArea::lines`]. When wrapping is enabled, the annotation is wrapped
+    /// with the line. The row is not adjusted when lines are inserted or removed by editing. Update the annotations as
+    /// needed.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["let x = foo();", "bar();"]);
+    /// textarea.set_eol_annotation(0, "// 3 errors");
+    /// assert_eq!(textarea.eol_annotation(0).unwrap().content, "// 3 errors");
+    /// assert!(textarea.eol_annotation(1).is_none());
     ///
-    /// assert!(!textarea.hard_tab_indent());
+    /// // The annotation is not a part of the text
+    /// assert_eq!(textarea.lines(), ["let x = foo();", "bar();"]);
+    ///
+    /// textarea.remove_eol_annotation(0);
+    /// assert!(textarea.eol_annotation(0).is_none());
+    /// ```
+    pub fn set_eol_annotation(&mut self, row: usize, annotation: impl Into<Span<'a>>) {
+        self.eol_annotations.insert(row, annotation.into());
+    }
+
+    /// Get the end-of-line annotation of the line at the row. See [`TextArea::set_eol_annotation`].
+    pub fn eol_annotation(&self, row: usize) -> Option<&Span<'a>> {
+        self.eol_annotations.get(&row)
+    }
+
+    /// Remove the end-of-line annotation of the line at the row. See [`TextArea::set_eol_annotation`].
+    pub fn remove_eol_annotation(&mut self, row: usize) {
+        self.eol_annotations.remove(&row);
+    }
+
+    /// Remove all end-of-line annotations.
+    pub fn clear_eol_annotations(&mut self) {
+        self.eol_annotations.clear();
+    }
+
+    /// Set the base style of end-of-line annotations. The style of each annotation is patched onto this style. The
+    /// default style is dimmed. See [`TextArea::set_eol_annotation`].
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::DarkGray);
+    /// textarea.set_eol_annotation_style(style);
+    /// assert_eq!(textarea.eol_annotation_style(), style);
+    /// ```
+    pub fn set_eol_annotation_style(&mut self, style: Style) {
+        self.eol_annotation_style = style;
+    }
+
+    /// Get the base style of end-of-line annotations. See [`TextArea::set_eol_annotation_style`].
+    pub fn eol_annotation_style(&self) -> Style {
+        self.eol_annotation_style
+    }
+
+    /// Set prompts shown before the lines for multi-line input of a REPL or a shell. `first` is shown before the first
+    /// line and `continuation` is shown before the other lines, like `>>> ` and `... ` of Python. The prompts are shown
+    /// after line numbers and the shorter one is padded with spaces.
+    ///
+    /// The prompts are not a part of the text. The cursor cannot move onto them, they cannot be edited, and they are
+    /// not included in the content such as [`TextArea::lines`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["def f():", "    pass"]);
+    /// textarea.set_prompt(">>> ", "... ");
+    /// assert_eq!(textarea.prompt(), Some((">>> ", "... ")));
+    ///
+    /// // The submitted content does not include the prompts
+    /// assert_eq!(textarea.lines(), ["def f():", "    pass"]);
+    ///
+    /// textarea.clear_prompt();
+    /// assert_eq!(textarea.prompt(), None);
+    /// ```
+    pub fn set_prompt(&mut self, first: impl Into<String>, continuation: impl Into<String>) {
+        self.prompt = Some((first.into(), continuation.into()));
+    }
+
+    /// Remove the prompts set by [`TextArea::set_prompt`].
+    pub fn clear_prompt(&mut self) {
+        self.prompt = None;
+    }
+
+    /// Get the prompts of the first line and the other lines. `None` is returned when no prompt is set. See
+    /// [`TextArea::set_prompt`].
+    pub fn prompt(&self) -> Option<(&str, &str)> {
+        self.prompt
+            .as_ref()
+            .map(|(first, continuation)| (first.as_str(), continuation.as_str()))
+    }
+
+    /// Set the style of the prompts. See [`TextArea::set_prompt`]. The default style is [`Style::default`].
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Green);
+    /// textarea.set_prompt_style(style);
+    /// assert_eq!(textarea.prompt_style(), style);
+    /// ```
+    pub fn set_prompt_style(&mut self, style: Style) {
+        self.prompt_style = style;
+    }
+
+    /// Get the style of the prompts. See [`TextArea::set_prompt_style`].
+    pub fn prompt_style(&self) -> Style {
+        self.prompt_style
+    }
+
+    /// Highlight the range of text between the (row, col) positions with the style. This is useful to show diagnostics
+    /// of a language server or semantic tokens. Highlights are put in layers ordered by the priority. The style of a
+    /// highlight is patched onto the styles of the highlights with lower priorities, so an underline of a diagnostic
+    /// keeps the color of the semantic token under it. Built-in highlights have the following priorities and their
+    /// styles replace the styles under them:
+    ///
+    /// | Highlight                                | Priority |
+    /// |------------------------------------------|----------|
+    /// | Whitespace                               | 10       |
+    /// | Template field, snippet stop, soft limit | 20       |
+    /// | Selection                                | 30       |
+    /// | Search match                             | 40       |
+    /// | Matching bracket                         | 50       |
+    /// | Cursor                                   | 60       |
+    /// | Jump label                               | 70       |
+    ///
+    /// Highlights with the same priority are layered in the order of addition. `tag` groups highlights so that they
+    /// can be removed with [`TextArea::clear_highlights`]. Highlights are not moved by edits, so replace them when the
+    /// text is changed.
+    /// ```
+    /// use ratatui::style::{Color, Modifier, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["let x = foo();"]);
+    ///
+    /// // Semantic token under the selection
+    /// textarea.add_highlight(((0, 8), (0, 11)), Style::default().fg(Color::Yellow), 5, "tokens");
+    /// // Diagnostic over the selection
+    /// let underline = Style::default().add_modifier(Modifier::UNDERLINED);
+    /// textarea.add_highlight(((0, 4), (0, 5)), underline, 35, "diagnostics");
+    ///
+    /// // Diagnostics are updated
+    /// textarea.clear_highlights("diagnostics");
+    /// ```
+    pub fn add_highlight(
+        &mut self,
+        range: ((usize, usize), (usize, usize)),
+        style: Style,
+        priority: u8,
+        tag: &str,
+    ) {
+        let (start, end) = range;
+        if start < end {
+            self.highlights.push(TextHighlight {
+                tag: tag.to_string(),
+                start,
+                end,
+                style,
+                priority,
+            });
+        }
+    }
+
+    /// Insert rich text such as HTML copied from a browser at the cursor position, keeping its basic emphasis. The
+    /// text is converted into plain text and the styles of its ranges are added as highlights with the priority 5 and
+    /// the tag `"rich-text"`, so they can be removed with [`TextArea::clear_highlights`]. See [`RichTextFormat`] for
+    /// the supported markup. This method returns if some text was inserted or not in the textarea. It is available
+    /// when `rich-text` feature is enabled.
+    /// ```
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_textarea::{RichTextFormat, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_rich_text("<p>Hello, <b>world</b></p><p>&lt;3</p>", RichTextFormat::Html);
+    /// assert_eq!(textarea.lines(), ["Hello, world", "<3"]);
+    ///
+    /// textarea.insert_rich_text(" **bold** and *italic*", RichTextFormat::Markdown);
+    /// assert_eq!(textarea.lines(), ["Hello, world", "<3 bold and italic"]);
+    /// assert_eq!(textarea.highlight_tags(), ["rich-text"]);
+    /// ```
+    #[cfg(feature = "rich-text")]
+    pub fn insert_rich_text(&mut self, source: &str, format: RichTextFormat) -> bool {
+        let rich = parse_rich_text(source, format);
+        // Styles can be put only when the text is inserted as it is
+        let joined = self.single_line && rich.text.contains('\n');
+        let as_is =
+            !(self.is_recording_ops() || joined) && self.constrain_input(&rich.text) == rich.text;
+        if !as_is {
+            return self.insert_str(rich.text);
+        }
+        let modified = self.delete_selection(false);
+        let (row, col) = self.cursor;
+        if !self.insert_str(&rich.text) {
+            return modified;
+        }
+        let offset = |(r, c): (usize, usize)| if r == 0 { (row, col + c) } else { (row + r, c) };
+        for (start, end, style) in rich.styles {
+            self.add_highlight((offset(start), offset(end)), style, 5, "rich-text");
+        }
+        true
+    }
+
+    /// Remove the highlights added by [`TextArea::add_highlight`] with the tag.
+    /// ```
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// let style = Style::default().add_modifier(Modifier::BOLD);
+    /// textarea.add_highlight(((0, 0), (0, 1)), style, 0, "a");
+    /// textarea.add_highlight(((0, 1), (0, 2)), style, 0, "b");
+    /// textarea.clear_highlights("a");
+    /// assert_eq!(textarea.highlight_tags(), ["b"]);
+    /// ```
+    pub fn clear_highlights(&mut self, tag: &str) {
+        self.highlights.retain(|h| h.tag != tag);
+    }
+
+    /// Remove all highlights added by [`TextArea::add_highlight`].
+    pub fn clear_all_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// Get the tags of the highlights added by [`TextArea::add_highlight`] without duplicates in the order of addition.
+    pub fn highlight_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = vec![];
+        for h in &self.highlights {
+            if !tags.contains(&h.tag.as_str()) {
+                tags.push(&h.tag);
+            }
+        }
+        tags
+    }
+
+    /// Display width of the gutter signs column. 0 means the column is not shown.
+    pub(crate) fn gutter_sign_width(&self) -> usize {
+        self.gutter_signs
+            .values()
+            .map(|s| s.width())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Rows of the lines which have end-of-line annotations in ascending order.
+    pub(crate) fn eol_annotation_rows(&self) -> Vec<usize> {
+        self.eol_annotations.keys().copied().collect()
+    }
+
+    /// Display width of the prompts column. 0 means no prompt is set.
+    pub(crate) fn prompt_width(&self) -> usize {
+        self.prompt.as_ref().map_or(0, |(first, continuation)| {
+            cmp::max(Span::raw(first).width(), Span::raw(continuation).width())
+        })
+    }
+
+    pub(crate) fn has_reserved_rows(&self) -> bool {
+        !self.reserved_rows.is_empty()
+    }
+
+    /// Get the screen areas of the reserved rows which were visible at the last rendering, paired with the 0-based row
+    /// of the line the rows are reserved after. Render the content of the inline widgets into these areas after
+    /// rendering the textarea. The areas are clipped by the textarea, so an area may be shorter than the number of
+    /// reserved rows when it is partially visible.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.reserve_rows_after(0, 2);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 4 };
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// assert_eq!(textarea.reserved_areas(), [(0, Rect { x: 0, y: 1, width: 10, height: 2 })]);
+    /// ```
+    pub fn reserved_areas(&self) -> Vec<(usize, Rect)> {
+        self.reserved_screen_areas()
+    }
+
+    /// Get the screen area where the text was rendered at the last rendering. Block borders and scrollbars are excluded
+    /// but the line numbers are included. This is useful to place popups such as a completion menu next to the
+    /// textarea. The area is empty before the first rendering.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Block, Borders, Widget as _};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 4 };
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// assert_eq!(textarea.viewport_area(), Rect { x: 1, y: 1, width: 8, height: 2 });
+    /// ```
+    pub fn viewport_area(&self) -> Rect {
+        let (_, _, width, height) = self.viewport.rect();
+        let (x, y) = self.viewport.origin();
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Get the range of rows in the text which were displayed at the last rendering. When the text is wrapped, a line
+    /// partially visible at the bottom is included.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+    /// textarea.move_cursor(tui_textarea::CursorMove::Bottom);
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 3 };
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// assert_eq!(textarea.viewport_rows(), 2..5);
+    /// ```
+    pub fn viewport_rows(&self) -> std::ops::Range<usize> {
+        self.visible_rows()
+    }
+
+    /// Convert a cell position (x, y) on the screen into a (row, col) position in the text based on the last
+    /// rendering. Block borders, line numbers, scrolling, wrapping and concealed text are taken into account. `None`
+    /// is returned when the position is outside the text area. A position after the end of a line is mapped to the
+    /// end of the line.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Block, Borders, Widget as _};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 4 };
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// assert_eq!(textarea.screen_to_text(3, 2), Some((1, 2)));
+    /// assert_eq!(textarea.screen_to_text(0, 0), None);
+    /// ```
+    pub fn screen_to_text(&self, x: u16, y: u16) -> Option<(usize, usize)> {
+        self.screen_to_text_pos(x, y, false)
+    }
+
+    /// Convert a (row, col) position in the text into a cell position (x, y) on the screen based on the last
+    /// rendering. This is the inverse of [`TextArea::screen_to_text`]. `None` is returned when the position is not
+    /// visible. This is useful to place a popup next to the cursor.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Block, Borders, Widget as _};
+    ///
+    /// let mut textarea = TextArea::from(["hello world", "foo"]);
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    ///
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 4 };
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// assert_eq!(textarea.text_to_screen(1, 2), Some((3, 2)));
+    /// // Out of the screen due to the width of the text area
+    /// assert_eq!(textarea.text_to_screen(0, 9), None);
+    /// ```
+    pub fn text_to_screen(&self, row: usize, col: usize) -> Option<(u16, u16)> {
+        self.text_to_screen_pos(row, col)
+    }
+
+    /// Set the block of textarea. By default, no block is set.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// assert!(textarea.block().is_some());
+    /// ```
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// textarea.remove_block();
+    /// assert!(textarea.block().is_none());
+    /// ```
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of textarea if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    ///
+    /// textarea.set_tab_length(8);
+    /// textarea.input(tab_input.clone());
+    /// assert_eq!(textarea.lines(), ["        "]);
+    ///
+    /// textarea.set_tab_length(2);
+    /// textarea.input(tab_input);
+    /// assert_eq!(textarea.lines(), ["          "]);
+    /// ```
+    pub fn set_tab_length(&mut self, len: u8) {
+        self.tab_len = len;
+    }
+
+    /// Get how many spaces are used for representing tab character. The default value is 4.
+    pub fn tab_length(&self) -> u8 {
+        self.tab_len
+    }
+
+    /// Set the display width of hard tab characters. A hard tab is expanded to the next tab stop only on rendering and
+    /// cursor columns keep counting it as one character. Scrolling and wrapping follow the expanded width. By default,
+    /// the display width follows the tab length set by [`TextArea::set_tab_length`]. To keep hard tabs on typing a tab
+    /// key, see [`TextArea::set_hard_tab_indent`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["\tfoo"]);
+    /// assert_eq!(textarea.tab_width(), 4);
+    ///
+    /// textarea.set_tab_width(8);
+    /// assert_eq!(textarea.tab_width(), 8);
+    /// assert_eq!(textarea.tab_length(), 4); // Spaces inserted by a tab key are not affected
+    /// ```
+    pub fn set_tab_width(&mut self, width: u8) {
+        self.tab_width = Some(width);
+    }
+
+    /// Get the display width of hard tab characters. See [`TextArea::set_tab_width`].
+    pub fn tab_width(&self) -> u8 {
+        self.tab_width.unwrap_or(self.tab_len)
+    }
+
+    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
+    /// spaces. By default, hard tab is disabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// ```
+    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
+        self.hard_tab_indent = enabled;
+    }
+
+    /// Get if a hard tab is used for indent or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.hard_tab_indent());
     /// textarea.set_hard_tab_indent(true);
     /// assert!(textarea.hard_tab_indent());
     /// ```
@@ -1772,29 +5547,306 @@ impl<'a> TextArea<'a> {
         self.hard_tab_indent
     }
 
-    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// Enable or disable auto-indent. When enabled, inserting a newline copies the leading whitespaces of the current
+    /// line to the new line. By default, auto-indent is disabled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["    if x {"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.set_auto_indent(true);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["    if x {", "    "]);
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    /// ```
+    pub fn set_auto_indent(&mut self, enabled: bool) {
+        self.auto_indent = enabled;
+    }
+
+    /// Get if auto-indent is enabled or not.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert_eq!(textarea.indent(), "    ");
-    /// textarea.set_tab_length(2);
-    /// assert_eq!(textarea.indent(), "  ");
-    /// textarea.set_hard_tab_indent(true);
-    /// assert_eq!(textarea.indent(), "\t");
+    /// assert!(!textarea.auto_indent());
+    /// textarea.set_auto_indent(true);
+    /// assert!(textarea.auto_indent());
     /// ```
-    pub fn indent(&self) -> &'static str {
-        if self.hard_tab_indent {
-            "\t"
-        } else {
-            spaces(self.tab_len)
+    pub fn auto_indent(&self) -> bool {
+        self.auto_indent
+    }
+
+    /// Set the single-line mode for inputs such as prompts, search boxes and form fields. While the mode is enabled,
+    ///
+    /// - Enter key does not insert a newline. Instead, the text is marked as submitted and it can be checked with
+    ///   [`TextArea::take_submitted`]
+    /// - Newlines in inserted or pasted text are replaced with spaces
+    /// - Existing lines are joined with spaces into one line when enabling the mode. The undo history is cleared in
+    ///   the case
+    ///
+    /// Since the text always has one line, vertical cursor movements do nothing and the textarea never scrolls
+    /// vertically. By default, the single-line mode is disabled.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_single_line(true);
+    /// assert_eq!(textarea.lines(), ["hello world"]);
+    ///
+    /// textarea.insert_str("\nfoo");
+    /// assert_eq!(textarea.lines(), [" foohello world"]);
+    ///
+    /// let modified = textarea.input(Input { key: Key::Enter, ..Default::default() });
+    /// assert!(!modified);
+    /// assert!(textarea.take_submitted());
+    /// assert!(!textarea.take_submitted());
+    /// ```
+    pub fn set_single_line(&mut self, single_line: bool) {
+        self.single_line = single_line;
+        if !single_line || self.lines.len() == 1 {
+            return;
         }
+        // Column offset of each line in the joined line
+        let offsets: Vec<usize> = self
+            .lines
+            .iter()
+            .scan(0, |offset, l| {
+                let start = *offset;
+                *offset += l.chars().count() + 1;
+                Some(start)
+            })
+            .collect();
+        let (row, col) = self.cursor;
+        self.lines = vec![self.lines.join(" ")];
+        self.mixed_endings.clear();
+        self.generation += 1;
+        self.cursor = (0, offsets[row] + col);
+        self.markers.map(|(r, c)| (0, offsets[r] + c));
+        self.bookmarks.map(|(r, c)| (0, offsets[r] + c));
+        self.folds.clear();
+        self.snippet = None;
+        self.selection_start = None;
+        self.set_max_histories(self.history.max_items());
     }
 
-    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
+    /// Get if the single-line mode is enabled.
+    pub fn single_line(&self) -> bool {
+        self.single_line
+    }
+
+    /// Get if the text was submitted by Enter key in single-line mode since the last call of this method. This method
+    /// resets the submitted state. See [`TextArea::set_single_line`].
+    pub fn take_submitted(&mut self) -> bool {
+        std::mem::replace(&mut self.submitted, false)
+    }
+
+    /// Set the max number of characters of the text. Newlines are counted as characters. Inputs and insertions by
+    /// [`TextArea::insert_char`], [`TextArea::insert_str`], [`TextArea::paste`] and so on are truncated so that the
+    /// text does not exceed the limit. Text which is already in the textarea is not truncated, and programmatic edits
+    /// such as [`TextArea::format_with`] and [`TextArea::apply_patch`] are not limited. By default, there is no limit.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_max_length(5);
+    /// assert_eq!(textarea.max_length(), Some(5));
+    ///
+    /// textarea.insert_str("abc");
+    /// textarea.insert_str("defg");
+    /// assert_eq!(textarea.lines(), ["abcde"]);
+    ///
+    /// textarea.insert_char('f');
+    /// assert_eq!(textarea.lines(), ["abcde"]);
+    /// ```
+    pub fn set_max_length(&mut self, max: usize) {
+        self.max_length = Some(max);
+    }
+
+    /// Remove the max number of characters set by [`TextArea::set_max_length`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_max_length(5);
+    /// textarea.clear_max_length();
+    /// assert_eq!(textarea.max_length(), None);
+    /// ```
+    pub fn clear_max_length(&mut self) {
+        self.max_length = None;
+    }
+
+    /// Get the max number of characters of the text. When no limit is set, `None` is returned.
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Set the soft limit of the text length. Unlike [`TextArea::set_max_length`], the text is not truncated and the
+    /// characters beyond the limit are rendered with the style set by [`TextArea::set_soft_limit_style`]. See
+    /// [`SoftLimit`] for the kinds of limits. By default, there is no soft limit.
+    /// ```
+    /// use tui_textarea::{SoftLimit, TextArea};
+    ///
+    /// // Warn about lines longer than 72 columns like commit messages
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_soft_limit(SoftLimit::Columns(72));
+    /// assert_eq!(textarea.soft_limit(), Some(SoftLimit::Columns(72)));
+    ///
+    /// // Warn about posts longer than 10 characters
+    /// textarea.set_soft_limit(SoftLimit::Chars(10));
+    /// textarea.insert_str("Hello, world!");
+    /// assert_eq!(textarea.lines(), ["Hello, world!"]);
+    /// assert!(textarea.soft_limit_exceeded());
+    /// ```
+    pub fn set_soft_limit(&mut self, limit: SoftLimit) {
+        self.soft_limit = Some(limit);
+    }
+
+    /// Remove the soft limit set by [`TextArea::set_soft_limit`].
+    /// ```
+    /// use tui_textarea::{SoftLimit, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_soft_limit(SoftLimit::Chars(280));
+    /// textarea.clear_soft_limit();
+    /// assert_eq!(textarea.soft_limit(), None);
+    /// ```
+    pub fn clear_soft_limit(&mut self) {
+        self.soft_limit = None;
+    }
+
+    /// Get the soft limit of the text length. When no soft limit is set, `None` is returned.
+    pub fn soft_limit(&self) -> Option<SoftLimit> {
+        self.soft_limit
+    }
+
+    /// Check if the text exceeds the soft limit set by [`TextArea::set_soft_limit`]. This is useful to show a warning
+    /// or to disable a submit button. When no soft limit is set, this method returns `false`.
+    /// ```
+    /// use tui_textarea::{SoftLimit, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["short", "too long line"]);
+    /// assert!(!textarea.soft_limit_exceeded());
+    ///
+    /// textarea.set_soft_limit(SoftLimit::Columns(8));
+    /// assert!(textarea.soft_limit_exceeded());
+    /// ```
+    pub fn soft_limit_exceeded(&self) -> bool {
+        self.soft_limit
+            .map_or(false, |l| l.is_exceeded(&self.lines, self.tab_width()))
+    }
+
+    /// Set the style of the text beyond the soft limit set by [`TextArea::set_soft_limit`]. The default style is red
+    /// foreground.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().bg(Color::LightRed);
+    /// textarea.set_soft_limit_style(style);
+    /// assert_eq!(textarea.soft_limit_style(), style);
+    /// ```
+    pub fn set_soft_limit_style(&mut self, style: Style) {
+        self.soft_limit_style = style;
+    }
+
+    /// Get the style of the text beyond the soft limit.
+    pub fn soft_limit_style(&self) -> Style {
+        self.soft_limit_style
+    }
+
+    /// Set the function to filter input characters. Characters for which the function returns `false` are removed
+    /// from inputs and insertions by [`TextArea::insert_char`], [`TextArea::insert_str`], [`TextArea::paste`] and so
+    /// on. The function also receives `'\n'` for a newline and `'\t'` for a hard tab. Programmatic edits such as
+    /// [`TextArea::format_with`] and [`TextArea::apply_patch`] are not filtered. By default, all characters are
+    /// accepted.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// // Digits-only field
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_input_filter(|c| c.is_ascii_digit());
+    ///
+    /// textarea.insert_str("1a2\n3");
+    /// textarea.insert_char('x');
+    /// assert_eq!(textarea.lines(), ["123"]);
+    /// ```
+    pub fn set_input_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(char) -> bool + Send + Sync + 'static,
+    {
+        self.input_filter = Some(InputFilter(Arc::new(filter)));
+    }
+
+    /// Remove the function to filter input characters set by [`TextArea::set_input_filter`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_input_filter(|c| c.is_ascii_digit());
+    /// textarea.clear_input_filter();
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    /// ```
+    pub fn clear_input_filter(&mut self) {
+        self.input_filter = None;
+    }
+
+    /// Set the read-only mode. While the mode is enabled, [`TextArea::input`] and
+    /// [`TextArea::input_without_shortcuts`] ignore the inputs which modify the text such as inserting and deleting
+    /// characters, pasting, cutting, undo and redo. Moving the cursor, selecting, copying and scrolling still work.
+    /// Methods to modify the text such as [`TextArea::insert_str`] are not affected so that the application can update
+    /// the text. By default, the read-only mode is disabled.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.set_read_only(true);
+    ///
+    /// let modified = textarea.input(Input { key: Key::Char('a'), ..Default::default() });
+    /// assert!(!modified);
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    ///
+    /// textarea.input(Input { key: Key::Right, ..Default::default() });
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// ```
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Get if the read-only mode is enabled.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.indent(), "    ");
+    /// textarea.set_tab_length(2);
+    /// assert_eq!(textarea.indent(), "  ");
+    /// textarea.set_hard_tab_indent(true);
+    /// assert_eq!(textarea.indent(), "\t");
+    /// ```
+    pub fn indent(&self) -> &'static str {
+        if self.hard_tab_indent {
+            "\t"
+        } else {
+            spaces(self.tab_len)
+        }
+    }
+
+    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo. Edits grouped by
+    /// [`TextArea::begin_undo_group`] or merged by [`TextArea::set_undo_coalescing`] are counted as one modification.
     pub fn set_max_histories(&mut self, max: usize) {
+        let coalescing = self.history.coalescing();
         self.history = History::new(max);
+        self.history.set_coalescing(coalescing);
     }
 
     /// Get how many modifications are remembered for undo/redo. The default value is 50.
@@ -1802,67 +5854,1939 @@ impl<'a> TextArea<'a> {
         self.history.max_items()
     }
 
-    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
-    /// cursor line, set the default style.
+    /// Start grouping edits into one undo step. All edits until the matching [`TextArea::end_undo_group`] call are
+    /// undone/redone at once. This is useful to make a batch of programmatic edits a single undo step. Groups can be
+    /// nested. Only the outermost group makes an undo step.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.begin_undo_group();
+    /// textarea.insert_str("hello");
+    /// textarea.insert_newline();
+    /// textarea.insert_str("world");
+    /// textarea.end_undo_group();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// textarea.redo();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// ```
+    pub fn begin_undo_group(&mut self) {
+        self.history.begin_group();
+    }
+
+    /// Finish grouping edits started by [`TextArea::begin_undo_group`]. Calling [`TextArea::undo`] or
+    /// [`TextArea::redo`] also finishes all ongoing groups.
+    pub fn end_undo_group(&mut self) {
+        self.history.end_group();
+    }
+
+    /// Push the current cursor position to the undo history as a checkpoint. [`TextArea::undo`] moves the cursor back
+    /// to the checkpoint without modifying the text, and [`TextArea::redo`] moves it to the position where the undo
+    /// happened. This is useful to make a programmatic jump such as 'go to definition' undoable. Like other undo steps,
+    /// pushing a checkpoint clears the redo history. Nothing is pushed when the last undo step is a checkpoint at the
+    /// same position.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from((0..100).map(|i| i.to_string()));
+    ///
+    /// textarea.push_cursor_checkpoint();
+    /// textarea.move_cursor(CursorMove::Jump(80, 0));
+    ///
+    /// // Jump back to the checkpoint
+    /// assert!(textarea.undo());
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// assert!(textarea.redo());
+    /// assert_eq!(textarea.cursor(), (80, 0));
+    /// ```
+    pub fn push_cursor_checkpoint(&mut self) {
+        self.history.push_checkpoint(self.cursor);
+    }
+
+    /// Set how consecutive edits are merged into one undo step. By default, every edit is a separate undo step. See
+    /// [`UndoCoalescing`] for the policies.
+    /// ```
+    /// use tui_textarea::{TextArea, UndoCoalescing};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_undo_coalescing(UndoCoalescing::Word);
+    ///
+    /// for c in "hello world".chars() {
+    ///     textarea.insert_char(c);
+    /// }
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hello "]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn set_undo_coalescing(&mut self, coalescing: UndoCoalescing) {
+        self.history.set_coalescing(coalescing);
+    }
+
+    /// Get how consecutive edits are merged into one undo step. The default value is [`UndoCoalescing::Never`].
+    pub fn undo_coalescing(&self) -> UndoCoalescing {
+        self.history.coalescing()
+    }
+
+    /// Get the behavioral settings of the textarea such as tab width, wrapping and auto-indent as [`Options`]. The
+    /// options can be applied to other textareas with [`TextArea::apply_options`].
+    /// ```
+    /// use tui_textarea::{Options, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.options(), Options::default());
+    ///
+    /// textarea.set_wrap(true);
+    /// assert!(textarea.options().wrap);
+    /// ```
+    pub fn options(&self) -> Options {
+        Options {
+            tab_length: self.tab_len,
+            tab_width: self.tab_width,
+            hard_tab_indent: self.hard_tab_indent,
+            auto_indent: self.auto_indent,
+            wrap: self.wrap,
+            read_only: self.read_only,
+            overwrite: self.overwrite,
+            max_length: self.max_length,
+            soft_limit: self.soft_limit,
+            max_histories: self.max_histories(),
+            undo_coalescing: self.undo_coalescing(),
+            max_yank_history: self.max_yank_history,
+            kill_accumulation: self.kill_accumulation,
+            bracket_pairs: self.bracket_pairs.clone(),
+            word_chars: self.word_chars.clone(),
+            trim_trailing_whitespace: self.trim_trailing_whitespace,
+            color_column: self.color_column,
+            comment_prefix: self.comment_prefix.clone(),
+        }
+    }
+
+    /// Apply all behavioral settings in [`Options`] at once. This is the same as calling the setter of each option.
+    /// The undo history is kept unless the max number of undo steps is changed.
+    /// ```
+    /// use tui_textarea::{Options, TextArea};
+    ///
+    /// let mut rust = TextArea::default();
+    /// rust.set_auto_indent(true);
+    /// rust.set_bracket_pairs([('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')]);
+    ///
+    /// // Share the settings with another buffer of the same file type
+    /// let mut textarea = TextArea::from(["fn main() {}"]);
+    /// textarea.insert_char('x');
+    /// textarea.apply_options(&rust.options());
+    /// assert!(textarea.auto_indent());
+    /// assert_eq!(textarea.bracket_pairs().len(), 4);
+    /// assert!(textarea.undo());
+    ///
+    /// // Back to the default settings
+    /// textarea.apply_options(&Options::default());
+    /// assert!(!textarea.auto_indent());
+    /// ```
+    pub fn apply_options(&mut self, options: &Options) {
+        self.tab_len = options.tab_length;
+        self.tab_width = options.tab_width;
+        self.hard_tab_indent = options.hard_tab_indent;
+        self.auto_indent = options.auto_indent;
+        self.wrap = options.wrap;
+        self.read_only = options.read_only;
+        self.overwrite = options.overwrite;
+        self.max_length = options.max_length;
+        self.soft_limit = options.soft_limit;
+        if options.max_histories != self.max_histories() {
+            self.set_max_histories(options.max_histories);
+        }
+        self.set_undo_coalescing(options.undo_coalescing);
+        self.set_max_yank_history(options.max_yank_history);
+        self.kill_accumulation = options.kill_accumulation;
+        self.bracket_pairs = options.bracket_pairs.clone();
+        self.word_chars = options.word_chars.clone();
+        self.trim_trailing_whitespace = options.trim_trailing_whitespace;
+        self.color_column = options.color_column;
+        self.comment_prefix = options.comment_prefix.clone();
+    }
+
+    /// Set the file type and apply its built-in options such as tab length, hard tab indent and trimming trailing
+    /// whitespace. The file type can be a name, an extension or a file name. See [`Options::for_filetype`] for the
+    /// built-in file types. Other options such as the read-only mode are kept. This method returns `false` and changes
+    /// nothing when the file type is unknown.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.set_filetype("yml"));
+    /// assert_eq!(textarea.filetype(), Some("yaml"));
+    /// assert_eq!(textarea.tab_length(), 2);
+    ///
+    /// assert!(textarea.set_filetype("Makefile"));
+    /// assert!(textarea.hard_tab_indent());
+    ///
+    /// assert!(!textarea.set_filetype("unknown"));
+    /// assert_eq!(textarea.filetype(), Some("make"));
+    /// ```
+    pub fn set_filetype(&mut self, filetype: &str) -> bool {
+        self.apply_profile(filetype::find(filetype))
+    }
+
+    /// Detect the file type from the file name (e.g. `Makefile`) or the extension of the path and apply its built-in
+    /// options in the same way as [`TextArea::set_filetype`]. This method returns `false` and changes nothing when the
+    /// file type is unknown.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.set_filetype_from_path("docs/README.md"));
+    /// assert_eq!(textarea.filetype(), Some("markdown"));
+    /// assert!(!textarea.trim_trailing_whitespace());
+    /// ```
+    pub fn set_filetype_from_path(&mut self, path: impl AsRef<Path>) -> bool {
+        self.apply_profile(filetype::detect(path.as_ref()))
+    }
+
+    fn apply_profile(&mut self, profile: Option<&'static Profile>) -> bool {
+        let profile = match profile {
+            Some(p) => p,
+            None => return false,
+        };
+        let mut options = self.options();
+        profile.apply(&mut options);
+        self.apply_options(&options);
+        self.filetype = Some(profile.name());
+        true
+    }
+
+    /// Get the file type set by [`TextArea::set_filetype`] or [`TextArea::set_filetype_from_path`]. By default no file
+    /// type is set.
+    pub fn filetype(&self) -> Option<&'static str> {
+        self.filetype
+    }
+
+    /// Enable or disable deleting trailing whitespace of each line on [`TextArea::save_to_path`]. The default value is
+    /// `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_trim_trailing_whitespace(true);
+    /// assert!(textarea.trim_trailing_whitespace());
+    /// ```
+    pub fn set_trim_trailing_whitespace(&mut self, enabled: bool) {
+        self.trim_trailing_whitespace = enabled;
+    }
+
+    /// Get if trailing whitespace is deleted on saving the text.
+    pub fn trim_trailing_whitespace(&self) -> bool {
+        self.trim_trailing_whitespace
+    }
+
+    /// Delete whitespace at the end of each line as one undo step. The cursor and the selection follow the deleted
+    /// text. This method returns if some whitespace was deleted or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["foo  ", "\t", "bar"]);
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert!(textarea.delete_trailing_whitespace());
+    /// assert_eq!(textarea.lines(), ["foo", "", "bar"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// assert!(!textarea.delete_trailing_whitespace());
+    /// ```
+    pub fn delete_trailing_whitespace(&mut self) -> bool {
+        let edits: Vec<_> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(row, line)| {
+                let trimmed = line.trim_end();
+                if trimmed.len() == line.len() {
+                    return None;
+                }
+                let start = trimmed.chars().count();
+                let end = start + line[trimmed.len()..].chars().count();
+                Some(TextEdit::new((row, start), (row, end), ""))
+            })
+            .collect();
+        !edits.is_empty() && self.apply_edits(&edits).is_ok()
+    }
+
+    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
+    /// cursor line, set the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_cursor_line_style(style);
+    /// assert_eq!(textarea.cursor_line_style(), style);
+    ///
+    /// // Disable cursor line style
+    /// textarea.set_cursor_line_style(Style::default());
+    /// ```
+    pub fn set_cursor_line_style(&mut self, style: Style) {
+        self.cursor_line_style = style;
+    }
+
+    /// Get the style of cursor line. By default it is styled with underline.
+    pub fn cursor_line_style(&self) -> Style {
+        self.cursor_line_style
+    }
+
+    /// Set if the cursor line style (see [`TextArea::set_cursor_line_style`]) is applied to the entire display row of
+    /// the cursor across the width of the textarea, not only to the text of the line. When the text is wrapped, only
+    /// the display row where the cursor is put is extended. This is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.cursor_line_full_width());
+    /// textarea.set_cursor_line_full_width(true);
+    /// assert!(textarea.cursor_line_full_width());
+    /// ```
+    pub fn set_cursor_line_full_width(&mut self, enabled: bool) {
+        self.cursor_line_full_width = enabled;
+    }
+
+    /// Get if the cursor line style is applied to the entire display row. See
+    /// [`TextArea::set_cursor_line_full_width`].
+    pub fn cursor_line_full_width(&self) -> bool {
+        self.cursor_line_full_width
+    }
+
+    /// Set the style of the column at cursor. By setting the style with this method, the cells at the cursor column
+    /// in all visible rows are highlighted like `cursorcolumn` of Vim. The highlight is disabled by default.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_cursor_column_style(style);
+    /// assert_eq!(textarea.cursor_column_style(), Some(style));
+    /// ```
+    pub fn set_cursor_column_style(&mut self, style: Style) {
+        self.cursor_column_style = Some(style);
+    }
+
+    /// Remove the style of cursor column which was set by [`TextArea::set_cursor_column_style`]. After calling this
+    /// method, the cursor column is no longer highlighted.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_cursor_column_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_cursor_column();
+    /// assert_eq!(textarea.cursor_column_style(), None);
+    /// ```
+    pub fn remove_cursor_column(&mut self) {
+        self.cursor_column_style = None;
+    }
+
+    /// Get the style of cursor column if set.
+    pub fn cursor_column_style(&self) -> Option<Style> {
+        self.cursor_column_style
+    }
+
+    /// Highlight the 0-based display column on all rows as a guide of the line length like `colorcolumn` of Vim. For
+    /// example, set 72 to highlight the column just after 72 characters. The column is highlighted with the style set
+    /// by [`TextArea::set_color_column_style`]. It is not shown when the text is aligned to the center or the right.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_color_column(80);
+    /// assert_eq!(textarea.color_column(), Some(80));
+    /// ```
+    pub fn set_color_column(&mut self, col: usize) {
+        self.color_column = Some(col);
+    }
+
+    /// Remove the color column set by [`TextArea::set_color_column`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_color_column(80);
+    /// textarea.clear_color_column();
+    /// assert_eq!(textarea.color_column(), None);
+    /// ```
+    pub fn clear_color_column(&mut self) {
+        self.color_column = None;
+    }
+
+    /// Get the color column if set.
+    pub fn color_column(&self) -> Option<usize> {
+        self.color_column
+    }
+
+    /// Set the style of the color column set by [`TextArea::set_color_column`]. The default style is dark gray
+    /// background.
+    pub fn set_color_column_style(&mut self, style: Style) {
+        self.color_column_style = style;
+    }
+
+    /// Get the style of the color column.
+    pub fn color_column_style(&self) -> Style {
+        self.color_column_style
+    }
+
+    /// Set the prefix of comment lines. Lines starting with the prefix are rendered with the style set by
+    /// [`TextArea::set_comment_style`] and they are removed by [`TextArea::text_without_comments`]. This is useful for
+    /// editing commit messages where lines starting with `#` are ignored. By default, no prefix is set.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_comment_prefix("#");
+    /// assert_eq!(textarea.comment_prefix(), Some("#"));
+    /// ```
+    pub fn set_comment_prefix(&mut self, prefix: impl Into<String>) {
+        let prefix = prefix.into();
+        self.comment_prefix = (!prefix.is_empty()).then(|| prefix);
+    }
+
+    /// Remove the prefix of comment lines set by [`TextArea::set_comment_prefix`].
+    pub fn clear_comment_prefix(&mut self) {
+        self.comment_prefix = None;
+    }
+
+    /// Get the prefix of comment lines if set.
+    pub fn comment_prefix(&self) -> Option<&str> {
+        self.comment_prefix.as_deref()
+    }
+
+    /// Set the style of comment lines. The default style is dim.
+    pub fn set_comment_style(&mut self, style: Style) {
+        self.comment_style = style;
+    }
+
+    /// Get the style of comment lines.
+    pub fn comment_style(&self) -> Style {
+        self.comment_style
+    }
+
+    fn is_comment_line(&self, line: &str) -> bool {
+        matches!(&self.comment_prefix, Some(p) if line.starts_with(p.as_str()))
+    }
+
+    /// Get the text without the comment lines set by [`TextArea::set_comment_prefix`] in the same manner as
+    /// `git commit --cleanup=strip`. Trailing whitespace of each line is removed, consecutive empty lines are
+    /// collapsed into one, and empty lines at the beginning and the end are removed. The text ends with a newline
+    /// unless it is empty. Lines are joined with the line ending of the textarea.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "Fix typo  ",
+    ///     "",
+    ///     "",
+    ///     "Details",
+    ///     "# Please enter the commit message for your changes.",
+    ///     "",
+    /// ]);
+    /// textarea.set_comment_prefix("#");
+    /// assert_eq!(textarea.text_without_comments(), "Fix typo\n\nDetails\n");
+    ///
+    /// let mut textarea = TextArea::from(["# Only comments"]);
+    /// textarea.set_comment_prefix("#");
+    /// assert_eq!(textarea.text_without_comments(), "");
+    /// ```
+    pub fn text_without_comments(&self) -> String {
+        let newline = self.line_ending.as_str();
+        let mut text = String::new();
+        let mut blank = false;
+        for line in &self.lines {
+            if self.is_comment_line(line) {
+                continue;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank = true;
+                continue;
+            }
+            if blank && !text.is_empty() {
+                text.push_str(newline);
+            }
+            blank = false;
+            text.push_str(line);
+            text.push_str(newline);
+        }
+        text
+    }
+
+    /// Set the color profile to render visual cues such as the cursor, the selection and search matches. When
+    /// [`ColorProfile::Monochrome`] is set, colors are not used and the cues are rendered with text attributes like
+    /// reverse video, underline and bold. This is useful for monochrome terminals or when `NO_COLOR` is set. The
+    /// default value is [`ColorProfile::Full`].
+    /// ```
+    /// use tui_textarea::{ColorProfile, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.color_profile(), ColorProfile::Full);
+    ///
+    /// textarea.set_color_profile(ColorProfile::Monochrome);
+    /// assert_eq!(textarea.color_profile(), ColorProfile::Monochrome);
+    /// ```
+    pub fn set_color_profile(&mut self, profile: ColorProfile) {
+        self.color_profile = profile;
+    }
+
+    /// Get the color profile. See [`TextArea::set_color_profile`].
+    pub fn color_profile(&self) -> ColorProfile {
+        self.color_profile
+    }
+
+    /// Convert the style of a visual cue for the current color profile.
+    pub(crate) fn cue_style(&self, style: Style, cue: Modifier) -> Style {
+        self.color_profile.apply(style, cue)
+    }
+
+    /// Visualize tabs, trailing spaces, non-breaking spaces and line ends with glyphs. See [`WhitespaceConfig`] for the
+    /// glyphs and their styles. Whitespaces are not visualized while the text is masked.
+    /// ```
+    /// use tui_textarea::{TextArea, WhitespaceConfig};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.show_whitespace(), None);
+    ///
+    /// textarea.set_show_whitespace(WhitespaceConfig::default());
+    /// assert!(textarea.show_whitespace().is_some());
+    ///
+    /// textarea.hide_whitespace();
+    /// assert_eq!(textarea.show_whitespace(), None);
+    /// ```
+    pub fn set_show_whitespace(&mut self, config: WhitespaceConfig) {
+        self.whitespace = Some(config);
+    }
+
+    /// Stop visualizing whitespaces which was enabled by [`TextArea::set_show_whitespace`].
+    pub fn hide_whitespace(&mut self) {
+        self.whitespace = None;
+    }
+
+    /// Get the configuration of whitespace visualization if enabled. See [`TextArea::set_show_whitespace`].
+    pub fn show_whitespace(&self) -> Option<&WhitespaceConfig> {
+        self.whitespace.as_ref()
+    }
+
+    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
+    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
+    /// the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Show line numbers in dark gray background
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_line_number_style(style);
+    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// ```
+    pub fn set_line_number_style(&mut self, style: Style) {
+        self.line_number_style = Some(style);
+    }
+
+    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
+    /// method, Line numbers will no longer be shown.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_line_number();
+    /// assert_eq!(textarea.line_number_style(), None);
+    /// ```
+    pub fn remove_line_number(&mut self) {
+        self.line_number_style = None;
+    }
+
+    /// Get the style of line number if set.
+    pub fn line_number_style(&self) -> Option<Style> {
+        self.line_number_style
+    }
+
+    /// Set the style of matching brackets. By setting the style with this method, when the cursor is on a bracket,
+    /// both the bracket and its matching bracket are highlighted with the style. The highlight is disabled by default.
+    /// The pairs of brackets can be configured with [`TextArea::set_bracket_pairs`].
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Yellow);
+    /// textarea.set_matched_bracket_style(style);
+    /// assert_eq!(textarea.matched_bracket_style(), Some(style));
+    /// ```
+    pub fn set_matched_bracket_style(&mut self, style: Style) {
+        self.matched_bracket_style = Some(style);
+    }
+
+    /// Remove the style of matching brackets which was set by [`TextArea::set_matched_bracket_style`]. After calling
+    /// this method, matching brackets will no longer be highlighted.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_matched_bracket_style(Style::default().bg(Color::Yellow));
+    /// textarea.remove_matched_bracket_style();
+    /// assert_eq!(textarea.matched_bracket_style(), None);
+    /// ```
+    pub fn remove_matched_bracket_style(&mut self) {
+        self.matched_bracket_style = None;
+    }
+
+    /// Get the style of matching brackets if set.
+    pub fn matched_bracket_style(&self) -> Option<Style> {
+        self.matched_bracket_style
+    }
+
+    /// Add a rule to conceal the text matching to `pattern`. The matched text is rendered as `replacement` while the
+    /// actual text is kept as-is. For example, `\lambda` can be rendered as `λ`, or `**` of Markdown can be hidden with
+    /// an empty replacement. The cursor line always shows the actual text so that it can be edited. When matches of
+    /// rules overlap, the leftmost match wins, then the rule added first wins. Masked text is not concealed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_conceal_rule("\\lambda", "λ");
+    /// textarea.add_conceal_rule("**", "");
+    /// assert_eq!(
+    ///     textarea.conceal_rules(),
+    ///     [("\\lambda".to_string(), "λ".to_string()), ("**".to_string(), "".to_string())],
+    /// );
+    /// ```
+    pub fn add_conceal_rule(&mut self, pattern: impl Into<String>, replacement: impl Into<String>) {
+        self.conceal_rules
+            .push((pattern.into(), replacement.into()));
+    }
+
+    /// Remove all rules added by [`TextArea::add_conceal_rule`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_conceal_rule("->", "→");
+    /// textarea.clear_conceal_rules();
+    /// assert!(textarea.conceal_rules().is_empty());
+    /// ```
+    pub fn clear_conceal_rules(&mut self) {
+        self.conceal_rules.clear();
+    }
+
+    /// Get the rules to conceal text added by [`TextArea::add_conceal_rule`]. Each rule is a pair of the pattern and
+    /// its replacement.
+    pub fn conceal_rules(&self) -> &[(String, String)] {
+        &self.conceal_rules
+    }
+
+    /// Add a ligature-like substitution which displays the character sequence as the glyph, such as `->` as `→`. It
+    /// only affects rendering. The text and the positions in the text such as the cursor position are not changed.
+    /// Unlike [`TextArea::add_conceal_rule`], it is also applied to the cursor line except for the sequence under the
+    /// cursor. Conceal rules take precedence over ligatures.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a -> b"]);
+    /// textarea.add_ligature("->", "→");
+    /// textarea.add_ligature("!=", "≠");
+    /// assert_eq!(textarea.ligatures()[0], ("->".to_string(), "→".to_string()));
+    /// assert_eq!(textarea.lines(), ["a -> b"]);
+    /// ```
+    pub fn add_ligature(&mut self, sequence: impl Into<String>, glyph: impl Into<String>) {
+        self.ligatures.push((sequence.into(), glyph.into()));
+    }
+
+    /// Remove all ligatures added by [`TextArea::add_ligature`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_ligature("->", "→");
+    /// textarea.clear_ligatures();
+    /// assert!(textarea.ligatures().is_empty());
+    /// ```
+    pub fn clear_ligatures(&mut self) {
+        self.ligatures.clear();
+    }
+
+    /// Get the ligatures added by [`TextArea::add_ligature`]. Each element is a pair of the character sequence and its
+    /// glyph.
+    pub fn ligatures(&self) -> &[(String, String)] {
+        &self.ligatures
+    }
+
+    /// Start the jump label mode like easymotion of Vim. Labels of 1 or 2 characters are put on the starts of words
+    /// in the lines displayed at the last rendering. While the mode is active, [`TextArea::input`] and
+    /// [`TextArea::input_without_shortcuts`] take characters of a label. When a label is completed, the cursor jumps
+    /// to its position and the mode ends. Any other input cancels the mode. This method returns if some label was put
+    /// or not.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar", "baz"]);
+    ///
+    /// // Render the textarea to determine the visible lines
+    /// let r = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// let mut b = Buffer::empty(r);
+    /// textarea.render(r, &mut b);
+    ///
+    /// // Labels 'a', 's', 'd' are put on "foo", "bar", "baz" respectively
+    /// assert!(textarea.start_jump_labels());
+    /// assert!(textarea.is_jumping());
+    ///
+    /// textarea.input(Input { key: Key::Char('s'), ..Default::default() });
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    /// assert!(!textarea.is_jumping());
+    /// ```
+    pub fn start_jump_labels(&mut self) -> bool {
+        let (top_row, _, _, height) = self.viewport.rect();
+        // The text may have been shrunk by edits after the last render
+        let top = cmp::min(top_row as usize, self.lines.len());
+        let bottom = if height == 0 {
+            self.lines.len() // Not rendered yet
+        } else {
+            cmp::min(top + height as usize, self.lines.len())
+        };
+        let positions = self.lines[top..bottom]
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| word_starts(line).into_iter().map(move |col| (top + i, col)))
+            .collect();
+        self.start_jump_labels_at(positions)
+    }
+
+    /// Start the jump label mode with labels on the given (row, col) positions such as search matches. See
+    /// [`TextArea::start_jump_labels`] for the mode. Positions out of the text or at the end of lines are ignored.
+    /// This method returns if some label was put or not.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar", "baz"]);
+    ///
+    /// assert!(textarea.start_jump_labels_at(vec![(0, 1), (1, 2), (2, 0)]));
+    /// textarea.input(Input { key: Key::Char('s'), ..Default::default() });
+    /// assert_eq!(textarea.cursor(), (1, 2));
+    /// ```
+    pub fn start_jump_labels_at(&mut self, positions: Vec<(usize, usize)>) -> bool {
+        let positions = positions
+            .into_iter()
+            .filter(|&(row, col)| {
+                self.lines
+                    .get(row)
+                    .map_or(false, |l| col < l.chars().count())
+            })
+            .collect();
+        let labels = JumpLabels::new(positions);
+        self.jump_labels = if labels.is_empty() {
+            None
+        } else {
+            Some(labels)
+        };
+        self.jump_labels.is_some()
+    }
+
+    /// Cancel the jump label mode started by [`TextArea::start_jump_labels`] or [`TextArea::start_jump_labels_at`].
+    pub fn cancel_jump_labels(&mut self) {
+        self.jump_labels = None;
+    }
+
+    /// Get if the jump label mode is active.
+    pub fn is_jumping(&self) -> bool {
+        self.jump_labels.is_some()
+    }
+
+    /// Open the completion menu with the items. The word before the cursor (a sequence of alphanumeric characters and
+    /// `_`) is completed and the items are narrowed down to the ones whose labels start with the word, ignoring case.
+    /// The menu is rendered as a popup below the word, or above the word when there is no space below.
+    ///
+    /// While the menu is open, [`TextArea::input`] and [`TextArea::input_without_shortcuts`] handle the following keys
+    /// for the menu. Other inputs are handled as usual and typing more characters narrows down the items. The menu is
+    /// closed when the cursor leaves the word or no item matches.
+    ///
+    /// | Mappings                | Description                                   |
+    /// |-------------------------|-----------------------------------------------|
+    /// | `↓`, `Ctrl+N`           | Select the next item                          |
+    /// | `↑`, `Ctrl+P`           | Select the previous item                      |
+    /// | `Tab`, `Enter`          | Replace the word with the selected item       |
+    /// | `Esc`                   | Close the menu                                |
+    ///
+    /// This method returns if the menu was opened or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CompletionItem, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["let x = pri"]);
+    /// textarea.move_cursor(tui_textarea::CursorMove::End);
+    ///
+    /// let items = ["print", "println", "private"].into_iter().map(CompletionItem::new).collect();
+    /// assert!(textarea.open_completion(items));
+    /// assert_eq!(textarea.completion_items().len(), 3);
+    ///
+    /// textarea.input(Input { key: Key::Char('n'), ..Default::default() });
+    /// assert_eq!(textarea.completion_items().len(), 2);
+    ///
+    /// textarea.input(Input { key: Key::Down, ..Default::default() });
+    /// textarea.input(Input { key: Key::Enter, ..Default::default() });
+    /// assert_eq!(textarea.lines(), ["let x = println"]);
+    /// assert!(!textarea.is_completing());
+    /// ```
+    pub fn open_completion(&mut self, items: Vec<CompletionItem>) -> bool {
+        let (row, col) = self.cursor;
+        self.completion = Some(Completion::new(
+            items,
+            (row, word_start(&self.lines[row], col)),
+        ));
+        self.update_completion();
+        self.completion.is_some()
+    }
+
+    /// Close the completion menu opened by [`TextArea::open_completion`] without modifying the text.
+    pub fn close_completion(&mut self) {
+        self.completion = None;
+    }
+
+    /// Get if the completion menu is open.
+    pub fn is_completing(&self) -> bool {
+        self.completion.is_some()
+    }
+
+    /// Get the items shown in the completion menu. They are the items matching the typed word.
+    pub fn completion_items(&self) -> Vec<&CompletionItem> {
+        self.completion
+            .as_ref()
+            .map_or(vec![], |c| c.items().collect())
+    }
+
+    /// Get the selected item in the completion menu.
+    pub fn selected_completion(&self) -> Option<&CompletionItem> {
+        self.completion.as_ref()?.selected()
+    }
+
+    /// Select the next item in the completion menu. After the last item, the first item is selected.
+    pub fn select_next_completion(&mut self) {
+        if let Some(c) = &mut self.completion {
+            c.select_next();
+        }
+    }
+
+    /// Select the previous item in the completion menu. Before the first item, the last item is selected.
+    pub fn select_prev_completion(&mut self) {
+        if let Some(c) = &mut self.completion {
+            c.select_prev();
+        }
+    }
+
+    /// Replace the word under the cursor with the selected item in the completion menu and close the menu. The
+    /// replacement is undone at once. This method returns if the text was modified or not.
+    pub fn accept_completion(&mut self) -> bool {
+        let completion = match self.completion.take() {
+            Some(c) => c,
+            None => return false,
+        };
+        let item = match completion.selected() {
+            Some(item) => item,
+            None => return false,
+        };
+        let (row, col) = self.cursor;
+        let start = completion.start;
+        let end = (row, word_end(&self.lines[row], col));
+        self.history.begin_group();
+        self.replace_range(start, end, item.text());
+        self.history.end_group();
+        true
+    }
+
+    /// Set the style of the completion menu. The default style is dark gray background.
+    pub fn set_completion_style(&mut self, style: Style) {
+        self.completion_style = style;
+    }
+
+    /// Get the style of the completion menu.
+    pub fn completion_style(&self) -> Style {
+        self.completion_style
+    }
+
+    /// Set the style of the selected item in the completion menu. The default style is reversed.
+    pub fn set_completion_selected_style(&mut self, style: Style) {
+        self.completion_selected_style = style;
+    }
+
+    /// Get the style of the selected item in the completion menu.
+    pub fn completion_selected_style(&self) -> Style {
+        self.completion_selected_style
+    }
+
+    pub(crate) fn completion_state(&self) -> Option<&Completion> {
+        self.completion.as_ref()
+    }
+
+    /// Insert the snippet at the cursor and start filling in its tab stops. The text of the selection is replaced with
+    /// the snippet. The following placeholders are supported in the snippet.
+    ///
+    /// - `$1`, `$2`, ...: Tab stops visited in the order of their numbers
+    /// - `${1:default}`: Tab stop with the default text. The text is selected when the tab stop is visited so that
+    ///   typing replaces it
+    /// - `$0`: Final cursor position. When it is omitted, the end of the snippet is the final position
+    ///
+    /// `\$`, `\}` and `\\` are inserted as `$`, `}` and `\`. When the same number appears more than once, only the first
+    /// one is a tab stop. Nested placeholders are not supported.
+    ///
+    /// The active tab stop is highlighted with [`TextArea::snippet_stop_style`]. Positions of tab stops follow the edits
+    /// in the textarea including undo and redo. While the snippet session is active, [`TextArea::input`] and
+    /// [`TextArea::input_without_shortcuts`] handle `Tab` and `Shift+Tab` to move to the next and previous tab stops.
+    /// The session ends when the cursor reaches the final position or leaves the active tab stop by the input.
+    ///
+    /// The insertion is undone at once. This method returns if the snippet was inserted or not. The snippet is not
+    /// inserted when a template is set or when some of its characters are rejected by [`TextArea::set_max_length`] or
+    /// [`TextArea::set_input_filter`].
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_snippet("fn ${1:name}($2) {\n    $0\n}");
+    /// assert_eq!(textarea.lines(), ["fn name() {", "    ", "}"]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 3), (0, 7))));
+    ///
+    /// for c in "main".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ..Default::default() });
+    /// }
+    /// textarea.input(Input { key: Key::Tab, ..Default::default() });
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    ///
+    /// textarea.input(Input { key: Key::Tab, ..Default::default() });
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    /// assert!(!textarea.is_snippet_active());
+    /// assert_eq!(textarea.lines(), ["fn main() {", "    ", "}"]);
+    /// ```
+    pub fn insert_snippet(&mut self, snippet: &str) -> bool {
+        if self.template.is_some() {
+            return false; // Field positions would be broken
+        }
+        let (text, stops) = snippet::parse(&snippet.replace("\r\n", "\n"));
+        if self.constrain_input(&text) != text.as_str() {
+            self.emit(UxEvent::EditRejected);
+            return false;
+        }
+
+        self.snippet = None;
+        self.history.begin_group();
+        let mut modified = self.delete_selection(false);
+        let origin = self.cursor;
+        modified |= self.insert_text(&text, false);
+        self.history.end_group();
+
+        let end = self.cursor;
+        let single_line = self.single_line;
+        let has_final = stops.last().map_or(false, |s| s.number == 0);
+        let mut stops: Vec<_> = stops
+            .into_iter()
+            .map(|s| TabStop {
+                start: snippet::position(&text, s.start, origin, single_line),
+                end: snippet::position(&text, s.end, origin, single_line),
+            })
+            .collect();
+        if !has_final {
+            stops.push(TabStop { start: end, end });
+        }
+        self.snippet = Some(SnippetSession::new(stops));
+        self.focus_snippet_stop(0);
+        modified
+    }
+
+    /// Move to the next tab stop of the snippet inserted by [`TextArea::insert_snippet`]. The placeholder text of the tab
+    /// stop is selected. After the last tab stop, the cursor moves to the final position and the snippet session ends.
+    /// This method returns if the cursor moved or not.
+    pub fn next_snippet_stop(&mut self) -> bool {
+        match &self.snippet {
+            Some(s) => self.focus_snippet_stop(s.active_index() + 1),
+            None => false,
+        }
+    }
+
+    /// Move to the previous tab stop of the snippet inserted by [`TextArea::insert_snippet`]. The placeholder text of
+    /// the tab stop is selected. This method returns if the cursor moved or not.
+    pub fn prev_snippet_stop(&mut self) -> bool {
+        match self
+            .snippet
+            .as_ref()
+            .and_then(|s| s.active_index().checked_sub(1))
+        {
+            Some(i) => self.focus_snippet_stop(i),
+            None => false,
+        }
+    }
+
+    fn focus_snippet_stop(&mut self, index: usize) -> bool {
+        let snippet = match &mut self.snippet {
+            Some(s) => s,
+            None => return false,
+        };
+        let stop = match snippet.stop(index) {
+            Some(stop) => stop,
+            None => return false,
+        };
+        if snippet.is_final(index) {
+            self.snippet = None;
+            self.selection_start = None;
+        } else {
+            snippet.set_active(index);
+            self.selection_start = Some(stop.start).filter(|&s| s != stop.end);
+        }
+        self.cursor = stop.end;
+        self.notify_cursor();
+        true
+    }
+
+    /// End the snippet session started by [`TextArea::insert_snippet`]. The text and the cursor are kept.
+    pub fn end_snippet(&mut self) {
+        self.snippet = None;
+    }
+
+    /// Get if the snippet session started by [`TextArea::insert_snippet`] is active.
+    pub fn is_snippet_active(&self) -> bool {
+        self.snippet.is_some()
+    }
+
+    /// Set the style of the active tab stop of the snippet. The default style is dark gray background.
+    pub fn set_snippet_stop_style(&mut self, style: Style) {
+        self.snippet_stop_style = style;
+    }
+
+    /// Get the style of the active tab stop of the snippet.
+    pub fn snippet_stop_style(&self) -> Style {
+        self.snippet_stop_style
+    }
+
+    /// Add a marker at the position. The position of the marker follows edits of the text including undo and redo, so
+    /// it is useful to keep stable locations such as diagnostics or bookmarks. When text is inserted at the marker, the
+    /// marker stays before the inserted text. When the text around the marker is deleted, the marker moves to the start
+    /// of the deletion and undoing the deletion doesn't move it back. The position is clamped to the text.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// let marker = textarea.add_marker(1, 1);
+    ///
+    /// textarea.insert_str("x\ny");
+    /// assert_eq!(textarea.marker_pos(marker), Some((2, 1)));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.marker_pos(marker), Some((1, 1)));
+    ///
+    /// assert!(textarea.remove_marker(marker));
+    /// assert_eq!(textarea.marker_pos(marker), None);
+    /// ```
+    pub fn add_marker(&mut self, row: usize, col: usize) -> Marker {
+        let row = cmp::min(row, self.lines.len() - 1);
+        let col = cmp::min(col, self.lines[row].chars().count());
+        self.markers.add((row, col))
+    }
+
+    /// Get the current (row, column) position of the marker. `None` is returned when the marker was removed.
+    pub fn marker_pos(&self, marker: Marker) -> Option<(usize, usize)> {
+        self.markers.get(marker)
+    }
+
+    /// Remove the marker. This method returns if the marker existed or not.
+    pub fn remove_marker(&mut self, marker: Marker) -> bool {
+        self.markers.remove(marker)
+    }
+
+    /// Toggle a bookmark on the cursor line. A bookmark follows edits of the text in the same way as a marker added by
+    /// [`TextArea::add_marker`] at the head of the line, except that it moves with the line when a newline is inserted
+    /// at the head. When the cursor line is already bookmarked, the bookmark is removed. This method returns `true`
+    /// when a bookmark was added. [`BookmarkList`] is a widget to list the bookmarks and jump to them.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert!(textarea.toggle_bookmark());
+    /// textarea.move_cursor(CursorMove::Top);
+    /// assert!(textarea.toggle_bookmark());
+    /// assert_eq!(textarea.bookmarks(), [0, 2]);
+    ///
+    /// // Bookmarks follow the edits
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.bookmarks(), [1, 3]);
+    ///
+    /// assert!(!textarea.toggle_bookmark());
+    /// assert_eq!(textarea.bookmarks(), [3]);
+    /// ```
+    ///
+    /// [`BookmarkList`]: crate::BookmarkList
+    pub fn toggle_bookmark(&mut self) -> bool {
+        let row = self.cursor.0;
+        if self.remove_bookmark(row) {
+            return false;
+        }
+        self.bookmarks.add((row, 0));
+        true
+    }
+
+    /// Remove the bookmark on the row. This method returns if a bookmark was removed or not.
+    pub fn remove_bookmark(&mut self, row: usize) -> bool {
+        self.bookmarks.retain(|(r, _)| r != row)
+    }
+
+    /// Remove all bookmarks.
+    pub fn clear_bookmarks(&mut self) {
+        self.bookmarks.clear();
+    }
+
+    /// Get the sorted rows of the bookmarked lines. When some bookmarks were moved to the same line by edits, the line
+    /// appears once.
+    pub fn bookmarks(&self) -> Vec<usize> {
+        let mut rows: Vec<_> = self.bookmarks.positions().map(|(r, _)| r).collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+
+    /// Move the cursor to the head of the bookmarked line at the index of [`TextArea::bookmarks`]. The selection is
+    /// cancelled and the fold containing the line is unfolded. This method returns `false` when the index is out of
+    /// range.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 2));
+    /// textarea.toggle_bookmark();
+    /// textarea.move_cursor(CursorMove::Top);
+    ///
+    /// assert!(textarea.jump_to_bookmark(0));
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert!(!textarea.jump_to_bookmark(1));
+    /// ```
+    pub fn jump_to_bookmark(&mut self, index: usize) -> bool {
+        let row = match self.bookmarks().get(index) {
+            Some(&row) => row,
+            None => return false,
+        };
+        self.set_cursor_with_shift((row, 0), false);
+        self.unfold_at_cursor();
+        self.record_visit();
+        true
+    }
+
+    /// Fold the lines in the range of 0-based rows. The folded lines are rendered as one placeholder line such as
+    /// `⋯ 42 lines` with [`TextArea::fold_style`]. The placeholder text can be changed by [`Messages::folded_lines`].
+    /// Folds overlapping with the range are merged into one fold. The range is clamped to the text.
+    ///
+    /// The cursor is never placed in folded lines. Relative cursor moves such as [`CursorMove::Down`] skip folded
+    /// lines. When the cursor is moved into folded lines by other means, such as [`CursorMove::Jump`], undo, search or a
+    /// mouse click, the fold is unfolded. When the cursor is in the range, it moves to the end of the nearest line
+    /// before the range, or the head of the nearest line after the range. Folds follow the edits of the text.
+    ///
+    /// This method returns if the lines were folded or not. The range cannot be empty and all lines cannot be folded.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    a;", "    b;", "}"]);
+    ///
+    /// assert!(textarea.fold(1..3));
+    /// assert!(textarea.is_folded(2));
+    /// assert_eq!(textarea.folds(), [1..3]);
+    ///
+    /// // Folded lines are skipped
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    ///
+    /// assert!(textarea.unfold(2));
+    /// assert!(!textarea.is_folded(2));
+    /// ```
+    ///
+    /// [`Messages::folded_lines`]: crate::Messages::folded_lines
+    pub fn fold(&mut self, rows: Range<usize>) -> bool {
+        let len = self.lines.len();
+        let rows = rows.start.min(len)..rows.end.min(len);
+        let mut folds = self.folds.clone();
+        if !folds.add(rows, false) {
+            return false;
+        }
+        self.replace_folds(folds)
+    }
+
+    /// Replace the folds and move the cursor out of the folded lines. When all lines would be folded, the folds are not
+    /// replaced and `false` is returned.
+    fn replace_folds(&mut self, folds: Folds) -> bool {
+        let prev = mem::replace(&mut self.folds, folds);
+        let row = match self.unfolded_row(self.cursor.0, false) {
+            Some(row) => row,
+            None => {
+                self.folds = prev;
+                return false;
+            }
+        };
+        if row != self.cursor.0 {
+            let col = if row < self.cursor.0 {
+                self.lines[row].chars().count()
+            } else {
+                0
+            };
+            self.cancel_selection();
+            self.cursor = (row, col);
+            self.notify_cursor();
+        }
+        true
+    }
+
+    /// Unfold the fold which contains the 0-based row. This method returns if some lines were unfolded or not.
+    pub fn unfold(&mut self, row: usize) -> bool {
+        self.folds.remove_at(row)
+    }
+
+    /// Unfold all folds. The line filter is also cleared.
+    pub fn unfold_all(&mut self) {
+        self.folds.clear();
+        #[cfg(feature = "search")]
+        {
+            self.search.filter = None;
+        }
+    }
+
+    /// Get if the line at the 0-based row is folded. Lines hidden by the line filter are also folded.
+    pub fn is_folded(&self, row: usize) -> bool {
+        self.folds.at(row).is_some()
+    }
+
+    /// Get the ranges of rows of the folds sorted by their positions. The lines hidden by the line filter are not
+    /// included.
+    pub fn folds(&self) -> Vec<Range<usize>> {
+        self.folds
+            .iter()
+            .filter(|f| !f.hidden)
+            .map(|f| f.rows.clone())
+            .collect()
+    }
+
+    /// Find the fold which hides the line at the row on rendering. The fold containing the cursor is not hidden.
+    pub(crate) fn fold_at(&self, row: usize) -> Option<Fold> {
+        self.folds
+            .at(row)
+            .filter(|f| !f.rows.contains(&self.cursor.0))
+            .cloned()
+    }
+
+    /// Iterate all folds including the hidden ones.
+    pub(crate) fn fold_regions(&self) -> impl Iterator<Item = &Fold> {
+        self.folds.iter()
+    }
+
+    pub(crate) fn has_folds(&self) -> bool {
+        !self.folds.is_empty()
+    }
+
+    /// Set the style of the placeholder line of folded lines. The default style is dark gray foreground.
+    pub fn set_fold_style(&mut self, style: Style) {
+        self.fold_style = style;
+    }
+
+    /// Get the style of the placeholder line of folded lines.
+    pub fn fold_style(&self) -> Style {
+        self.fold_style
+    }
+
+    /// Show only the lines matching the regular expression, like a built-in grep view. The other lines are hidden
+    /// without placeholders. Line numbers of the shown lines are the original ones, and editing the shown lines edits
+    /// the text as usual. This method returns the number of the matched lines.
+    ///
+    /// The hidden lines are folds without placeholders, so they behave like the folds of [`TextArea::fold`]: relative
+    /// cursor moves skip them, and they are shown again when the cursor is moved into them by other means. The filter
+    /// is applied to the text at the time of calling this method. Lines edited not to match the pattern remain shown
+    /// until the filter is set again. When no line matches the pattern, no line is hidden.
+    ///
+    /// When the pattern is invalid, the filter is not updated and an error is returned.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn a() {", "    todo!()", "}", "fn b() {", "    todo!()", "}"]);
+    ///
+    /// assert_eq!(textarea.set_line_filter("todo"), Ok(2));
+    /// assert_eq!(textarea.line_filter(), Some("todo"));
+    /// assert_eq!(textarea.line_filter_matches(), [(1, 1), (4, 1)]);
+    ///
+    /// // The cursor moves over the hidden lines
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (4, 0));
+    ///
+    /// textarea.clear_line_filter();
+    /// assert!(!textarea.is_folded(0));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_line_filter(&mut self, pattern: impl AsRef<str>) -> Result<usize, regex::Error> {
+        let filter = regex::Regex::new(pattern.as_ref())?;
+        let mut folds = self.folds.clone();
+        folds.remove_if(true);
+
+        let mut matched = 0;
+        let mut hidden_start = None;
+        for (row, line) in self.lines.iter().enumerate() {
+            let is_match = filter.is_match(line);
+            matched += is_match as usize;
+            // Lines in the existing folds are not hidden to keep the folds as they are
+            if is_match || folds.at(row).is_some() {
+                if let Some(start) = hidden_start.take() {
+                    folds.add(start..row, true);
+                }
+            } else if hidden_start.is_none() {
+                hidden_start = Some(row);
+            }
+        }
+        if let Some(start) = hidden_start {
+            folds.add(start..self.lines.len(), true);
+        }
+        if matched == 0 {
+            folds.remove_if(true);
+        }
+
+        self.replace_folds(folds);
+        self.search.filter = Some(filter);
+        Ok(matched)
+    }
+
+    /// Clear the line filter set by [`TextArea::set_line_filter`] and show the hidden lines. Folds created by
+    /// [`TextArea::fold`] are kept.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn clear_line_filter(&mut self) {
+        self.folds.remove_if(true);
+        self.search.filter = None;
+    }
+
+    /// Get the pattern of the line filter set by [`TextArea::set_line_filter`].
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn line_filter(&self) -> Option<&str> {
+        self.search.filter.as_ref().map(regex::Regex::as_str)
+    }
+
+    /// Get the 0-based rows of the lines matching the line filter with the numbers of the matches in the lines. The
+    /// current text is matched, so the result reflects the edits after the filter was set. An empty vector is returned
+    /// when no filter is set.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn line_filter_matches(&self) -> Vec<(usize, usize)> {
+        let filter = match &self.search.filter {
+            Some(filter) => filter,
+            None => return vec![],
+        };
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(row, line)| {
+                let count = filter.find_iter(line).count();
+                (count > 0).then(|| (row, count))
+            })
+            .collect()
+    }
+
+    /// Start recording editing operations as a macro. Inserting and deleting text, moving the cursor, selecting, copying,
+    /// cutting, and pasting text are recorded both when they are done by key inputs with [`TextArea::input`] and when
+    /// they are done by calling the methods such as [`TextArea::insert_str`] and [`TextArea::move_cursor`]. Line
+    /// commands such as [`TextArea::move_lines_up`], [`TextArea::apply_edits`], [`TextArea::apply_patch`],
+    /// [`TextArea::format_with`], and [`TextArea::resolve_conflict`] are recorded as one operation each. Undo, redo,
+    /// scrolling, and mouse inputs are not recorded. When recording is already in progress, the recorded operations are
+    /// discarded and the recording restarts.
+    ///
+    /// Since the operations are recorded instead of key inputs, Vim-style `q` and `@` commands can be implemented with
+    /// this method, [`TextArea::stop_recording`], and [`TextArea::replay`].
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar", "baz"]);
+    ///
+    /// textarea.start_recording();
+    /// assert!(textarea.is_recording());
+    /// textarea.input(Input { key: Key::End, ..Default::default() });
+    /// textarea.input(Input { key: Key::Char(';'), ..Default::default() });
+    /// textarea.move_cursor(CursorMove::Down);
+    /// let m = textarea.stop_recording();
+    /// assert!(!textarea.is_recording());
+    ///
+    /// textarea.replay(&m, 2);
+    /// assert_eq!(textarea.lines(), ["foo;", "bar;", "baz;"]);
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.recording = Some(EditMacro::default());
+    }
+
+    /// Stop recording editing operations started by [`TextArea::start_recording`] and return the recorded macro. When no
+    /// recording is in progress, this method returns an empty macro.
+    pub fn stop_recording(&mut self) -> EditMacro {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// Get if recording editing operations by [`TextArea::start_recording`] is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Check if editing operations need to be recorded for a macro or for a replay report.
+    fn is_recording_ops(&self) -> bool {
+        #[cfg(feature = "arbitrary")]
+        if self.replay_report.is_some() {
+            return true;
+        }
+        self.recording.is_some()
+    }
+
+    /// Write a report to reproduce a bug to the file at `path` when a panic or a broken invariant of the textarea is
+    /// found while editing the text. The current text is saved as the initial text and the editing operations are
+    /// recorded from now on in the same way as [`TextArea::start_recording`]. The invariants checked by
+    /// [`EditSequence::run`] are checked after each operation, and a panic hook is installed to catch a panic on
+    /// applying an operation. When a bug is found, the operations are minimized with [`EditSequence::minimize`] and the
+    /// error and the [`EditSequence`] (the initial text and the operations) are written to the file. The report is
+    /// written only once and reporting stops after a broken invariant is reported.
+    ///
+    /// This is intended for debugging since checking the invariants after each operation makes editing slower. Settings
+    /// such as wrapping are not a part of the report. Calling this method again restarts recording with the current
+    /// text. This method is only available when `arbitrary` feature is enabled.
+    /// ```no_run
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.enable_replay_report("replay-report.txt");
+    /// assert!(textarea.is_replay_report_enabled());
+    ///
+    /// // Edit the text. When the textarea panics, the minimized operations are written to `replay-report.txt`
+    /// textarea.insert_str("world");
+    /// ```
+    ///
+    /// [`EditSequence::run`]: crate::EditSequence::run
+    /// [`EditSequence::minimize`]: crate::EditSequence::minimize
+    /// [`EditSequence`]: crate::EditSequence
+    #[cfg(feature = "arbitrary")]
+    pub fn enable_replay_report(&mut self, path: impl Into<PathBuf>) {
+        self.replay_report = Some(ReplayReport::new(path.into(), self.text()));
+    }
+
+    /// Get if the replay report enabled by [`TextArea::enable_replay_report`] is still active. It is disabled after
+    /// a report is written on a broken invariant. This method is only available when `arbitrary` feature is enabled.
+    #[cfg(feature = "arbitrary")]
+    pub fn is_replay_report_enabled(&self) -> bool {
+        self.replay_report.is_some()
+    }
+
+    /// Take the replay report while applying the operation so that nested operations are not recorded.
+    #[cfg(feature = "arbitrary")]
+    fn take_replay_report(&mut self, op: &MacroOp) -> Option<ReplayReport> {
+        let report = self.replay_report.take()?;
+        report.record(op);
+        Some(report)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn restore_replay_report(&mut self, report: Option<ReplayReport>) {
+        if let Some(report) = report {
+            if report.check(self) {
+                self.replay_report = Some(report);
+            }
+        }
+    }
+
+    /// Replay the operations recorded in the macro `count` times. All changes by the replay are grouped into one undo
+    /// step so that a single [`TextArea::undo`] call reverts them. Operations which cannot be applied, such as deleting
+    /// a character at the head of the text, are skipped and the replay continues. When a macro is replayed while
+    /// recording, its operations are added to the recording macro. This method returns if some text was modified or not
+    /// in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a b c d"]);
+    ///
+    /// textarea.start_recording();
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_char();
+    /// let m = textarea.stop_recording();
+    /// assert_eq!(textarea.lines(), ["b c d"]);
+    ///
+    /// assert!(textarea.replay(&m, 2));
+    /// assert_eq!(textarea.lines(), ["d"]);
+    ///
+    /// // The replay is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["b c d"]);
+    /// ```
+    pub fn replay(&mut self, m: &EditMacro, count: usize) -> bool {
+        if m.is_empty() || count == 0 {
+            return false;
+        }
+        let recording = self.recording.take();
+        let mut modified = false;
+        self.history.begin_group();
+        for _ in 0..count {
+            for op in m.ops() {
+                modified |= self.apply_op(op);
+            }
+        }
+        self.history.end_group();
+        if let Some(mut recording) = recording {
+            for _ in 0..count {
+                recording.extend(m);
+            }
+            self.recording = Some(recording);
+        }
+        modified
+    }
+
+    fn record_op(&mut self, op: MacroOp) -> bool {
+        // Operations called while applying the operation must not be recorded
+        let recording = self.recording.take();
+        #[cfg(feature = "arbitrary")]
+        let report = self.take_replay_report(&op);
+        let modified = self.apply_op(&op);
+        self.recording = recording;
+        #[cfg(feature = "arbitrary")]
+        self.restore_replay_report(report);
+        if let Some(recording) = &mut self.recording {
+            recording.push(op);
+        }
+        modified
+    }
+
+    /// Same as [`TextArea::record_op`] for the operations which can fail. The operation is recorded only when it
+    /// succeeded.
+    fn record_fallible<T, E>(
+        &mut self,
+        op: MacroOp,
+        f: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let recording = self.recording.take();
+        #[cfg(feature = "arbitrary")]
+        let report = self.take_replay_report(&op);
+        let result = f(self);
+        self.recording = recording;
+        #[cfg(feature = "arbitrary")]
+        self.restore_replay_report(report);
+        if let (Some(recording), Ok(_)) = (&mut self.recording, &result) {
+            recording.push(op);
+        }
+        result
+    }
+
+    fn apply_op(&mut self, op: &MacroOp) -> bool {
+        match op {
+            MacroOp::InsertChar(c) => self.insert_char_checked(*c),
+            MacroOp::InsertNewline => self.insert_newline_checked(),
+            MacroOp::InsertTab => self.insert_tab(),
+            MacroOp::InsertStr(s) => self.insert_str(s),
+            MacroOp::DeleteChar => self.delete_char(),
+            MacroOp::DeleteNextChar => self.delete_next_char(),
+            MacroOp::DeleteNewline => self.delete_newline(),
+            MacroOp::DeleteStr(chars) => self.delete_str(*chars),
+            MacroOp::DeleteLineByEnd => self.delete_line_by_end(),
+            MacroOp::DeleteLineByHead => self.delete_line_by_head(),
+            MacroOp::DeleteWord => self.delete_word(),
+            MacroOp::DeleteNextWord => self.delete_next_word(),
+            MacroOp::DeleteWholeLine => self.delete_whole_line(),
+            MacroOp::DeleteTo(m) => self.delete_to(*m),
+            MacroOp::Operator(op, m, kind) => self.apply_operator_as(*op, *m, *kind),
+            MacroOp::OperatorObject(op, obj) => self.apply_operator_to_object(*op, *obj),
+            MacroOp::Move(m, shift) => {
+                self.move_cursor_with_shift(*m, *shift);
+                self.record_visit();
+                false
+            }
+            MacroOp::StartSelection => {
+                self.start_selection();
+                false
+            }
+            MacroOp::CancelSelection => {
+                self.cancel_selection();
+                false
+            }
+            MacroOp::SelectAll => {
+                self.select_all();
+                false
+            }
+            MacroOp::Copy => {
+                self.copy();
+                false
+            }
+            MacroOp::Cut => self.cut(),
+            MacroOp::Paste => self.paste(),
+            MacroOp::YankPop => self.yank_pop(),
+            MacroOp::YankToRegister(r) => {
+                self.yank_to_register(*r);
+                false
+            }
+            MacroOp::PasteFromRegister(r) => self.paste_from_register(*r),
+            MacroOp::IndentSelection => self.indent_selection(),
+            MacroOp::DedentSelection => self.dedent_selection(),
+            MacroOp::MoveLinesUp => self.move_lines_up(),
+            MacroOp::MoveLinesDown => self.move_lines_down(),
+            MacroOp::DuplicateLines => self.duplicate_lines(),
+            MacroOp::SortLines => self.sort_lines(),
+            MacroOp::JoinLines => self.join_lines(),
+            MacroOp::ToggleComment(prefix) => self.toggle_comment(prefix),
+            MacroOp::ApplyEdits(edits) => self.apply_edits(edits).map_or(false, |r| !r.is_empty()),
+            MacroOp::ApplyPatch(hunks) => self.apply_patch(hunks).unwrap_or(false),
+            MacroOp::ResolveConflict(r) => self.resolve_conflict(*r),
+            MacroOp::UpdateLines(lines) => self.update_lines(lines.clone()),
+        }
+    }
+
+    fn move_cursor_by_key(&mut self, m: CursorMove, shift: bool) {
+        if self.is_recording_ops() {
+            self.record_op(MacroOp::Move(m, shift));
+            return;
+        }
+        self.move_cursor_with_shift(m, shift);
+    }
+
+    /// Keep the markers, the bookmarks and the folds in the text after the whole text was replaced.
+    fn clamp_positions(&mut self) {
+        self.folds.clamp(self.lines.len());
+        let lines = &self.lines;
+        let clamp = |(r, c): (usize, usize)| {
+            let r = cmp::min(r, lines.len() - 1);
+            (r, cmp::min(c, lines[r].chars().count()))
+        };
+        self.markers.map(clamp);
+        self.bookmarks.map(clamp);
+    }
+
+    /// Set the style of jump labels. The default style is red and bold.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    /// textarea.set_jump_label_style(style);
+    /// assert_eq!(textarea.jump_label_style(), style);
+    /// ```
+    pub fn set_jump_label_style(&mut self, style: Style) {
+        self.jump_label_style = style;
+    }
+
+    /// Get the style of jump labels.
+    pub fn jump_label_style(&self) -> Style {
+        self.jump_label_style
+    }
+
+    fn record_visit(&mut self) {
+        self.visits.record(self.cursor.0);
+    }
+
+    /// Set the max number of lines whose visit counts are tracked. A visit is counted each time the cursor enters a
+    /// line by inputs or [`TextArea::move_cursor`]. The counts are useful to render a heatmap of frequently visited
+    /// lines on a scrollbar or a minimap. When the number of tracked lines exceeds the max, the least visited line is
+    /// forgotten. Setting `0` disables the tracking and clears the counts. By default, the tracking is disabled.
+    ///
+    /// The counts are keyed by rows and the rows are not adjusted when lines are inserted or removed by editing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.set_max_visited_lines(100);
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::Up);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::End); // Moving in the same line is not counted
+    ///
+    /// assert_eq!(textarea.visit_count(0), 1);
+    /// assert_eq!(textarea.visit_count(1), 2);
+    /// assert_eq!(textarea.visit_counts(), [(0, 1), (1, 2)]);
+    /// ```
+    pub fn set_max_visited_lines(&mut self, max: usize) {
+        self.visits.set_max_lines(max);
+    }
+
+    /// Get the max number of lines whose visit counts are tracked. See [`TextArea::set_max_visited_lines`].
+    pub fn max_visited_lines(&self) -> usize {
+        self.visits.max_lines()
+    }
+
+    /// Get how many times the cursor entered the line at the 0-based row. See [`TextArea::set_max_visited_lines`].
+    pub fn visit_count(&self, row: usize) -> u32 {
+        self.visits.count(row)
+    }
+
+    /// Get the visit counts of all tracked lines as pairs of a 0-based row and its count, in ascending order of rows.
+    /// See [`TextArea::set_max_visited_lines`].
+    pub fn visit_counts(&self) -> Vec<(usize, u32)> {
+        self.visits.counts()
+    }
+
+    /// Clear all visit counts. The tracking is still enabled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    /// textarea.set_max_visited_lines(100);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.clear_visits();
+    /// assert_eq!(textarea.visit_counts(), []);
+    /// ```
+    pub fn clear_visits(&mut self) {
+        self.visits.clear();
+    }
+
+    /// Set the pairs of open and close brackets which are used for highlighting matching brackets and
+    /// [`CursorMove::MatchingBracket`]. The default pairs are `()`, `[]`, and `{}`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["<a>"]);
+    ///
+    /// textarea.set_bracket_pairs([('<', '>')]);
+    /// assert_eq!(textarea.bracket_pairs(), [('<', '>')]);
+    ///
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// ```
+    pub fn set_bracket_pairs(&mut self, pairs: impl IntoIterator<Item = (char, char)>) {
+        self.bracket_pairs = pairs.into_iter().collect();
+    }
+
+    /// Get the pairs of open and close brackets.
+    pub fn bracket_pairs(&self) -> &[(char, char)] {
+        &self.bracket_pairs
+    }
+
+    /// Set the punctuations which are treated as a part of word by word motions, [`TextArea::delete_word`],
+    /// [`TextArea::delete_next_word`] and the word text objects. For example, `"-"` makes `foo-bar` one word as in
+    /// CSS or Lisp. By default, all ASCII punctuations separate words. This setting does not change word completion
+    /// and [`TextArea::swap_with_next_word`], which look for identifiers, nor the WORD motions such as
+    /// [`CursorMove::BigWordForward`], which split words only at whitespaces.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo-bar baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    ///
+    /// textarea.set_word_chars("-");
+    /// assert_eq!(textarea.word_chars(), "-");
+    /// textarea.move_cursor(CursorMove::Head);
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// ```
+    pub fn set_word_chars(&mut self, chars: impl Into<String>) {
+        self.word_chars = chars.into();
+    }
+
+    /// Get the punctuations which are treated as a part of word. See [`TextArea::set_word_chars`].
+    pub fn word_chars(&self) -> &str {
+        &self.word_chars
+    }
+
+    /// Replace the text with the [`Template`] and start fill-in-the-blanks mode. While a template is set,
+    /// [`TextArea::input`] and [`TextArea::input_without_shortcuts`] only edit the text in the focused field. The text
+    /// outside the fields is read-only. Key inputs are handled as follows.
+    ///
+    /// | Key                   | Action                                  |
+    /// |-----------------------|-----------------------------------------|
+    /// | Tab, Down             | Focus the next field                    |
+    /// | Shift+Tab, Up         | Focus the previous field                |
+    /// | Left, Right           | Move cursor in the field                |
+    /// | Home, End             | Move cursor to the head/end of field    |
+    /// | Backspace, Delete     | Delete a character in the field         |
+    /// | Characters            | Insert a character to the field         |
+    /// | Mouse click on field  | Focus the clicked field                 |
+    ///
+    /// The first field is focused and the edit history is cleared. Undo and redo are disabled while a template is set.
+    /// Modifying the text with other methods such as [`TextArea::insert_str`] breaks the field positions, so they
+    /// should not be called in this mode. To get the values of the fields, use [`TextArea::field_values`].
+    /// ```
+    /// use tui_textarea::{TextArea, Template, TemplateField, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_template(
+    ///     Template::new()
+    ///         .text("host: ")
+    ///         .field(TemplateField::new("host").value("localhost"))
+    ///         .text("\nport: ")
+    ///         .field(TemplateField::new("port")),
+    /// );
+    /// assert_eq!(textarea.lines(), ["host: localhost", "port: "]);
+    /// assert_eq!(textarea.focused_field(), Some("host"));
+    ///
+    /// textarea.input(Input { key: Key::Tab, ..Default::default() });
+    /// assert_eq!(textarea.focused_field(), Some("port"));
+    /// for c in "8080".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ..Default::default() });
+    /// }
+    ///
+    /// // Read-only text cannot be deleted
+    /// textarea.input(Input { key: Key::Home, ..Default::default() });
+    /// assert!(!textarea.input(Input { key: Key::Backspace, ..Default::default() }));
+    ///
+    /// assert_eq!(textarea.lines(), ["host: localhost", "port: 8080"]);
+    /// assert_eq!(textarea.field_values(), [("host", "localhost"), ("port", "8080")]);
+    /// ```
+    pub fn set_template(&mut self, template: Template) {
+        let (lines, state) = template.build();
+        self.lines = lines;
+        self.mixed_endings.clear();
+        self.generation += 1;
+        self.template = Some(state);
+        self.snippet = None;
+        self.clamp_positions();
+        self.cancel_selection();
+        self.set_max_histories(self.history.max_items());
+        self.focus_field_at(0);
+        if self.template.as_ref().map_or(true, |t| t.fields.is_empty()) {
+            self.cursor = (0, 0);
+        }
+    }
+
+    /// Stop fill-in-the-blanks mode started by [`TextArea::set_template`]. The text is kept and all of it becomes
+    /// editable.
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, Template, TemplateField};
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_template(Template::new().text("a: ").field(TemplateField::new("a").value("1")));
     ///
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_cursor_line_style(style);
-    /// assert_eq!(textarea.cursor_line_style(), style);
-    ///
-    /// // Disable cursor line style
-    /// textarea.set_cursor_line_style(Style::default());
+    /// textarea.remove_template();
+    /// assert_eq!(textarea.lines(), ["a: 1"]);
+    /// assert_eq!(textarea.focused_field(), None);
     /// ```
-    pub fn set_cursor_line_style(&mut self, style: Style) {
-        self.cursor_line_style = style;
+    pub fn remove_template(&mut self) {
+        self.template = None;
     }
 
-    /// Get the style of cursor line. By default it is styled with underline.
-    pub fn cursor_line_style(&self) -> Style {
-        self.cursor_line_style
+    /// Focus the next field of the template. After the last field, the first field is focused. The cursor moves to the
+    /// end of the field. This method returns if some field was focused or not.
+    pub fn next_field(&mut self) -> bool {
+        let (focus, len) = match &self.template {
+            Some(t) if !t.fields.is_empty() => (t.focus, t.fields.len()),
+            _ => return false,
+        };
+        self.focus_field_at((focus + 1) % len)
     }
 
-    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
-    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
-    /// the default style.
+    /// Focus the previous field of the template. Before the first field, the last field is focused. The cursor moves
+    /// to the end of the field. This method returns if some field was focused or not.
+    pub fn prev_field(&mut self) -> bool {
+        let (focus, len) = match &self.template {
+            Some(t) if !t.fields.is_empty() => (t.focus, t.fields.len()),
+            _ => return false,
+        };
+        self.focus_field_at((focus + len - 1) % len)
+    }
+
+    fn focus_field_at(&mut self, index: usize) -> bool {
+        let t = match &mut self.template {
+            Some(t) => t,
+            None => return false,
+        };
+        let field = match t.fields.get(index) {
+            Some(f) => f,
+            None => return false,
+        };
+        t.focus = index;
+        self.cursor = (field.row, field.end());
+        self.selection_start = None;
+        true
+    }
+
+    /// Get the name of the focused field of the template.
+    pub fn focused_field(&self) -> Option<&str> {
+        let field = self.template.as_ref()?.focused()?;
+        Some(field.field.name())
+    }
+
+    /// Get the current value of the field of the template by its name.
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, Template, TemplateField};
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_template(Template::new().text("a: ").field(TemplateField::new("a").value("1")));
     ///
-    /// // Show line numbers in dark gray background
-    /// let style = Style::default().bg(Color::DarkGray);
-    /// textarea.set_line_number_style(style);
-    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// assert_eq!(textarea.field_value("a"), Some("1"));
+    /// assert_eq!(textarea.field_value("b"), None);
     /// ```
-    pub fn set_line_number_style(&mut self, style: Style) {
-        self.line_number_style = Some(style);
+    pub fn field_value(&self, name: &str) -> Option<&str> {
+        let t = self.template.as_ref()?;
+        let field = t.fields.iter().find(|f| f.field.name() == name)?;
+        Some(field.value(&self.lines))
     }
 
-    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
-    /// method, Line numbers will no longer be shown.
+    /// Get the pairs of the name and the current value of all fields of the template in order.
+    pub fn field_values(&self) -> Vec<(&str, &str)> {
+        self.template
+            .iter()
+            .flat_map(|t| t.fields.iter())
+            .map(|f| (f.field.name(), f.value(&self.lines)))
+            .collect()
+    }
+
+    /// Validate all fields of the template with their validators and return the pairs of the name and the error
+    /// message of invalid fields. When all fields are valid, the returned vector is empty. Each field is validated
+    /// independently. Invalid fields are highlighted with [`TextArea::invalid_field_style`].
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, Template, TemplateField};
     ///
+    /// let number = |s: &str| s.parse::<u32>().map(|_| ()).map_err(|e| e.to_string());
     /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
-    /// textarea.remove_line_number();
-    /// assert_eq!(textarea.line_number_style(), None);
+    /// textarea.set_template(
+    ///     Template::new()
+    ///         .field(TemplateField::new("a").value("1").validator(number))
+    ///         .text(" + ")
+    ///         .field(TemplateField::new("b").value("x").validator(number)),
+    /// );
+    ///
+    /// let errors = textarea.field_errors();
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].0, "b");
     /// ```
-    pub fn remove_line_number(&mut self) {
-        self.line_number_style = None;
+    pub fn field_errors(&self) -> Vec<(&str, String)> {
+        self.template
+            .iter()
+            .flat_map(|t| t.fields.iter())
+            .filter_map(|f| {
+                let err = f.field.validate(f.value(&self.lines)).err()?;
+                Some((f.field.name(), err))
+            })
+            .collect()
     }
 
-    /// Get the style of line number if set.
-    pub fn line_number_style(&self) -> Option<Style> {
-        self.line_number_style
+    /// Set the style of the fields of the template. The default style is dark gray background.
+    pub fn set_template_field_style(&mut self, style: Style) {
+        self.template_field_style = style;
+    }
+
+    /// Get the style of the fields of the template.
+    pub fn template_field_style(&self) -> Style {
+        self.template_field_style
+    }
+
+    /// Set the style of the fields of the template whose values are invalid. The default style is red background.
+    pub fn set_invalid_field_style(&mut self, style: Style) {
+        self.invalid_field_style = style;
+    }
+
+    /// Get the style of the fields of the template whose values are invalid.
+    pub fn invalid_field_style(&self) -> Style {
+        self.invalid_field_style
     }
 
     /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
@@ -1902,6 +7826,7 @@ impl<'a> TextArea<'a> {
     }
 
     /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// When no placeholder text is set, the placeholder of [`TextArea::messages`] is returned.
     /// ```
     /// use tui_textarea::TextArea;
     ///
@@ -1909,7 +7834,11 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.placeholder_text(), "");
     /// ```
     pub fn placeholder_text(&self) -> &'_ str {
-        self.placeholder.as_str()
+        if self.placeholder.is_empty() {
+            &self.messages.placeholder
+        } else {
+            self.placeholder.as_str()
+        }
     }
 
     /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
@@ -1924,7 +7853,7 @@ impl<'a> TextArea<'a> {
     /// assert!(textarea.placeholder_style().is_some());
     /// ```
     pub fn placeholder_style(&self) -> Option<Style> {
-        if self.placeholder.is_empty() {
+        if self.placeholder_text().is_empty() {
             None
         } else {
             Some(self.placeholder_style)
@@ -1943,96 +7872,503 @@ impl<'a> TextArea<'a> {
     /// textarea.set_mask_char('●');
     /// assert_eq!(textarea.mask_char(), Some('●'));
     /// ```
-    pub fn set_mask_char(&mut self, mask: char) {
-        self.mask = Some(mask);
+    pub fn set_mask_char(&mut self, mask: char) {
+        self.mask = Some(mask);
+    }
+
+    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.clear_mask_char();
+    /// assert_eq!(textarea.mask_char(), None);
+    /// ```
+    pub fn clear_mask_char(&mut self) {
+        self.mask = None;
+    }
+
+    /// Get the character to mask text. When no character is set, `None` is returned.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// ```
+    pub fn mask_char(&self) -> Option<char> {
+        self.mask
+    }
+
+    /// Set if text deleted or copied from the textarea is yanked while the text is masked by
+    /// [`TextArea::set_mask_char`]. Disabling this prevents a password from leaking through [`TextArea::paste`] or
+    /// [`TextArea::yank_text`]. The text set by [`TextArea::set_yank_text`] is not affected. By default, this is
+    /// enabled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["secret"]);
+    /// textarea.set_mask_char('•');
+    /// textarea.set_yank_masked(false);
+    /// assert!(!textarea.yank_masked());
+    ///
+    /// textarea.select_all();
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "");
+    ///
+    /// textarea.move_cursor(CursorMove::Head);
+    /// textarea.delete_line_by_end();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn set_yank_masked(&mut self, enabled: bool) {
+        self.yank_masked = enabled;
+    }
+
+    /// Get if text is yanked while the text is masked. See [`TextArea::set_yank_masked`].
+    pub fn yank_masked(&self) -> bool {
+        self.yank_masked
+    }
+
+    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
+    /// cursor line hides a cursor.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_cursor_style(style);
+    /// assert_eq!(textarea.cursor_style(), style);
+    /// ```
+    pub fn set_cursor_style(&mut self, style: Style) {
+        self.cursor_style = style;
+    }
+
+    /// Get the style of cursor.
+    pub fn cursor_style(&self) -> Style {
+        self.cursor_style
+    }
+
+    /// Enable or disable overwrite mode. In overwrite mode, a typed character replaces the character under the cursor
+    /// instead of being inserted before it. At the end of a line, characters are inserted as usual. Replacing a
+    /// character is one undo step which restores the replaced character. With the default key mappings, the Insert key
+    /// toggles this mode. The cursor is rendered with the style set by [`TextArea::set_overwrite_cursor_style`] while
+    /// this mode is enabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// textarea.set_overwrite(true);
+    /// assert!(textarea.overwrite());
+    /// textarea.insert_char('x');
+    /// textarea.insert_char('y');
+    /// assert_eq!(textarea.lines(), ["xyc"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["xbc"]);
+    /// ```
+    pub fn set_overwrite(&mut self, enabled: bool) {
+        self.overwrite = enabled;
+    }
+
+    /// Get if overwrite mode is enabled. See [`TextArea::set_overwrite`].
+    pub fn overwrite(&self) -> bool {
+        self.overwrite
+    }
+
+    /// Set the style of cursor in overwrite mode. The default style is black text on light yellow background so that
+    /// users can tell overwrite mode from insert mode.
+    /// ```
+    /// use ratatui::style::{Style, Modifier};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().add_modifier(Modifier::UNDERLINED);
+    /// textarea.set_overwrite_cursor_style(style);
+    /// assert_eq!(textarea.overwrite_cursor_style(), style);
+    /// ```
+    pub fn set_overwrite_cursor_style(&mut self, style: Style) {
+        self.overwrite_cursor_style = style;
+    }
+
+    /// Get the style of cursor in overwrite mode.
+    pub fn overwrite_cursor_style(&self) -> Style {
+        self.overwrite_cursor_style
+    }
+
+    pub(crate) fn current_cursor_style(&self) -> Style {
+        if self.overwrite {
+            self.overwrite_cursor_style
+        } else {
+            self.cursor_style
+        }
+    }
+
+    /// Set if the cursor and the cursor line are hidden. This is useful for a read-only viewer such as a log pane. See
+    /// also [`TextArea::set_read_only`]. The cursor still exists and moves even while it is hidden.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.cursor_hidden());
+    /// textarea.set_cursor_hidden(true);
+    /// assert!(textarea.cursor_hidden());
+    /// ```
+    pub fn set_cursor_hidden(&mut self, hidden: bool) {
+        self.cursor_hidden = hidden;
+    }
+
+    /// Get if the cursor and the cursor line are hidden.
+    pub fn cursor_hidden(&self) -> bool {
+        self.cursor_hidden
+    }
+
+    /// Show or hide the vertical and horizontal scrollbars. The vertical scrollbar occupies the rightmost column and
+    /// the horizontal scrollbar occupies the bottom row of the text area. The horizontal scrollbar is not rendered
+    /// while the text is wrapped (see [`TextArea::set_wrap`]). Both are hidden by default.
+    ///
+    /// The scrollbars follow the viewport at the last rendering. When wrapping is enabled, the vertical scrollbar counts
+    /// display rows instead of lines.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.vertical_scrollbar());
+    ///
+    /// textarea.show_scrollbar(true, false);
+    /// assert!(textarea.vertical_scrollbar());
+    /// assert!(!textarea.horizontal_scrollbar());
+    /// ```
+    #[cfg(feature = "ratatui")]
+    pub fn show_scrollbar(&mut self, vertical: bool, horizontal: bool) {
+        self.scrollbars = (vertical, horizontal);
+    }
+
+    /// Get if the vertical scrollbar is shown. See [`TextArea::show_scrollbar`].
+    #[cfg(feature = "ratatui")]
+    pub fn vertical_scrollbar(&self) -> bool {
+        self.scrollbars.0
+    }
+
+    /// Get if the horizontal scrollbar is shown. See [`TextArea::show_scrollbar`].
+    #[cfg(feature = "ratatui")]
+    pub fn horizontal_scrollbar(&self) -> bool {
+        self.scrollbars.1
+    }
+
+    /// Set the style of scrollbars. The default value is the default style.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::DarkGray);
+    /// textarea.set_scrollbar_style(style);
+    /// assert_eq!(textarea.scrollbar_style(), style);
+    /// ```
+    #[cfg(feature = "ratatui")]
+    pub fn set_scrollbar_style(&mut self, style: Style) {
+        self.scrollbar_style = style;
+    }
+
+    /// Get the style of scrollbars. See [`TextArea::set_scrollbar_style`].
+    #[cfg(feature = "ratatui")]
+    pub fn scrollbar_style(&self) -> Style {
+        self.scrollbar_style
+    }
+
+    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
+    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
+    /// file must end with a newline.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.lines(), [""]);
+    ///
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["a", ""]);
+    ///
+    /// textarea.insert_char('b');
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// ```
+    pub fn lines(&'a self) -> &'a [String] {
+        &self.lines
+    }
+
+    /// Convert [`TextArea`] instance into line texts.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// ```
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines
+    }
+
+    /// Get the text content joined with the line endings. The line endings are detected from the lines on creating the
+    /// textarea and the line ending of each line is kept. See [`TextArea::set_line_ending`] for more details.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from("hello\r\nworld\r\n".split('\n'));
+    /// assert_eq!(textarea.lines(), ["hello", "world", ""]);
+    /// assert_eq!(textarea.text(), "hello\r\nworld\r\n");
+    /// ```
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for (row, line) in self.lines.iter().enumerate() {
+            if row > 0 {
+                text.push_str(self.line_ending_after(row - 1).as_str());
+            }
+            text.push_str(line);
+        }
+        text
+    }
+
+    /// Get the line ending of the newline after the line at the row.
+    fn line_ending_after(&self, row: usize) -> LineEnding {
+        self.mixed_endings.ending(row, self.line_ending)
+    }
+
+    /// Set the line ending used to join the lines by [`TextArea::text`]. On creating a textarea, `\r` at the end of
+    /// each line except the last one is removed and the line ending used by most lines is set. It is also detected
+    /// when a text is inserted into an empty textarea with [`TextArea::insert_str`]. Lines returned from
+    /// [`TextArea::lines`] never contain line endings so a stray `\r` is not rendered. When line endings are mixed in
+    /// a text, the line ending of each line is kept and new lines use the detected one. Setting the line ending with
+    /// this method unifies the line endings of all lines. The default value is [`LineEnding::Lf`].
+    /// ```
+    /// use tui_textarea::{TextArea, LineEnding};
+    ///
+    /// let mut textarea = TextArea::from("a\r\nb\r\nc\nd".split('\n'));
+    /// assert_eq!(textarea.line_ending(), LineEnding::CrLf);
+    /// assert_eq!(textarea.lines(), ["a", "b", "c", "d"]);
+    /// assert_eq!(textarea.text(), "a\r\nb\r\nc\nd");
+    ///
+    /// textarea.set_line_ending(LineEnding::Lf);
+    /// assert_eq!(textarea.text(), "a\nb\nc\nd");
+    /// ```
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+        self.mixed_endings.clear();
+    }
+
+    /// Get the line ending used to join the lines. See [`TextArea::set_line_ending`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, LineEnding};
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.line_ending(), LineEnding::Lf);
+    /// ```
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Write the text joined with the line ending (see [`TextArea::text`]) to the file at the path. Unlike
+    /// [`std::fs::write`], the existing file is not truncated before the new content is written by default. See
+    /// [`SaveOptions`] for the details of each option. When `watch` feature is enabled and the path is the attached
+    /// file, the written content is not reported as a change by [`TextArea::poll_file`]. When
+    /// [`TextArea::set_trim_trailing_whitespace`] is enabled, trailing whitespace is deleted with
+    /// [`TextArea::delete_trailing_whitespace`] before writing.
+    /// ```no_run
+    /// use tui_textarea::{TextArea, SaveOptions};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world", ""]);
+    ///
+    /// let mut options = SaveOptions::default();
+    /// options.backup = true;
+    /// textarea.save_to_path("hello.txt", options).unwrap();
+    /// ```
+    pub fn save_to_path(&mut self, path: impl AsRef<Path>, options: SaveOptions) -> io::Result<()> {
+        let path = path.as_ref();
+        if self.trim_trailing_whitespace {
+            self.delete_trailing_whitespace();
+        }
+        let text = self.text();
+        save(path, &text, &options)?;
+        #[cfg(feature = "watch")]
+        if let Some(file) = &mut self.file {
+            if file.path() == path {
+                file.written(&text)?;
+            }
+        }
+        Ok(())
     }
 
-    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
-    /// ```
+    /// Load the file at the path into the textarea and attach it as the backing file. The text, the cursor and the edit
+    /// history are reset and the line ending is detected from the file content. After attaching the file, changes of
+    /// the file on disk can be checked with [`TextArea::poll_file`] and reloaded with
+    /// [`TextArea::reload_preserving_view`]. This method is only available when `watch` feature is enabled.
+    /// ```no_run
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.clear_mask_char();
-    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.attach_file("README.md").unwrap();
+    /// assert_eq!(textarea.attached_file().unwrap().to_str(), Some("README.md"));
     /// ```
-    pub fn clear_mask_char(&mut self) {
-        self.mask = None;
+    #[cfg(feature = "watch")]
+    pub fn attach_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let (file, content) = AttachedFile::open(path.as_ref().to_path_buf())?;
+        let mut lines: Vec<_> = content.split('\n').map(String::from).collect();
+        let (line_ending, mixed_endings) = LineEnding::strip(&mut lines);
+        self.line_ending = line_ending;
+        self.mixed_endings = mixed_endings;
+        self.lines = lines;
+        self.generation += 1;
+        self.cursor = (0, 0);
+        self.snippet = None;
+        self.clamp_positions();
+        self.cancel_selection();
+        self.set_max_histories(self.history.max_items());
+        self.file = Some(file);
+        Ok(())
     }
 
-    /// Get the character to mask text. When no character is set, `None` is returned.
-    /// ```
+    /// Get the path of the file attached by [`TextArea::attach_file`]. This method is only available when `watch`
+    /// feature is enabled.
+    #[cfg(feature = "watch")]
+    pub fn attached_file(&self) -> Option<&Path> {
+        self.file.as_ref().map(|f| f.path())
+    }
+
+    /// Stop tracking the file attached by [`TextArea::attach_file`]. The text is kept. This method is only available
+    /// when `watch` feature is enabled.
+    #[cfg(feature = "watch")]
+    pub fn detach_file(&mut self) {
+        self.file = None;
+    }
+
+    /// Check if the attached file was changed on disk since it was attached or reloaded. Only the modification time and
+    /// the size of the file are checked until they are changed, so this method is cheap enough to call on every tick
+    /// of the event loop. When a change is newly detected, [`UxEvent::FileChanged`] is emitted to the hook set by
+    /// [`TextArea::set_event_hook`] so the application can ask users to reload the file. This method returns `false`
+    /// when no file is attached. This method is only available when `watch` feature is enabled.
+    /// ```no_run
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.attach_file("notes.txt").unwrap();
     ///
-    /// assert_eq!(textarea.mask_char(), None);
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// // In the event loop
+    /// if textarea.poll_file() {
+    ///     textarea.reload_preserving_view().unwrap();
+    /// }
     /// ```
-    pub fn mask_char(&self) -> Option<char> {
-        self.mask
+    #[cfg(feature = "watch")]
+    pub fn poll_file(&mut self) -> bool {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => return false,
+        };
+        if file.poll() {
+            self.emit(UxEvent::FileChanged);
+        }
+        self.file.as_ref().map_or(false, |f| f.changed())
     }
 
-    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
-    /// cursor line hides a cursor.
+    /// Reload the attached file. Instead of replacing the whole text, only the changed lines are updated as one undo
+    /// step like [`TextArea::format_with`]. The cursor stays at the nearest position in the new text and the scroll
+    /// position is kept when possible. Selection is cancelled. This method returns if the text was modified or not.
+    /// When no file is attached, this method does nothing and returns `Ok(false)`. This method is only available when
+    /// `watch` feature is enabled.
+    #[cfg(feature = "watch")]
+    pub fn reload_preserving_view(&mut self) -> io::Result<bool> {
+        let content = match &mut self.file {
+            Some(file) => file.read()?,
+            None => return Ok(false),
+        };
+        let mut lines: Vec<_> = content.split('\n').map(String::from).collect();
+        let (line_ending, mixed_endings) = LineEnding::strip(&mut lines);
+        let modified = self.update_lines(lines);
+        self.line_ending = line_ending;
+        self.mixed_endings = mixed_endings;
+        Ok(modified)
+    }
+
+    /// Calculate the hash of the text content. This is cheaper than copying the text to detect changes, for example
+    /// to deduplicate autosaves or to check if the file was changed on disk. The hash is 64-bit FNV-1a of the text
+    /// returned from [`TextArea::text`], so the lines are joined with their line endings. Unlike [`std::hash::Hash`],
+    /// the algorithm is fixed so the value is stable across processes and can be compared with [`TextArea::hash_text`]
+    /// of the file content.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// let saved = textarea.content_hash();
+    /// assert_eq!(saved, TextArea::hash_text("hello\nworld"));
     ///
-    /// let style = Style::default().bg(Color::Red);
-    /// textarea.set_cursor_style(style);
-    /// assert_eq!(textarea.cursor_style(), style);
+    /// textarea.insert_char('!');
+    /// assert_ne!(textarea.content_hash(), saved);
     /// ```
-    pub fn set_cursor_style(&mut self, style: Style) {
-        self.cursor_style = style;
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = Fnv1a::default();
+        for (row, line) in self.lines.iter().enumerate() {
+            if row > 0 {
+                hash.write(self.line_ending_after(row - 1).as_str().as_bytes());
+            }
+            hash.write(line.as_bytes());
+        }
+        hash.0
     }
 
-    /// Get the style of cursor.
-    pub fn cursor_style(&self) -> Style {
-        self.cursor_style
+    /// Calculate the hash of the text in the same way as [`TextArea::content_hash`]. `\r\n` is not normalized.
+    pub fn hash_text(text: &str) -> u64 {
+        let mut hash = Fnv1a::default();
+        hash.write(text.as_bytes());
+        hash.0
     }
 
-    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
-    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
-    /// file must end with a newline.
+    /// Check if the text content of the two textareas are equal. Other states such as the cursor position, styles
+    /// and the undo history are not compared. This is the same as comparing [`TextArea::content`] of them with `==`.
     /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.lines(), [""]);
-    ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// textarea.insert_newline();
-    /// assert_eq!(textarea.lines(), ["a", ""]);
+    /// let mut a = TextArea::from(["hello"]);
+    /// let b = TextArea::from(["hello"]);
+    /// a.move_cursor(CursorMove::End);
+    /// assert!(a.content_eq(&b));
     ///
-    /// textarea.insert_char('b');
-    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// a.insert_char('!');
+    /// assert!(!a.content_eq(&b));
     /// ```
-    pub fn lines(&'a self) -> &'a [String] {
-        &self.lines
+    pub fn content_eq(&self, other: &TextArea<'_>) -> bool {
+        self.content() == other.content()
     }
 
-    /// Convert [`TextArea`] instance into line texts.
+    /// Get the text content as [`Content`], which implements [`PartialEq`], [`Eq`] and [`Hash`](std::hash::Hash).
+    /// `TextArea` itself does not implement them since it is ambiguous which states should be compared, so use this
+    /// method to compare the texts of textareas with `==` or to put them in a `HashSet`.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// let mut a = TextArea::from(["hello"]);
+    /// let b = TextArea::from(["hello"]);
+    /// assert_eq!(a.content(), b.content());
+    /// assert_eq!(a.content().lines(), ["hello"]);
     ///
-    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// a.insert_char('!');
+    /// assert_ne!(a.content(), b.content());
     /// ```
-    pub fn into_lines(self) -> Vec<String> {
-        self.lines
+    pub fn content(&self) -> Content<'_> {
+        Content::new(&self.lines)
     }
 
     /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
@@ -2096,6 +8432,200 @@ impl<'a> TextArea<'a> {
         })
     }
 
+    /// Describe the state of the textarea in plain text for screen readers. The description consists of the cursor
+    /// position, the range of visible lines at the last rendering, the selection, the text of the cursor line, and
+    /// whether the textarea is read-only. Each item is put on its own line. When the text is masked (see
+    /// [`TextArea::set_mask_char`]), the text of the cursor line is not revealed. The wording can be localized with
+    /// [`TextArea::set_messages`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert_eq!(
+    ///     textarea.accessible_description(),
+    ///     "Line 2 of 2, column 6\nSelected 5 characters from line 2, column 1 to line 2, column 6\nCurrent line: world",
+    /// );
+    /// ```
+    pub fn accessible_description(&self) -> String {
+        let m = &self.messages;
+        let (row, col) = self.cursor;
+        let mut desc = vec![Messages::format(
+            &m.a11y_cursor,
+            &[
+                ("row", &(row + 1)),
+                ("lines", &self.lines.len()),
+                ("col", &(col + 1)),
+            ],
+        )];
+
+        let visible = self.visible_rows();
+        if !visible.is_empty() {
+            desc.push(Messages::format(
+                &m.a11y_visible,
+                &[("start", &(visible.start + 1)), ("end", &visible.end)],
+            ));
+        }
+
+        if let Some(((sr, sc), (er, ec))) = self.selection_range() {
+            desc.push(Messages::format(
+                &m.a11y_selection,
+                &[
+                    ("len", &self.selected_len()),
+                    ("start_row", &(sr + 1)),
+                    ("start_col", &(sc + 1)),
+                    ("end_row", &(er + 1)),
+                    ("end_col", &(ec + 1)),
+                ],
+            ));
+        }
+
+        let line = &self.lines[row];
+        let text = if line.is_empty() {
+            m.a11y_blank.to_string()
+        } else if self.mask.is_some() {
+            Messages::format(&m.a11y_hidden, &[("len", &line.chars().count())])
+        } else {
+            line.clone()
+        };
+        desc.push(Messages::format(&m.a11y_line, &[("text", &text)]));
+
+        if self.read_only {
+            desc.push(m.a11y_read_only.to_string());
+        }
+        desc.join("\n")
+    }
+
+    /// Export the whole text with the current styling to a string. See [`TextArea::export_rows`] for the details.
+    /// ```
+    /// use tui_textarea::{ExportFormat, TextArea};
+    ///
+    /// let textarea = TextArea::from(["<hello>", "world"]);
+    ///
+    /// assert_eq!(textarea.export(ExportFormat::PlainWithLineNumbers), "1  <hello>\n2  world\n");
+    /// assert_eq!(
+    ///     textarea.export(ExportFormat::Html),
+    ///     "<pre class=\"tui-textarea\">&lt;hello&gt;\nworld\n</pre>\n",
+    /// );
+    /// ```
+    pub fn export(&self, format: ExportFormat) -> String {
+        self.export_rows(0..self.lines.len(), format)
+    }
+
+    /// Export the lines in the range of 0-based rows with the current styling to a string, for printing the text to a
+    /// terminal, copying it as HTML or generating reports. The range is clamped to the text.
+    ///
+    /// [`ExportFormat::Ansi`] and [`ExportFormat::Html`] export the lines as they are rendered: highlights, search
+    /// matches, whitespace glyphs, the gutter and line numbers (when [`TextArea::set_line_number_style`] is set) are
+    /// styled in the same way. The cursor, the cursor line and the selection are not exported, and folded lines are
+    /// exported as they are. [`ExportFormat::PlainWithLineNumbers`] exports the text without styles, prefixing each
+    /// line with its line number. The masked text is exported with the mask character in all formats.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{ExportFormat, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["let x = 1;", "let y = 2;"]);
+    /// textarea.add_highlight(((1, 4), (1, 5)), Style::default().fg(Color::Red), 0, "vars");
+    ///
+    /// assert_eq!(textarea.export_rows(1..2, ExportFormat::Ansi), "let \x1b[31my\x1b[0m = 2;\n");
+    /// ```
+    pub fn export_rows(&self, rows: Range<usize>, format: ExportFormat) -> String {
+        let len = self.lines.len();
+        let rows = rows.start.min(len)..rows.end.min(len);
+        let lnum_len = num_digits(len);
+
+        if format == ExportFormat::PlainWithLineNumbers {
+            let mut out = String::new();
+            for row in rows {
+                let line = &self.lines[row];
+                let text = match self.mask {
+                    Some(c) => line.chars().map(|_| c).collect(),
+                    None => line.clone(),
+                };
+                out.push_str(&format!(
+                    "{:>width$}  {}\n",
+                    row + 1,
+                    text,
+                    width = lnum_len as usize,
+                ));
+            }
+            return out;
+        }
+
+        // Render the lines without the visual cues of the cursor and the selection
+        let mut textarea = self.clone();
+        textarea.cursor_hidden = true;
+        textarea.selection_start = None;
+        textarea.block_selection = None;
+        let settings = textarea.render_settings_hash();
+        let lines: Vec<_> = rows
+            .map(|row| textarea.render_line(&textarea.lines[row], row, lnum_len, None, settings))
+            .collect();
+        styled_text(&lines, format)
+    }
+
+    /// Render the whole text as plain lines for terminals and pipes which cannot address the cursor, such as screen
+    /// readers and braille displays. Each line is prefixed with its 1-based line number padded to the width of the
+    /// last line number and `: `, and the cursor line is marked with `>` instead of styling it. Folded lines are
+    /// rendered as one placeholder line and end-of-line annotations follow the text. When the text is masked (see
+    /// [`TextArea::set_mask_char`]), every character is replaced with the mask character.
+    ///
+    /// Unlike rendering the textarea as a widget, this method does not depend on the viewport so the output can be
+    /// written to a scrolling terminal as-is.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {", "}"]);
+    /// textarea.move_cursor(CursorMove::Down);
+    ///
+    /// assert_eq!(textarea.render_linear(), "  1: fn main() {\n> 2: }\n");
+    /// ```
+    pub fn render_linear(&self) -> String {
+        let width = num_digits(self.lines.len()) as usize;
+        let mut out = String::new();
+        let mut row = 0;
+        while row < self.lines.len() {
+            let marker = if row == self.cursor.0 { '>' } else { ' ' };
+            if let Some(Fold { rows: fold, hidden }) = self.fold_at(row) {
+                if !hidden {
+                    let text =
+                        Messages::format(&self.messages.folded_lines, &[("lines", &fold.len())]);
+                    out.push_str(&format!(
+                        "{} {:>width$}: {}\n",
+                        marker,
+                        row + 1,
+                        text,
+                        width = width
+                    ));
+                }
+                row = fold.end;
+                continue;
+            }
+            let line = &self.lines[row];
+            let text = match self.mask {
+                Some(c) => line.chars().map(|_| c).collect(),
+                None => line.clone(),
+            };
+            out.push_str(&format!(
+                "{} {:>width$}: {}",
+                marker,
+                row + 1,
+                text,
+                width = width
+            ));
+            if let Some(annotation) = self.eol_annotation(row) {
+                out.push(' ');
+                out.push_str(&annotation.content);
+            }
+            out.push('\n');
+            row += 1;
+        }
+        out
+    }
+
     /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
     /// disabled because those alignments don't work well with line numbers.
     /// ```
@@ -2141,45 +8671,382 @@ impl<'a> TextArea<'a> {
         self.lines == [""]
     }
 
-    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
-    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
-    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
-    /// always joined with `\n`.
+    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
+    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
+    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
+    /// always joined with `\n`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.yank_text(), "abc");
+    ///
+    /// // Multiple lines are joined with \n
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.delete_str(5);
+    /// assert_eq!(textarea.yank_text(), "abc\nd");
+    /// ```
+    pub fn yank_text(&self) -> String {
+        self.yank.to_string()
+    }
+
+    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
+    /// but `\r` isn't.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_yank_text("hello\nworld");
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// ```
+    pub fn set_yank_text(&mut self, text: impl Into<String>) {
+        // `str::lines` is not available since it strips a newline at end
+        let lines: Vec<_> = text
+            .into()
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        let yank = lines.into();
+        self.push_yank_history(&yank);
+        self.yank = yank;
+    }
+
+    /// Get the history of yanked texts. The newest text comes first. Every text yanked by [`TextArea::copy`],
+    /// [`TextArea::cut`], deletions, or set by [`TextArea::set_yank_text`] is recorded, and yanking the same text again
+    /// moves it to the front. When multiple lines were yanked, they are joined with `\n`. This is useful to build a
+    /// "clipboard history" popup. See also [`TextArea::paste_yank_history`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.yank_history(), [" def", "abc"]);
+    /// ```
+    pub fn yank_history(&self) -> Vec<String> {
+        self.yank_history.iter().map(|y| y.to_string()).collect()
+    }
+
+    /// Get one-line previews of the yank history for showing them in a narrow list. Each preview fits in `max_width`
+    /// display width. Newlines are shown as `↵`, tabs are shown as spaces, and a truncated preview ends with `…`. The
+    /// order is the same as [`TextArea::yank_history`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_yank_text("hello\nworld");
+    /// textarea.set_yank_text("hi");
+    /// assert_eq!(textarea.yank_history_previews(8), ["hi", "hello↵w…"]);
+    /// ```
+    pub fn yank_history_previews(&self, max_width: usize) -> Vec<String> {
+        self.yank_history
+            .iter()
+            .map(|y| y.preview(max_width))
+            .collect()
+    }
+
+    /// Paste the text at the index of the yank history (see [`TextArea::yank_history`]). The text becomes the current
+    /// yanked text and is moved to the front of the history. This method returns if the text was modified. When the
+    /// index is out of range, nothing happens and `false` is returned.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_yank_text("foo");
+    /// textarea.set_yank_text("bar");
+    ///
+    /// assert!(textarea.paste_yank_history(1));
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// assert_eq!(textarea.yank_text(), "foo");
+    /// assert_eq!(textarea.yank_history(), ["foo", "bar"]);
+    /// ```
+    pub fn paste_yank_history(&mut self, index: usize) -> bool {
+        let yank = match self.yank_history.remove(index) {
+            Some(yank) => yank,
+            None => return false,
+        };
+        self.yank_history.push_front(yank.clone());
+        self.yank = yank;
+        self.paste()
+    }
+
+    /// Set how many yanked texts are remembered in the yank history. Setting 0 disables the history. The default value
+    /// is 10.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_max_yank_history(2);
+    /// for s in ["a", "b", "c"] {
+    ///     textarea.set_yank_text(s);
+    /// }
+    /// assert_eq!(textarea.yank_history(), ["c", "b"]);
+    /// ```
+    pub fn set_max_yank_history(&mut self, max: usize) {
+        self.max_yank_history = max;
+        self.yank_history.truncate(max);
+    }
+
+    /// Get how many yanked texts are remembered in the yank history. See [`TextArea::set_max_yank_history`].
+    pub fn max_yank_history(&self) -> usize {
+        self.max_yank_history
+    }
+
+    /// Set the table of user-facing strings to localize them. See [`Messages`] for the strings.
+    /// ```
+    /// use tui_textarea::{Messages, TextArea};
+    ///
+    /// let mut messages = Messages::default();
+    /// messages.placeholder = "Tapez votre message".into();
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_messages(messages);
+    /// assert_eq!(textarea.placeholder_text(), "Tapez votre message");
+    /// ```
+    pub fn set_messages(&mut self, messages: Messages) {
+        self.messages = messages;
+    }
+
+    /// Get the table of user-facing strings. See [`TextArea::set_messages`].
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
+
+    /// Set a callback to receive UX events such as failed cursor movements, reaching the edge of the text, or wrapped
+    /// text search. The host application can ring a bell, flash the block border, or show a transient message on the
+    /// events. See [`UxEvent`] for all events. Setting a new callback replaces the previous one.
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use tui_textarea::{TextArea, CursorMove, UxEvent};
+    ///
+    /// let events = Arc::new(Mutex::new(vec![]));
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// let e = events.clone();
+    /// textarea.set_event_hook(move |event| e.lock().unwrap().push(event));
+    ///
+    /// textarea.move_cursor(CursorMove::Up);
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(*events.lock().unwrap(), [UxEvent::BufferEdge, UxEvent::MotionFailed]);
+    /// ```
+    pub fn set_event_hook(&mut self, hook: impl Fn(UxEvent) + Send + Sync + 'static) {
+        self.event_hook = Some(EventHook(Arc::new(hook)));
+    }
+
+    /// Remove the callback set by [`TextArea::set_event_hook`].
+    pub fn clear_event_hook(&mut self) {
+        self.event_hook = None;
+    }
+
+    fn emit(&self, event: UxEvent) {
+        if let Some(hook) = &self.event_hook {
+            (hook.0)(event);
+        }
+    }
+
+    /// Set a callback to observe changes of the textarea. Inserted and deleted text, cursor movements and selection
+    /// changes are reported as [`ChangeEvent`] right after they happen, including the changes by undo and redo. It is
+    /// useful to maintain a dirty flag, trigger autosave, or send incremental updates to a language server without
+    /// comparing the whole text on every frame. Setting a new callback replaces the previous one.
+    ///
+    /// Note that the text replaced at once by [`TextArea::set_template`] is not reported.
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use tui_textarea::{TextArea, ChangeEvent};
+    ///
+    /// let changes = Arc::new(Mutex::new(vec![]));
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let c = changes.clone();
+    /// textarea.set_change_hook(move |change| c.lock().unwrap().push(change.clone()));
+    ///
+    /// textarea.insert_str("hi");
+    /// assert_eq!(
+    ///     *changes.lock().unwrap(),
+    ///     [
+    ///         ChangeEvent::Inserted { start: (0, 0), end: (0, 2), text: "hi".to_string() },
+    ///         ChangeEvent::CursorMoved { from: (0, 0), to: (0, 2) },
+    ///     ],
+    /// );
+    /// ```
+    pub fn set_change_hook(&mut self, hook: impl Fn(&ChangeEvent) + Send + Sync + 'static) {
+        self.change_hook = Some(ChangeHook(Arc::new(hook)));
+        self.notified_cursor = self.cursor;
+        self.notified_selection = self.selection_range();
+    }
+
+    /// Remove the callback set by [`TextArea::set_change_hook`].
+    pub fn clear_change_hook(&mut self) {
+        self.change_hook = None;
+    }
+
+    /// Set a callback called before rendering each line. The callback receives the 0-based row and the text of the
+    /// line. When it returns `Some`, the returned line is rendered instead of the line rendered by the textarea. This is
+    /// useful to render some lines with your own logic such as a separator or a truncated long line. Setting a new
+    /// callback replaces the previous one.
+    ///
+    /// Note that the returned line is not taken into account on wrapping lines, placing the cursor and handling mouse
+    /// events, so it should keep the width of the original text.
+    /// ```
+    /// use ratatui::text::Line;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "---", "def"]);
+    /// textarea.set_before_line_hook(|_, line| (line == "---").then(|| Line::from("───")));
+    /// ```
+    pub fn set_before_line_hook(
+        &mut self,
+        hook: impl Fn(usize, &str) -> Option<Line<'static>> + Send + Sync + 'static,
+    ) {
+        self.before_line_hook = Some(BeforeLineHook(Arc::new(hook)));
+    }
+
+    /// Remove the callback set by [`TextArea::set_before_line_hook`].
+    pub fn clear_before_line_hook(&mut self) {
+        self.before_line_hook = None;
+    }
+
+    /// Set a callback called after rendering each line. The callback receives the 0-based row and the rendered line
+    /// including the line number and the gutter, and can modify its spans. This is useful to post-process the rendered
+    /// lines such as appending badges. Lines returned from the callback set by [`TextArea::set_before_line_hook`] are
+    /// also passed. Setting a new callback replaces the previous one.
+    ///
+    /// Like [`TextArea::set_before_line_hook`], the modified spans are not taken into account on wrapping lines,
+    /// placing the cursor and handling mouse events.
+    /// ```
+    /// use ratatui::text::Span;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {}"]);
+    /// textarea.set_after_line_hook(|row, line| {
+    ///     if row == 0 {
+    ///         line.spans.push(Span::raw(" [entry]"));
+    ///     }
+    /// });
+    /// ```
+    pub fn set_after_line_hook(
+        &mut self,
+        hook: impl Fn(usize, &mut Line<'_>) + Send + Sync + 'static,
+    ) {
+        self.after_line_hook = Some(AfterLineHook(Arc::new(hook)));
+    }
+
+    /// Remove the callback set by [`TextArea::set_after_line_hook`].
+    pub fn clear_after_line_hook(&mut self) {
+        self.after_line_hook = None;
+    }
+
+    fn notify_change(&self, change: &ChangeEvent) {
+        if let Some(hook) = &self.change_hook {
+            (hook.0)(change);
+        }
+    }
+
+    /// Report the cursor and the selection to the change hook when they were changed since the last report.
+    fn notify_cursor(&mut self) {
+        let hook = match &self.change_hook {
+            Some(hook) => hook.clone(),
+            None => return,
+        };
+        if self.notified_cursor != self.cursor {
+            (hook.0)(&ChangeEvent::CursorMoved {
+                from: self.notified_cursor,
+                to: self.cursor,
+            });
+            self.notified_cursor = self.cursor;
+        }
+        let selection = self.selection_range();
+        if self.notified_selection != selection {
+            (hook.0)(&ChangeEvent::SelectionChanged(selection));
+            self.notified_selection = selection;
+        }
+    }
+
+    /// Set if the texts deleted by consecutive kill operations are joined into one yanked text, like consecutive `C-k`
+    /// of Emacs. The kill operations are [`TextArea::delete_line_by_end`], [`TextArea::delete_line_by_head`],
+    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`] and [`TextArea::delete_whole_line`]. Texts deleted
+    /// forward are appended and texts deleted backward are prepended. Moving the cursor or other edits between kills
+    /// stop the accumulation. While this is enabled, a newline deleted by a kill operation is also yanked. By default,
+    /// the accumulation is disabled and each kill replaces the yanked text.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc"]);
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.yank_text(), "abc");
+    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    /// textarea.set_kill_accumulation(true);
     ///
-    /// // Multiple lines are joined with \n
-    /// let mut textarea = TextArea::from(["abc", "def"]);
-    /// textarea.delete_str(5);
-    /// assert_eq!(textarea.yank_text(), "abc\nd");
+    /// // Kill two lines with `C-k` four times
+    /// for _ in 0..4 {
+    ///     textarea.delete_line_by_end();
+    /// }
+    /// assert_eq!(textarea.lines(), ["ccc"]);
+    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\n");
+    /// assert_eq!(textarea.yank_history(), ["aaa\nbbb\n"]);
     /// ```
-    pub fn yank_text(&self) -> String {
-        self.yank.to_string()
+    pub fn set_kill_accumulation(&mut self, enabled: bool) {
+        self.kill_accumulation = enabled;
     }
 
-    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
-    /// but `\r` isn't.
+    /// Get if the kill accumulation is enabled. See [`TextArea::set_kill_accumulation`].
+    pub fn kill_accumulation(&self) -> bool {
+        self.kill_accumulation
+    }
+
+    /// Clear the yank history. The current yanked text is not cleared.
+    pub fn clear_yank_history(&mut self) {
+        self.yank_history.clear();
+    }
+
+    /// Set the text of the register named `register`. Setting an empty string clears the register. `\n` and `\r\n`
+    /// are recognized as newline but `\r` isn't. See [`TextArea::yank_to_register`] for registers.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_register_text('a', "hello");
+    /// assert_eq!(textarea.register_text('a').as_deref(), Some("hello"));
     ///
-    /// textarea.set_yank_text("hello\nworld");
-    /// textarea.paste();
-    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// textarea.set_register_text('a', "");
+    /// assert_eq!(textarea.register_text('a'), None);
     /// ```
-    pub fn set_yank_text(&mut self, text: impl Into<String>) {
-        // `str::lines` is not available since it strips a newline at end
+    pub fn set_register_text(&mut self, register: char, text: impl Into<String>) {
+        let text = text.into();
+        if text.is_empty() {
+            self.registers.remove(&register);
+            return;
+        }
         let lines: Vec<_> = text
-            .into()
             .split('\n')
             .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
             .collect();
-        self.yank = lines.into();
+        self.registers.insert(register, lines.into());
+    }
+
+    /// Get the text of the register named `register`. `None` is returned when the register is empty. When multiple
+    /// lines are stored, they are joined with `\n`.
+    pub fn register_text(&self, register: char) -> Option<String> {
+        self.registers.get(&register).map(|y| y.to_string())
+    }
+
+    /// Get the names of the registers which have text in ascending order. This is useful to show the list of
+    /// registers.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_register_text('b', "foo");
+    /// textarea.set_register_text('a', "bar");
+    /// assert_eq!(textarea.register_names(), ['a', 'b']);
+    /// ```
+    pub fn register_names(&self) -> Vec<char> {
+        self.registers.keys().copied().collect()
+    }
+
+    /// Clear the texts of all registers.
+    pub fn clear_registers(&mut self) {
+        self.registers.clear();
     }
 
     /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
@@ -2277,9 +9144,13 @@ impl<'a> TextArea<'a> {
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
     pub fn search_forward(&mut self, match_cursor: bool) -> bool {
         if let Some(cursor) = self.search.forward(&self.lines, self.cursor, match_cursor) {
+            if cursor < self.cursor || (cursor == self.cursor && !match_cursor) {
+                self.emit(UxEvent::SearchWrapped);
+            }
             self.cursor = cursor;
             true
         } else {
+            self.emit(UxEvent::SearchFailed);
             false
         }
     }
@@ -2321,9 +9192,13 @@ impl<'a> TextArea<'a> {
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
     pub fn search_back(&mut self, match_cursor: bool) -> bool {
         if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
+            if cursor > self.cursor || (cursor == self.cursor && !match_cursor) {
+                self.emit(UxEvent::SearchWrapped);
+            }
             self.cursor = cursor;
             true
         } else {
+            self.emit(UxEvent::SearchFailed);
             false
         }
     }
@@ -2363,6 +9238,187 @@ impl<'a> TextArea<'a> {
         self.search.style = style;
     }
 
+    /// Set the replacement text for the matches of text search. `$1` and `${name}` in the text are replaced with the
+    /// capture groups of the match as [`regex::Captures::expand`] does. While the replacement is set and a match
+    /// starts at the cursor, the replacement text is previewed after the match with
+    /// [`TextArea::replace_preview_style`] so that the substitution can be confirmed before applying it with
+    /// [`TextArea::replace_search_match`]. Passing `None` clears the replacement.
+    ///
+    /// The preview is not a part of the text. It is not shown while the text is masked.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo(1)"]);
+    ///
+    /// textarea.set_search_pattern(r"foo\((\d)\)").unwrap();
+    /// textarea.set_search_replacement(Some("bar($1, 0)"));
+    /// assert_eq!(textarea.search_replacement(), Some("bar($1, 0)"));
+    ///
+    /// assert!(textarea.replace_search_match());
+    /// assert_eq!(textarea.lines(), ["bar(1, 0)"]);
+    ///
+    /// textarea.set_search_replacement(None);
+    /// assert_eq!(textarea.search_replacement(), None);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_replacement(&mut self, replacement: Option<&str>) {
+        self.search.replacement = replacement.map(str::to_string);
+    }
+
+    /// Get the replacement text set by [`TextArea::set_search_replacement`].
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_replacement(&self) -> Option<&str> {
+        self.search.replacement.as_deref()
+    }
+
+    /// Get the text style of the replacement preview shown after the match at the cursor. The default style is dimmed.
+    /// ```
+    /// use ratatui::style::{Style, Modifier};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.replace_preview_style(), Style::default().add_modifier(Modifier::DIM));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_preview_style(&self) -> Style {
+        self.search.preview_style
+    }
+
+    /// Set the text style of the replacement preview shown after the match at the cursor. See
+    /// [`TextArea::set_search_replacement`].
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let gray = Style::default().fg(Color::DarkGray);
+    /// textarea.set_replace_preview_style(gray);
+    /// assert_eq!(textarea.replace_preview_style(), gray);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_replace_preview_style(&mut self, style: Style) {
+        self.search.preview_style = style;
+    }
+
+    /// Replace the match of text search starting at the cursor with the replacement text set by
+    /// [`TextArea::set_search_replacement`]. The cursor moves to the end of the replaced text. This method returns
+    /// `false` when no match starts at the cursor or no replacement is set. The replacement is one undo step.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello, world"]);
+    ///
+    /// textarea.set_search_pattern("world").unwrap();
+    /// textarea.set_search_replacement(Some("rust"));
+    ///
+    /// // No match at the cursor
+    /// assert!(!textarea.replace_search_match());
+    ///
+    /// textarea.search_forward(false);
+    /// assert!(textarea.replace_search_match());
+    /// assert_eq!(textarea.lines(), ["hello, rust"]);
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_search_match(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let offset = self.line_offset(row, col);
+        let (start, end, text) = match self.search.replacement_at(line, offset) {
+            Some(r) => r,
+            None => return false,
+        };
+        let end_col = col + line[start..end].chars().count();
+        self.history.begin_group();
+        self.replace_range((row, col), (row, end_col), &text);
+        self.history.end_group();
+        true
+    }
+
+    /// Replace all matches of text search with the replacement text set by [`TextArea::set_search_replacement`] as
+    /// one undo step. This method returns the number of the replaced matches. The cursor stays at the same position
+    /// as far as possible.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a-1", "b-2", "c"]);
+    ///
+    /// textarea.set_search_pattern(r"(\w)-(\d)").unwrap();
+    /// textarea.set_search_replacement(Some("$2-$1"));
+    ///
+    /// assert_eq!(textarea.replace_all_search_matches(), 2);
+    /// assert_eq!(textarea.lines(), ["1-a", "2-b", "c"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["a-1", "b-2", "c"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_all_search_matches(&mut self) -> usize {
+        let cursor = self.cursor;
+        let mut count = 0;
+        self.history.begin_group();
+        // Replace from the bottom so that rows and offsets of the remaining matches are not shifted
+        for row in (0..self.lines.len()).rev() {
+            for (start, end, text) in self.search.replacements(&self.lines[row]).into_iter().rev() {
+                let line = &self.lines[row];
+                let start_col = line[..start].chars().count();
+                let end_col = start_col + line[start..end].chars().count();
+                self.replace_range((row, start_col), (row, end_col), &text);
+                count += 1;
+            }
+        }
+        self.history.end_group();
+        self.cursor = self.clamp_cursor(cursor);
+        count
+    }
+
+    /// Search the pattern from the `from` position and select the text of the found match. The cursor moves to the
+    /// start of the match. The `from` position is clamped to the text.
+    #[cfg(feature = "search")]
+    pub(crate) fn select_search_match(
+        &mut self,
+        from: (usize, usize),
+        match_cursor: bool,
+        backward: bool,
+    ) -> Option<(usize, usize)> {
+        let from = self.clamp_cursor(from);
+        let (row, col) = if backward {
+            self.search.back(&self.lines, from, match_cursor)?
+        } else {
+            self.search.forward(&self.lines, from, match_cursor)?
+        };
+
+        let line = &self.lines[row];
+        let start = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let len = self
+            .search
+            .pat
+            .as_ref()
+            .and_then(|pat| pat.find_at(line, start))
+            .map(|m| line[m.start()..m.end()].chars().count())
+            .unwrap_or(0);
+
+        self.cancel_selection();
+        if len > 0 {
+            self.cursor = (row, col + len);
+            self.start_selection();
+        }
+        self.cursor = (row, col);
+        Some((row, col))
+    }
+
     /// Scroll the textarea. See [`Scrolling`] for the argument.
     /// The cursor will not move until it goes out the viewport. When the cursor position is outside the viewport after scroll,
     /// the cursor position will be adjusted to stay in the viewport using the same logic as [`CursorMove::InViewport`].
@@ -2417,6 +9473,22 @@ impl<'a> TextArea<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ratatui::buffer::Buffer;
+
+    fn render_buffer(t: &TextArea, r: Rect) -> Buffer {
+        let mut b = Buffer::empty(r);
+        t.render(r, &mut b);
+        b
+    }
+
+    #[cfg(feature = "ratatui")]
+    fn render_rows(t: &TextArea, w: u16, h: u16) -> Vec<String> {
+        render_buffer(t, Rect::new(0, 0, w, h))
+            .content
+            .chunks(w as usize)
+            .map(|row| row.iter().map(|c| c.symbol()).collect())
+            .collect()
+    }
 
     // Separate tests for tui-rs support
     #[test]
@@ -2442,4 +9514,703 @@ mod tests {
         textarea.scroll((-5, 0));
         assert_eq!(textarea.cursor(), (12, 0));
     }
+
+    #[test]
+    fn mouse_click_and_drag() {
+        use crate::ratatui::widgets::Borders;
+
+        let mouse = |key| Input {
+            key,
+            ..Default::default()
+        };
+
+        let mut textarea = TextArea::from(["abc", "\tdef", "あいう"]);
+        textarea.set_block(Block::default().borders(Borders::ALL));
+        let r = Rect {
+            x: 2,
+            y: 1,
+            width: 12,
+            height: 6,
+        };
+        render_buffer(&textarea, r);
+
+        // Text area starts at (3, 2) inside the borders
+        textarea.input(mouse(Key::MouseClick(4, 2)));
+        assert_eq!(textarea.cursor(), (0, 1));
+        textarea.input(mouse(Key::MouseClick(10, 2)));
+        assert_eq!(textarea.cursor(), (0, 3));
+        textarea.input(mouse(Key::MouseClick(8, 3))); // On 'e' after the tab
+        assert_eq!(textarea.cursor(), (1, 2));
+        textarea.input(mouse(Key::MouseClick(6, 4))); // On the right half of 'い'
+        assert_eq!(textarea.cursor(), (2, 1));
+
+        // Clicking on the border or outside does nothing
+        textarea.input(mouse(Key::MouseClick(2, 1)));
+        assert_eq!(textarea.cursor(), (2, 1));
+
+        textarea.input(mouse(Key::MouseClick(3, 2)));
+        textarea.input(mouse(Key::MouseDrag(7, 3)));
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 1))));
+        // Dragging outside the area extends the selection to the edge
+        textarea.input(mouse(Key::MouseDrag(0, 20)));
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 0))));
+
+        // Clicking cancels the selection
+        textarea.input(mouse(Key::MouseClick(3, 2)));
+        assert!(!textarea.is_selecting());
+    }
+
+    #[test]
+    fn mouse_click_wrapped_lines() {
+        let mut textarea = TextArea::from(["aaa bbb ccc", "ddd"]);
+        textarea.set_wrap(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 4,
+        };
+        render_buffer(&textarea, r);
+
+        // "aaa bbb " / "ccc" / "ddd"
+        let click = |t: &mut TextArea, x, y| {
+            t.input(Input {
+                key: Key::MouseClick(x, y),
+                ..Default::default()
+            });
+            t.cursor()
+        };
+        assert_eq!(click(&mut textarea, 5, 0), (0, 5));
+        assert_eq!(click(&mut textarea, 1, 1), (0, 9));
+        assert_eq!(click(&mut textarea, 7, 1), (0, 11));
+        assert_eq!(click(&mut textarea, 2, 2), (1, 2));
+        assert_eq!(click(&mut textarea, 2, 3), (1, 3));
+    }
+
+    #[test]
+    fn matched_bracket_highlight() {
+        let bracket = Style::default().bg(Color::Yellow);
+        let render = |t: &TextArea| {
+            let r = Rect {
+                x: 0,
+                y: 0,
+                width: 6,
+                height: 2,
+            };
+            let highlighted: Vec<_> = render_buffer(t, r)
+                .content
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.bg == Color::Yellow)
+                .map(|(i, _)| (i as u16 % r.width, i as u16 / r.width))
+                .collect();
+            highlighted
+        };
+
+        let mut textarea = TextArea::from(["(a[b])", "x"]);
+        textarea.set_cursor_line_style(Style::default());
+        assert_eq!(render(&textarea), []);
+
+        textarea.set_matched_bracket_style(bracket);
+        // The bracket under the cursor is styled with the cursor style
+        assert_eq!(render(&textarea), [(5, 0)]);
+
+        textarea.move_cursor(CursorMove::Jump(0, 4));
+        assert_eq!(render(&textarea), [(2, 0)]);
+
+        // Not on a bracket
+        textarea.move_cursor(CursorMove::Jump(0, 1));
+        assert_eq!(render(&textarea), []);
+
+        textarea.remove_matched_bracket_style();
+        textarea.move_cursor(CursorMove::Jump(0, 0));
+        assert_eq!(render(&textarea), []);
+    }
+
+    #[test]
+    fn template_field_highlight() {
+        use crate::template::{Template, TemplateField};
+
+        let mut textarea = TextArea::default();
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_template_field_style(Style::default().bg(Color::Green));
+        textarea.set_invalid_field_style(Style::default().bg(Color::Red));
+        textarea.set_template(
+            Template::new()
+                .text("a:")
+                .field(TemplateField::new("a").value("xy"))
+                .text(" b:")
+                .field(
+                    TemplateField::new("b")
+                        .value("z")
+                        .validator(|_| Err("!".into())),
+                ),
+        );
+        textarea.input(Input {
+            key: Key::Tab,
+            ..Default::default()
+        });
+
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 1,
+        };
+        let b = render_buffer(&textarea, r);
+        let bg: Vec<_> = b.content[..r.width as usize].iter().map(|c| c.bg).collect();
+        #[rustfmt::skip]
+        assert_eq!(
+            bg,
+            [
+                Color::Reset, Color::Reset, Color::Green, Color::Green, Color::Reset,
+                Color::Reset, Color::Reset, Color::Red, Color::Reset, Color::Reset,
+            ],
+        );
+    }
+
+    #[test]
+    fn reserved_rows() {
+        let render = |t: &TextArea| {
+            render_buffer(t, Rect::new(0, 0, 3, 4));
+            t.viewport.scroll_top().0
+        };
+
+        let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+        textarea.reserve_rows_after(0, 2);
+        assert_eq!(render(&textarea), 0);
+        assert_eq!(textarea.reserved_areas(), [(0, Rect::new(0, 1, 3, 2))]);
+
+        // Clicking the reserved rows does not move the cursor
+        textarea.input(Input {
+            key: Key::MouseClick(0, 2),
+            ..Default::default()
+        });
+        assert_eq!(textarea.cursor(), (0, 0));
+        textarea.input(Input {
+            key: Key::MouseClick(0, 3),
+            ..Default::default()
+        });
+        assert_eq!(textarea.cursor(), (1, 0));
+
+        // Scrolling takes the reserved rows into account
+        textarea.move_cursor(CursorMove::Jump(2, 0));
+        assert_eq!(render(&textarea), 1);
+        assert_eq!(textarea.reserved_areas(), []);
+
+        // Reserved rows are clipped at the bottom of the textarea
+        textarea.reserve_rows_after(2, 3);
+        assert_eq!(render(&textarea), 1);
+        assert_eq!(textarea.reserved_areas(), [(2, Rect::new(0, 2, 3, 2))]);
+
+        // The reserved rows before the cursor line push it down
+        textarea.move_cursor(CursorMove::Jump(3, 0));
+        assert_eq!(render(&textarea), 3);
+        assert_eq!(textarea.reserved_areas(), []);
+
+        textarea.clear_reserved_rows();
+        textarea.set_wrap(true);
+        textarea.reserve_rows_after(1, 2);
+        textarea.move_cursor(CursorMove::Top);
+        assert_eq!(render(&textarea), 0);
+        textarea.move_cursor(CursorMove::Jump(2, 0));
+        assert_eq!(render(&textarea), 1);
+        assert_eq!(textarea.reserved_areas(), [(1, Rect::new(0, 1, 3, 2))]);
+    }
+
+    #[test]
+    fn hidden_cursor() {
+        let render = |t: &TextArea| {
+            render_buffer(t, Rect::new(0, 0, 4, 1))
+                .content
+                .iter()
+                .map(|c| c.modifier)
+                .collect::<Vec<_>>()
+        };
+
+        let mut textarea = TextArea::from(["ab"]);
+        assert_ne!(render(&textarea), [Modifier::empty(); 4]);
+        textarea.set_cursor_hidden(true);
+        assert_eq!(render(&textarea), [Modifier::empty(); 4]);
+    }
+
+    #[test]
+    fn conceal_cursor_line_and_click() {
+        let mut textarea = TextArea::from(["**a** b", "**a** b"]);
+        textarea.add_conceal_rule("**", "");
+        assert_eq!(textarea.conceals(0, "**a** b"), []);
+        assert_eq!(textarea.conceals(1, "**a** b"), [(0, 2, ""), (3, 5, "")]);
+
+        textarea.set_mask_char('*');
+        assert_eq!(textarea.conceals(1, "**a** b"), []);
+        textarea.clear_mask_char();
+
+        let r = Rect::new(0, 0, 10, 2);
+        render_buffer(&textarea, r);
+
+        // The second line is displayed as "a b"
+        textarea.input(Input {
+            key: Key::MouseClick(2, 1),
+            ..Default::default()
+        });
+        assert_eq!(textarea.cursor(), (1, 6));
+
+        // The first line is now concealed since the cursor moved
+        textarea.input(Input {
+            key: Key::MouseClick(1, 0),
+            ..Default::default()
+        });
+        assert_eq!(textarea.cursor(), (0, 5));
+    }
+
+    #[test]
+    fn ligatures() {
+        let mut textarea = TextArea::from(["a->b!=c", "**->**"]);
+        textarea.add_ligature("->", "→");
+        textarea.add_ligature("!=", "≠");
+        assert_eq!(textarea.conceals(0, "a->b!=c"), [(1, 3, "→"), (4, 6, "≠")]);
+
+        // The ligature under the cursor is not substituted
+        textarea.move_cursor(CursorMove::Jump(0, 2));
+        assert_eq!(textarea.conceals(0, "a->b!=c"), [(4, 6, "≠")]);
+        textarea.move_cursor(CursorMove::Jump(0, 3));
+        assert_eq!(textarea.conceals(0, "a->b!=c"), [(1, 3, "→"), (4, 6, "≠")]);
+
+        // Conceal rules take precedence
+        textarea.add_conceal_rule("**-", "*");
+        assert_eq!(textarea.conceals(1, "**->**"), [(0, 3, "*")]);
+        textarea.clear_conceal_rules();
+        textarea.add_conceal_rule("**", "");
+        assert_eq!(
+            textarea.conceals(1, "**->**"),
+            [(0, 2, ""), (2, 4, "→"), (4, 6, "")],
+        );
+
+        textarea.set_mask_char('*');
+        assert_eq!(textarea.conceals(0, "a->b!=c"), []);
+    }
+
+    #[test]
+    fn click_masked_text() {
+        let mut textarea = TextArea::from(["あい\tう"]);
+        textarea.set_mask_char('*');
+        let r = Rect::new(0, 0, 10, 1);
+        render_buffer(&textarea, r);
+
+        // Displayed as "****"
+        for (x, want) in [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (9, 4)] {
+            textarea.input(Input {
+                key: Key::MouseClick(x, 0),
+                ..Default::default()
+            });
+            assert_eq!(textarea.cursor(), (0, want), "x={}", x);
+        }
+    }
+
+    #[test]
+    fn jump_labels() {
+        let mut textarea = TextArea::from(["abcdefghijklmnopqrstuvwxyz", "x->y"]);
+        textarea.add_ligature("->", "→");
+        assert!(!textarea.start_jump_labels_at(vec![(0, 26), (2, 0)]));
+
+        // Two-character labels are used when there are more than 26 targets
+        let mut positions: Vec<_> = (0..26).map(|col| (0, col)).collect();
+        positions.extend([(1, 0), (1, 1), (1, 2)]);
+        assert!(textarea.start_jump_labels_at(positions));
+        assert_eq!(
+            textarea.conceals(1, "x->y"),
+            [(0, 1, "sa"), (1, 2, "ss"), (2, 4, "sd")],
+        );
+
+        // Labels which don't match to the typed characters are hidden
+        textarea.input(Input {
+            key: Key::Char('s'),
+            ..Default::default()
+        });
+        assert_eq!(
+            textarea.conceals(1, "x->y"),
+            [(0, 1, "a"), (1, 2, "s"), (2, 3, "d")],
+        );
+        assert_eq!(textarea.conceals(0, "abcdefghijklmnopqrstuvwxyz"), []);
+
+        textarea.input(Input {
+            key: Key::Char('a'),
+            ..Default::default()
+        });
+        assert!(!textarea.is_jumping());
+        assert_eq!(textarea.cursor(), (1, 0));
+        assert_eq!(textarea.conceals(1, "x->y"), [(1, 3, "→")]);
+
+        // Other inputs cancel the mode
+        assert!(textarea.start_jump_labels());
+        assert!(!textarea.input(Input {
+            key: Key::Esc,
+            ..Default::default()
+        }));
+        assert!(!textarea.is_jumping());
+        assert_eq!(textarea.cursor(), (1, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "ratatui")]
+    fn scrollbars() {
+        let render = |t: &TextArea| render_buffer(t, Rect::new(0, 0, 4, 3));
+
+        let mut textarea = TextArea::from(["abcdefgh", "b", "c", "d", "e", "f"]);
+        textarea.show_scrollbar(true, true);
+        render(&textarea);
+        // One column and one row are occupied by scrollbars
+        assert_eq!(textarea.viewport.rect(), (0, 0, 3, 2));
+
+        let b = render(&textarea);
+        assert_ne!(b[(3, 0)].symbol(), " ");
+        assert_ne!(b[(0, 2)].symbol(), " ");
+
+        // Horizontal scrollbar is hidden while wrapping
+        textarea.set_wrap(true);
+        render(&textarea);
+        assert_eq!(textarea.viewport.rect(), (0, 0, 3, 3));
+
+        textarea.show_scrollbar(false, false);
+        render(&textarea);
+        assert_eq!(textarea.viewport.rect(), (0, 0, 4, 3));
+    }
+
+    #[test]
+    fn screen_text_mapping() {
+        let render = |t: &TextArea| {
+            render_buffer(t, Rect::new(2, 1, 8, 3));
+        };
+
+        let mut textarea = TextArea::from(["abc defghij", "x", "y", "z"]);
+        assert_eq!(textarea.text_to_screen(0, 0), None);
+        textarea.set_line_number_style(Style::default());
+        render(&textarea);
+        // Line number takes 3 cells
+        assert_eq!(textarea.viewport_rows(), 0..3);
+        assert_eq!(textarea.text_to_screen(0, 0), Some((5, 1)));
+        assert_eq!(textarea.text_to_screen(1, 1), Some((6, 2)));
+        assert_eq!(textarea.text_to_screen(0, 6), None);
+        assert_eq!(textarea.text_to_screen(3, 0), None);
+        assert_eq!(textarea.screen_to_text(6, 2), Some((1, 1)));
+        assert_eq!(textarea.screen_to_text(1, 1), None);
+
+        // Scroll horizontally
+        textarea.move_cursor(CursorMove::End);
+        render(&textarea);
+        assert_eq!(textarea.text_to_screen(0, 11), Some((9, 1)));
+        assert_eq!(textarea.screen_to_text(9, 1), Some((0, 11)));
+
+        // Wrapped rows
+        textarea.move_cursor(CursorMove::Head);
+        textarea.set_wrap(true);
+        render(&textarea);
+        assert_eq!(textarea.viewport_rows(), 0..2);
+        for col in 0..=11 {
+            let (x, y) = textarea.text_to_screen(0, col).unwrap();
+            assert_eq!(textarea.screen_to_text(x, y), Some((0, col)), "col={col}");
+        }
+        assert_eq!(textarea.text_to_screen(1, 0), Some((5, 3)));
+    }
+
+    #[test]
+    fn cursor_line_and_column() {
+        let render = |t: &TextArea| {
+            render_buffer(t, Rect::new(0, 0, 4, 2))
+                .content
+                .iter()
+                .map(|c| c.modifier)
+                .collect::<Vec<_>>()
+        };
+        const U: Modifier = Modifier::UNDERLINED;
+        const R: Modifier = Modifier::REVERSED;
+        const B: Modifier = Modifier::BOLD;
+        const E: Modifier = Modifier::empty();
+
+        let mut textarea = TextArea::from(["ab", "c"]);
+        assert_eq!(render(&textarea), [R, U, E, E, E, E, E, E]);
+
+        textarea.set_cursor_line_full_width(true);
+        assert_eq!(render(&textarea), [R, U, U, U, E, E, E, E]);
+
+        textarea.set_cursor_column_style(Style::default().add_modifier(B));
+        textarea.move_cursor(CursorMove::Forward);
+        assert_eq!(render(&textarea), [U, R, U, U, E, B, E, E]);
+
+        // Cursor at the end of line occupies the cell after the text
+        textarea.move_cursor(CursorMove::Down);
+        assert_eq!(render(&textarea), [E, B, E, E, U, R, U, U]);
+
+        textarea.set_cursor_hidden(true);
+        assert_eq!(render(&textarea), [E; 8]);
+    }
+
+    #[test]
+    fn scroll_with_tabs() {
+        let mut textarea = TextArea::from(["\t\t\tx"]);
+        textarea.move_cursor(CursorMove::End);
+        let r = Rect::new(0, 0, 6, 1);
+        render_buffer(&textarea, r);
+        // The cursor is at the display column 13
+        assert_eq!(textarea.viewport.scroll_top(), (0, 8));
+        assert_eq!(textarea.text_to_screen(0, 4), Some((5, 0)));
+        assert_eq!(textarea.screen_to_text(4, 0), Some((0, 3)));
+
+        textarea.set_tab_width(2);
+        render_buffer(&textarea, r);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 7));
+        assert_eq!(textarea.text_to_screen(0, 4), Some((0, 0)));
+    }
+
+    #[test]
+    fn monochrome_profile() {
+        let render = |t: &TextArea| {
+            render_buffer(t, Rect::new(0, 0, 3, 1))
+                .content
+                .iter()
+                .map(|c| (c.modifier, c.bg))
+                .collect::<Vec<_>>()
+        };
+
+        let mut textarea = TextArea::from(["ab"]);
+        textarea.set_cursor_line_style(Style::default());
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Forward);
+        let (blue, reset) = (Color::LightBlue, Color::Reset);
+        let rev = Modifier::REVERSED;
+        assert_eq!(
+            render(&textarea),
+            [
+                (Modifier::empty(), blue),
+                (rev, reset),
+                (Modifier::empty(), reset)
+            ],
+        );
+
+        textarea.set_color_profile(ColorProfile::Monochrome);
+        assert_eq!(
+            render(&textarea),
+            [
+                (rev, reset),
+                (rev | Modifier::BOLD, reset),
+                (Modifier::empty(), reset)
+            ],
+        );
+    }
+
+    #[cfg(feature = "ratatui")]
+    #[test]
+    fn scroll_in_wrapped_line() {
+        // The second line is wrapped into 10 rows and the last row has 5 characters
+        let long = "0123456789".repeat(9) + "abcde";
+        let mut textarea = TextArea::from(["x", long.as_str(), "y"]);
+        textarea.set_wrap(true);
+        let render = |t: &TextArea| render_rows(t, 10, 3);
+        assert_eq!(render(&textarea)[0], "x         ");
+
+        // The viewport starts in the middle of the line to show the cursor
+        textarea.move_cursor(CursorMove::Jump(1, 95));
+        assert_eq!(
+            render(&textarea),
+            ["0123456789", "0123456789", "abcde     "],
+        );
+        assert_eq!(textarea.viewport.scroll_top().0, 1);
+        assert_eq!(textarea.viewport.skipped_rows(), 7);
+        assert_eq!(textarea.text_to_screen(1, 95), Some((5, 2)));
+        assert_eq!(textarea.text_to_screen(1, 69), None);
+        assert_eq!(textarea.screen_to_text(3, 0), Some((1, 73)));
+        assert_eq!(textarea.visible_rows(), 1..2);
+
+        // Moving the cursor upward in the line scrolls up by wrapped rows
+        textarea.move_cursor(CursorMove::Jump(1, 55));
+        render(&textarea);
+        assert_eq!(textarea.viewport.skipped_rows(), 5);
+        assert_eq!(textarea.text_to_screen(1, 55), Some((5, 0)));
+
+        // Moving to the next line scrolls by lines as usual
+        textarea.move_cursor(CursorMove::Bottom);
+        assert_eq!(render(&textarea)[0], "y         ");
+        assert_eq!(textarea.viewport.scroll_top().0, 2);
+        assert_eq!(textarea.viewport.skipped_rows(), 0);
+
+        textarea.move_cursor(CursorMove::Jump(1, 12));
+        assert_eq!(
+            render(&textarea),
+            ["0123456789", "0123456789", "0123456789"],
+        );
+        assert_eq!(textarea.viewport.skipped_rows(), 0);
+        assert_eq!(textarea.text_to_screen(1, 12), Some((2, 1)));
+
+        // Very long line
+        let mut textarea = TextArea::from(["a".repeat(9995)]);
+        textarea.set_wrap(true);
+        textarea.move_cursor(CursorMove::End);
+        assert_eq!(render(&textarea)[2], "aaaaa     ");
+        assert_eq!(textarea.viewport.skipped_rows(), 997);
+        assert_eq!(textarea.text_to_screen(0, 9995), Some((5, 2)));
+    }
+
+    #[test]
+    #[cfg(feature = "ratatui")]
+    fn soft_limit_spans() {
+        let spans = |t: &TextArea, row: usize| {
+            let line = &t.lines()[row];
+            t.line_spans(line, row, 1, None)
+                .spans
+                .iter()
+                .map(|s| (s.content.to_string(), s.style.fg))
+                .collect::<Vec<_>>()
+        };
+        let s = |text: &str, fg: Option<Color>| (text.to_string(), fg);
+        let red = Some(Color::Red);
+
+        let mut textarea = TextArea::from(["subject", "", "body text"]);
+        textarea.set_cursor_hidden(true);
+        textarea.set_soft_limit(SoftLimit::Columns(4));
+        assert_eq!(spans(&textarea, 0), [s("subj", None), s("ect", red)]);
+        assert_eq!(spans(&textarea, 1), []);
+        assert_eq!(spans(&textarea, 2), [s("body", None), s(" text", red)]);
+
+        // Newlines are counted as characters
+        textarea.set_soft_limit(SoftLimit::Chars(11));
+        assert_eq!(spans(&textarea, 0), [s("subject", None)]);
+        assert_eq!(spans(&textarea, 2), [s("bo", None), s("dy text", red)]);
+
+        textarea.set_soft_limit_style(Style::default().fg(Color::Yellow));
+        assert_eq!(spans(&textarea, 2)[1], s("dy text", Some(Color::Yellow)));
+
+        textarea.clear_soft_limit();
+        assert_eq!(spans(&textarea, 2), [s("body text", None)]);
+    }
+
+    #[test]
+    #[cfg(feature = "ratatui")]
+    fn highlight_spans() {
+        let spans = |t: &TextArea, row: usize| {
+            let line = &t.lines()[row];
+            t.line_spans(line, row, 1, None)
+                .spans
+                .iter()
+                .map(|s| (s.content.to_string(), s.style))
+                .collect::<Vec<_>>()
+        };
+        let s = |text: &str, style: Style| (text.to_string(), style);
+        let token = Style::default().fg(Color::Yellow);
+        let error = Style::default().add_modifier(Modifier::UNDERLINED);
+        let none = Style::default();
+
+        let mut textarea = TextArea::from(["foo bar", "baz"]);
+        textarea.set_cursor_hidden(true);
+        textarea.add_highlight(((0, 4), (1, 2)), error, 10, "diagnostics");
+        textarea.add_highlight(((0, 0), (0, 3)), token, 0, "tokens");
+        textarea.add_highlight(((1, 0), (1, 3)), token, 0, "tokens");
+        textarea.add_highlight(((1, 1), (1, 1)), error, 0, "empty");
+        assert_eq!(textarea.highlight_tags(), ["diagnostics", "tokens"]);
+
+        assert_eq!(
+            spans(&textarea, 0),
+            [s("foo", token), s(" ", none), s("bar", error)],
+        );
+        assert_eq!(
+            spans(&textarea, 1),
+            [s("ba", token.patch(error)), s("z", token)],
+        );
+
+        textarea.clear_highlights("diagnostics");
+        assert_eq!(textarea.highlight_tags(), ["tokens"]);
+        assert_eq!(spans(&textarea, 1), [s("baz", token)]);
+
+        textarea.clear_all_highlights();
+        assert_eq!(spans(&textarea, 0), [s("foo bar", none)]);
+    }
+
+    #[test]
+    #[cfg(feature = "ratatui")]
+    fn render_cache() {
+        let render = |t: &TextArea| render_buffer(t, Rect::new(0, 0, 10, 3));
+
+        let mut textarea = TextArea::from(["abc", "def", "ghi", "jkl", "mno"]);
+        textarea.set_line_number_style(Style::default());
+        let steps: [fn(&mut TextArea); 12] = [
+            |t| t.move_cursor(CursorMove::Down),
+            |t| t.insert_char('x'),
+            |t| {
+                t.start_selection();
+                t.move_cursor(CursorMove::Down);
+            },
+            |t| t.cancel_selection(),
+            |t| t.add_highlight(((0, 1), (1, 2)), Style::default().fg(Color::Red), 0, "h"),
+            |t| t.set_cursor_line_style(Style::default().bg(Color::Blue)),
+            |t| {
+                t.undo();
+            },
+            |t| t.set_gutter_sign(2, "!"),
+            // Line which is not the cursor line is modified
+            |t| {
+                t.apply_edits(&[TextEdit::new((2, 0), (2, 1), "G")])
+                    .unwrap();
+            },
+            |t| t.move_cursor(CursorMove::Bottom),
+            |t| t.insert_newline(),
+            |t| t.set_mask_char('*'),
+        ];
+        render(&textarea);
+        for (i, step) in steps.iter().enumerate() {
+            step(&mut textarea);
+            let mut uncached = textarea.clone();
+            uncached.set_render_cache(false);
+            assert_eq!(render(&textarea), render(&uncached), "step {}", i);
+        }
+
+        // Lines out of the viewport are not kept
+        assert_eq!(textarea.render_cache.rows(), [3, 4, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "ratatui")]
+    fn block_selection_rendering() {
+        let cells = |t: &TextArea, row: usize| {
+            let line = t.line_spans(&t.lines[row], row, 0, None);
+            let mut cells = vec![];
+            for span in line.spans {
+                for c in span.content.chars() {
+                    cells.push((c, span.style));
+                }
+            }
+            cells
+        };
+
+        let mut t = TextArea::from(["ab", "a", "abcd"]);
+        t.set_cursor_line_style(Style::default());
+        let (n, sel, cur) = (Style::default(), t.selection_style(), t.cursor_style());
+        t.move_cursor(CursorMove::Forward);
+        t.select_block(CursorMove::Down);
+        t.select_block(CursorMove::Down);
+        t.select_block(CursorMove::Forward);
+        t.select_block(CursorMove::Forward);
+        assert_eq!(cells(&t, 0), [('a', n), ('b', sel), (' ', sel)]);
+        // Selected columns in virtual space
+        assert_eq!(cells(&t, 1), [('a', n), (' ', sel), (' ', sel)]);
+        assert_eq!(cells(&t, 2), [('a', n), ('b', sel), ('c', sel), ('d', cur)],);
+
+        // The cursor in virtual space
+        t.select_block(CursorMove::Forward);
+        t.select_block(CursorMove::Forward);
+        assert_eq!(t.cursor(), (2, 4));
+        assert_eq!(
+            cells(&t, 2),
+            [
+                ('a', n),
+                ('b', sel),
+                ('c', sel),
+                ('d', sel),
+                (' ', sel),
+                (' ', cur),
+            ],
+        );
+        assert_eq!(cells(&t, 1).len(), 5);
+
+        t.cancel_selection();
+        assert_eq!(cells(&t, 1), [('a', n)]);
+    }
 }
@@ -1,3 +1,8 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation as _;
+use unicode_width::UnicodeWidthChar as _;
+
 pub fn spaces(size: u8) -> &'static str {
     const SPACES: &str = "                                                                                                                                                                                                                                                                ";
     &SPACES[..size as usize]
@@ -8,6 +13,7 @@ pub fn num_digits(i: usize) -> u8 {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pos {
     pub row: usize,
     pub col: usize,
@@ -20,70 +26,224 @@ impl Pos {
     }
 }
 
-/// Calculate number of rows for a wrapped line
-pub fn line_rows(line: &String, wrap_width: u16, has_lnum: bool, num_lines: usize) -> u16 {
-    let lnum_span_len = if has_lnum {
+/// Calculate number of rows for a wrapped line. `sign_width` is a width of the gutter signs column.
+pub fn line_rows(
+    line: &str,
+    wrap_width: u16,
+    has_lnum: bool,
+    num_lines: usize,
+    sign_width: u16,
+    tab_len: u8,
+) -> u16 {
+    let lnum_len = if has_lnum {
         // Longest line number plus space on each side
-        num_digits(num_lines) + 2
+        num_digits(num_lines) as u16 + 2
     } else {
         0
     };
+    wrapped_row_starts(line, wrap_width, sign_width + lnum_len, tab_len).len() as u16
+}
+
+/// Calculate character columns where each wrapped row of the line starts. The first element is always 0. This follows
+/// the word wrapping of `ratatui::widgets::Paragraph` with `Wrap { trim: false }`. `prefix_width` is a width of spans
+/// put before the line text such as a line number. Tabs are expanded to the next tab stop.
+pub fn wrapped_row_starts(
+    line: &str,
+    wrap_width: u16,
+    prefix_width: u16,
+    tab_len: u8,
+) -> Vec<usize> {
+    let width = wrap_width as usize;
+    let mut starts = vec![0];
+    if width == 0 {
+        return starts;
+    }
 
-    let mut curr_line_len = lnum_span_len;
-    let mut wraps = 0;
-    let mut in_whitespace = false;
+    let mut curr_row_len = prefix_width as usize;
+    let mut word_start = 0;
     let mut word_len = 0;
 
-    // Return new cur_line_len and wraps resulting from word
-    fn add_word_to_line(word_len: u8, mut curr_line_len: u8, width: u8) -> (u8, u8) {
-        let mut wraps = 0;
-
-        // Overflow case: Word cannot fit on a single line
-        // It is guaranteed to start on next line, and will wrap a known number of times
-        if word_len > width {
-            // Add one to round up, and one for initial wrap
-            wraps += (word_len / width) + 1 + 1;
-            curr_line_len = word_len % width;
-            return (curr_line_len, wraps);
+    // Put the word at `start` column on the current row or wrap it
+    let mut add_word = |start: usize, len: usize, curr_row_len: &mut usize| {
+        if len == 0 {
+            return;
+        }
+        if *curr_row_len + len <= width {
+            *curr_row_len += len;
+            return;
+        }
+        // Overflow case: Word cannot fit on a single row. It starts on the next row and is split at the width
+        if len > width {
+            if *curr_row_len > 0 {
+                starts.push(start);
+            }
+            let mut row_len = 0;
+            for (col, c) in line.chars().enumerate().skip(start) {
+                if c.is_whitespace() {
+                    break;
+                }
+                let w = c.width().unwrap_or(0);
+                if row_len > 0 && row_len + w > width {
+                    starts.push(col);
+                    row_len = 0;
+                }
+                row_len += w;
+            }
+            *curr_row_len = row_len;
+            return;
         }
+        starts.push(start);
+        *curr_row_len = len;
+    };
 
-        if curr_line_len + word_len > width {
-            wraps += 1;
-            curr_line_len = word_len;
+    let mut text_width = 0; // Display width from the start of the line to expand tabs
+    for (col, c) in line.chars().enumerate() {
+        if c.is_whitespace() {
+            add_word(word_start, word_len, &mut curr_row_len);
+            word_len = 0;
+            let w = if c == '\t' {
+                char_width(c, text_width, tab_len)
+            } else {
+                1
+            };
+            curr_row_len += w;
+            text_width += w;
         } else {
-            curr_line_len += word_len;
+            if word_len == 0 {
+                word_start = col;
+            }
+            let w = c.width().unwrap_or(0);
+            word_len += w;
+            text_width += w;
         }
+    }
+    add_word(word_start, word_len, &mut curr_row_len);
 
-        (curr_line_len, wraps)
+    starts
+}
+
+/// Calculate the display width of the first `col` characters of the line. Tabs are expanded to the next tab stop.
+pub fn display_width(line: &str, col: usize, tab_len: u8) -> usize {
+    let mut width = 0;
+    for c in line.chars().take(col) {
+        width += char_width(c, width, tab_len);
     }
+    width
+}
 
-    for c in line.chars() {
-        if c.is_whitespace() {
-            // Add last complete word
-            if !in_whitespace && word_len > 0 {
-                let added_wraps;
-                (curr_line_len, added_wraps) =
-                    add_word_to_line(word_len, curr_line_len, wrap_width as u8);
-                wraps += added_wraps;
-                word_len = 0;
-                in_whitespace = true;
-            }
-            if c == '\t' {
-                // FIXME: Count tabs properly
+/// Find the character column displayed at the display width `width` of the line. When the width is beyond the end of
+/// the line, the column at the end of line is returned.
+pub fn col_at_display_width(line: &str, width: usize, tab_len: u8) -> usize {
+    let mut current = 0;
+    for (col, c) in line.chars().enumerate() {
+        let w = char_width(c, current, tab_len);
+        if current + w > width {
+            return col;
+        }
+        current += w;
+    }
+    line.chars().count()
+}
+
+/// Same as [`display_width`] but the concealed ranges of the line are displayed as their replacements. When the
+/// column is in a concealed range, the width at the end of its replacement is returned.
+pub fn concealed_display_width(
+    line: &str,
+    col: usize,
+    tab_len: u8,
+    conceals: &[(usize, usize, &str)],
+) -> usize {
+    let mut width = 0;
+    let mut conceals = conceals.iter().peekable();
+    let mut concealed_end = 0;
+    for (i, c) in line.char_indices().take(col) {
+        if i < concealed_end {
+            continue;
+        }
+        width += match conceals.peek() {
+            Some(&&(start, end, replacement)) if start == i => {
+                conceals.next();
+                concealed_end = end;
+                replacement
+                    .chars()
+                    .fold(0, |w, c| w + char_width(c, width + w, tab_len))
             }
-            curr_line_len += 1;
-        } else {
-            if in_whitespace {
-                word_len = 0;
-                in_whitespace = false;
+            _ => char_width(c, width, tab_len),
+        };
+    }
+    width
+}
+
+/// Same as [`col_at_display_width`] but the concealed ranges of the line are displayed as their replacements. Each
+/// concealed range is a tuple of the start byte offset, the end byte offset and the replacement. When the width is in
+/// a replacement, the column at the start of the concealed range is returned.
+pub fn col_at_concealed_width(
+    line: &str,
+    width: usize,
+    tab_len: u8,
+    conceals: &[(usize, usize, &str)],
+) -> usize {
+    let mut current = 0;
+    let mut conceals = conceals.iter().peekable();
+    let mut concealed_end = 0;
+    for (col, (i, c)) in line.char_indices().enumerate() {
+        if i < concealed_end {
+            continue;
+        }
+        let w = match conceals.peek() {
+            Some(&&(start, end, replacement)) if start == i => {
+                conceals.next();
+                concealed_end = end;
+                replacement
+                    .chars()
+                    .fold(0, |w, c| w + char_width(c, current + w, tab_len))
             }
-            // FIXME: Unicode grapheme clusters are counted individually instead of visible char
-            word_len += 1;
+            _ => char_width(c, current, tab_len),
+        };
+        if current + w > width {
+            return col;
+        }
+        current += w;
+    }
+    line.chars().count()
+}
+
+/// Find the character column of the next grapheme cluster boundary after `col`. When `col` is at the end of the line,
+/// the end of the line is returned.
+pub fn next_grapheme_col(line: &str, col: usize) -> usize {
+    let mut current = 0;
+    for g in line.graphemes(true) {
+        current += g.chars().count();
+        if current > col {
+            return current;
         }
     }
+    current
+}
 
-    // Add 1 to account for the last line
-    (wraps + 1).max(1) as u16
+/// Find the character column of the previous grapheme cluster boundary before `col`. When `col` is 0, 0 is returned.
+pub fn prev_grapheme_col(line: &str, col: usize) -> usize {
+    let mut current = 0;
+    for g in line.graphemes(true) {
+        let next = current + g.chars().count();
+        if next >= col {
+            return current;
+        }
+        current = next;
+    }
+    current
+}
+
+fn char_width(c: char, current: usize, tab_len: u8) -> usize {
+    if c == '\t' {
+        if tab_len == 0 {
+            0
+        } else {
+            tab_len as usize - current % tab_len as usize
+        }
+    } else {
+        c.width().unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
@@ -97,8 +257,7 @@ mod line_wrap_tests {
         num_lines: usize,
         expected: u16,
     ) {
-        let line = line.to_string();
-        let result = line_rows(&line, wrap_width, has_lnum, num_lines);
+        let result = line_rows(line, wrap_width, has_lnum, num_lines, 0, 4);
         assert_eq!(
             result, expected,
             "with string: '{}', width: {}, lnum: {}, num_lines: {}",
@@ -203,9 +362,8 @@ mod line_wrap_tests {
 
         // _1_ Longer
         run_line_rows_test("Longer", 10, true, 1, 1);
-        // _10_
-        // Longer
-        run_line_rows_test("Longer", 10, true, 10, 2);
+        // _10_Longer
+        run_line_rows_test("Longer", 10, true, 10, 1);
     }
 
     #[test]
@@ -218,4 +376,28 @@ mod line_wrap_tests {
             4,
         )
     }
+
+    #[test]
+    fn test_wrapping_tabs() {
+        // Tabs are expanded to the next tab stop
+        assert_eq!(wrapped_row_starts("\tab cd", 10, 0, 8), [0, 4]);
+        assert_eq!(wrapped_row_starts("\tab cd", 10, 0, 4), [0]);
+        assert_eq!(wrapped_row_starts("a\tb cd", 10, 0, 8), [0, 4]);
+    }
+
+    #[test]
+    fn test_grapheme_cols() {
+        // 'e' + U+0301 and a family emoji joined with ZWJ are single grapheme clusters
+        let line = "ae\u{301}\u{1f469}\u{200d}\u{1f467}b";
+        assert_eq!(next_grapheme_col(line, 0), 1);
+        assert_eq!(next_grapheme_col(line, 1), 3);
+        assert_eq!(next_grapheme_col(line, 3), 6);
+        assert_eq!(next_grapheme_col(line, 6), 7);
+        assert_eq!(next_grapheme_col(line, 7), 7);
+        assert_eq!(prev_grapheme_col(line, 7), 6);
+        assert_eq!(prev_grapheme_col(line, 6), 3);
+        assert_eq!(prev_grapheme_col(line, 3), 1);
+        assert_eq!(prev_grapheme_col(line, 1), 0);
+        assert_eq!(prev_grapheme_col(line, 0), 0);
+    }
 }
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+/// Visit counts of lines by the cursor. The number of tracked lines is bounded by `max_lines`.
+#[derive(Clone, Debug, Default)]
+pub struct Visits {
+    counts: BTreeMap<usize, u32>,
+    max_lines: usize,
+    last_row: Option<usize>,
+}
+
+impl Visits {
+    pub fn set_max_lines(&mut self, max: usize) {
+        self.max_lines = max;
+        if max == 0 {
+            self.clear();
+        }
+        while self.counts.len() > max {
+            self.forget_least_visited();
+        }
+    }
+
+    pub fn max_lines(&self) -> usize {
+        self.max_lines
+    }
+
+    /// Count a visit when the cursor moved to another row since the last call.
+    pub fn record(&mut self, row: usize) {
+        if self.max_lines == 0 || self.last_row == Some(row) {
+            return;
+        }
+        self.last_row = Some(row);
+        if !self.counts.contains_key(&row) && self.counts.len() >= self.max_lines {
+            self.forget_least_visited();
+        }
+        let count = self.counts.entry(row).or_insert(0);
+        *count = count.saturating_add(1);
+    }
+
+    fn forget_least_visited(&mut self) {
+        let least = self
+            .counts
+            .iter()
+            .min_by_key(|(_, &count)| count)
+            .map(|(&row, _)| row);
+        if let Some(row) = least {
+            self.counts.remove(&row);
+        }
+    }
+
+    pub fn count(&self, row: usize) -> u32 {
+        self.counts.get(&row).copied().unwrap_or(0)
+    }
+
+    pub fn counts(&self) -> Vec<(usize, u32)> {
+        self.counts.iter().map(|(&r, &c)| (r, c)).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.last_row = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let mut v = Visits::default();
+        v.record(0);
+        assert_eq!(v.counts(), []);
+
+        v.set_max_lines(2);
+        for row in [0, 0, 1, 0, 1, 0, 2] {
+            v.record(row);
+        }
+        // Row 1 was the least visited
+        assert_eq!(v.counts(), [(0, 3), (2, 1)]);
+
+        v.set_max_lines(1);
+        assert_eq!(v.counts(), [(0, 3)]);
+
+        v.set_max_lines(0);
+        assert_eq!(v.counts(), []);
+        v.record(1);
+        assert_eq!(v.counts(), []);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::textarea::TextArea;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// State of the file on disk at some point. Comparing the modification time and the size is cheap so the content is
+/// only read when they are changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> io::Result<Self> {
+        let meta = fs::metadata(path)?;
+        Ok(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+/// File attached to [`TextArea`] as its backing file.
+#[derive(Clone, Debug)]
+pub struct AttachedFile {
+    path: PathBuf,
+    stamp: Stamp,
+    /// Hash of the file content when it was read last time
+    hash: u64,
+    changed: bool,
+}
+
+impl AttachedFile {
+    /// Read the file and start tracking its state. The content is returned to load it into the textarea.
+    pub fn open(path: PathBuf) -> io::Result<(Self, String)> {
+        let stamp = Stamp::of(&path)?;
+        let content = fs::read_to_string(&path)?;
+        let file = Self {
+            path,
+            stamp,
+            hash: TextArea::hash_text(&content),
+            changed: false,
+        };
+        Ok((file, content))
+    }
+
+    /// Read the file again and reset the tracked state.
+    pub fn read(&mut self) -> io::Result<String> {
+        let stamp = Stamp::of(&self.path)?;
+        let content = fs::read_to_string(&self.path)?;
+        self.stamp = stamp;
+        self.hash = TextArea::hash_text(&content);
+        self.changed = false;
+        Ok(content)
+    }
+
+    /// Update the tracked state after the content was written to the file.
+    pub fn written(&mut self, content: &str) -> io::Result<()> {
+        self.stamp = Stamp::of(&self.path)?;
+        self.hash = TextArea::hash_text(content);
+        self.changed = false;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Check if the file content on disk is different from the content read last time. This method returns `true`
+    /// only when the change is newly detected.
+    pub fn poll(&mut self) -> bool {
+        let stamp = match Stamp::of(&self.path) {
+            Ok(stamp) => stamp,
+            Err(_) => return false, // The file may be being replaced. Check it again next time
+        };
+        if stamp == self.stamp {
+            return false;
+        }
+        self.stamp = stamp;
+        let changed = match fs::read_to_string(&self.path) {
+            Ok(content) => TextArea::hash_text(&content) != self.hash,
+            Err(_) => false,
+        };
+        let detected = changed && !self.changed;
+        self.changed = changed;
+        detected
+    }
+}
@@ -0,0 +1,52 @@
+use crate::ratatui::style::{Color, Style};
+
+/// Configuration to visualize whitespaces and line ends in the textarea. Each kind of character is shown as a glyph
+/// with its own style. `None` disables the visualization of the kind. Set the configuration with
+/// [`TextArea::set_show_whitespace`].
+///
+/// Glyphs should be one cell wide. A tab is shown as the glyph followed by spaces up to the next tab stop. The newline
+/// glyph is shown after every line except for the last one, and the EOF glyph is shown after the last line.
+///
+/// This type is marked as `#[non_exhaustive]` since more kinds may be added in the future. Create an instance with
+/// [`WhitespaceConfig::default`] and update its fields.
+/// ```
+/// use ratatui::style::{Color, Style};
+/// use tui_textarea::{TextArea, WhitespaceConfig};
+///
+/// let mut config = WhitespaceConfig::default();
+/// config.tab = Some(('»', Style::default().fg(Color::Blue)));
+/// config.newline = None;
+///
+/// let mut textarea = TextArea::default();
+/// textarea.set_show_whitespace(config);
+/// assert_eq!(textarea.show_whitespace(), Some(&config));
+/// ```
+///
+/// [`TextArea::set_show_whitespace`]: https://docs.rs/tui-textarea/latest/tui_textarea/struct.TextArea.html#method.set_show_whitespace
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WhitespaceConfig {
+    /// Glyph and style of tab characters. The default glyph is `→`.
+    pub tab: Option<(char, Style)>,
+    /// Glyph and style of spaces at the end of line. The default glyph is `·`.
+    pub trailing_space: Option<(char, Style)>,
+    /// Glyph and style of non-breaking spaces (U+00A0). The default glyph is `⍽`.
+    pub nbsp: Option<(char, Style)>,
+    /// Glyph and style shown at the end of each line except for the last one. The default glyph is `↵`.
+    pub newline: Option<(char, Style)>,
+    /// Glyph and style shown at the end of the last line. This is disabled by default.
+    pub eof: Option<(char, Style)>,
+}
+
+impl Default for WhitespaceConfig {
+    fn default() -> Self {
+        let style = Style::default().fg(Color::DarkGray);
+        Self {
+            tab: Some(('→', style)),
+            trailing_space: Some(('·', style)),
+            nbsp: Some(('⍽', style)),
+            newline: Some(('↵', style)),
+            eof: None,
+        }
+    }
+}
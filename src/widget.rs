@@ -1,16 +1,24 @@
+use crate::fold::Fold;
+use crate::message::Messages;
 use crate::ratatui::buffer::Buffer;
-use crate::ratatui::layout::Rect;
+use crate::ratatui::layout::{Alignment, Rect};
+use crate::ratatui::style::Modifier;
 use crate::ratatui::text::{Span, Text};
-use crate::ratatui::widgets::{Paragraph, Widget};
+use crate::ratatui::widgets::{Paragraph, Widget, Wrap};
 use crate::textarea::TextArea;
-use crate::util::{line_rows, num_digits};
+use crate::util::{
+    col_at_concealed_width, col_at_display_width, concealed_display_width, display_width,
+    line_rows, num_digits, wrapped_row_starts,
+};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
-use ratatui::widgets::Wrap;
+use std::borrow::Cow;
 use std::cmp;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU16, AtomicU32, AtomicU64, Ordering};
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
+use unicode_width::UnicodeWidthChar as _;
 
 // &mut 'a (u16, u16, u16, u16) is not available since `render` method takes immutable reference of TextArea
 // instance. In the case, the TextArea instance cannot be accessed from any other objects since it is mutablly
@@ -20,13 +28,18 @@ use tui::text::Spans as Line;
 // point we stick with using `ratatui::Frame::render_widget` because it is simpler API. Users don't need to
 // manage states of textarea instances separately.
 // https://docs.rs/ratatui/latest/ratatui/terminal/struct.Frame.html#method.render_stateful_widget
+//
+// The third value is the number of wrapped rows of the top line which are scrolled out above the viewport. It is not
+// zero only when a wrapped line is taller than the viewport.
 #[derive(Default, Debug)]
-pub struct Viewport(AtomicU64);
+pub struct Viewport(AtomicU64, AtomicU32, AtomicU16);
 
 impl Clone for Viewport {
     fn clone(&self) -> Self {
         let u = self.0.load(Ordering::Relaxed);
-        Viewport(AtomicU64::new(u))
+        let o = self.1.load(Ordering::Relaxed);
+        let s = self.2.load(Ordering::Relaxed);
+        Viewport(AtomicU64::new(u), AtomicU32::new(o), AtomicU16::new(s))
     }
 }
 
@@ -36,6 +49,12 @@ impl Viewport {
         ((u >> 16) as u16, u as u16)
     }
 
+    /// Screen position (x, y) of the top-left corner of the text area at the last rendering.
+    pub fn origin(&self) -> (u16, u16) {
+        let o = self.1.load(Ordering::Relaxed);
+        ((o >> 16) as u16, o as u16)
+    }
+
     pub fn rect(&self) -> (u16, u16, u16, u16) {
         let u = self.0.load(Ordering::Relaxed);
         let width = (u >> 48) as u16;
@@ -65,6 +84,28 @@ impl Viewport {
         self.0.store(u, Ordering::Relaxed);
     }
 
+    fn store_origin(&self, x: u16, y: u16) {
+        self.1
+            .store(((x as u32) << 16) | y as u32, Ordering::Relaxed);
+    }
+
+    /// Number of wrapped rows of the top line hidden above the viewport at the last rendering.
+    pub fn skipped_rows(&self) -> u16 {
+        self.2.load(Ordering::Relaxed)
+    }
+
+    fn store_skipped_rows(&self, rows: u16) {
+        self.2.store(rows, Ordering::Relaxed);
+    }
+
+    pub fn set_scroll_top(&mut self, row: u16, col: u16) {
+        let u = self.0.get_mut();
+        if (*u >> 16) as u16 != row {
+            *self.2.get_mut() = 0; // The rows are skipped only in the top line
+        }
+        *u = (*u & 0xffff_ffff_0000_0000) | ((row as u64) << 16) | (col as u64);
+    }
+
     pub fn scroll(&mut self, rows: i16, cols: i16) {
         fn apply_scroll(pos: u16, delta: i16) -> u16 {
             if delta >= 0 {
@@ -74,10 +115,8 @@ impl Viewport {
             }
         }
 
-        let u = self.0.get_mut();
-        let row = apply_scroll((*u >> 16) as u16, rows);
-        let col = apply_scroll(*u as u16, cols);
-        *u = (*u & 0xffff_ffff_0000_0000) | ((row as u64) << 16) | (col as u64);
+        let (row, col) = self.scroll_top();
+        self.set_scroll_top(apply_scroll(row, rows), apply_scroll(col, cols));
     }
 }
 
@@ -96,17 +135,69 @@ impl<'a> TextArea<'a> {
     fn text_widget(&'a self, top_row: usize, height: usize) -> Text<'a> {
         let lines_len = self.lines().len();
         let lnum_len = num_digits(lines_len);
-        let bottom_row = cmp::min(top_row + height, lines_len);
-        let mut lines = Vec::with_capacity(bottom_row - top_row);
-        for (i, line) in self.lines()[top_row..bottom_row].iter().enumerate() {
-            lines.push(self.line_spans(line.as_str(), top_row + i, lnum_len));
+        let brackets = self.matched_brackets();
+        let settings = self.render_settings_hash();
+        let mut lines = Vec::with_capacity(cmp::min(height, lines_len - top_row));
+        let mut row = top_row;
+        let mut shown = 0;
+        while row < lines_len && shown < height {
+            if let Some(Fold { rows: fold, hidden }) = self.fold_at(row) {
+                // The top of the viewport may be in the middle of the fold
+                if fold.start == row && !hidden {
+                    lines.push(self.fold_placeholder(fold.len()));
+                    shown += 1;
+                }
+                row = fold.end;
+                continue;
+            }
+            let line = self.lines()[row].as_str();
+            lines.push(self.render_line(line, row, lnum_len, brackets, settings));
+            // Blank rows where the host application renders its own widgets
+            for _ in 0..self.reserved_rows_after(row) {
+                lines.push(Line::default());
+            }
+            row += 1;
+            shown += 1;
         }
+        self.render_cache.retain(top_row..row);
         Text::from(lines)
     }
 
+    /// Line rendered in place of the folded lines.
+    fn fold_placeholder(&self, folded: usize) -> Line<'a> {
+        let text = Messages::format(&self.messages().folded_lines, &[("lines", &folded)]);
+        let style = self.cue_style(self.fold_style(), Modifier::DIM);
+        Line::from(vec![
+            Span::raw(" ".repeat(self.gutter_width())),
+            Span::styled(text, style),
+        ])
+    }
+
+    /// Number of display rows of the line at the row including the rows reserved after it. Folded lines are displayed
+    /// as one placeholder row at the first line of the fold. Hidden folds have no row.
+    fn display_rows_at(&self, row: usize, width: u16) -> usize {
+        if let Some(Fold { rows: fold, hidden }) = self.fold_at(row) {
+            return (fold.start == row && !hidden) as usize;
+        }
+        let rows = if self.get_wrap() {
+            self.wrapped_row_starts(row, width).len()
+        } else {
+            1
+        };
+        rows + self.reserved_rows_after(row) as usize
+    }
+
+    fn placeholder_shown(&self) -> bool {
+        !self.placeholder_text().is_empty() && self.is_empty()
+    }
+
     fn placeholder_widget(&'a self) -> Text<'a> {
-        let cursor = Span::styled(" ", self.cursor_style);
-        let text = Span::raw(self.placeholder.as_str());
+        let style = self.cue_style(
+            self.current_cursor_style(),
+            Modifier::REVERSED | Modifier::BOLD,
+        );
+        let cursor = Span::styled(" ", style);
+        let text = Span::raw(self.placeholder_text());
         Text::from(Line::from(vec![cursor, text]))
     }
 
@@ -114,11 +205,42 @@ impl<'a> TextArea<'a> {
         next_scroll_top(prev_top, self.cursor().0 as u16, height)
     }
 
+    /// Display column of the cursor in the cursor line. Tabs and wide characters are taken into account.
+    fn cursor_display_col(&self) -> u16 {
+        let (row, col) = self.cursor();
+        self.screen_width_at_col(row, col) as u16
+    }
+
+    /// Character columns where each wrapped row of the line at the row starts. The rows of the end-of-line annotation
+    /// follow the rows of the line and their columns are after the end of line.
+    fn wrapped_row_starts(&self, row: usize, width: u16) -> Vec<usize> {
+        let text = self.text_with_annotation(row);
+        wrapped_row_starts(&text, width, self.gutter_width() as u16, self.tab_width())
+    }
+
+    /// Text of the line at the row followed by its end-of-line annotation as it is rendered.
+    fn text_with_annotation(&self, row: usize) -> Cow<'_, str> {
+        let line = &self.lines()[row];
+        match self.eol_annotation(row) {
+            Some(annotation) => Cow::Owned(format!("{} {}", line, annotation.content)),
+            None => Cow::Borrowed(line),
+        }
+    }
+
+    /// Character columns where each display row of the cursor line starts at the last rendering.
+    pub(crate) fn cursor_row_starts(&self) -> Vec<usize> {
+        let (_, _, width, _) = self.viewport.rect();
+        if !self.get_wrap() || width == 0 {
+            return vec![0];
+        }
+        self.wrapped_row_starts(self.cursor().0, width)
+    }
+
     fn scroll_top_col(&self, prev_top: u16, width: u16) -> u16 {
-        let mut cursor = self.cursor().1 as u16;
-        // Adjust the cursor position due to the width of line number.
-        if self.line_number_style().is_some() {
-            let lnum = num_digits(self.lines().len()) as u16 + 2; // `+ 2` for margins
+        let mut cursor = self.cursor_display_col();
+        // Adjust the cursor position due to the width of line number and gutter signs.
+        let lnum = self.gutter_width() as u16;
+        if lnum > 0 {
             if cursor <= lnum {
                 cursor *= 2; // Smoothly slide the line number into the screen on scrolling left
             } else {
@@ -127,95 +249,578 @@ impl<'a> TextArea<'a> {
         }
         next_scroll_top(prev_top, cursor, width)
     }
+
+    /// Replace the wrapped rows of the lines which have end-of-line annotations with the rows including the annotations.
+    fn add_annotation_rows(&self, wrapped_rows: &mut [u16], width: u16) {
+        for row in self.eol_annotation_rows() {
+            if row < wrapped_rows.len() {
+                wrapped_rows[row] = self.wrapped_row_starts(row, width).len() as u16;
+            }
+        }
+    }
+
+    /// Add the reserved rows to the display rows of each line. The reserved rows after the cursor line are not added
+    /// since they don't need to be on the screen to show the cursor.
+    fn add_reserved_rows(&self, display_rows: &mut [u16], cursor_row: usize) {
+        for (row, rows) in display_rows.iter_mut().enumerate() {
+            if row != cursor_row {
+                *rows = rows.saturating_add(self.reserved_rows_after(row));
+            }
+        }
+    }
+
+    /// Replace the display rows of folded lines. The first line of each fold has one row for the placeholder and the
+    /// other lines have no row. Hidden folds have no row.
+    fn add_fold_rows(&self, display_rows: &mut [u16]) {
+        let cursor_row = self.cursor().0;
+        for fold in self.fold_regions() {
+            let rows = &fold.rows;
+            if !rows.contains(&cursor_row) {
+                display_rows[rows.start] = !fold.hidden as u16;
+                display_rows[rows.start + 1..rows.end].fill(0);
+            }
+        }
+    }
+
+    /// Width of the gutter put before each line. It consists of the gutter signs, line numbers and prompts.
+    pub(crate) fn gutter_width(&self) -> usize {
+        let lnum = if self.line_number_style().is_some() {
+            num_digits(self.lines().len()) as usize + 2 // `+ 2` for margins
+        } else {
+            0
+        };
+        self.gutter_sign_width() + lnum + self.prompt_width()
+    }
+
+    /// Convert a cell position on the screen into a (row, col) position in the text based on the last rendering. When
+    /// `clamp` is `true`, a position outside the text area is moved to its nearest edge. Otherwise `None` is returned
+    /// for such position.
+    pub(crate) fn screen_to_text_pos(&self, x: u16, y: u16, clamp: bool) -> Option<(usize, usize)> {
+        let (top_row, top_col, width, height) = self.viewport.rect();
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let (left, top) = self.viewport.origin();
+        let (right, bottom) = (
+            left.saturating_add(width - 1),
+            top.saturating_add(height - 1),
+        );
+        if !clamp && (x < left || right < x || y < top || bottom < y) {
+            return None;
+        }
+        let wrap = self.get_wrap();
+        // Wrapped lines are not scrolled horizontally
+        let top_col = if wrap { 0 } else { top_col as usize };
+        let dx = (x.clamp(left, right) - left) as usize + top_col;
+        let dy = (y.clamp(top, bottom) - top) as usize;
+
+        let lines = self.lines();
+        let gutter_width = self.gutter_width();
+        if !wrap && !self.has_reserved_rows() && !self.has_folds() {
+            let row = cmp::min(top_row as usize + dy, lines.len() - 1);
+            let col = self.col_at_screen_width(row, dx.saturating_sub(gutter_width));
+            return Some((row, col));
+        }
+
+        // Count the wrapped rows of the top line hidden above the viewport
+        let mut remaining = dy + self.viewport.skipped_rows() as usize;
+        for (row, line) in lines.iter().enumerate().skip(top_row as usize) {
+            if let Some(Fold { rows: fold, hidden }) = self.fold_at(row) {
+                if fold.start == row && !hidden {
+                    if remaining == 0 {
+                        // Clicking the placeholder moves the cursor into the fold and unfolds it
+                        return Some((row, 0));
+                    }
+                    remaining -= 1;
+                }
+                continue;
+            }
+            let starts = if wrap {
+                self.wrapped_row_starts(row, width)
+            } else {
+                vec![0]
+            };
+            if let Some(&start) = starts.get(remaining) {
+                let dx = if remaining == 0 {
+                    dx.saturating_sub(gutter_width)
+                } else {
+                    dx
+                };
+                if !wrap {
+                    return Some((row, self.col_at_screen_width(row, dx)));
+                }
+                let base = display_width(line, start, self.tab_width());
+                let mut col = col_at_display_width(line, base + dx, self.tab_width());
+                if let Some(&next) = starts.get(remaining + 1) {
+                    // Stay on the clicked row even if the click is after the end of the wrapped row
+                    col = cmp::max(start, cmp::min(col, next.saturating_sub(1)));
+                }
+                // Clicking the end-of-line annotation moves the cursor to the end of line
+                return Some((row, cmp::min(col, line.chars().count())));
+            }
+            remaining -= starts.len();
+
+            let reserved = self.reserved_rows_after(row) as usize;
+            if remaining < reserved {
+                // Clicking the reserved rows does not move the cursor
+                return clamp.then(|| (row, line.chars().count()));
+            }
+            remaining -= reserved;
+        }
+
+        let row = lines.len() - 1;
+        Some((row, lines[row].chars().count()))
+    }
+
+    /// Find the character column of the line at the row which is displayed at the display width `width`, taking the
+    /// masked or concealed text into account.
+    fn col_at_screen_width(&self, row: usize, width: usize) -> usize {
+        let line = &self.lines()[row];
+        if let Some(mask) = self.mask_char() {
+            // All characters including tabs are displayed as the mask character
+            let mask_width = cmp::max(mask.width().unwrap_or(1), 1);
+            return cmp::min(width / mask_width, line.chars().count());
+        }
+        let conceals = self.conceals(row, line);
+        if conceals.is_empty() {
+            col_at_display_width(line, width, self.tab_width())
+        } else {
+            col_at_concealed_width(line, width, self.tab_width(), &conceals)
+        }
+    }
+
+    /// Inverse of [`TextArea::col_at_screen_width`]. Calculate the display width of the line at the row until the
+    /// character column, taking the masked or concealed text into account.
+    fn screen_width_at_col(&self, row: usize, col: usize) -> usize {
+        let line = &self.lines()[row];
+        if let Some(mask) = self.mask_char() {
+            let mask_width = cmp::max(mask.width().unwrap_or(1), 1);
+            return cmp::min(col, line.chars().count()) * mask_width;
+        }
+        let conceals = self.conceals(row, line);
+        if conceals.is_empty() {
+            display_width(line, col, self.tab_width())
+        } else {
+            concealed_display_width(line, col, self.tab_width(), &conceals)
+        }
+    }
+
+    /// Convert a (row, col) position in the text into a cell position on the screen based on the last rendering.
+    /// `None` is returned when the position is not visible.
+    pub(crate) fn text_to_screen_pos(&self, row: usize, col: usize) -> Option<(u16, u16)> {
+        let (top_row, top_col, width, height) = self.viewport.rect();
+        let lines = self.lines();
+        if width == 0 || height == 0 || row < top_row as usize || lines.len() <= row {
+            return None;
+        }
+        if self.fold_at(row).is_some() {
+            return None;
+        }
+        let (left, top) = self.viewport.origin();
+        let gutter_width = self.gutter_width();
+        let wrap = self.get_wrap();
+        let skipped = self.viewport.skipped_rows() as usize;
+
+        // Rows are counted from the first wrapped row of the top line including the hidden ones
+        let mut dy = 0;
+        for r in top_row as usize..row {
+            dy += self.display_rows_at(r, width);
+            if dy >= height as usize + skipped {
+                return None;
+            }
+        }
+
+        let line = &lines[row];
+        let dx = if wrap {
+            let starts = self.wrapped_row_starts(row, width);
+            let idx = starts.partition_point(|&s| s <= col).saturating_sub(1);
+            let tab = self.tab_width();
+            let dx = display_width(line, col, tab) - display_width(line, starts[idx], tab);
+            dy = (dy + idx).checked_sub(skipped)?;
+            if idx == 0 {
+                dx + gutter_width
+            } else {
+                dx
+            }
+        } else {
+            (self.screen_width_at_col(row, col) + gutter_width).checked_sub(top_col as usize)?
+        };
+
+        if dx >= width as usize || dy >= height as usize {
+            return None;
+        }
+        Some((left + dx as u16, top + dy as u16))
+    }
+
+    /// Calculate the range of rows which were displayed in the text area at the last rendering. A partially visible
+    /// wrapped line at the bottom is included.
+    pub(crate) fn visible_rows(&self) -> Range<usize> {
+        let (top_row, _, width, height) = self.viewport.rect();
+        let top_row = top_row as usize;
+
+        let mut dy = 0;
+        let mut bottom_row = top_row;
+        let bottom = height as usize + self.viewport.skipped_rows() as usize;
+        for row in top_row..self.lines().len() {
+            if dy >= bottom {
+                break;
+            }
+            dy += self.display_rows_at(row, width);
+            bottom_row = row + 1;
+        }
+        top_row..bottom_row
+    }
+
+    /// Extend the cursor line style to the right edge of the text area and highlight the cursor column. This is done on
+    /// the buffer after rendering the text since the rendered lines are not padded to the width of the text area.
+    fn render_cursor_line_and_column(&self, area: Rect, buf: &mut Buffer) {
+        let (row, col) = self.cursor();
+        let (x, y) = match self.text_to_screen_pos(row, col) {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        if self.cursor_line_full_width() && self.alignment() == Alignment::Left {
+            // Find the end of the text in the display row of the cursor
+            let line = &self.lines()[row];
+            let len = line.chars().count();
+            let gutter_width = self.gutter_width();
+            let (top_col, width) = {
+                let (_, top_col, width, _) = self.viewport.rect();
+                (top_col as usize, width)
+            };
+            let annotation = self.eol_annotation(row);
+            let (text_width, last) = if self.get_wrap() {
+                let text = self.text_with_annotation(row);
+                let starts = self.wrapped_row_starts(row, width);
+                let idx = starts.partition_point(|&s| s <= col).saturating_sub(1);
+                let end = starts.get(idx + 1).copied().unwrap_or(usize::MAX);
+                let tab = self.tab_width();
+                let w = display_width(&text, end, tab) - display_width(&text, starts[idx], tab);
+                let w = if idx == 0 { w + gutter_width } else { w };
+                (w, idx + 1 == starts.len())
+            } else {
+                let w = gutter_width + self.screen_width_at_col(row, len);
+                let w = w + annotation.map_or(0, |a| a.width() + 1);
+                (w.saturating_sub(top_col), true)
+            };
+            // The cell after the text is occupied by the cursor when it is at the end of line. When the line has an
+            // annotation, the cell is already counted as the space before the annotation
+            let text_width = if last && col == len && annotation.is_none() {
+                text_width + 1
+            } else {
+                text_width
+            };
+            let start = area.x.saturating_add(text_width as u16);
+            if start < area.right() {
+                let rest = Rect {
+                    x: start,
+                    y,
+                    width: area.right() - start,
+                    height: 1,
+                };
+                let style = self.cue_style(self.cursor_line_style(), Modifier::empty());
+                buf.set_style(rest, style);
+            }
+        }
+
+        if let Some(style) = self.cursor_column_style() {
+            let style = self.cue_style(style, Modifier::BOLD);
+            for cy in area.top()..area.bottom() {
+                if cy != y {
+                    buf.set_style(Rect::new(x, cy, 1, 1), style);
+                }
+            }
+        }
+    }
+
+    /// Highlight the color column on all rows in the buffer since the rendered lines are not padded to the width of the
+    /// text area. The cell of the cursor is not highlighted to keep the cursor visible.
+    fn render_color_column(&self, area: Rect, buf: &mut Buffer) {
+        let col = match self.color_column() {
+            Some(col) if self.alignment() == Alignment::Left => col,
+            _ => return,
+        };
+        let (_, top_col, _, _) = self.viewport.rect();
+        let top_col = if self.get_wrap() { 0 } else { top_col as usize };
+        let x = match (self.gutter_width() + col).checked_sub(top_col) {
+            Some(x) if x < area.width as usize => area.x + x as u16,
+            _ => return,
+        };
+        let cursor = if self.cursor_hidden() {
+            None
+        } else {
+            let (row, col) = self.cursor();
+            self.text_to_screen_pos(row, col)
+        };
+        let style = self.cue_style(self.color_column_style(), Modifier::REVERSED);
+        for y in area.top()..area.bottom() {
+            if cursor != Some((x, y)) {
+                buf.set_style(Rect::new(x, y, 1, 1), style);
+            }
+        }
+    }
+
+    /// Render the completion menu as a popup below the completed word. When there is no space below the word, it is
+    /// rendered above the word. The popup may overflow the text area but is clipped by the buffer.
+    fn render_completion(&self, buf: &mut Buffer) {
+        const MAX_ROWS: usize = 8;
+
+        let completion = match self.completion_state() {
+            Some(c) => c,
+            None => return,
+        };
+        let (row, col) = completion.start;
+        let (x, y) = match self
+            .text_to_screen_pos(row, col)
+            .or_else(|| self.text_to_screen_pos(self.cursor().0, self.cursor().1))
+        {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        let items: Vec<_> = completion.items().collect();
+        let str_width = |s: &str| s.chars().map(|c| c.width().unwrap_or(0)).sum::<usize>();
+        let label_width = items
+            .iter()
+            .map(|i| str_width(i.label()))
+            .max()
+            .unwrap_or(0);
+        let detail_width = items
+            .iter()
+            .filter_map(|i| i.detail_text())
+            .map(|d| str_width(d) + 1) // +1 for the space between the label and the detail
+            .max()
+            .unwrap_or(0);
+
+        let bounds = *buf.area();
+        let width = cmp::min(label_width + detail_width + 2, bounds.width as usize) as u16;
+        let height = cmp::min(items.len(), MAX_ROWS) as u16;
+        if width == 0 || height == 0 {
+            return;
+        }
+        let top = if y + 1 + height <= bounds.bottom() {
+            y + 1
+        } else if y >= bounds.y + height {
+            y - height
+        } else {
+            bounds.bottom().saturating_sub(height)
+        };
+        // Align the labels with the word. The first column of the popup is padding
+        let left = cmp::min(
+            cmp::max(x.saturating_sub(1), bounds.x),
+            bounds.right() - width,
+        );
+        let style = self.cue_style(self.completion_style(), Modifier::empty());
+        let selected_style = self.cue_style(self.completion_selected_style(), Modifier::REVERSED);
+
+        // Scroll the items so that the selected item is visible
+        let selected = completion.selected_index();
+        let offset = (selected + 1).saturating_sub(height as usize);
+        for (i, item) in items.iter().enumerate().skip(offset).take(height as usize) {
+            let y = top + (i - offset) as u16;
+            let style = if i == selected { selected_style } else { style };
+            // Clear the text under the popup
+            buf.set_stringn(left, y, " ".repeat(width as usize), width as usize, style);
+            let label = item.label();
+            let mut text = format!(" {}", label);
+            if let Some(detail) = item.detail_text() {
+                text.push_str(&" ".repeat(label_width - str_width(label) + 1));
+                text.push_str(detail);
+            }
+            buf.set_stringn(left, y, text, width as usize, style);
+        }
+    }
+
+    /// Calculate the areas of the reserved rows on the screen based on the last rendering. Only visible areas are
+    /// returned and they are clipped by the text area.
+    pub(crate) fn reserved_screen_areas(&self) -> Vec<(usize, Rect)> {
+        let (top_row, _, width, height) = self.viewport.rect();
+        let (x, y) = self.viewport.origin();
+        let wrap = self.get_wrap();
+
+        // The reserved rows of the top line are after its hidden rows, so `dy` never gets smaller than `skipped`
+        let skipped = self.viewport.skipped_rows();
+        let bottom = height.saturating_add(skipped);
+        let mut areas = vec![];
+        let mut dy = 0u16;
+        for row in top_row as usize..self.lines().len() {
+            if dy >= bottom {
+                break;
+            }
+            if self.fold_at(row).is_some() {
+                dy = dy.saturating_add(self.display_rows_at(row, width) as u16);
+                continue;
+            }
+            dy = dy.saturating_add(if wrap {
+                self.wrapped_row_starts(row, width).len() as u16
+            } else {
+                1
+            });
+            let reserved = self.reserved_rows_after(row);
+            if reserved > 0 && dy < bottom {
+                let area = Rect {
+                    x,
+                    y: y + (dy - skipped),
+                    width,
+                    height: cmp::min(reserved, bottom - dy),
+                };
+                areas.push((row, area));
+            }
+            dy = dy.saturating_add(reserved);
+        }
+        areas
+    }
 }
 
 impl Widget for &TextArea<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let Rect { width, height, .. } = if let Some(b) = self.block() {
+        let inner_area = if let Some(b) = self.block() {
             b.inner(area)
         } else {
             area
         };
+        #[cfg(feature = "ratatui")]
+        let (text_area, scrollbar_areas) = self.split_scrollbar_areas(inner_area);
+        #[cfg(feature = "tuirs")]
+        let text_area = inner_area;
+        let Rect { width, height, .. } = text_area;
 
-        let (top_row, top_col) = self.viewport.scroll_top();
-        let mut top_row = self.scroll_top_row(top_row, height);
+        let (prev_top_row, top_col) = self.viewport.scroll_top();
+        // Scrolling by the number of lines would go too far when some lines are folded. The top row is calculated with
+        // the display rows of the lines below
+        let mut top_row = if self.has_folds() {
+            prev_top_row
+        } else {
+            self.scroll_top_row(prev_top_row, height)
+        };
         let mut top_col = self.scroll_top_col(top_col, width);
+        let mut skipped = if top_row == prev_top_row {
+            self.viewport.skipped_rows()
+        } else {
+            0
+        };
 
         let cursor = self.cursor();
+        let cursor_col = self.cursor_display_col();
         let wrap = self.get_wrap();
-        if wrap {
-            let wrapped_rows =
-                wrapped_rows(&self.lines(), width, self.line_number_style().is_some());
-            top_row = next_scroll_row_wrapped(top_row, cursor.0 as u16, height, &wrapped_rows);
-            // Column for scoll should never change with wrapping (no horiz scroll)
-            // FIXME: Edge case where line can't fit in screen and overflows?
+        // Number of display rows of each line when it is not 1
+        #[cfg_attr(feature = "tuirs", allow(unused_variables))]
+        let display_rows = if wrap {
+            let mut wrapped_rows = wrapped_rows(
+                self.lines(),
+                width,
+                self.line_number_style().is_some(),
+                (self.gutter_sign_width() + self.prompt_width()) as u16,
+                self.tab_width(),
+            );
+            self.add_annotation_rows(&mut wrapped_rows, width);
+            self.add_reserved_rows(&mut wrapped_rows, cursor.0);
+            self.add_fold_rows(&mut wrapped_rows);
+            // Column for scoll should never change with wrapping (no horiz scroll). Instead, the viewport can start in
+            // the middle of the cursor line when the line is taller than the viewport
+            let starts = self.wrapped_row_starts(cursor.0, width);
+            let cursor_wrap = starts.partition_point(|&s| s <= cursor.1).saturating_sub(1) as u16;
+            let (row, rows) = next_scroll_row_wrapped(
+                (top_row, skipped),
+                (cursor.0 as u16, cursor_wrap),
+                height,
+                &wrapped_rows,
+            );
+            top_row = row;
+            skipped = rows;
+            Some(wrapped_rows)
+        } else if self.has_reserved_rows() || self.has_folds() {
+            let mut display_rows = vec![1; self.lines().len()];
+            self.add_reserved_rows(&mut display_rows, cursor.0);
+            self.add_fold_rows(&mut display_rows);
+            let (row, _) =
+                next_scroll_row_wrapped((top_row, 0), (cursor.0 as u16, 0), height, &display_rows);
+            top_row = row;
+            skipped = 0;
+            top_col = next_scroll_top(top_col, cursor_col, width);
+            Some(display_rows)
         } else {
             top_row = next_scroll_top(top_row, cursor.0 as u16, height);
-            top_col = next_scroll_top(top_col, cursor.1 as u16, width);
-        }
-        let (top_row, top_col) = (top_row, top_col);
+            skipped = 0;
+            top_col = next_scroll_top(top_col, cursor_col, width);
+            None
+        };
+        let (top_row, top_col, skipped) = (top_row, top_col, skipped);
 
         // Transform lines into array of row count for each line
-        fn wrapped_rows(lines: &[String], wrap_width: u16, has_lnum: bool) -> Vec<u16> {
+        fn wrapped_rows(
+            lines: &[String],
+            wrap_width: u16,
+            has_lnum: bool,
+            sign_width: u16,
+            tab_len: u8,
+        ) -> Vec<u16> {
             let num_lines = lines.len();
             lines
                 .iter()
-                .map(|line| line_rows(&line, wrap_width, has_lnum, num_lines))
+                .map(|line| line_rows(line, wrap_width, has_lnum, num_lines, sign_width, tab_len))
                 .collect()
         }
 
-        let (text, style) = if !self.placeholder.is_empty() && self.is_empty() {
-            (self.placeholder_widget(), self.placeholder_style)
+        let (text, style) = if self.placeholder_shown() {
+            let style = self.cue_style(self.placeholder_style, Modifier::DIM);
+            (self.placeholder_widget(), style)
         } else {
-            (self.text_widget(top_row as _, height as _), self.style())
+            let style = self.cue_style(self.style(), Modifier::empty());
+            (self.text_widget(top_row as _, height as _), style)
         };
+        /// Calculate the next top row and the number of its wrapped rows hidden above the viewport so that the cursor
+        /// is on the screen. The whole cursor line is put on the screen when it fits in the viewport. Otherwise the
+        /// viewport starts in the middle of the cursor line.
         fn next_scroll_row_wrapped(
-            prev_top_row: u16,
-            cursor_row: u16,
+            (prev_top_row, prev_skipped): (u16, u16),
+            (cursor_row, cursor_wrap): (u16, u16),
             viewport_height: u16,
-            wrapped_rows: &Vec<u16>,
-        ) -> u16 {
-            if cursor_row < prev_top_row {
-                return cursor_row;
-            } else {
-                // Calculate the number of wrap rows between the top row and the cursor row
-                // TODO: Clarify why +1 is needed
-                let rows_from_top_to_cursor = wrapped_rows
-                    [prev_top_row as usize..cursor_row as usize]
-                    .iter()
-                    .sum::<u16>()
-                    + 1;
-                let cursor_row_wraps = wrapped_rows[cursor_row as usize] - 1;
-                let cursor_line_on_screen =
-                    rows_from_top_to_cursor + cursor_row_wraps <= viewport_height;
-                let rows_to_move =
-                    (rows_from_top_to_cursor + cursor_row_wraps).saturating_sub(viewport_height);
-
-                if !cursor_line_on_screen {
-                    // Count how many lines add up to enough rows to get entire cursor line on screen again
-                    let lines_to_move = wrapped_rows[prev_top_row as usize..cursor_row as usize]
-                        .iter()
-                        .scan(0, |acc, &row| {
-                            // Sum wrap rows to this line
-                            *acc += row;
-                            Some(*acc)
-                        })
-                        // Return index of line where acc exceeds rows_to_move
-                        .position(|sum| sum >= rows_to_move)
-                        .unwrap_or(0) as u16;
-                    let lines_to_move = lines_to_move + 1; // Convert from index
-
-                    // Never move below cursor row in case terminal can't fit it
-                    return (prev_top_row + lines_to_move).min(cursor_row);
+            wrapped_rows: &[u16],
+        ) -> (u16, u16) {
+            // Hidden rows of the cursor line when it is the top line
+            let skipped_in_cursor_line = |skipped: u16| {
+                if cursor_wrap < skipped {
+                    cursor_wrap
+                } else if skipped + viewport_height <= cursor_wrap {
+                    cursor_wrap + 1 - viewport_height
                 } else {
-                    return prev_top_row;
+                    skipped
                 }
             };
+            if cursor_row < prev_top_row {
+                return (cursor_row, skipped_in_cursor_line(0));
+            }
+            // The line may have been shortened since the last rendering
+            let prev_skipped = cmp::min(
+                prev_skipped,
+                wrapped_rows[prev_top_row as usize].saturating_sub(1),
+            );
+            if cursor_row == prev_top_row {
+                return (cursor_row, skipped_in_cursor_line(prev_skipped));
+            }
+
+            // Move the top row until the entire cursor line is on the screen, but never below the cursor row
+            let (mut top_row, mut skipped) = (prev_top_row, prev_skipped);
+            let mut rows_to_bottom = wrapped_rows[top_row as usize..=cursor_row as usize]
+                .iter()
+                .map(|&r| r as usize)
+                .sum::<usize>()
+                - skipped as usize;
+            while rows_to_bottom > viewport_height as usize && top_row < cursor_row {
+                rows_to_bottom -= (wrapped_rows[top_row as usize] - skipped) as usize;
+                top_row += 1;
+                skipped = 0;
+            }
+            if top_row == cursor_row {
+                skipped = skipped_in_cursor_line(0);
+            }
+            (top_row, skipped)
         }
 
         // To get fine control over the text color and the surrrounding block they have to be rendered separately
         // see https://github.com/ratatui/ratatui/issues/144
-        let mut text_area = area;
         let mut inner = Paragraph::new(text)
             .style(style)
             .alignment(self.alignment());
@@ -223,21 +828,118 @@ impl Widget for &TextArea<'_> {
             inner = inner.wrap(Wrap { trim: false });
         }
         if let Some(b) = self.block() {
-            text_area = b.inner(area);
             // ratatui does not need `clone()` call because `Block` implements `WidgetRef` and `&T` implements `Widget`
             // where `T: WidgetRef`. So `b.render` internally calls `b.render_ref` and it doesn't move out `self`.
             #[cfg(feature = "tuirs")]
             let b = b.clone();
             b.render(area, buf)
         }
-        if top_col != 0 {
-            inner = inner.scroll((0, top_col));
+        if top_col != 0 || skipped != 0 {
+            inner = inner.scroll((skipped, top_col));
         }
-        // TODO: Vertical scroll to position top edge in middle of wrapped line
 
         // Store scroll top position for rendering on the next tick
         self.viewport.store(top_row, top_col, width, height);
+        self.viewport.store_skipped_rows(skipped);
+        self.viewport.store_origin(text_area.x, text_area.y);
 
         inner.render(text_area, buf);
+        self.render_color_column(text_area, buf);
+        if !self.cursor_hidden() && !self.placeholder_shown() {
+            self.render_cursor_line_and_column(text_area, buf);
+        }
+
+        #[cfg(feature = "ratatui")]
+        self.render_scrollbars(
+            scrollbar_areas,
+            display_rows.as_deref(),
+            (top_row, top_col),
+            buf,
+        );
+
+        self.render_completion(buf);
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl TextArea<'_> {
+    /// Split the area into the text area and the areas of the vertical and horizontal scrollbars. The horizontal
+    /// scrollbar is not shown when the text is wrapped.
+    fn split_scrollbar_areas(&self, area: Rect) -> (Rect, (Option<Rect>, Option<Rect>)) {
+        let mut text_area = area;
+        let vertical = if self.vertical_scrollbar() && text_area.width > 1 {
+            text_area.width -= 1;
+            Some(Rect {
+                x: text_area.right(),
+                width: 1,
+                ..text_area
+            })
+        } else {
+            None
+        };
+        let horizontal = if self.horizontal_scrollbar() && !self.get_wrap() && text_area.height > 1
+        {
+            text_area.height -= 1;
+            Some(Rect {
+                y: text_area.bottom(),
+                height: 1,
+                ..text_area
+            })
+        } else {
+            None
+        };
+        (text_area, (vertical, horizontal))
+    }
+
+    fn render_scrollbars(
+        &self,
+        (vertical, horizontal): (Option<Rect>, Option<Rect>),
+        display_rows: Option<&[u16]>,
+        (top_row, top_col): (u16, u16),
+        buf: &mut Buffer,
+    ) {
+        use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget};
+
+        // Content length is the number of scroll positions so that the thumb reaches the end of the track when the
+        // last row is at the bottom of the viewport
+        let mut render = |area: Rect, orientation, total: usize, position: usize, viewport: u16| {
+            let content = total.saturating_sub(viewport as usize) + 1;
+            let mut state = ScrollbarState::new(content)
+                .position(position)
+                .viewport_content_length(viewport as usize);
+            Scrollbar::new(orientation)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(self.cue_style(self.scrollbar_style(), Modifier::empty()))
+                .render(area, buf, &mut state);
+        };
+
+        if let Some(area) = vertical {
+            let (total, position) = match display_rows {
+                Some(rows) => (
+                    rows.iter().map(|&r| r as usize).sum(),
+                    rows[..top_row as usize]
+                        .iter()
+                        .map(|&r| r as usize)
+                        .sum::<usize>()
+                        + self.viewport.skipped_rows() as usize,
+                ),
+                None => (self.lines().len(), top_row as usize),
+            };
+            let orientation = ScrollbarOrientation::VerticalRight;
+            render(area, orientation, total, position, area.height);
+        }
+
+        if let Some(area) = horizontal {
+            let tab = self.tab_width();
+            // +1 for the cursor at the end of line
+            let total = (0..self.lines().len())
+                .map(|row| display_width(&self.text_with_annotation(row), usize::MAX, tab) + 1)
+                .max()
+                .unwrap_or(0)
+                + self.gutter_width();
+            let orientation = ScrollbarOrientation::HorizontalBottom;
+            render(area, orientation, total, top_col as usize, area.width);
+        }
     }
 }
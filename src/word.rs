@@ -1,3 +1,18 @@
+/// Rule to classify characters on finding word boundaries.
+#[derive(Clone, Copy, Default)]
+pub struct WordClass<'a> {
+    /// Punctuations which are a part of word. See `TextArea::set_word_chars`
+    word_chars: &'a str,
+    /// Words are separated only by whitespaces like `WORD` of Vim
+    big: bool,
+}
+
+impl<'a> WordClass<'a> {
+    pub fn new(word_chars: &'a str, big: bool) -> Self {
+        Self { word_chars, big }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum CharKind {
     Space,
@@ -6,10 +21,10 @@ enum CharKind {
 }
 
 impl CharKind {
-    fn new(c: char) -> Self {
+    fn new(c: char, class: WordClass<'_>) -> Self {
         if c.is_whitespace() {
             Self::Space
-        } else if c.is_ascii_punctuation() {
+        } else if c.is_ascii_punctuation() && !class.big && !class.word_chars.contains(c) {
             Self::Punct
         } else {
             Self::Other
@@ -17,11 +32,20 @@ impl CharKind {
     }
 }
 
-pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
+/// Characters of an identifier-like word such as `foo_1`. Unlike word motions, `_` is a part of word.
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+pub fn find_word_start_forward(
+    line: &str,
+    start_col: usize,
+    class: WordClass<'_>,
+) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
+    let mut prev = CharKind::new(it.next()?.1, class);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = CharKind::new(c, class);
         if cur != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -30,11 +54,15 @@ pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
     None
 }
 
-pub fn find_word_exclusive_end_forward(line: &str, start_col: usize) -> Option<usize> {
+pub fn find_word_exclusive_end_forward(
+    line: &str,
+    start_col: usize,
+    class: WordClass<'_>,
+) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
+    let mut prev = CharKind::new(it.next()?.1, class);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = CharKind::new(c, class);
         if prev != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -43,12 +71,16 @@ pub fn find_word_exclusive_end_forward(line: &str, start_col: usize) -> Option<u
     None
 }
 
-pub fn find_word_inclusive_end_forward(line: &str, start_col: usize) -> Option<usize> {
+pub fn find_word_inclusive_end_forward(
+    line: &str,
+    start_col: usize,
+    class: WordClass<'_>,
+) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
     let (mut last_col, c) = it.next()?;
-    let mut prev = CharKind::new(c);
+    let mut prev = CharKind::new(c, class);
     for (col, c) in it {
-        let cur = CharKind::new(c);
+        let cur = CharKind::new(c, class);
         if prev != CharKind::Space && cur != prev {
             return Some(col.saturating_sub(1));
         }
@@ -62,16 +94,20 @@ pub fn find_word_inclusive_end_forward(line: &str, start_col: usize) -> Option<u
     }
 }
 
-pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
+pub fn find_word_start_backward(
+    line: &str,
+    start_col: usize,
+    class: WordClass<'_>,
+) -> Option<usize> {
     let idx = line
         .char_indices()
         .nth(start_col)
         .map(|(i, _)| i)
         .unwrap_or(line.len());
     let mut it = line[..idx].chars().rev().enumerate();
-    let mut cur = CharKind::new(it.next()?.1);
+    let mut cur = CharKind::new(it.next()?.1, class);
     for (i, c) in it {
-        let next = CharKind::new(c);
+        let next = CharKind::new(c, class);
         if cur != CharKind::Space && next != cur {
             return Some(start_col - i);
         }
@@ -79,3 +115,56 @@ pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
     }
     (cur != CharKind::Space).then(|| 0)
 }
+
+/// Find the last end of word before the column. This is similar to `ge` of Vim.
+pub fn find_word_end_backward(line: &str, start_col: usize, class: WordClass<'_>) -> Option<usize> {
+    let kinds: Vec<CharKind> = line
+        .chars()
+        .take(start_col)
+        .map(|c| CharKind::new(c, class))
+        .collect();
+    // The character at `start_col` is the next of the last character in `kinds`
+    let next = line.chars().nth(start_col).map(|c| CharKind::new(c, class));
+    (0..kinds.len()).rev().find(|&col| {
+        let next = kinds.get(col + 1).copied().or(next);
+        kinds[col] != CharKind::Space && next != Some(kinds[col])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_classes() {
+        let word = WordClass::default();
+        let dash = WordClass::new("-", false);
+        let big = WordClass::new("", true);
+        let line = "foo-bar (baz)";
+        assert_eq!(find_word_start_forward(line, 0, word), Some(3));
+        assert_eq!(find_word_start_forward(line, 0, dash), Some(8));
+        assert_eq!(find_word_start_forward(line, 8, dash), Some(9));
+        assert_eq!(find_word_start_forward(line, 8, big), None);
+        assert_eq!(find_word_inclusive_end_forward(line, 0, word), Some(2));
+        assert_eq!(find_word_inclusive_end_forward(line, 0, dash), Some(6));
+        assert_eq!(find_word_start_backward(line, 13, big), Some(8));
+        assert_eq!(find_word_start_backward(line, 7, dash), Some(0));
+    }
+
+    #[test]
+    fn word_end_backward() {
+        let word = WordClass::default();
+        let big = WordClass::new("", true);
+        let line = "foo-bar  (baz)";
+        assert_eq!(find_word_end_backward(line, 14, word), Some(13));
+        assert_eq!(find_word_end_backward(line, 13, word), Some(12));
+        assert_eq!(find_word_end_backward(line, 11, word), Some(9));
+        assert_eq!(find_word_end_backward(line, 9, word), Some(6));
+        assert_eq!(find_word_end_backward(line, 5, word), Some(3));
+        assert_eq!(find_word_end_backward(line, 3, word), Some(2));
+        assert_eq!(find_word_end_backward(line, 2, word), None);
+        assert_eq!(find_word_end_backward(line, 11, big), Some(6));
+        assert_eq!(find_word_end_backward("  a", 2, word), None);
+        assert_eq!(find_word_end_backward("", 0, word), None);
+    }
+}
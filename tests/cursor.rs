@@ -14,6 +14,8 @@ fn empty_textarea() {
         Down,
         Head,
         End,
+        VisualHead,
+        VisualEnd,
         Top,
         Bottom,
         WordForward,
@@ -21,6 +23,9 @@ fn empty_textarea() {
         WordBack,
         ParagraphForward,
         ParagraphBack,
+        ConflictForward,
+        ConflictBack,
+        MatchingBracket,
         Jump(0, 0),
         Jump(u16::MAX, u16::MAX),
     ] {
@@ -110,14 +115,18 @@ fn back() {
 
 #[test]
 fn up() {
-    for text in [["abc", "def", "ghi"], ["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"]]
-    {
+    // Columns in the last line where the cursor is put. The cursor is not put inside the grapheme cluster 🤟🏿
+    for (text, last_cols) in [
+        (["abc", "def", "ghi"], [0, 1, 2, 3]),
+        (["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"], [0, 1, 1, 3]),
+    ] {
         let mut t = TextArea::from(text);
 
-        for col in 0..=3 {
+        for (col, &last_col) in last_cols.iter().enumerate() {
             let mut row = 2;
 
             t.move_cursor(CursorMove::Jump(2, col as u16));
+            let col = last_col;
             assert_eq!(t.cursor(), (row, col), "{:?}", t.lines());
 
             while row > 0 {
@@ -129,6 +138,20 @@ fn up() {
     }
 }
 
+#[test]
+fn vertical_grapheme_clusters() {
+    // Skin tone modifiers and ZWJ sequences are single grapheme clusters. The cursor is not put inside them.
+    let mut t = TextArea::from(["abcde", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾", "abcde"]);
+    for (col, expected) in [(0, 0), (1, 1), (2, 1), (3, 3), (4, 3), (5, 3)] {
+        t.move_cursor(CursorMove::Jump(0, col));
+        t.move_cursor(CursorMove::Down);
+        assert_eq!(t.cursor(), (1, expected), "col={}", col);
+        t.move_cursor(CursorMove::Jump(2, col));
+        t.move_cursor(CursorMove::Up);
+        assert_eq!(t.cursor(), (1, expected), "col={}", col);
+    }
+}
+
 #[test]
 fn up_trim() {
     for text in [["", "a", "bcd", "efgh"], ["", "👪", "🐶!🐱", "あ?い!"]] {
@@ -144,11 +167,13 @@ fn up_trim() {
 
 #[test]
 fn down() {
-    for text in [["abc", "def", "ghi"], ["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"]]
-    {
+    for (text, last_cols) in [
+        (["abc", "def", "ghi"], [0, 1, 2, 3]),
+        (["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"], [0, 1, 1, 3]),
+    ] {
         let mut t = TextArea::from(text);
 
-        for col in 0..=3 {
+        for (col, &last_col) in last_cols.iter().enumerate() {
             let mut row = 0;
 
             t.move_cursor(CursorMove::Jump(0, col as u16));
@@ -157,6 +182,7 @@ fn down() {
             while row < 2 {
                 t.move_cursor(CursorMove::Down);
                 row += 1;
+                let col = if row == 2 { last_col } else { col };
                 assert_eq!(t.cursor(), (row, col), "{:?}", t.lines());
             }
         }
@@ -213,14 +239,21 @@ fn end() {
 
 #[test]
 fn top() {
-    for text in [["abc", "def", "ghi"], ["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"]]
-    {
+    for (text, last_cols) in [
+        (["abc", "def", "ghi"], [0, 1, 2, 3]),
+        (["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"], [0, 1, 1, 3]),
+    ] {
         let mut t = TextArea::from(text);
         for row in 0..=2 {
             for col in 0..=3 {
                 t.move_cursor(CursorMove::Jump(row, col));
                 t.move_cursor(CursorMove::Top);
-                assert_eq!(t.cursor(), (0, col as usize), "{:?}", t.lines());
+                let col = if row == 2 {
+                    last_cols[col as usize]
+                } else {
+                    col as usize
+                };
+                assert_eq!(t.cursor(), (0, col), "{:?}", t.lines());
             }
         }
     }
@@ -244,14 +277,17 @@ fn top_trim() {
 
 #[test]
 fn bottom() {
-    for text in [["abc", "def", "ghi"], ["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"]]
-    {
+    for (text, last_cols) in [
+        (["abc", "def", "ghi"], [0, 1, 2, 3]),
+        (["あいう", "🐶🐱🐰", "👪🤟🏿👩🏻‍❤️‍💋‍👨🏾"], [0, 1, 1, 3]),
+    ] {
         let mut t = TextArea::from(text);
         for row in 0..=2 {
             for col in 0..=3 {
                 t.move_cursor(CursorMove::Jump(row, col));
                 t.move_cursor(CursorMove::Bottom);
-                assert_eq!(t.cursor(), (2, col as usize), "{:?}", t.lines());
+                let col = last_cols[col as usize];
+                assert_eq!(t.cursor(), (2, col), "{:?}", t.lines());
             }
         }
     }
@@ -326,3 +362,63 @@ fn word_end() {
         }
     }
 }
+
+#[test]
+fn conflict_forward_back() {
+    #[rustfmt::skip]
+    let mut t = TextArea::from([
+        "a",
+        "<<<<<<< HEAD", "b", "=======", "c", ">>>>>>> topic",
+        "d",
+        "<<<<<<< HEAD", "e", "||||||| base", "f", "=======", "g", ">>>>>>> topic",
+        "h",
+    ]);
+
+    t.move_cursor(CursorMove::Jump(0, 1));
+    for pos in [(1, 0), (7, 0), (7, 0)] {
+        t.move_cursor(CursorMove::ConflictForward);
+        assert_eq!(t.cursor(), pos);
+    }
+
+    t.move_cursor(BOTTOM_RIGHT);
+    for pos in [(7, 0), (1, 0), (1, 0)] {
+        t.move_cursor(CursorMove::ConflictBack);
+        assert_eq!(t.cursor(), pos);
+    }
+
+    // In a conflict region, move to the start of the region
+    t.move_cursor(CursorMove::Jump(10, 0));
+    t.move_cursor(CursorMove::ConflictBack);
+    assert_eq!(t.cursor(), (7, 0));
+}
+
+#[test]
+fn matching_bracket() {
+    #[rustfmt::skip]
+    let mut t = TextArea::from([
+        "fn f() {",
+        "    g([1, (2)]);",
+        "}",
+    ]);
+
+    for (from, to) in [
+        ((0, 4), (0, 5)),
+        ((0, 5), (0, 4)),
+        ((0, 7), (2, 0)),
+        ((2, 0), (0, 7)),
+        ((1, 5), (1, 14)),
+        ((1, 6), (1, 13)),
+        ((1, 10), (1, 12)),
+        ((1, 0), (1, 0)),
+        ((0, 8), (0, 8)),
+    ] {
+        t.move_cursor(CursorMove::Jump(from.0, from.1));
+        t.move_cursor(CursorMove::MatchingBracket);
+        assert_eq!(t.cursor(), to, "{:?}", from);
+    }
+
+    t.set_bracket_pairs([('(', ')')]);
+    t.move_cursor(CursorMove::Jump(0, 7));
+    t.move_cursor(CursorMove::MatchingBracket);
+    assert_eq!(t.cursor(), (0, 7));
+}
@@ -1,4 +1,5 @@
-use tui_textarea::TextArea;
+use std::time::Duration;
+use tui_textarea::{CursorMove, TextArea, UndoCoalescing};
 
 // Regression test for #4
 #[test]
@@ -8,3 +9,126 @@ fn disable_history() {
     assert!(t.insert_str("hello"));
     assert_eq!(t.lines(), ["hello"]);
 }
+
+#[test]
+fn undo_group() {
+    let mut t = TextArea::from(["abc"]);
+    t.begin_undo_group();
+    t.insert_char('x');
+    t.begin_undo_group(); // Nested group is merged into outer one
+    t.insert_newline();
+    t.end_undo_group();
+    t.delete_str(1);
+    t.end_undo_group();
+    t.insert_char('y');
+    assert_eq!(t.lines(), ["x", "ybc"]);
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x", "bc"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.undo());
+
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["x", "bc"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["x", "ybc"]);
+}
+
+#[test]
+fn empty_undo_group() {
+    let mut t = TextArea::default();
+    t.insert_char('a');
+    t.begin_undo_group();
+    t.end_undo_group();
+    t.insert_char('b');
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a"]);
+}
+
+#[test]
+fn undo_group_counted_as_one_history() {
+    let mut t = TextArea::default();
+    t.set_max_histories(2);
+    for s in ["a", "b", "c"] {
+        t.begin_undo_group();
+        t.insert_str(s);
+        t.insert_str(s);
+        t.end_undo_group();
+    }
+    assert!(t.undo());
+    assert!(t.undo());
+    assert!(!t.undo());
+    assert_eq!(t.lines(), ["aa"]);
+}
+
+#[test]
+fn coalesce_by_word() {
+    let mut t = TextArea::default();
+    t.set_undo_coalescing(UndoCoalescing::Word);
+    for c in "ab cd".chars() {
+        t.insert_char(c);
+    }
+    // Moving the cursor breaks the coalescing
+    t.move_cursor(CursorMove::Head);
+    t.insert_char('x');
+    t.insert_char('y');
+    assert_eq!(t.lines(), ["xyab cd"]);
+
+    for want in ["ab cd", "ab ", ""] {
+        assert!(t.undo());
+        assert_eq!(t.lines(), [want]);
+    }
+
+    for c in "abc".chars() {
+        t.insert_char(c);
+    }
+    t.delete_char();
+    t.delete_char();
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+
+    t.insert_char('d');
+    t.begin_undo_group();
+    t.insert_char('e');
+    t.end_undo_group();
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcd"]);
+}
+
+#[test]
+fn coalesce_by_time() {
+    let mut t = TextArea::default();
+    t.set_undo_coalescing(UndoCoalescing::Time(Duration::from_secs(60)));
+    t.insert_char('a');
+    t.insert_char(' ');
+    t.insert_char('b');
+    t.insert_str("cd"); // Inserting string is not merged
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a b"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+
+    t.set_undo_coalescing(UndoCoalescing::Time(Duration::ZERO));
+    t.insert_char('a');
+    std::thread::sleep(Duration::from_millis(1));
+    t.insert_char('b');
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a"]);
+
+    // Coalescing policy is kept after resetting history
+    t.set_max_histories(10);
+    assert_eq!(t.undo_coalescing(), UndoCoalescing::Time(Duration::ZERO));
+
+    // The first edit in an undo group is not merged into the previous step
+    t.set_undo_coalescing(UndoCoalescing::Time(Duration::from_secs(60)));
+    t.insert_char('a');
+    t.begin_undo_group();
+    t.insert_char('b');
+    t.insert_char('c');
+    t.end_undo_group();
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["aa"]);
+}
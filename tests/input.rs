@@ -40,6 +40,7 @@ fn test_input_all_combinations_sanity() {
         Down,
         Tab,
         Delete,
+        Insert,
         Home,
         End,
         PageUp,
@@ -0,0 +1,330 @@
+#![cfg(feature = "ratatui")]
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget as _;
+use tui_textarea::{CompletionItem, CursorMove, ExportFormat, Input, Key, Messages, TextArea};
+
+fn render(t: &TextArea, w: u16, h: u16) -> Buffer {
+    let r = Rect::new(0, 0, w, h);
+    let mut b = Buffer::empty(r);
+    t.render(r, &mut b);
+    b
+}
+
+fn render_rows(t: &TextArea, w: u16, h: u16) -> Vec<String> {
+    render(t, w, h)
+        .content
+        .chunks(w as usize)
+        .map(|row| row.iter().map(|c| c.symbol()).collect())
+        .collect()
+}
+
+fn click(t: &mut TextArea, x: u16, y: u16) -> (usize, usize) {
+    t.input(Input {
+        key: Key::MouseClick(x, y),
+        ..Default::default()
+    });
+    t.cursor()
+}
+
+#[test]
+fn test_jump_labels_after_text_shrunk() {
+    let mut t = TextArea::from((0..100).map(|i| format!("line {}", i)));
+    t.move_cursor(CursorMove::Bottom);
+    render(&t, 10, 5);
+
+    // The viewport of the last render is out of the text
+    t.select_all();
+    t.cut();
+    assert!(!t.start_jump_labels());
+    assert!(!t.is_jumping());
+}
+
+#[test]
+fn test_gutter_signs() {
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.set_cursor_hidden(true);
+    assert_eq!(render_rows(&t, 10, 3)[0], "a         ");
+
+    t.set_gutter_sign(1, Span::raw("●"));
+    t.set_gutter_sign(2, Span::raw("++"));
+    assert_eq!(
+        render_rows(&t, 10, 3),
+        ["  a       ", "● b       ", "++c       "],
+    );
+
+    // Signs are put before line numbers
+    t.set_line_number_style(Style::default());
+    assert_eq!(
+        render_rows(&t, 10, 3),
+        ["   1 a    ", "●  2 b    ", "++ 3 c    "],
+    );
+
+    // Clicking the gutter moves the cursor to the head of line
+    assert_eq!(t.text_to_screen(1, 0), Some((5, 1)));
+    assert_eq!(t.screen_to_text(1, 1), Some((1, 0)));
+}
+
+#[test]
+fn test_completion_popup() {
+    let mut t = TextArea::from(["x = fo"]);
+    t.move_cursor(CursorMove::End);
+    let items = vec![
+        CompletionItem::new("foo").detail("fn"),
+        CompletionItem::new("fold"),
+        CompletionItem::new("bar"),
+    ];
+    assert!(t.open_completion(items));
+    // The popup is shifted to the left to fit in the screen
+    assert_eq!(
+        render_rows(&t, 10, 4),
+        ["x = fo    ", "  foo  fn ", "  fold    ", "          "],
+    );
+
+    t.input(Input {
+        key: Key::Down,
+        ..Default::default()
+    });
+    t.input(Input {
+        key: Key::Char('l'),
+        ..Default::default()
+    });
+    assert_eq!(
+        render_rows(&t, 10, 4),
+        ["x = fol   ", "    fold  ", "          ", "          "],
+    );
+
+    // Rendered above the word when no space below
+    let mut t = TextArea::from(["", "", "x = fo"]);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    assert!(t.open_completion(vec![CompletionItem::new("foo")]));
+    assert_eq!(
+        render_rows(&t, 10, 3),
+        ["          ", "    foo   ", "x = fo    "],
+    );
+}
+
+#[test]
+fn test_fold_rendering() {
+    let mut t: TextArea = (0..10).map(|i| format!("line{}", i)).collect();
+    assert!(t.fold(2..8));
+    assert_eq!(
+        render_rows(&t, 12, 5),
+        [
+            "line0       ",
+            "line1       ",
+            "⋯ 6 lines   ",
+            "line8       ",
+            "line9       ",
+        ],
+    );
+
+    // Scrolling takes the folded lines into account
+    t.move_cursor(CursorMove::Bottom);
+    assert_eq!(
+        render_rows(&t, 12, 3),
+        ["⋯ 6 lines   ", "line8       ", "line9       "],
+    );
+    assert_eq!(t.text_to_screen(9, 0), Some((0, 2)));
+    assert_eq!(t.text_to_screen(4, 0), None);
+
+    // Line numbers are not shown for the placeholder
+    t.set_line_number_style(Style::default());
+    let mut messages = Messages::default();
+    messages.folded_lines = "+{lines}".into();
+    t.set_messages(messages);
+    assert_eq!(
+        render_rows(&t, 12, 3),
+        ["    +6      ", "  9 line8   ", " 10 line9   "],
+    );
+
+    // Clicking the placeholder unfolds the lines
+    assert_eq!(click(&mut t, 5, 0), (2, 0));
+    assert!(t.folds().is_empty());
+    assert_eq!(
+        render_rows(&t, 12, 3),
+        ["  3 line2   ", "  4 line3   ", "  5 line4   "],
+    );
+}
+
+#[test]
+#[cfg(feature = "search")]
+fn test_replace_preview_rendering() {
+    let mut t = TextArea::from(["let foo = 1;", "foo"]);
+    t.set_search_pattern("foo").unwrap();
+    t.set_search_replacement(Some("bar"));
+    assert_eq!(render_rows(&t, 12, 2), ["let foo = 1;", "foo         "]);
+
+    t.search_forward(false);
+    assert_eq!(render_rows(&t, 12, 2), ["let foobar =", "foo         "]);
+
+    t.search_forward(false);
+    assert_eq!(render_rows(&t, 12, 2), ["let foo = 1;", "foobar      "]);
+
+    t.set_mask_char('*');
+    assert_eq!(render_rows(&t, 12, 2), ["************", "***         "]);
+}
+
+#[test]
+fn test_color_column_and_comments() {
+    let cells = |t: &TextArea| {
+        render(t, 6, 2)
+            .content
+            .iter()
+            .map(|c| (c.bg, c.modifier))
+            .collect::<Vec<_>>()
+    };
+    const E: (Color, Modifier) = (Color::Reset, Modifier::empty());
+    const C: (Color, Modifier) = (Color::DarkGray, Modifier::empty());
+    const R: (Color, Modifier) = (Color::Reset, Modifier::REVERSED);
+    const D: (Color, Modifier) = (Color::Reset, Modifier::DIM);
+    const DC: (Color, Modifier) = (Color::DarkGray, Modifier::DIM);
+
+    let mut t = TextArea::from(["ab", "#cd"]);
+    t.set_cursor_line_style(Style::default());
+    t.set_color_column(2);
+    // The cursor cell is not highlighted
+    assert_eq!(cells(&t), [R, E, C, E, E, E, E, E, C, E, E, E]);
+
+    t.set_comment_prefix("#");
+    t.move_cursor(CursorMove::End);
+    assert_eq!(cells(&t), [E, E, R, E, E, E, D, D, DC, E, E, E]);
+
+    // The column is not drawn when it is scrolled out of the view
+    t.clear_comment_prefix();
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.insert_str("12345678");
+    assert_eq!(cells(&t), [E, E, E, E, E, R, E, E, E, E, E, E]);
+
+    t.clear_color_column();
+    t.move_cursor(CursorMove::Head);
+    assert_eq!(cells(&t)[2], E);
+}
+
+#[test]
+fn test_prompt_rendering() {
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.set_prompt("> ", "... ");
+    assert_eq!(render_rows(&t, 8, 3), [">   ab  ", "... cd  ", "        "]);
+
+    // Clicking the prompt moves the cursor to the head of line
+    assert_eq!(click(&mut t, 1, 1), (1, 0));
+    assert_eq!(click(&mut t, 5, 1), (1, 1));
+    assert_eq!(t.text_to_screen(1, 2), Some((6, 1)));
+
+    // The prompt width is taken into account on wrapping
+    t.set_wrap(true);
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.insert_str("0123");
+    assert_eq!(render_rows(&t, 8, 3), [">       ", "0123ab  ", "... cd  "]);
+}
+
+#[test]
+fn test_eol_annotation_rendering() {
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.set_cursor_line_style(Style::default());
+    t.set_eol_annotation(0, "hint");
+    assert_eq!(render_rows(&t, 8, 3), ["ab hint ", "cd      ", "        "]);
+    assert_eq!(render(&t, 8, 3)[(3, 0)].modifier, Modifier::DIM);
+
+    // The annotation cannot be clicked and the cursor at the end of line is put before it
+    assert_eq!(click(&mut t, 5, 0), (0, 2));
+    assert_eq!(t.text_to_screen(0, 2), Some((2, 0)));
+    assert_eq!(render_rows(&t, 8, 3)[0], "ab hint ");
+
+    // The annotation is wrapped with the line
+    t.set_wrap(true);
+    t.set_eol_annotation(0, "long hint");
+    assert_eq!(render_rows(&t, 8, 3), ["ab long ", "hint    ", "cd      "]);
+    assert_eq!(click(&mut t, 3, 1), (0, 2));
+    assert_eq!(t.text_to_screen(1, 0), Some((0, 2)));
+
+    t.clear_eol_annotations();
+    assert_eq!(render_rows(&t, 8, 3), ["ab      ", "cd      ", "        "]);
+}
+
+#[test]
+fn test_line_hooks() {
+    let mut t = TextArea::from(["abc", "---", "def"]);
+    t.set_before_line_hook(|_, line| (line == "---").then(|| Line::from("===")));
+    t.set_after_line_hook(|row, line| {
+        if row != 0 {
+            line.spans.push(Span::raw(" !"));
+        }
+    });
+    let want = ["abc     ", "=== !   ", "def !   "];
+    assert_eq!(render_rows(&t, 8, 3), want);
+    // Spans added by the hook are not cached
+    assert_eq!(render_rows(&t, 8, 3), want);
+
+    t.clear_before_line_hook();
+    assert_eq!(render_rows(&t, 8, 3), ["abc     ", "--- !   ", "def !   "]);
+    t.clear_after_line_hook();
+    assert_eq!(render_rows(&t, 8, 3), ["abc     ", "---     ", "def     "]);
+}
+
+#[test]
+#[cfg(feature = "search")]
+fn test_line_filter_rendering() {
+    let mut t: TextArea = (0..10).map(|i| format!("line{}", i)).collect();
+    t.set_line_number_style(Style::default());
+    t.move_cursor(CursorMove::Jump(4, 0));
+    assert_eq!(t.set_line_filter("[1357]"), Ok(4));
+    // The cursor moved to the nearest shown line above
+    assert_eq!(t.cursor(), (3, 5));
+    assert_eq!(
+        render_rows(&t, 12, 5),
+        [
+            "  2 line1   ",
+            "  4 line3   ",
+            "  6 line5   ",
+            "  8 line7   ",
+            "            ",
+        ],
+    );
+    assert_eq!(
+        t.render_linear(),
+        "   2: line1\n>  4: line3\n   6: line5\n   8: line7\n",
+    );
+
+    // Folds are kept and the filter is applied to the text when it is set
+    t.clear_line_filter();
+    assert!(t.fold(0..2));
+    t.insert_str("x");
+    assert_eq!(t.set_line_filter("[x5]"), Ok(2));
+    assert_eq!(t.line_filter_matches(), [(3, 1), (5, 1)]);
+    assert_eq!(
+        render_rows(&t, 12, 3),
+        ["    ⋯ 2 line", "  4 line3x  ", "  6 line5   "],
+    );
+    assert_eq!(t.folds(), vec![(0..2)]);
+
+    // No line matches
+    assert_eq!(t.set_line_filter("nothing"), Ok(0));
+    assert_eq!(t.line_filter(), Some("nothing"));
+    assert_eq!(render_rows(&t, 12, 3)[1], "  3 line2   ");
+    assert!(t.set_line_filter("(").is_err());
+    assert_eq!(t.line_filter(), Some("nothing"));
+    t.unfold_all();
+    assert_eq!(t.line_filter(), None);
+    assert!(t.line_filter_matches().is_empty());
+}
+
+#[test]
+fn test_export_styled_lines() {
+    let mut t = TextArea::from(["a", "b"]);
+    t.set_line_number_style(Style::default().fg(Color::DarkGray));
+    t.set_cursor_line_style(Style::default().add_modifier(Modifier::UNDERLINED));
+    assert_eq!(
+        t.export(ExportFormat::Ansi),
+        "\x1b[90m 1 \x1b[0ma\n\x1b[90m 2 \x1b[0mb\n",
+    );
+    assert_eq!(
+        t.export_rows(1..2, ExportFormat::Html),
+        "<pre class=\"tui-textarea\"><span style=\"color:#7f7f7f\"> 2 </span>b\n</pre>\n",
+    );
+}
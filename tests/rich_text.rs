@@ -0,0 +1,33 @@
+#![cfg(feature = "rich-text")]
+
+use tui_textarea::{CursorMove, RichTextFormat, TextArea};
+
+#[test]
+fn insert_rich_text_at_cursor() {
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.insert_rich_text("<i>x</i><br><u>y</u>z", RichTextFormat::Html));
+    assert_eq!(t.lines(), ["ax", "yzb"]);
+    assert_eq!(t.cursor(), (1, 2));
+    assert_eq!(t.highlight_tags(), ["rich-text"]);
+
+    // Undo removes the inserted text at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    t.clear_highlights("rich-text");
+
+    // Only markup
+    assert!(!t.insert_rich_text("<b></b>", RichTextFormat::Html));
+    assert!(t.highlight_tags().is_empty());
+
+    // Styles are dropped when the text is constrained
+    t.set_max_length(3);
+    assert!(t.insert_rich_text("**xyz**", RichTextFormat::Markdown));
+    assert_eq!(t.lines(), ["axb"]);
+    assert!(t.highlight_tags().is_empty());
+
+    let mut t = TextArea::default();
+    t.set_single_line(true);
+    assert!(t.insert_rich_text("<p>a</p><p>b</p>", RichTextFormat::Html));
+    assert_eq!(t.lines(), ["a b"]);
+}
@@ -0,0 +1,91 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use tui_textarea::{SaveOptions, TextArea};
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("tui-textarea-{}-{}", std::process::id(), name));
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    fn files(&self) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(&self.0)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn save_atomically() {
+    let dir = TempDir::new("save-atomic");
+    let path = dir.0.join("a.txt");
+    let mut t = TextArea::from("hello\r\nworld\r\n".split('\n'));
+
+    t.save_to_path(&path, SaveOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hello\r\nworld\r\n");
+
+    t.insert_char('!');
+    t.save_to_path(&path, SaveOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "!hello\r\nworld\r\n");
+    assert_eq!(dir.files(), ["a.txt"]); // No temporary file remains
+}
+
+#[test]
+fn save_with_backup() {
+    let dir = TempDir::new("save-backup");
+    let path = dir.0.join("b.txt");
+    fs::write(&path, "old").unwrap();
+
+    let mut options = SaveOptions::default();
+    options.backup = true;
+    options.atomic_rename = false;
+    options.fsync = false;
+    let mut t = TextArea::from(["new"]);
+    t.save_to_path(&path, options).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    assert_eq!(fs::read_to_string(dir.0.join("b.txt~")).unwrap(), "old");
+    assert_eq!(dir.files(), ["b.txt", "b.txt~"]);
+}
+
+#[test]
+fn save_to_missing_dir() {
+    let dir = TempDir::new("save-missing");
+    let path = dir.0.join("no-such-dir").join("c.txt");
+    let mut t = TextArea::from(["c"]);
+    assert!(t.save_to_path(&path, SaveOptions::default()).is_err());
+    assert!(dir.files().is_empty());
+}
+
+#[test]
+fn save_trimming_trailing_whitespace() {
+    let dir = TempDir::new("save-trim");
+    let path = dir.0.join("d.yml");
+    let mut t = TextArea::from(["a: 1  ", "b:\t", ""]);
+    assert!(t.set_filetype_from_path(&path));
+    assert!(t.trim_trailing_whitespace());
+    t.save_to_path(&path, SaveOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a: 1\nb:\n");
+
+    // Trimming is one undo step
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a: 1  ", "b:\t", ""]);
+
+    let path = dir.0.join("e.md");
+    assert!(t.set_filetype_from_path(&path));
+    t.save_to_path(&path, SaveOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a: 1  \nb:\t\n");
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "search")]
 
-use tui_textarea::{CursorMove, TextArea};
+use tui_textarea::{CursorMove, IncrementalSearch, SearchStatus, TextArea};
 
 #[test]
 fn search_lines_forward() {
@@ -130,3 +130,216 @@ fn set_search_pattern() {
     assert!(!textarea.search_back(true));
     assert!(!textarea.search_back(false));
 }
+
+#[test]
+fn incremental_search_forward() {
+    let mut textarea = TextArea::from(["abc", "ab", "abcd", "xyz"]);
+    textarea.move_cursor(CursorMove::Jump(1, 1));
+
+    let mut search = IncrementalSearch::new(&mut textarea);
+    assert_eq!(search.pattern(), "");
+    assert!(!search.is_backward());
+
+    for (c, status, pos, selection) in [
+        ('a', SearchStatus::Found, (2, 0), ((2, 0), (2, 1))),
+        ('b', SearchStatus::Found, (2, 0), ((2, 0), (2, 2))),
+        ('c', SearchStatus::Found, (2, 0), ((2, 0), (2, 3))),
+        ('$', SearchStatus::Wrapped, (0, 0), ((0, 0), (0, 3))),
+    ] {
+        assert_eq!(search.push_char(&mut textarea, c), Ok(status), "{:?}", c);
+        assert_eq!(textarea.cursor(), pos, "{:?}", c);
+        assert_eq!(textarea.selection_range(), Some(selection), "{:?}", c);
+        assert_eq!(search.current_match(), Some(pos), "{:?}", c);
+    }
+    assert_eq!(search.pattern(), "abc$");
+    assert_eq!(textarea.search_pattern().unwrap().as_str(), "abc$");
+
+    // Match is not found. The cursor goes back to the start position
+    assert_eq!(
+        search.push_char(&mut textarea, 'x'),
+        Ok(SearchStatus::NotFound),
+    );
+    assert_eq!(textarea.cursor(), (1, 1));
+    assert_eq!(textarea.selection_range(), None);
+    assert_eq!(search.current_match(), None);
+    assert_eq!(search.next_match(&mut textarea), SearchStatus::NotFound);
+
+    assert_eq!(search.pop_char(&mut textarea), Ok(SearchStatus::Wrapped));
+    assert_eq!(search.pop_char(&mut textarea), Ok(SearchStatus::Found));
+    assert_eq!(textarea.cursor(), (2, 0));
+
+    // Invalid pattern keeps the previous matches
+    assert!(search.push_char(&mut textarea, '(').is_err());
+    assert_eq!(search.pattern(), "abc(");
+    assert_eq!(textarea.search_pattern().unwrap().as_str(), "abc");
+    assert_eq!(textarea.cursor(), (2, 0));
+    assert!(search.pop_char(&mut textarea).is_ok());
+
+    for (status, pos) in [
+        (SearchStatus::Wrapped, (0, 0)),
+        (SearchStatus::Found, (2, 0)),
+    ] {
+        assert_eq!(search.next_match(&mut textarea), status);
+        assert_eq!(textarea.cursor(), pos);
+    }
+    for (status, pos) in [
+        (SearchStatus::Found, (0, 0)),
+        (SearchStatus::Wrapped, (2, 0)),
+    ] {
+        assert_eq!(search.prev_match(&mut textarea), status);
+        assert_eq!(textarea.cursor(), pos);
+    }
+
+    search.finish(&mut textarea);
+    assert_eq!(textarea.cursor(), (2, 0));
+    assert_eq!(textarea.selection_range(), None);
+    assert_eq!(textarea.search_pattern().unwrap().as_str(), "abc");
+}
+
+#[test]
+fn incremental_search_backward() {
+    let mut textarea = TextArea::from(["abc", "ab", "abcd", "xyz"]);
+    textarea.move_cursor(CursorMove::Jump(1, 1));
+
+    let mut search = IncrementalSearch::backward(&mut textarea);
+    assert!(search.is_backward());
+
+    assert_eq!(
+        search.set_pattern(&mut textarea, "ab"),
+        Ok(SearchStatus::Found),
+    );
+    assert_eq!(textarea.cursor(), (1, 0));
+    assert_eq!(
+        search.set_pattern(&mut textarea, "abcd"),
+        Ok(SearchStatus::Wrapped),
+    );
+    assert_eq!(textarea.cursor(), (2, 0));
+    assert_eq!(
+        search.set_pattern(&mut textarea, "abc"),
+        Ok(SearchStatus::Found),
+    );
+    assert_eq!(textarea.cursor(), (0, 0));
+
+    assert_eq!(search.next_match(&mut textarea), SearchStatus::Wrapped);
+    assert_eq!(textarea.cursor(), (2, 0));
+    assert_eq!(search.prev_match(&mut textarea), SearchStatus::Wrapped);
+    assert_eq!(textarea.cursor(), (0, 0));
+}
+
+#[test]
+fn incremental_search_cancel() {
+    let mut textarea = TextArea::from(["abc", "abc"]);
+    textarea.move_cursor(CursorMove::Jump(0, 2));
+
+    let mut search = IncrementalSearch::new(&mut textarea);
+    assert_eq!(search.pop_char(&mut textarea), Ok(SearchStatus::NotFound));
+    search.push_char(&mut textarea, 'a').unwrap();
+    assert_eq!(textarea.cursor(), (1, 0));
+
+    search.cancel(&mut textarea);
+    assert_eq!(textarea.cursor(), (0, 2));
+    assert_eq!(textarea.selection_range(), None);
+    assert!(textarea.search_pattern().is_none());
+}
+
+#[test]
+fn search_events() {
+    use std::sync::{Arc, Mutex};
+    use tui_textarea::UxEvent;
+
+    let events = Arc::new(Mutex::new(vec![]));
+    let mut textarea = TextArea::from(["abc", "def", "abc"]);
+    let e = events.clone();
+    textarea.set_event_hook(move |event| e.lock().unwrap().push(event));
+
+    textarea.set_search_pattern("abc").unwrap();
+    assert!(textarea.search_forward(false));
+    assert!(textarea.search_forward(false));
+    assert!(textarea.search_back(false));
+    assert!(textarea.search_back(false));
+    textarea.set_search_pattern("xyz").unwrap();
+    assert!(!textarea.search_forward(false));
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            UxEvent::SearchWrapped,
+            UxEvent::SearchWrapped,
+            UxEvent::SearchFailed,
+        ],
+    );
+}
+
+#[test]
+fn replace_search_matches() {
+    let mut textarea = TextArea::from(["foo bar", "bar foo foo"]);
+    textarea.set_search_pattern("fo(o)").unwrap();
+    assert!(!textarea.replace_search_match());
+
+    textarea.set_search_replacement(Some("b${1}z"));
+    assert!(textarea.replace_search_match());
+    assert_eq!(textarea.lines(), ["boz bar", "bar foo foo"]);
+    assert_eq!(textarea.cursor(), (0, 3));
+    assert!(!textarea.replace_search_match());
+
+    let mut search = IncrementalSearch::backward(&mut textarea);
+    search.set_pattern(&mut textarea, "foo").unwrap();
+    textarea.set_search_replacement(Some("boz"));
+    assert_eq!(textarea.cursor(), (1, 8));
+    assert_eq!(search.replace_match(&mut textarea), SearchStatus::Found);
+    assert_eq!(textarea.cursor(), (1, 4));
+    assert_eq!(textarea.lines(), ["boz bar", "bar foo boz"]);
+    search.finish(&mut textarea);
+
+    textarea.undo();
+    assert_eq!(textarea.lines(), ["boz bar", "bar foo foo"]);
+
+    assert_eq!(textarea.replace_all_search_matches(), 2);
+    assert_eq!(textarea.lines(), ["boz bar", "bar boz boz"]);
+    textarea.undo();
+    assert_eq!(textarea.lines(), ["boz bar", "bar foo foo"]);
+
+    textarea.set_search_replacement(None);
+    assert_eq!(textarea.replace_all_search_matches(), 0);
+}
+
+#[test]
+fn replace_search_match_with_newline() {
+    let mut textarea = TextArea::from(["a,b,c", "d,e"]);
+    textarea.set_search_pattern(",").unwrap();
+    textarea.set_search_replacement(Some("\n"));
+    assert_eq!(textarea.replace_all_search_matches(), 3);
+    assert_eq!(textarea.lines(), ["a", "b", "c", "d", "e"]);
+}
+
+#[test]
+fn edit_filtered_lines() {
+    let mut textarea = TextArea::from(["error: a", "info: b", "error: c", "info: d"]);
+    assert_eq!(textarea.set_line_filter("^error"), Ok(2));
+
+    // Edits on the shown lines are applied to the whole text
+    textarea.move_cursor(CursorMove::End);
+    textarea.insert_str("!");
+    textarea.move_cursor(CursorMove::Down);
+    assert_eq!(textarea.cursor(), (2, 7));
+    textarea.move_cursor(CursorMove::End);
+    textarea.insert_newline();
+    textarea.insert_str("error: e");
+    assert_eq!(
+        textarea.lines(),
+        ["error: a!", "info: b", "error: c", "error: e", "info: d"],
+    );
+    // The hidden lines follow the edits
+    assert!(textarea.is_folded(1));
+    assert!(textarea.is_folded(4));
+    assert!(!textarea.is_folded(3));
+
+    // Undo restores the text without showing the hidden lines
+    textarea.undo();
+    textarea.undo();
+    assert_eq!(textarea.cursor(), (2, 8));
+    assert!(textarea.is_folded(1));
+    assert_eq!(textarea.line_filter_matches(), [(0, 1), (2, 1)]);
+
+    textarea.clear_line_filter();
+    assert!(!textarea.is_folded(1));
+}
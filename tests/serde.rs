@@ -1,6 +1,6 @@
 #![cfg(feature = "serde")]
 
-use tui_textarea::{CursorMove, Input, Key, Scrolling};
+use tui_textarea::{CursorMove, Input, Key, Options, Scrolling, TextArea};
 
 #[test]
 fn test_serde_key() {
@@ -45,3 +45,95 @@ fn test_serde_cursor_move() {
     let d: CursorMove = serde_json::from_str(&s).unwrap();
     assert_eq!(d, c);
 }
+
+#[test]
+fn test_serde_textarea() {
+    let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+    textarea.set_hard_tab_indent(true);
+    textarea.set_wrap(true);
+    textarea.set_auto_indent(false);
+    textarea.move_cursor(CursorMove::Bottom);
+    textarea.toggle_bookmark();
+    textarea.insert_str("xyz\n");
+    textarea.delete_line_by_end();
+    textarea.scroll((1, 0));
+    textarea.move_cursor(CursorMove::Jump(1, 1));
+    textarea.start_selection();
+    textarea.move_cursor(CursorMove::End);
+
+    let s = serde_json::to_string(&textarea).unwrap();
+    let mut d: TextArea = serde_json::from_str(&s).unwrap();
+    assert_eq!(d.lines(), textarea.lines());
+    assert_eq!(d.cursor(), (1, 3));
+    assert_eq!(d.selection_range(), Some(((1, 1), (1, 3))));
+    assert_eq!(d.bookmarks(), [3]);
+    assert!(d.hard_tab_indent());
+    assert!(d.get_wrap());
+    assert!(!d.auto_indent());
+    assert_eq!(serde_json::to_string(&d).unwrap(), s);
+
+    assert!(d.undo());
+    assert_eq!(d.lines(), ["abc", "def", "xyz", "ghi"]);
+    assert!(d.undo());
+    assert_eq!(d.lines(), ["abc", "def", "ghi"]);
+    assert!(!d.undo());
+    assert!(d.redo());
+    assert_eq!(d.lines(), ["abc", "def", "xyz", "ghi"]);
+
+    // Empty text and out-of-range positions are fixed up
+    let json = s.replace("\"lines\":[\"abc\",\"def\",\"xyz\",\"\"]", "\"lines\":[]");
+    let mut d: TextArea = serde_json::from_str(&json).unwrap();
+    assert_eq!(d.lines(), [""]);
+    assert_eq!(d.cursor(), (0, 0));
+    assert!(d.bookmarks().is_empty());
+    assert!(!d.undo());
+
+    // Mixed line endings are restored
+    let textarea = TextArea::from("a\r\nb\nc\r\nd".split('\n'));
+    let s = serde_json::to_string(&textarea).unwrap();
+    let d: TextArea = serde_json::from_str(&s).unwrap();
+    assert_eq!(d.text(), "a\r\nb\nc\r\nd");
+}
+
+#[test]
+fn test_serde_textarea_mismatched_history() {
+    let mut textarea = TextArea::from(["hi"]);
+    textarea.insert_str("hello ");
+    textarea.insert_newline();
+    let s = serde_json::to_string(&textarea).unwrap();
+    assert!(s.contains("\"lines\":[\"hello \",\"hi\"]"), "{}", s);
+
+    // The history doesn't match the text after lines are swapped so it is dropped
+    let json = s.replace(
+        "\"lines\":[\"hello \",\"hi\"]",
+        "\"lines\":[\"hi\",\"hello \"]",
+    );
+    let mut d: TextArea = serde_json::from_str(&json).unwrap();
+    assert_eq!(d.lines(), ["hi", "hello "]);
+    assert!(!d.undo());
+    assert!(!d.redo());
+    assert_eq!(d.lines(), ["hi", "hello "]);
+
+    // Undo index out of the edits
+    let json = s.replace("\"index\":2", "\"index\":3");
+    assert_ne!(json, s);
+    let mut d: TextArea = serde_json::from_str(&json).unwrap();
+    assert!(!d.undo());
+
+    // Consistent history is kept
+    let mut d: TextArea = serde_json::from_str(&s).unwrap();
+    assert!(d.undo());
+    assert!(d.undo());
+    assert_eq!(d.lines(), ["hi"]);
+    assert!(!d.undo());
+}
+
+#[test]
+fn test_serde_options() {
+    let mut options = Options::default();
+    options.wrap = true;
+    options.bracket_pairs.push(('<', '>'));
+    let s = serde_json::to_string(&options).unwrap();
+    let d: Options = serde_json::from_str(&s).unwrap();
+    assert_eq!(d, options);
+}
@@ -1,6 +1,12 @@
 use std::cmp;
+use std::collections::HashSet;
 use std::fmt::Debug;
-use tui_textarea::{CursorMove, TextArea};
+use std::ops::Range;
+use tui_textarea::{
+    BookmarkList, ConflictResolution, CursorMove, EditMacro, ExportFormat, Hunk, Input, Key,
+    LineEnding, Operator, Options, PatchError, SoftLimit, Template, TemplateField, TextArea,
+    TextEdit, TextObject,
+};
 
 fn assert_undo_redo<T: Debug>(
     before_pos: (usize, usize),
@@ -626,6 +632,30 @@ fn test_delete_str_nothing() {
     assert_eq!(t.cursor(), (0, 0));
 }
 
+#[test]
+fn test_delete_str_beyond_end() {
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.delete_str(100));
+    assert_eq!(t.lines(), ["a"]);
+    assert_eq!(t.yank_text(), "b\ncd");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+    assert_eq!(t.cursor(), (1, 2));
+
+    let mut t = TextArea::from(["ab"]);
+    assert!(t.delete_str(100));
+    assert_eq!(t.lines(), [""]);
+
+    // The cursor stays in the text on undoing the deletion at the end of text
+    let mut t = TextArea::from(["ab", ""]);
+    t.move_cursor(CursorMove::Bottom);
+    t.delete_str(100);
+    t.undo();
+    assert_eq!(t.lines(), ["ab", ""]);
+    assert_eq!(t.cursor(), (1, 0));
+}
+
 #[test]
 fn test_delete_str_within_line() {
     for i in 0.."abc".len() {
@@ -1669,3 +1699,2416 @@ fn test_delete_next_word() {
     t.test((1, 0), (1, 0, &["word  ことば 🐶", ""], " x"));
     t.test((1, 2), (1, 2, t.0, ""));
 }
+
+#[test]
+fn test_format_with() {
+    for (before, cursor, formatted, after, want_cursor) in [
+        (
+            &["a", "b", "c"][..],
+            (2, 1),
+            "a\nb\nc",
+            &["a", "b", "c"][..],
+            (2, 1),
+        ),
+        (
+            &["a", "b", "c"][..],
+            (2, 1),
+            "x\na\nb\nc\n",
+            &["x", "a", "b", "c"][..],
+            (3, 1),
+        ),
+        (
+            &["a", "b", "c"][..],
+            (2, 1),
+            "a\nc",
+            &["a", "c"][..],
+            (1, 1),
+        ),
+        (
+            &["a", "b", "c"][..],
+            (1, 1),
+            "a\nBB\nc",
+            &["a", "BB", "c"][..],
+            (1, 1),
+        ),
+        (
+            &["a", "b", "c"][..],
+            (2, 1),
+            "a\nb",
+            &["a", "b"][..],
+            (1, 1),
+        ),
+        (
+            &["a", "b", "c"][..],
+            (0, 1),
+            "b\nc",
+            &["b", "c"][..],
+            (0, 1),
+        ),
+        (&["a", "b", "c"][..], (0, 0), "", &[""][..], (0, 0)),
+        (&[""][..], (0, 0), "a\r\nb\r\n", &["a", "b"][..], (0, 0)),
+        (&["abc"][..], (0, 3), "abc\n\n", &["abc", ""][..], (0, 3)),
+    ] {
+        let mut t = TextArea::from(before.iter().copied());
+        t.move_cursor(CursorMove::Jump(cursor.0, cursor.1));
+        let modified = t.format_with(|s| {
+            assert_eq!(s, before.join("\n"));
+            Some(formatted.to_string())
+        });
+        assert_eq!(modified, before != after, "{:?}", formatted);
+        assert_eq!(t.lines(), after, "{:?}", formatted);
+        assert_eq!(t.cursor(), want_cursor, "{:?}", formatted);
+        if modified {
+            assert!(t.undo());
+            assert_eq!(t.lines(), before, "{:?}", formatted);
+            assert!(t.redo());
+            assert_eq!(t.lines(), after, "{:?}", formatted);
+        }
+    }
+
+    let mut t = TextArea::from(["abc"]);
+    assert!(!t.format_with(|_| None));
+    assert_eq!(t.lines(), ["abc"]);
+}
+
+#[test]
+fn test_apply_patch() {
+    let before = ["a", "b", "c", "d", "e"];
+    for (hunks, after, want_cursor) in [
+        (vec![], &before[..], (2, 1)),
+        (
+            vec![Hunk::new(1, ["b", "c"], ["b", "x", "c"])],
+            &["a", "b", "x", "c", "d", "e"][..],
+            (3, 1),
+        ),
+        (
+            vec![
+                Hunk::new(0, ["a"], ["A"]),
+                Hunk::new(2, ["c", "d"], ["d"]),
+                Hunk::new(5, [""; 0], ["f", "g"]),
+            ],
+            &["A", "b", "d", "e", "f", "g"][..],
+            (2, 1),
+        ),
+        (vec![Hunk::new(0, before, [""; 0])], &[""][..], (0, 0)),
+        (vec![Hunk::new(2, ["c"], ["c"])], &before[..], (2, 1)),
+    ] {
+        let mut t = TextArea::from(before);
+        t.move_cursor(CursorMove::Jump(2, 1));
+        let modified = t.apply_patch(&hunks).unwrap();
+        assert_eq!(modified, before != after, "{:?}", hunks);
+        assert_eq!(t.lines(), after, "{:?}", hunks);
+        assert_eq!(t.cursor(), want_cursor, "{:?}", hunks);
+        if modified {
+            assert!(t.undo());
+            assert_eq!(t.lines(), before, "{:?}", hunks);
+            assert!(!t.undo());
+            assert!(t.redo());
+            assert_eq!(t.lines(), after, "{:?}", hunks);
+        }
+    }
+}
+
+#[test]
+fn test_apply_patch_conflict() {
+    let before = ["a", "b", "c"];
+    for (hunks, hunk, row) in [
+        (vec![Hunk::new(0, ["x"], ["y"])], 0, 0),
+        (vec![Hunk::new(2, ["c", "d"], ["y"])], 0, 2),
+        (vec![Hunk::new(4, [""; 0], ["y"])], 0, 4),
+        (
+            vec![Hunk::new(0, ["a"], ["A"]), Hunk::new(1, ["c"], ["C"])],
+            1,
+            1,
+        ),
+        (
+            vec![Hunk::new(1, ["b", "c"], ["B"]), Hunk::new(2, ["c"], ["C"])],
+            1,
+            2,
+        ),
+        (
+            vec![Hunk::new(1, ["b"], ["B"]), Hunk::new(0, ["a"], ["A"])],
+            1,
+            0,
+        ),
+    ] {
+        let mut t = TextArea::from(before);
+        let err = t.apply_patch(&hunks).unwrap_err();
+        assert_eq!(err, PatchError::Conflict { hunk, row }, "{:?}", hunks);
+        assert_eq!(t.lines(), before, "{:?}", hunks);
+        assert!(!t.undo(), "{:?}", hunks);
+    }
+}
+
+#[test]
+fn test_apply_edits() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    let marker = t.add_marker(2, 0);
+
+    let edits = [
+        TextEdit::new((0, 3), (0, 3), "2"),
+        TextEdit::new((0, 0), (0, 0), "x"),
+        TextEdit::new((0, 3), (0, 3), "3"),
+        TextEdit::new((0, 0), (0, 0), "y"),
+        TextEdit::new((2, 0), (2, 1), "G\nH"),
+    ];
+    assert_eq!(t.apply_edits(&edits), Ok(vec![0..1, 2..4]));
+    assert_eq!(t.lines(), ["xyabc23", "def", "G", "Hhi"]);
+    assert_eq!(t.selection_range(), Some(((1, 1), (3, 2))));
+    assert_eq!(t.marker_pos(marker), Some((2, 0)));
+
+    assert_eq!(t.apply_edits(&[]), Ok(vec![]));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert!(!t.undo());
+}
+
+#[test]
+fn test_apply_edits_order() {
+    // An insertion and a replacement at the same position are accepted in any order
+    let insert = TextEdit::new((0, 0), (0, 0), ">");
+    let replace = TextEdit::new((0, 0), (0, 3), "xyz");
+    for edits in [[replace.clone(), insert.clone()], [insert, replace]] {
+        let mut t = TextArea::from(["abcd"]);
+        assert_eq!(
+            t.apply_edits(&edits),
+            Ok(vec![Range { start: 0, end: 1 }]),
+            "{:?}",
+            edits
+        );
+        assert_eq!(t.lines(), [">xyzd"], "{:?}", edits);
+    }
+}
+
+#[test]
+fn test_apply_edits_single_line() {
+    // Newlines are not inserted in single-line mode so the changed rows are in the text
+    let mut t = TextArea::from(["hello"]);
+    t.set_single_line(true);
+    let edits = [TextEdit::new((0, 5), (0, 5), "\nworld")];
+    assert_eq!(t.apply_edits(&edits), Ok(vec![Range { start: 0, end: 1 }]));
+    assert_eq!(t.lines(), ["hello world"]);
+}
+
+#[test]
+fn test_apply_edits_invalid_range() {
+    let before = ["abc", "def"];
+    for (edits, edit) in [
+        (vec![TextEdit::new((0, 4), (0, 4), "x")], 0),
+        (vec![TextEdit::new((2, 0), (2, 0), "x")], 0),
+        (vec![TextEdit::new((0, 2), (0, 1), "x")], 0),
+        (vec![TextEdit::new((0, 5), (1, 0), "x")], 0),
+        (
+            vec![
+                TextEdit::new((0, 0), (0, 1), "x"),
+                TextEdit::new((1, 0), (1, 2), "y"),
+                TextEdit::new((0, 2), (1, 1), "z"),
+            ],
+            1,
+        ),
+    ] {
+        let mut t = TextArea::from(before);
+        let err = t.apply_edits(&edits).unwrap_err();
+        assert_eq!(err, PatchError::InvalidRange { edit }, "{:?}", edits);
+        assert_eq!(t.lines(), before, "{:?}", edits);
+        assert!(!t.undo(), "{:?}", edits);
+    }
+}
+
+#[test]
+fn test_apply_unified_diff() {
+    let mut t = TextArea::from(["fn main() {", "    println!(\"hello\");", "}"]);
+    let diff = "\
+--- a/main.rs
++++ b/main.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    println!(\"hello\");
++    let name = \"world\";
++    println!(\"hello {name}\");
+ }
+";
+    let hunks = Hunk::parse_unified(diff).unwrap();
+    assert_eq!(t.apply_patch(&hunks), Ok(true));
+    assert_eq!(
+        t.lines(),
+        [
+            "fn main() {",
+            "    let name = \"world\";",
+            "    println!(\"hello {name}\");",
+            "}",
+        ],
+    );
+}
+
+#[test]
+fn test_auto_indent() {
+    for (before, cursor, after, want_cursor) in [
+        (&["    foo"][..], (0, 7), &["    foo", "    "][..], (1, 4)),
+        (&["\t  foo"][..], (0, 6), &["\t  foo", "\t  "][..], (1, 3)),
+        (&["    foo"][..], (0, 2), &["  ", "    foo"][..], (1, 2)),
+        (&["  foo bar"][..], (0, 6), &["  foo ", "  bar"][..], (1, 2)),
+        (&["foo"][..], (0, 3), &["foo", ""][..], (1, 0)),
+        (&["    "][..], (0, 4), &["    ", "    "][..], (1, 4)),
+    ] {
+        let mut t = TextArea::from(before.iter().copied());
+        t.set_auto_indent(true);
+        t.move_cursor(CursorMove::Jump(cursor.0, cursor.1));
+        t.insert_newline();
+        assert_eq!(t.lines(), after, "{:?}", before);
+        assert_eq!(t.cursor(), want_cursor, "{:?}", before);
+
+        // Newline and indent are undone at once
+        assert!(t.undo());
+        assert_eq!(t.lines(), before, "{:?}", before);
+        assert!(!t.undo());
+        assert!(t.redo());
+        assert_eq!(t.lines(), after, "{:?}", before);
+    }
+
+    // Auto-indent also works with key input
+    let mut t = TextArea::from(["  a"]);
+    t.set_auto_indent(true);
+    t.move_cursor(CursorMove::End);
+    t.input(tui_textarea::Input {
+        key: tui_textarea::Key::Enter,
+        ..Default::default()
+    });
+    t.insert_char('b');
+    assert_eq!(t.lines(), ["  a", "  b"]);
+}
+
+#[test]
+fn test_indent_dedent_selection() {
+    // (lines, selection start, cursor, lines after indent, selection after indent)
+    for (before, start, cursor, after, want_sel) in [
+        (
+            &["a", "b", "c"][..],
+            None,
+            (1, 1),
+            &["a", "    b", "c"][..],
+            None,
+        ),
+        (
+            &["a", "b", "c"][..],
+            Some((0, 1)),
+            (1, 0),
+            &["    a", "b", "c"][..],
+            Some(((0, 5), (1, 0))),
+        ),
+        (
+            &["a", "", "c"][..],
+            Some((2, 1)),
+            (0, 0),
+            &["    a", "", "    c"][..],
+            Some(((0, 4), (2, 5))),
+        ),
+        (
+            &["a", "  b", "c"][..],
+            Some((0, 0)),
+            (2, 1),
+            &["    a", "      b", "    c"][..],
+            Some(((0, 4), (2, 5))),
+        ),
+    ] {
+        let mut t = TextArea::from(before.iter().copied());
+        if let Some((r, c)) = start {
+            t.move_cursor(CursorMove::Jump(r as u16, c as u16));
+            t.start_selection();
+        }
+        t.move_cursor(CursorMove::Jump(cursor.0, cursor.1));
+        let sel = t.selection_range();
+
+        assert!(t.indent_selection(), "{:?}", before);
+        assert_eq!(t.lines(), after, "{:?}", before);
+        if start.is_some() {
+            assert_eq!(t.selection_range(), want_sel, "{:?}", before);
+        } else {
+            assert_eq!(t.cursor(), (cursor.0 as usize, cursor.1 as usize + 4));
+        }
+
+        assert!(t.dedent_selection(), "{:?}", before);
+        assert_eq!(t.lines(), before, "{:?}", before);
+        assert_eq!(t.selection_range(), sel, "{:?}", before);
+
+        // Each command is undone at once
+        assert!(t.undo());
+        assert_eq!(t.lines(), after, "{:?}", before);
+        assert!(t.undo());
+        assert_eq!(t.lines(), before, "{:?}", before);
+    }
+}
+
+#[test]
+fn test_dedent_selection() {
+    for (before, tab_len, after) in [
+        ("\t\tfoo", 4, "\tfoo"),
+        ("      foo", 4, "  foo"),
+        ("  \tfoo", 4, "\tfoo"),
+        ("   foo", 2, " foo"),
+        ("foo", 4, "foo"),
+        ("  foo", 0, "  foo"),
+    ] {
+        let mut t = TextArea::from([before]);
+        t.set_tab_length(tab_len);
+        assert_eq!(t.dedent_selection(), before != after, "{:?}", before);
+        assert_eq!(t.lines(), [after], "{:?}", before);
+    }
+
+    let mut t = TextArea::from(["a"]);
+    t.set_tab_length(2);
+    t.set_hard_tab_indent(true);
+    assert!(t.indent_selection());
+    assert_eq!(t.lines(), ["\ta"]);
+    t.set_tab_length(0);
+    assert!(!t.indent_selection());
+    assert_eq!(t.lines(), ["\ta"]);
+}
+
+#[test]
+fn test_resolve_conflict() {
+    let two_way = &[
+        "a",
+        "<<<<<<< HEAD",
+        "b",
+        "=======",
+        "c",
+        "d",
+        ">>>>>>> topic",
+    ][..];
+    let diff3 = &[
+        "<<<<<<< HEAD",
+        "||||||| base",
+        "x",
+        "=======",
+        "c",
+        ">>>>>>> topic",
+    ][..];
+    for (before, resolution, after) in [
+        (two_way, ConflictResolution::Ours, &["a", "b"][..]),
+        (two_way, ConflictResolution::Theirs, &["a", "c", "d"][..]),
+        (two_way, ConflictResolution::Both, &["a", "b", "c", "d"][..]),
+        (diff3, ConflictResolution::Ours, &[""][..]),
+        (diff3, ConflictResolution::Theirs, &["c"][..]),
+        (diff3, ConflictResolution::Both, &["c"][..]),
+    ] {
+        let mut t = TextArea::from(before.iter().copied());
+        t.move_cursor(CursorMove::Bottom);
+        assert!(t.resolve_conflict(resolution), "{:?}", resolution);
+        assert_eq!(t.lines(), after, "{:?}", resolution);
+        assert!(t.conflicts().is_empty(), "{:?}", resolution);
+
+        assert!(t.undo(), "{:?}", resolution);
+        assert_eq!(t.lines(), before, "{:?}", resolution);
+        assert!(!t.undo(), "{:?}", resolution);
+    }
+
+    // Cursor is not in a conflict
+    let mut t = TextArea::from(two_way.iter().copied());
+    assert!(!t.resolve_conflict(ConflictResolution::Ours));
+    assert_eq!(t.lines(), two_way);
+}
+
+#[test]
+fn test_template_fields() {
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let mut t = TextArea::default();
+    t.set_template(
+        Template::new()
+            .text("[")
+            .field(TemplateField::new("a").value("x"))
+            .text("|")
+            .field(TemplateField::new("b"))
+            .text("]\n> ")
+            .field(TemplateField::new("c").value("zz")),
+    );
+    assert_eq!(t.lines(), ["[x|]", "> zz"]);
+    assert_eq!(t.focused_field(), Some("a"));
+    assert_eq!(t.cursor(), (0, 2));
+
+    // Editing a field shifts the following fields in the same row
+    assert!(t.input(key(Key::Char('y'))));
+    assert_eq!(t.lines(), ["[xy|]", "> zz"]);
+    t.input(key(Key::Left));
+    t.input(key(Key::Left));
+    assert_eq!(t.cursor(), (0, 1));
+    // Cannot move out of the field
+    t.input(key(Key::Left));
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(!t.input(key(Key::Backspace)));
+    assert!(t.input(key(Key::Delete)));
+    assert_eq!(t.lines(), ["[y|]", "> zz"]);
+    t.input(key(Key::End));
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(!t.input(key(Key::Delete)));
+    // Newlines and shortcuts are ignored
+    assert!(!t.input(key(Key::Enter)));
+    assert!(!t.input(key(Key::Char('\n'))));
+    assert!(!t.input(Input {
+        key: Key::Char('k'),
+        ctrl: true,
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["[y|]", "> zz"]);
+
+    // Empty field
+    t.input(key(Key::Tab));
+    assert_eq!(t.focused_field(), Some("b"));
+    assert_eq!(t.cursor(), (0, 3));
+    assert!(!t.input(key(Key::Backspace)));
+    t.input(key(Key::Char('1')));
+    t.input(key(Key::Char('2')));
+    assert_eq!(t.lines(), ["[y|12]", "> zz"]);
+    assert!(t.input(key(Key::Backspace)));
+    assert_eq!(t.lines(), ["[y|1]", "> zz"]);
+
+    // Navigation wraps around
+    t.input(key(Key::Down));
+    assert_eq!(t.focused_field(), Some("c"));
+    assert_eq!(t.cursor(), (1, 4));
+    t.input(key(Key::Tab));
+    assert_eq!(t.focused_field(), Some("a"));
+    t.input(Input {
+        key: Key::Tab,
+        shift: true,
+        ..Default::default()
+    });
+    assert_eq!(t.focused_field(), Some("c"));
+    t.input(key(Key::Up));
+    assert_eq!(t.focused_field(), Some("b"));
+
+    assert_eq!(t.field_values(), [("a", "y"), ("b", "1"), ("c", "zz")]);
+    assert_eq!(t.field_value("b"), Some("1"));
+
+    // Undo is disabled in template mode
+    assert!(!t.undo());
+    assert_eq!(t.lines(), ["[y|1]", "> zz"]);
+
+    t.remove_template();
+    assert_eq!(t.focused_field(), None);
+    assert!(t.field_values().is_empty());
+    assert!(t.input(key(Key::Enter)));
+    assert_eq!(t.lines(), ["[y|1", "]", "> zz"]);
+}
+
+#[test]
+fn test_template_field_errors() {
+    let non_empty = |s: &str| {
+        if s.is_empty() {
+            Err("required".to_string())
+        } else {
+            Ok(())
+        }
+    };
+    let mut t = TextArea::default();
+    t.set_template(
+        Template::new()
+            .text("a=")
+            .field(TemplateField::new("a").validator(non_empty))
+            .text(" b=")
+            .field(TemplateField::new("b").validator(non_empty))
+            .text(" c=")
+            .field(TemplateField::new("c")),
+    );
+    assert_eq!(
+        t.field_errors(),
+        [("a", "required".to_string()), ("b", "required".to_string())],
+    );
+    t.input(Input {
+        key: Key::Char('1'),
+        ..Default::default()
+    });
+    assert_eq!(t.field_errors(), [("b", "required".to_string())]);
+
+    // Template without fields
+    t.set_template(Template::new().text("hello"));
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.next_field());
+    assert!(!t.input(Input {
+        key: Key::Char('a'),
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["hello"]);
+}
+
+#[test]
+fn test_read_only() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.insert_char('x');
+    t.set_yank_text("y");
+    t.set_read_only(true);
+    assert!(t.read_only());
+
+    let edits = [
+        Input {
+            key: Key::Char('a'),
+            ..Default::default()
+        },
+        Input {
+            key: Key::Enter,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Tab,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Backspace,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Delete,
+            alt: true,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Char('k'),
+            ctrl: true,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Char('u'),
+            ctrl: true,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Paste,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Cut,
+            ..Default::default()
+        },
+    ];
+    for input in edits.iter() {
+        assert!(!t.input(input.clone()), "{:?}", input);
+        assert!(!t.input_without_shortcuts(input.clone()), "{:?}", input);
+        assert_eq!(t.lines(), ["xabc", "def"], "{:?}", input);
+    }
+
+    // Moving the cursor, selecting and copying still work
+    t.input(Input {
+        key: Key::Down,
+        shift: true,
+        ..Default::default()
+    });
+    assert_eq!(t.cursor(), (1, 1));
+    t.input(Input {
+        key: Key::Char('c'),
+        ctrl: true,
+        ..Default::default()
+    });
+    assert_eq!(t.yank_text(), "abc\nd");
+
+    // Methods to modify the text are not affected
+    t.insert_str("!");
+    assert_eq!(t.lines(), ["xabc", "d!ef"]);
+
+    t.set_read_only(false);
+    assert!(t.input(Input {
+        key: Key::Char('u'),
+        ctrl: true,
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["xabc", "def"]);
+}
+
+#[test]
+fn test_single_line() {
+    let mut t = TextArea::from(["ab", "cd", "ef"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.set_single_line(true);
+    assert!(t.single_line());
+    assert_eq!(t.lines(), ["ab cd ef"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert_no_undo_redo(&mut t, "set_single_line");
+
+    for input in [
+        Input {
+            key: Key::Enter,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Char('m'),
+            ctrl: true,
+            ..Default::default()
+        },
+    ] {
+        assert!(!t.input(input.clone()), "{:?}", input);
+        assert!(t.take_submitted(), "{:?}", input);
+        assert!(!t.take_submitted(), "{:?}", input);
+        assert_eq!(t.lines(), ["ab cd ef"], "{:?}", input);
+    }
+    assert!(!t.input_without_shortcuts(Input {
+        key: Key::Enter,
+        ..Default::default()
+    }));
+    assert!(t.take_submitted());
+
+    t.set_yank_text("x\ny\r\nz");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ab cx y zd ef"]);
+    assert_eq!(t.cursor(), (0, 9));
+
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (0, 9));
+
+    t.set_single_line(false);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["ab cx y z", "d ef"]);
+    assert!(!t.take_submitted());
+}
+
+#[test]
+fn test_max_length() {
+    let mut t = TextArea::default();
+    t.set_max_length(6);
+
+    for c in "abcdefg".chars() {
+        t.input(Input {
+            key: Key::Char(c),
+            ..Default::default()
+        });
+    }
+    assert_eq!(t.lines(), ["abcdef"]);
+    assert!(!t.input(Input {
+        key: Key::Enter,
+        ..Default::default()
+    }));
+    assert!(!t.insert_str("x"));
+
+    // Selected text is replaced so it is not counted
+    t.move_cursor(CursorMove::Head);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.insert_str("x\ny\r\nz"));
+    assert_eq!(t.lines(), ["x", "cdef"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Undo restores the text before the truncated insertion and the deletion of the selection
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["cdef"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcdef"]);
+
+    t.set_yank_text("123");
+    t.move_cursor(CursorMove::Head);
+    t.delete_next_char();
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["1bcdef"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    t.set_tab_length(2);
+    assert!(!t.insert_tab());
+
+    t.clear_max_length();
+    assert!(t.insert_tab());
+    assert_eq!(t.lines(), ["1 bcdef"]);
+}
+
+#[test]
+fn test_input_filter() {
+    let mut t = TextArea::default();
+    t.set_input_filter(|c| c.is_ascii_digit());
+
+    for c in "1a2".chars() {
+        t.input(Input {
+            key: Key::Char(c),
+            ..Default::default()
+        });
+    }
+    assert_eq!(t.lines(), ["12"]);
+    assert!(!t.input(Input {
+        key: Key::Char('b'),
+        ..Default::default()
+    }));
+    assert!(!t.input(Input {
+        key: Key::Enter,
+        ..Default::default()
+    }));
+    assert!(!t.input(Input {
+        key: Key::Tab,
+        ..Default::default()
+    }));
+    assert!(!t.insert_str("abc"));
+
+    // Rejected input does not delete the selection
+    t.select_all();
+    assert!(!t.insert_str("x"));
+    assert_eq!(t.lines(), ["12"]);
+    assert!(t.is_selecting());
+
+    t.set_yank_text("3\nx4");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["34"]);
+
+    t.set_input_filter(|c| c != ' ');
+    t.insert_str("a b");
+    assert_eq!(t.lines(), ["34ab"]);
+    t.clear_input_filter();
+    t.insert_str(" ");
+    assert_eq!(t.lines(), ["34ab "]);
+}
+
+#[test]
+fn test_constraints_on_programmatic_edits() {
+    let mut t = TextArea::from(["10", "20", "30"]);
+    t.set_input_filter(|c| c.is_ascii_digit());
+    assert!(t.format_with(|text| Some(text.replace('2', "4"))));
+    assert_eq!(t.lines(), ["10", "40", "30"]);
+
+    let mut t = TextArea::from(["hello", "world"]);
+    t.set_max_length(5);
+    let hunks = [Hunk::new(1, ["world"], ["patched", "world"])];
+    assert_eq!(t.apply_patch(&hunks), Ok(true));
+    assert_eq!(t.lines(), ["hello", "patched", "world"]);
+
+    // User input is still constrained
+    assert!(!t.insert_str("x"));
+}
+
+#[test]
+fn test_yank_history() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.copy();
+    t.delete_line_by_end();
+    assert_eq!(t.yank_history(), ["ef", "abc\nd"]);
+    assert_eq!(t.yank_history_previews(4), ["ef", "abc…"]);
+
+    // Yanking the same text again moves it to the front
+    t.set_yank_text("abc\nd");
+    assert_eq!(t.yank_history(), ["abc\nd", "ef"]);
+
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(t.paste_yank_history(1));
+    assert_eq!(t.lines(), ["abc", "d", "efghi"]);
+    assert!(!t.paste_yank_history(2));
+
+    // Masked text is not recorded unless yanking masked text is allowed
+    t.set_mask_char('*');
+    t.set_yank_masked(false);
+    t.delete_line_by_end();
+    assert_eq!(t.yank_history(), ["ef", "abc\nd"]);
+
+    t.set_max_yank_history(1);
+    assert_eq!(t.yank_history(), ["ef"]);
+    t.set_max_yank_history(0);
+    t.set_yank_text("x");
+    assert!(t.yank_history().is_empty());
+}
+
+#[test]
+fn test_event_hook() {
+    use std::sync::{Arc, Mutex};
+    use tui_textarea::UxEvent;
+
+    let events = Arc::new(Mutex::new(vec![]));
+    let mut t = TextArea::from(["abc", "(de)"]);
+    let e = events.clone();
+    t.set_event_hook(move |event| e.lock().unwrap().push(event));
+
+    t.move_cursor(CursorMove::Back);
+    t.move_cursor(CursorMove::Forward);
+    t.move_cursor(CursorMove::MatchingBracket);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::MatchingBracket);
+    t.move_cursor(CursorMove::Jump(1, 3));
+    assert_eq!(
+        events.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        [
+            UxEvent::BufferEdge,
+            UxEvent::MotionFailed,
+            UxEvent::BufferEdge,
+            UxEvent::MotionFailed,
+        ],
+    );
+
+    t.set_max_length(7);
+    assert!(!t.input(Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    }));
+    t.clear_max_length();
+    t.set_read_only(true);
+    assert!(!t.input(Input {
+        key: Key::Backspace,
+        ..Default::default()
+    }));
+    assert_eq!(
+        events.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        [UxEvent::EditRejected, UxEvent::EditRejected],
+    );
+
+    t.clear_event_hook();
+    t.move_cursor(CursorMove::Down);
+    assert!(events.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_accessible_description() {
+    let mut t = TextArea::from(["abc", "", "xyz"]);
+    assert_eq!(
+        t.accessible_description(),
+        "Line 1 of 3, column 1\nCurrent line: abc",
+    );
+
+    t.move_cursor(CursorMove::Down);
+    t.set_read_only(true);
+    assert_eq!(
+        t.accessible_description(),
+        "Line 2 of 3, column 1\nCurrent line: blank\nRead-only",
+    );
+
+    t.set_read_only(false);
+    t.move_cursor(CursorMove::Bottom);
+    t.set_mask_char('*');
+    assert_eq!(
+        t.accessible_description(),
+        "Line 3 of 3, column 1\nCurrent line: 3 hidden characters",
+    );
+}
+
+#[test]
+fn test_render_linear() {
+    let lines: Vec<_> = (1..=10).map(|i| format!("line {}", i)).collect();
+    let mut t = TextArea::from(lines);
+    t.move_cursor(CursorMove::Jump(9, 0));
+    assert!(t.fold(2..5));
+    let want = [
+        "   1: line 1",
+        "   2: line 2",
+        "   3: ⋯ 3 lines",
+        "   6: line 6",
+        "   7: line 7",
+        "   8: line 8",
+        "   9: line 9",
+        "> 10: line 10",
+        "",
+    ];
+    assert_eq!(t.render_linear(), want.join("\n"));
+
+    let mut t = TextArea::from(["ab", ""]);
+    t.set_mask_char('*');
+    assert_eq!(t.render_linear(), "> 1: **\n  2: \n");
+}
+
+#[test]
+fn test_content_hash() {
+    // Known values of 64-bit FNV-1a
+    assert_eq!(TextArea::default().content_hash(), 0xcbf29ce484222325);
+    assert_eq!(TextArea::from(["a"]).content_hash(), 0xaf63dc4c8601ec8c);
+
+    let mut t = TextArea::from(["a", "b"]);
+    let before = t.content_hash();
+    assert_eq!(before, TextArea::hash_text("a\nb"));
+    assert_ne!(before, TextArea::from(["ab"]).content_hash());
+
+    t.insert_newline();
+    assert_ne!(t.content_hash(), before);
+    t.undo();
+    assert_eq!(t.content_hash(), before);
+    assert!(t.content_eq(&TextArea::from(["a", "b"])));
+    assert!(t.content() == TextArea::from(["a", "b"]).content());
+    assert!(t.content() != TextArea::from(["a", "c"]).content());
+
+    // Contents can be deduplicated with a hash set
+    let textareas = [
+        TextArea::from(["a"]),
+        TextArea::from(["b"]),
+        TextArea::from(["a"]),
+    ];
+    let contents: HashSet<_> = textareas.iter().map(|t| t.content()).collect();
+    assert_eq!(contents.len(), 2);
+
+    // The hash is calculated from the text joined with the line endings
+    for text in ["a\r\nb\r\n", "a\r\nb\nc"] {
+        let t = TextArea::from(text.split('\n'));
+        assert_eq!(t.content_hash(), TextArea::hash_text(&t.text()));
+        assert_eq!(t.content_hash(), TextArea::hash_text(text));
+    }
+}
+
+#[test]
+fn test_grapheme_clusters() {
+    // Family emoji joined with ZWJ, 'e' with a combining acute accent and a flag
+    let family = "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+    let line = format!("a{}e\u{301}\u{1f1ef}\u{1f1f5}", family);
+    let mut t = TextArea::from([line.as_str()]);
+
+    let mut cols = vec![];
+    for _ in 0..5 {
+        t.move_cursor(CursorMove::Forward);
+        cols.push(t.cursor().1);
+    }
+    assert_eq!(cols, [1, 6, 8, 10, 10]);
+
+    let mut cols = vec![];
+    for _ in 0..4 {
+        t.move_cursor(CursorMove::Back);
+        cols.push(t.cursor().1);
+    }
+    assert_eq!(cols, [8, 6, 1, 0]);
+
+    t.move_cursor(CursorMove::End);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), [format!("a{}e\u{301}", family)]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), [format!("a{}", family)]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["a"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [format!("a{}", family)]);
+    assert_eq!(t.cursor(), (0, 6));
+
+    t.move_cursor(CursorMove::Head);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.delete_next_char());
+    assert_eq!(t.lines(), ["a"]);
+}
+
+#[test]
+fn test_line_ending() {
+    let mut t = TextArea::from("abc\r\ndef\r\n".split('\n'));
+    assert_eq!(t.lines(), ["abc", "def", ""]);
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+    t.move_cursor(CursorMove::Bottom);
+    t.insert_str("ghi\r\njkl");
+    assert_eq!(t.lines(), ["abc", "def", "ghi", "jkl"]);
+    assert_eq!(t.text(), "abc\r\ndef\r\nghi\r\njkl");
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+
+    // The line ending of each line is kept when line endings are mixed
+    let text = "a\nb\r\nc\nd";
+    let mut t = TextArea::from(text.split('\n'));
+    assert_eq!(t.lines(), ["a", "b", "c", "d"]);
+    assert_eq!(t.line_ending(), LineEnding::Lf);
+    assert_eq!(t.text(), text);
+    let t2 = TextArea::from(["a\r", "b", "c\r", "d"]);
+    assert_eq!(t2.line_ending(), LineEnding::CrLf);
+    assert_eq!(t2.text(), "a\r\nb\nc\r\nd");
+
+    // New lines take the dominant line ending and the ending of each line follows edits
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.insert_newline();
+    assert_eq!(t.text(), "a\nb\n\r\nc\nd");
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.delete_next_char();
+    assert_eq!(t.text(), "ab\n\r\nc\nd");
+    t.undo();
+    t.undo();
+    assert_eq!(t.text(), text);
+
+    // The last line is not followed by a newline so its `\r` is kept
+    let t = TextArea::from(["a\r"]);
+    assert_eq!(t.lines(), ["a\r"]);
+    assert_eq!(t.line_ending(), LineEnding::Lf);
+    assert_eq!(t.text(), "a\r");
+
+    // Detected when inserting a document into an empty textarea
+    let mut t = TextArea::default();
+    t.insert_str("a\r\nb");
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+    t.insert_str("\nc");
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+    t.select_all();
+    t.cut();
+    t.insert_str("a\nb");
+    assert_eq!(t.line_ending(), LineEnding::Lf);
+}
+
+#[test]
+fn test_change_hook() {
+    use std::sync::{Arc, Mutex};
+    use tui_textarea::ChangeEvent;
+
+    fn offset(text: &str, (row, col): (usize, usize)) -> usize {
+        let line_start: usize = text.split('\n').take(row).map(|l| l.len() + 1).sum();
+        let line = text.split('\n').nth(row).unwrap();
+        line_start + line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+    }
+
+    // Apply the text changes to a shadow copy of the text
+    let shadow = Arc::new(Mutex::new(String::from("abc\ndef")));
+    let events = Arc::new(Mutex::new(vec![]));
+    let mut t = TextArea::from(["abc", "def"]);
+    let (s, e) = (shadow.clone(), events.clone());
+    t.set_change_hook(move |change| {
+        let mut text = s.lock().unwrap();
+        match change {
+            ChangeEvent::Inserted { start, text: t, .. } => {
+                let i = offset(&text, *start);
+                text.insert_str(i, t);
+            }
+            ChangeEvent::Deleted {
+                start,
+                end,
+                text: t,
+            } => {
+                let (i, j) = (offset(&text, *start), offset(&text, *end));
+                assert_eq!(&text[i..j], t);
+                text.replace_range(i..j, "");
+            }
+            _ => {}
+        }
+        e.lock().unwrap().push(change.clone());
+    });
+
+    let check = |t: &TextArea| assert_eq!(*shadow.lock().unwrap(), t.lines().join("\n"));
+
+    t.move_cursor(CursorMove::End);
+    t.insert_str("x\ny\nz");
+    check(&t);
+    t.set_auto_indent(true);
+    t.insert_str("  q");
+    t.insert_newline();
+    check(&t);
+    t.move_cursor(CursorMove::Head);
+    t.delete_char();
+    check(&t);
+    t.start_selection();
+    t.move_cursor(CursorMove::Top);
+    t.cut();
+    check(&t);
+    while t.undo() {
+        check(&t);
+    }
+    while t.redo() {
+        check(&t);
+    }
+    t.select_all();
+    t.delete_char();
+    check(&t);
+    assert_eq!(t.lines(), [""]);
+
+    // Cursor and selection changes
+    let mut t = TextArea::from(["abc"]);
+    let events = Arc::new(Mutex::new(vec![]));
+    let e = events.clone();
+    t.set_change_hook(move |change| e.lock().unwrap().push(change.clone()));
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Back); // Cursor at head of line cannot move further
+    t.move_cursor(CursorMove::Back);
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            ChangeEvent::SelectionChanged(Some(((0, 0), (0, 0)))),
+            ChangeEvent::CursorMoved {
+                from: (0, 0),
+                to: (0, 1)
+            },
+            ChangeEvent::SelectionChanged(Some(((0, 0), (0, 1)))),
+            ChangeEvent::SelectionChanged(None),
+            ChangeEvent::CursorMoved {
+                from: (0, 1),
+                to: (0, 0)
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_swap_words_and_arguments() {
+    let mut t = TextArea::from(["let (x, y) = (foo(1, 2), bar);"]);
+    t.move_cursor(CursorMove::Jump(0, 14));
+    assert!(t.swap_arguments());
+    assert_eq!(t.lines(), ["let (x, y) = (bar, foo(1, 2));"]);
+    assert_eq!(t.cursor(), (0, 19));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["let (x, y) = (foo(1, 2), bar);"]);
+    assert!(!t.undo());
+
+    // Not in brackets
+    t.move_cursor(CursorMove::Jump(0, 0));
+    assert!(!t.swap_arguments());
+
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.swap_with_next_word());
+    assert_eq!(t.lines(), ["let (y, x) = (foo(1, 2), bar);"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["let (x, y) = (foo(1, 2), bar);"]);
+
+    // Same words are not swapped
+    let mut t = TextArea::from(["a a"]);
+    assert!(!t.swap_with_next_word());
+    assert!(!t.undo());
+}
+
+#[test]
+fn test_completion() {
+    use tui_textarea::CompletionItem;
+
+    let items = || {
+        vec![
+            CompletionItem::new("format").insert_text("format!()"),
+            CompletionItem::new("for"),
+        ]
+    };
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+
+    // Whole word under the cursor is replaced
+    let mut t = TextArea::from(["a forxyz b"]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+    assert!(t.open_completion(items()));
+    assert_eq!(t.selected_completion().unwrap().label(), "format");
+    t.input(key(Key::Up));
+    assert_eq!(t.selected_completion().unwrap().label(), "for");
+    t.input(key(Key::Up));
+    assert!(t.input(key(Key::Tab)));
+    assert_eq!(t.lines(), ["a format!() b"]);
+    assert_eq!(t.cursor(), (0, 11));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a forxyz b"]);
+
+    // Escape closes the menu and the key is not handled as usual
+    t.move_cursor(CursorMove::Jump(0, 4));
+    assert!(t.open_completion(items()));
+    assert!(!t.input(key(Key::Esc)));
+    assert!(!t.is_completing());
+    assert_eq!(t.lines(), ["a forxyz b"]);
+
+    // Closed when the cursor leaves the word or no item matches
+    assert!(t.open_completion(items()));
+    t.input(key(Key::Left));
+    assert!(t.is_completing());
+    t.input(key(Key::Left));
+    t.input(key(Key::Left));
+    assert!(!t.is_completing());
+    t.move_cursor(CursorMove::Jump(0, 4));
+    assert!(t.open_completion(items()));
+    t.input(key(Key::Char('q')));
+    assert!(!t.is_completing());
+    assert!(!t.open_completion(items()));
+}
+
+#[test]
+fn test_snippet() {
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let shift_tab = Input {
+        key: Key::Tab,
+        shift: true,
+        ..Default::default()
+    };
+
+    let mut t = TextArea::from(["let x = ;"]);
+    t.move_cursor(CursorMove::Jump(0, 8));
+    assert!(t.insert_snippet("if ${1:cond} {\n    ${2:a}\n} else {\n    $3\n}"));
+    assert_eq!(
+        t.lines(),
+        ["let x = if cond {", "    a", "} else {", "    ", "};"],
+    );
+    assert!(t.is_snippet_active());
+    assert_eq!(t.selection_range(), Some(((0, 11), (0, 15))));
+
+    // Typing replaces the placeholder and following tab stops follow the edit
+    for c in "ok".chars() {
+        t.input(key(Key::Char(c)));
+    }
+    t.input(key(Key::Tab));
+    assert_eq!(t.selection_range(), Some(((1, 4), (1, 5))));
+    t.input(key(Key::Delete));
+    t.input(key(Key::Enter));
+    assert!(t.is_snippet_active());
+    t.input(key(Key::Tab));
+    assert_eq!(t.cursor(), (4, 4));
+    assert_eq!(t.selection_range(), None);
+
+    // Back to the previous tab stop which now spans two lines
+    t.input(shift_tab);
+    assert_eq!(t.selection_range(), Some(((1, 4), (2, 0))));
+    t.input(key(Key::Tab));
+
+    // The final position is the end of the snippet when `$0` is omitted
+    assert!(t.input(key(Key::Char('b'))));
+    t.input(key(Key::Tab));
+    assert!(!t.is_snippet_active());
+    assert_eq!(t.cursor(), (5, 1));
+    assert_eq!(
+        t.lines(),
+        ["let x = if ok {", "    ", "", "} else {", "    b", "};"],
+    );
+
+    // Leaving the active tab stop ends the session
+    let mut t = TextArea::default();
+    t.insert_snippet("f($1, $2)");
+    assert_eq!(t.cursor(), (0, 2));
+    t.input(key(Key::Left));
+    assert!(!t.is_snippet_active());
+    t.input(key(Key::Tab));
+    assert_eq!(t.lines(), ["f   (, )"]);
+
+    // Tab stops follow undo
+    let mut t = TextArea::default();
+    t.insert_snippet("${1:a} = ${2:b};$0");
+    t.input(key(Key::Char('x')));
+    t.undo();
+    t.undo();
+    assert_eq!(t.lines(), ["a = b;"]);
+    t.next_snippet_stop();
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 5))));
+    t.end_snippet();
+    assert!(!t.next_snippet_stop());
+
+    // Snippet without tab stops just moves the cursor
+    let mut t = TextArea::default();
+    assert!(t.insert_snippet("a$0b\nc"));
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(!t.is_snippet_active());
+
+    // Whole insertion is undone at once
+    t.select_all();
+    assert!(t.insert_snippet("${1:x}"));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "c"]);
+
+    // Rejected characters don't insert broken snippet
+    let mut t = TextArea::default();
+    t.set_max_length(3);
+    assert!(!t.insert_snippet("${1:abcd}"));
+    assert_eq!(t.lines(), [""]);
+}
+
+#[test]
+fn test_markers() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    let before = t.add_marker(0, 1);
+    let at = t.add_marker(1, 0);
+    let after = t.add_marker(1, 2);
+    let clamped = t.add_marker(10, 10);
+    assert_eq!(t.marker_pos(clamped), Some((2, 3)));
+
+    // Text inserted at the marker is put after it
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.insert_str("xy\nz");
+    assert_eq!(t.lines(), ["abc", "xy", "zdef", "ghi"]);
+    assert_eq!(t.marker_pos(before), Some((0, 1)));
+    assert_eq!(t.marker_pos(at), Some((1, 0)));
+    assert_eq!(t.marker_pos(after), Some((2, 3)));
+    assert_eq!(t.marker_pos(clamped), Some((3, 3)));
+
+    // Markers in deleted text move to the start of the deletion
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 4));
+    t.cut();
+    assert_eq!(t.lines(), ["ab", "ghi"]);
+    assert_eq!(t.marker_pos(before), Some((0, 1)));
+    assert_eq!(t.marker_pos(at), Some((0, 2)));
+    assert_eq!(t.marker_pos(after), Some((0, 2)));
+    assert_eq!(t.marker_pos(clamped), Some((1, 3)));
+
+    // Undo and redo move the markers as well
+    assert!(t.undo());
+    assert_eq!(t.marker_pos(clamped), Some((3, 3)));
+    assert!(t.undo());
+    assert_eq!(t.marker_pos(clamped), Some((2, 3)));
+    assert!(t.redo());
+    assert_eq!(t.marker_pos(clamped), Some((3, 3)));
+    // Markers moved by the deletion are not restored
+    assert_eq!(t.marker_pos(after), Some((0, 2)));
+
+    // Joining lines in single-line mode
+    t.set_single_line(true);
+    assert_eq!(t.lines(), ["abc xy zdef ghi"]);
+    assert_eq!(t.marker_pos(before), Some((0, 1)));
+    assert_eq!(t.marker_pos(clamped), Some((0, 15)));
+
+    assert!(t.remove_marker(after));
+    assert!(!t.remove_marker(after));
+    assert_eq!(t.marker_pos(after), None);
+}
+
+#[test]
+fn test_select_to() {
+    let mut t = TextArea::from(["abc def ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+
+    // Extends the selection in both directions from the start
+    t.select_to(CursorMove::WordForward);
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 8))));
+    t.select_to(CursorMove::Down);
+    assert_eq!(t.selection_range(), Some(((0, 4), (1, 3))));
+    t.select_to(CursorMove::Top);
+    t.select_to(CursorMove::Head);
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 4))));
+    t.copy();
+    assert_eq!(t.yank_text(), "abc ");
+
+    // Starts a new selection at the cursor
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.select_to(CursorMove::WordBack);
+    assert_eq!(t.selection_range(), Some(((1, 0), (1, 1))));
+}
+
+#[test]
+fn test_delete_to() {
+    let mut t = TextArea::from(["abc def ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+
+    // Deletes backward and forward ranges and yanks the deleted text
+    assert!(t.delete_to(CursorMove::WordForward));
+    assert_eq!(t.lines(), ["abc ghi", "jkl"]);
+    assert_eq!(t.yank_text(), "def ");
+    assert!(t.delete_to(CursorMove::Down));
+    assert_eq!(t.lines(), ["abc "]);
+    assert_eq!(t.yank_text(), "ghi\njkl");
+    assert_eq!(t.cursor(), (0, 4));
+
+    // The existing selection is ignored
+    t.start_selection();
+    t.move_cursor(CursorMove::Head);
+    assert!(t.delete_to(CursorMove::End));
+    assert_eq!(t.lines(), [""]);
+    assert!(!t.is_selecting());
+    assert!(!t.delete_to(CursorMove::End));
+    assert!(!t.is_selecting());
+
+    // Each deletion is one undo step
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc "]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.paste();
+    assert_eq!(t.lines(), ["abc abc ghi", "jkl"]);
+}
+
+#[test]
+fn test_macro() {
+    let mut t = TextArea::from(["foo", "bar", "baz", "qux"]);
+    assert!(t.stop_recording().is_empty());
+
+    // Key inputs are recorded as editing operations
+    t.start_recording();
+    for input in [
+        Input {
+            key: Key::Char('-'),
+            ..Default::default()
+        },
+        Input {
+            key: Key::Char(' '),
+            ..Default::default()
+        },
+        Input {
+            key: Key::Down,
+            ..Default::default()
+        },
+        Input {
+            key: Key::Home,
+            ..Default::default()
+        },
+    ] {
+        t.input(input);
+    }
+    let m = t.stop_recording();
+    assert_eq!(m.len(), 4);
+    assert_eq!(t.lines(), ["- foo", "bar", "baz", "qux"]);
+
+    // All changes by the replay are one undo step
+    assert!(t.replay(&m, 2));
+    assert_eq!(t.lines(), ["- foo", "- bar", "- baz", "qux"]);
+    assert_eq!(t.cursor(), (3, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["- foo", "bar", "baz", "qux"]);
+    assert!(!t.replay(&m, 0));
+    assert!(!t.replay(&EditMacro::default(), 1));
+
+    // Replaying a macro while recording records its operations
+    t.move_cursor(CursorMove::Jump(3, 0));
+    t.start_recording();
+    t.replay(&m, 1);
+    t.select_to(CursorMove::End);
+    t.cut();
+    let m2 = t.stop_recording();
+    assert_eq!(m2.len(), 6);
+    assert_eq!(t.lines(), ["- foo", "bar", "baz", ""]);
+    t.undo();
+    t.undo();
+    assert_eq!(t.lines(), ["- foo", "bar", "baz", "qux"]);
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.replay(&m2, 1);
+    assert_eq!(t.lines(), ["- foo", "- bar", "", "qux"]);
+}
+
+#[test]
+fn test_macro_composite_edits() {
+    // Each composite edit is recorded as one operation so that replaying it reproduces the same text
+    let check = |lines: &[&str], edit: &dyn Fn(&mut TextArea<'static>)| {
+        let mut t = TextArea::from(lines.iter().copied());
+        t.start_recording();
+        edit(&mut t);
+        let m = t.stop_recording();
+        assert_eq!(m.len(), 1, "{:?}", m);
+
+        let mut r = TextArea::from(lines.iter().copied());
+        r.replay(&m, 1);
+        assert_eq!(r.lines(), t.lines());
+        assert_eq!(r.cursor(), t.cursor());
+        r.undo();
+        assert_eq!(r.lines(), lines);
+    };
+
+    check(&["c", "b", "a"], &|t| {
+        t.move_lines_down();
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.duplicate_lines();
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.sort_lines();
+    });
+    check(&["c", "  b", "a"], &|t| {
+        t.join_lines();
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.toggle_comment("//");
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.indent_selection();
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.apply_edits(&[
+            TextEdit::new((0, 0), (1, 1), "x\ny"),
+            TextEdit::new((2, 1), (2, 1), "!"),
+        ])
+        .unwrap();
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.apply_patch(&[Hunk::new(1, ["b"], ["B", "BB"])]).unwrap();
+    });
+    check(&["c", "b", "a"], &|t| {
+        t.format_with(|s| Some(s.to_uppercase() + "\nd"));
+    });
+    check(
+        &["<<<<<<< HEAD", "ours", "=======", "theirs", ">>>>>>> x"],
+        &|t| {
+            t.resolve_conflict(ConflictResolution::Both);
+        },
+    );
+
+    // Failed edits are not recorded
+    let mut t = TextArea::from(["a"]);
+    t.start_recording();
+    assert!(t.apply_patch(&[Hunk::new(0, ["b"], ["c"])]).is_err());
+    assert!(t.stop_recording().is_empty());
+}
+
+#[test]
+fn test_operator() {
+    let mut t = TextArea::from(["foo bar", "baz", "", "qux"]);
+
+    // `cw` does not change the spaces after the word
+    assert!(t.apply_operator(Operator::Change, CursorMove::WordForward));
+    assert_eq!(t.lines(), [" bar", "baz", "", "qux"]);
+    assert_eq!(t.yank_text(), "foo");
+
+    // `dw` at the last word of a line does not join the lines
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.apply_operator(Operator::Delete, CursorMove::WordForward));
+    assert_eq!(t.lines(), [" ", "baz", "", "qux"]);
+
+    // `d}` from the head of a line is linewise
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.apply_operator(Operator::Delete, CursorMove::ParagraphForward));
+    assert_eq!(t.lines(), [" ", "qux"]);
+    assert_eq!(t.yank_text(), "baz\n\n");
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Backward inclusive range includes the character under the cursor
+    let mut t = TextArea::from(["f(a) b"]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+    assert!(t.apply_operator(Operator::Delete, CursorMove::MatchingBracket));
+    assert_eq!(t.lines(), ["f b"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(!t.apply_operator(Operator::Delete, CursorMove::MatchingBracket));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["f(a) b"]);
+
+    // Yank moves the cursor to the start
+    t.move_cursor(CursorMove::End);
+    assert!(!t.apply_operator(Operator::Yank, CursorMove::WordBack));
+    assert_eq!(t.yank_text(), "b");
+    assert_eq!(t.cursor(), (0, 5));
+
+    // `cc` keeps the indentation with auto-indent
+    let mut t = TextArea::from(["{", "    a", "    b", "}"]);
+    t.set_auto_indent(true);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    assert!(t.apply_operator(Operator::Change, CursorMove::Down));
+    assert_eq!(t.lines(), ["{", "    ", "}"]);
+    assert_eq!(t.cursor(), (1, 4));
+    assert_eq!(t.yank_text(), "    a\n    b\n");
+
+    // `di{` across lines and `dd` of the last line
+    let mut t = TextArea::from(["{", "    a", "}"]);
+    t.move_cursor(CursorMove::Jump(1, 4));
+    assert!(t.apply_operator_to_object(Operator::Delete, TextObject::InnerBracket));
+    assert_eq!(t.lines(), ["{}"]);
+    assert!(!t.apply_operator_to_object(Operator::Delete, TextObject::InnerBracket));
+    assert!(t.apply_operator_to_object(Operator::Delete, TextObject::Line));
+    assert_eq!(t.lines(), [""]);
+    assert!(!t.apply_operator_to_object(Operator::Delete, TextObject::Line));
+    assert!(!t.apply_operator_to_object(Operator::Delete, TextObject::InnerWord));
+
+    // Operators are recorded in macros
+    let mut t = TextArea::from(["a b", "c d"]);
+    t.start_recording();
+    t.apply_operator_to_object(Operator::Delete, TextObject::AroundWord);
+    t.apply_operator(Operator::Indent, CursorMove::Forward);
+    t.move_cursor(CursorMove::Down);
+    let m = t.stop_recording();
+    assert_eq!(m.len(), 3);
+    t.move_cursor(CursorMove::Head);
+    t.replay(&m, 1);
+    assert_eq!(t.lines(), ["    b", "    d"]);
+}
+
+#[test]
+fn test_overwrite() {
+    let mut t = TextArea::from(["ab🐶c", "d"]);
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    assert!(!t.input(key(Key::Insert)));
+    assert!(t.overwrite());
+
+    // Replaces characters and appends at the end of line
+    for c in "xyzwv".chars() {
+        assert!(t.input(key(Key::Char(c))));
+    }
+    assert_eq!(t.lines(), ["xyzwv", "d"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Each replacement restores the replaced character on undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xyzw", "d"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xyzc", "d"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xy🐶c", "d"]);
+
+    // Selected text is replaced as usual and newline is not overwritten
+    t.move_cursor(CursorMove::Head);
+    t.select_to(CursorMove::Forward);
+    t.select_to(CursorMove::Forward);
+    t.input(key(Key::Char('a')));
+    assert_eq!(t.lines(), ["a🐶c", "d"]);
+    t.input(key(Key::Enter));
+    assert_eq!(t.lines(), ["a", "🐶c", "d"]);
+
+    // Max length does not reject replacing characters
+    let mut t = TextArea::from(["abc"]);
+    t.set_max_length(3);
+    t.set_overwrite(true);
+    assert!(t.input(key(Key::Char('x'))));
+    assert_eq!(t.lines(), ["xbc"]);
+    t.move_cursor(CursorMove::End);
+    assert!(!t.input(key(Key::Char('y'))));
+    assert_eq!(t.cursor(), (0, 3));
+
+    assert!(!t.input(key(Key::Insert)));
+    assert!(!t.overwrite());
+    t.move_cursor(CursorMove::Head);
+    assert!(!t.input(key(Key::Char('y'))));
+    assert_eq!(t.lines(), ["xbc"]);
+}
+
+#[test]
+fn test_bookmarks() {
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    let mut list = BookmarkList::default();
+    assert!(!list.input(key(Key::Down), &mut textarea));
+
+    for row in [3, 1, 2] {
+        textarea.move_cursor(CursorMove::Jump(row, 1));
+        assert!(textarea.toggle_bookmark());
+    }
+    assert_eq!(textarea.bookmarks(), [1, 2, 3]);
+
+    assert!(list.input(key(Key::End), &mut textarea));
+    assert_eq!(list.selected(), 2);
+    assert!(list.input(key(Key::Down), &mut textarea));
+    assert_eq!(list.selected(), 2);
+    assert!(list.input(key(Key::Delete), &mut textarea));
+    assert_eq!(textarea.bookmarks(), [1, 2]);
+    assert_eq!(list.selected(), 1);
+    assert!(!list.input(key(Key::Char('x')), &mut textarea));
+
+    // Joining lines keeps one bookmark per line
+    textarea.move_cursor(CursorMove::Jump(2, 0));
+    textarea.delete_newline();
+    assert_eq!(textarea.bookmarks(), [1]);
+
+    textarea.move_cursor(CursorMove::Top);
+    assert!(list.input(key(Key::Enter), &mut textarea));
+    assert_eq!(textarea.cursor(), (1, 0));
+
+    textarea.clear_bookmarks();
+    assert!(textarea.bookmarks().is_empty());
+    assert!(!textarea.jump_to_bookmark(0));
+}
+
+#[test]
+fn test_options() {
+    let mut textarea = TextArea::from(["abc"]);
+    let defaults = textarea.options();
+    assert_eq!(defaults, Options::default());
+
+    let mut options = Options::default();
+    options.tab_length = 2;
+    options.tab_width = Some(8);
+    options.max_length = Some(4);
+    options.max_histories = 10;
+    options.overwrite = true;
+    textarea.apply_options(&options);
+    assert_eq!(textarea.options(), options);
+    assert_eq!(textarea.tab_length(), 2);
+    assert_eq!(textarea.tab_width(), 8);
+
+    // Typing overwrites characters and the max length is applied
+    textarea.insert_char('x');
+    textarea.move_cursor(CursorMove::End);
+    assert!(textarea.insert_str("y"));
+    assert!(!textarea.insert_str("z"));
+    assert_eq!(textarea.lines(), ["xbcy"]);
+
+    // History is kept while the max number of undo steps is unchanged
+    textarea.apply_options(&options);
+    assert!(textarea.undo());
+    textarea.apply_options(&defaults);
+    assert_eq!(textarea.options(), defaults);
+    assert!(!textarea.undo());
+}
+
+#[test]
+fn test_filetype() {
+    let mut t = TextArea::default();
+    assert_eq!(t.filetype(), None);
+    t.set_read_only(true);
+    t.set_max_length(10);
+
+    assert!(t.set_filetype("Makefile"));
+    assert_eq!(t.filetype(), Some("make"));
+    assert!(t.hard_tab_indent());
+    assert_eq!(t.tab_length(), 8);
+    assert!(t.trim_trailing_whitespace());
+    let mut expected = Options::for_filetype("make").unwrap();
+    expected.read_only = true;
+    expected.max_length = Some(10);
+    assert_eq!(t.options(), expected);
+
+    // Switching the file type resets the options of the previous file type
+    assert!(t.set_filetype("YAML"));
+    assert_eq!(t.filetype(), Some("yaml"));
+    assert!(!t.hard_tab_indent());
+    assert_eq!(t.tab_length(), 2);
+    assert!(t.read_only());
+
+    assert!(t.set_filetype("md"));
+    assert!(!t.trim_trailing_whitespace());
+    assert!(t.get_wrap());
+
+    // Unknown file type changes nothing
+    let options = t.options();
+    assert!(!t.set_filetype("no-such-type"));
+    assert!(!t.set_filetype_from_path("LICENSE"));
+    assert_eq!(t.filetype(), Some("markdown"));
+    assert_eq!(t.options(), options);
+}
+
+#[test]
+fn test_commit_message_preset() {
+    let mut t = TextArea::from([
+        "Add a preset for commit messages",
+        "",
+        "# Please enter the commit message for your changes.",
+    ]);
+    assert!(t.set_filetype_from_path(".git/COMMIT_EDITMSG"));
+    assert_eq!(t.filetype(), Some("gitcommit"));
+    assert_eq!(
+        t.soft_limit(),
+        Some(SoftLimit::FirstLineColumns {
+            first: 50,
+            rest: 72
+        }),
+    );
+    assert_eq!(t.color_column(), Some(72));
+    assert_eq!(t.comment_prefix(), Some("#"));
+    assert!(!t.soft_limit_exceeded());
+
+    t.move_cursor(CursorMove::End);
+    t.insert_str(" and strip comment lines on submit");
+    assert!(t.soft_limit_exceeded());
+    t.move_cursor(CursorMove::Down);
+    t.insert_str("Body");
+    assert_eq!(
+        t.text_without_comments(),
+        "Add a preset for commit messages and strip comment lines on submit\nBody\n",
+    );
+
+    // Switching to another file type resets the preset
+    assert!(t.set_filetype("text"));
+    assert_eq!(t.soft_limit(), None);
+    assert_eq!(t.color_column(), None);
+    assert_eq!(t.comment_prefix(), None);
+}
+
+#[test]
+fn test_delete_trailing_whitespace() {
+    let mut t = TextArea::from(["ab \u{3000}", "  ", "c", "d\t "]);
+    t.move_cursor(CursorMove::Jump(3, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.delete_trailing_whitespace());
+    assert_eq!(t.lines(), ["ab", "", "c", "d"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (3, 1))));
+    assert!(!t.delete_trailing_whitespace());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab \u{3000}", "  ", "c", "d\t "]);
+}
+
+#[test]
+fn test_yank_pop() {
+    let mut t = TextArea::from(["x"]);
+    t.set_yank_text("a\nb");
+    t.set_yank_text("c");
+    t.move_cursor(CursorMove::End);
+
+    assert!(!t.yank_pop()); // Nothing was pasted
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["xc"]);
+    t.set_read_only(true); // Read-only text is not modified
+    assert!(!t.input(Input {
+        key: Key::Char('y'),
+        alt: true,
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["xc"]);
+    t.set_read_only(false);
+    assert!(t.input(Input {
+        key: Key::Char('y'),
+        alt: true,
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["xa", "b"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.yank_text(), "a\nb");
+    assert_eq!(t.yank_history(), ["c", "a\nb"]);
+
+    // Replacing the pasted text is undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xc"]);
+
+    // Not available after moving the cursor
+    assert!(t.paste());
+    t.move_cursor(CursorMove::Head);
+    assert!(!t.yank_pop());
+    t.move_cursor(CursorMove::End);
+    assert!(!t.yank_pop());
+
+    // The yank history is necessary
+    let mut t = TextArea::default();
+    t.set_max_yank_history(0);
+    t.set_yank_text("a");
+    assert!(t.paste());
+    assert!(!t.yank_pop());
+}
+
+#[test]
+fn test_registers() {
+    let mut t = TextArea::from(["abc", "def"]);
+    assert!(!t.yank_to_register('a'));
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.yank_to_register('a'));
+    assert!(!t.is_selecting());
+    assert_eq!(t.register_text('a').as_deref(), Some("abc\n"));
+    assert_eq!(t.register_names(), ['a']);
+
+    t.move_cursor(CursorMove::End);
+    assert!(t.paste_from_register('a'));
+    assert_eq!(t.lines(), ["abc", "defabc", ""]);
+    assert!(!t.paste_from_register('b'));
+
+    // Register operations are recorded in macros
+    let mut t = TextArea::from(["one two"]);
+    t.start_recording();
+    t.start_selection();
+    t.move_cursor(CursorMove::WordForward);
+    assert!(t.yank_to_register('x'));
+    t.move_cursor(CursorMove::End);
+    assert!(t.paste_from_register('x'));
+    let m = t.stop_recording();
+    assert_eq!(t.lines(), ["one twoone "]);
+    t.clear_registers();
+    assert_eq!(t.register_text('x'), None);
+    t.move_cursor(CursorMove::Head);
+    assert!(t.replay(&m, 1));
+    assert_eq!(t.lines(), ["one twoone one "]);
+
+    // Masked text is not copied unless allowed
+    let mut t = TextArea::from(["secret"]);
+    t.set_mask_char('*');
+    t.set_yank_masked(false);
+    t.select_all();
+    assert!(t.yank_to_register('a'));
+    assert_eq!(t.register_text('a'), None);
+}
+
+#[test]
+fn test_external_edit() {
+    let mut t = TextArea::from(["a", "b", ""]);
+    t.set_line_ending(LineEnding::CrLf);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    let text = t.export_for_external_edit();
+    assert_eq!(text, "a\r\nb\r\n\r\n");
+
+    // Edit inputs are rejected while the text is edited externally
+    assert!(t.is_editing_externally());
+    assert!(!t.input(Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    }));
+    t.input(Input {
+        key: Key::Up,
+        ..Default::default()
+    });
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Unchanged text round-trips without modification
+    assert!(!t.import_external_edit(&text));
+    assert!(!t.is_editing_externally());
+    assert!(t.input(Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["ax", "b", ""]);
+
+    t.export_for_external_edit();
+    assert!(t.import_external_edit("ax\nc\n"));
+    assert_eq!(t.lines(), ["ax", "c"]);
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+    assert_eq!(t.cursor(), (0, 2));
+
+    t.export_for_external_edit();
+    t.cancel_external_edit();
+    assert!(!t.is_editing_externally());
+    assert_eq!(t.lines(), ["ax", "c"]);
+}
+
+#[test]
+fn test_kill_accumulation() {
+    // Disabled by default. A deleted newline is not yanked
+    let mut t = TextArea::from(["aaa", "bbb"]);
+    assert!(!t.kill_accumulation());
+    t.delete_line_by_end();
+    t.delete_line_by_end();
+    assert_eq!(t.lines(), ["bbb"]);
+    assert_eq!(t.yank_text(), "aaa");
+
+    let mut t = TextArea::from(["aaa bbb", "ccc"]);
+    t.set_kill_accumulation(true);
+    assert!(t.options().kill_accumulation);
+
+    // Backward kills are prepended
+    t.move_cursor(CursorMove::Jump(1, 3));
+    t.delete_word();
+    t.delete_word();
+    t.delete_word();
+    assert_eq!(t.lines(), ["aaa "]);
+    assert_eq!(t.yank_text(), "bbb\nccc");
+
+    // Moving the cursor stops the accumulation
+    t.move_cursor(CursorMove::Head);
+    t.delete_next_word();
+    assert_eq!(t.yank_text(), "aaa");
+    t.delete_next_word();
+    assert_eq!(t.yank_text(), "aaa ");
+    assert_eq!(t.yank_history(), ["aaa ", "bbb\nccc"]);
+
+    // Other edits stop the accumulation
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.set_kill_accumulation(true);
+    t.delete_whole_line();
+    t.insert_char('x');
+    t.delete_char();
+    t.delete_whole_line();
+    assert_eq!(t.lines(), ["c"]);
+    assert_eq!(t.yank_text(), "b\n");
+
+    // Mixing forward and backward kills
+    let mut t = TextArea::from(["abc def"]);
+    t.set_kill_accumulation(true);
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.delete_line_by_end();
+    t.delete_line_by_head();
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.yank_text(), "abc def");
+}
+
+#[test]
+fn test_delete_whole_line() {
+    let mut t = TextArea::from(["abc"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert!(t.delete_whole_line());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.yank_text(), "abc");
+    assert!(!t.is_selecting());
+    assert!(!t.delete_whole_line());
+
+    // Recorded in macros and undone at once
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.start_recording();
+    t.delete_whole_line();
+    let m = t.stop_recording();
+    assert!(t.replay(&m, 1));
+    assert_eq!(t.lines(), ["c"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["b", "c"]);
+}
+
+#[test]
+fn test_word_motions() {
+    let mut t = TextArea::from(["foo-bar (baz)", "", "qux"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    for pos in [
+        (1, 0),
+        (0, 12),
+        (0, 11),
+        (0, 8),
+        (0, 6),
+        (0, 3),
+        (0, 2),
+        (0, 0),
+    ] {
+        t.move_cursor(CursorMove::WordEndBack);
+        assert_eq!(t.cursor(), pos);
+    }
+
+    t.move_cursor(CursorMove::BigWordForward);
+    assert_eq!(t.cursor(), (0, 8));
+    t.move_cursor(CursorMove::BigWordEnd);
+    assert_eq!(t.cursor(), (0, 12));
+    t.move_cursor(CursorMove::BigWordEnd);
+    assert_eq!(t.cursor(), (2, 2));
+    for pos in [(2, 0), (1, 0), (0, 13), (0, 8)] {
+        t.move_cursor(CursorMove::BigWordBack);
+        assert_eq!(t.cursor(), pos);
+    }
+    t.move_cursor(CursorMove::BigWordEndBack);
+    assert_eq!(t.cursor(), (0, 6));
+
+    t.move_cursor(CursorMove::Head);
+    t.move_cursor(CursorMove::WordForward);
+    assert_eq!(t.cursor(), (0, 3));
+    t.set_word_chars("-");
+    t.move_cursor(CursorMove::Head);
+    t.move_cursor(CursorMove::WordForward);
+    assert_eq!(t.cursor(), (0, 8));
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.apply_operator_to_object(Operator::Delete, TextObject::InnerWord));
+    assert_eq!(t.lines(), [" (baz)", "", "qux"]);
+
+    let mut t = TextArea::from(["a foo-bar"]);
+    let mut options = Options::default();
+    options.word_chars = "-".into();
+    t.apply_options(&options);
+    assert_eq!(t.options().word_chars, "-");
+    t.move_cursor(CursorMove::End);
+    assert!(t.delete_word());
+    assert_eq!(t.lines(), ["a "]);
+
+    // `cW` does not change the spaces after the WORD
+    let mut t = TextArea::from(["foo(a) bar"]);
+    assert!(t.apply_operator(Operator::Change, CursorMove::BigWordForward));
+    assert_eq!(t.lines(), [" bar"]);
+
+    // `dge` is inclusive
+    let mut t = TextArea::from(["foo bar"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.apply_operator(Operator::Delete, CursorMove::WordEndBack));
+    assert_eq!(t.lines(), ["for"]);
+}
+
+#[test]
+fn test_sentence_motions() {
+    let mut t = TextArea::from(["One. Two.", "", "Three"]);
+    for pos in [(0, 5), (1, 0), (2, 0), (2, 5)] {
+        t.move_cursor(CursorMove::SentenceForward);
+        assert_eq!(t.cursor(), pos);
+    }
+    for pos in [(2, 0), (1, 0), (0, 5), (0, 0)] {
+        t.move_cursor(CursorMove::SentenceBack);
+        assert_eq!(t.cursor(), pos);
+    }
+
+    let mut t = TextArea::from(["One. Two."]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.apply_operator(Operator::Delete, CursorMove::SentenceForward));
+    assert_eq!(t.lines(), ["OTwo."]);
+}
+
+#[test]
+fn test_cursor_checkpoint() {
+    use std::sync::{Arc, Mutex};
+    use tui_textarea::ChangeEvent;
+
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.push_cursor_checkpoint();
+    t.push_cursor_checkpoint(); // Ignored since the position is the same
+    t.move_cursor(CursorMove::Jump(2, 1));
+    t.insert_char('x');
+    t.push_cursor_checkpoint();
+    t.move_cursor(CursorMove::Jump(1, 3));
+
+    assert!(t.undo());
+    assert_eq!(t.cursor(), (2, 2));
+    assert_eq!(t.lines(), ["abc", "def", "gxhi"]);
+    assert!(t.undo());
+    assert_eq!(t.cursor(), (2, 1));
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert!(t.undo());
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.undo());
+
+    // Redo of a checkpoint moves the cursor to the position where it was undone
+    assert!(t.redo());
+    assert_eq!(t.cursor(), (2, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["abc", "def", "gxhi"]);
+    assert!(t.redo());
+    assert_eq!(t.cursor(), (1, 3));
+    assert!(!t.redo());
+
+    // Checkpoints do not notify changes of the text
+    let mut t = TextArea::from(["abc"]);
+    t.push_cursor_checkpoint();
+    t.move_cursor(CursorMove::End);
+    let events = Arc::new(Mutex::new(vec![]));
+    let e = events.clone();
+    t.set_change_hook(move |change| e.lock().unwrap().push(change.clone()));
+    assert!(t.undo());
+    assert_eq!(
+        *events.lock().unwrap(),
+        [ChangeEvent::CursorMoved {
+            from: (0, 3),
+            to: (0, 0)
+        }],
+    );
+}
+
+#[test]
+fn test_line_commands() {
+    let mut t = TextArea::from(["a", "b", "c", "d"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["a", "d", "b", "c"]);
+    assert_eq!(t.selection_range(), Some(((2, 1), (3, 1))));
+    assert!(!t.move_lines_down());
+    assert!(t.move_lines_up());
+    assert!(t.move_lines_up());
+    assert_eq!(t.lines(), ["b", "c", "a", "d"]);
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 1))));
+    // Each move is undone at once
+    t.undo();
+    assert_eq!(t.lines(), ["a", "b", "c", "d"]);
+    t.undo();
+    assert_eq!(t.lines(), ["a", "d", "b", "c"]);
+
+    let mut t = TextArea::from(["x", "y"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.duplicate_lines());
+    // The selection ending at the head of `y` does not include the line
+    assert_eq!(t.lines(), ["x", "x", "y"]);
+    assert_eq!(t.selection_range(), Some(((1, 0), (2, 0))));
+
+    let mut t = TextArea::from(["b", "a", "c"]);
+    assert!(!t.sort_lines());
+    t.start_selection();
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.sort_lines());
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert!(!t.sort_lines());
+    t.undo();
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+
+    let mut t = TextArea::from(["a  ", "  b", "", "c", "d"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.join_lines());
+    assert_eq!(t.lines(), ["a  b", "c", "d"]);
+    assert_eq!(t.selection_range(), None);
+    assert!(t.join_lines());
+    assert_eq!(t.lines(), ["a  b c", "d"]);
+    t.undo();
+    t.undo();
+    assert_eq!(t.lines(), ["a  ", "  b", "", "c", "d"]);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(!t.join_lines());
+
+    let mut t = TextArea::from(["  a", "    # b", "  c"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.toggle_comment("#"));
+    assert_eq!(t.lines(), ["  # a", "    # b", "  c"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(t.toggle_comment("#"));
+    assert_eq!(t.cursor(), (0, 2));
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.toggle_comment("#"));
+    assert_eq!(t.lines(), ["  a", "    b", "  c"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(!t.toggle_comment(""));
+}
+
+#[test]
+fn test_command_registry() {
+    use tui_textarea::{CommandError, CommandRegistry};
+
+    let mut registry = CommandRegistry::default();
+    assert_eq!(registry.names().count(), 0);
+    registry.register_with_description("repeat", "Insert the text N times", |t, args| {
+        let (n, text) = match args {
+            [n, text] => (n.parse::<usize>().map_err(|e| e.to_string())?, text),
+            _ => return Err("usage: repeat N TEXT".into()),
+        };
+        t.insert_str(text.repeat(n));
+        Ok(())
+    });
+    registry.register("noop", |_, _| Ok(()));
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["noop", "repeat"]);
+    assert_eq!(
+        registry.description("repeat"),
+        Some("Insert the text N times")
+    );
+    assert_eq!(registry.description("noop"), Some(""));
+    assert_eq!(registry.description("undo"), None);
+
+    let mut t = TextArea::default();
+    registry.execute_line(&mut t, "repeat 3 ab").unwrap();
+    assert_eq!(t.lines(), ["ababab"]);
+    assert_eq!(
+        registry.execute_line(&mut t, "repeat x ab"),
+        Err(CommandError::Failed {
+            name: "repeat".into(),
+            message: "invalid digit found in string".into(),
+        }),
+    );
+    assert_eq!(
+        registry.execute_line(&mut t, "  "),
+        Err(CommandError::Parse("empty command line".into())),
+    );
+    assert!(registry.unregister("noop"));
+    assert!(!registry.unregister("noop"));
+    assert!(!registry.contains("noop"));
+
+    let registry = CommandRegistry::with_builtins();
+    assert!(CommandRegistry::builtin_names().all(|name| registry.contains(name)));
+    let mut t = TextArea::from(["a", "b", "c"]);
+    registry.execute_line(&mut t, "goto 3 2").unwrap();
+    assert_eq!(t.cursor(), (2, 1));
+    let err = registry.execute_line(&mut t, "goto 0").unwrap_err();
+    assert_eq!(err.to_string(), "command goto failed: invalid arguments: 0");
+    // The comment prefix of the textarea is used when no prefix is given
+    assert!(registry.execute_line(&mut t, "toggle-comment").is_err());
+    t.set_comment_prefix("#");
+    registry.execute_line(&mut t, "toggle-comment").unwrap();
+    registry.execute_line(&mut t, "toggle-comment //").unwrap();
+    assert_eq!(t.lines(), ["a", "b", "// # c"]);
+}
+
+#[test]
+fn test_export() {
+    let mut t = TextArea::from(["a<b", "", "c"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+
+    // The cursor and the selection are not exported
+    assert_eq!(t.export_rows(0..1, ExportFormat::Ansi), "a<b\n");
+    assert_eq!(
+        t.export_rows(1..10, ExportFormat::Html),
+        "<pre class=\"tui-textarea\">\nc\n</pre>\n",
+    );
+    assert_eq!(t.export_rows(5..10, ExportFormat::Ansi), "");
+
+    t.set_mask_char('*');
+    assert_eq!(
+        t.export(ExportFormat::PlainWithLineNumbers),
+        "1  ***\n2  \n3  *\n",
+    );
+}
+
+#[test]
+fn test_block_selection_input() {
+    let alt_shift = |key| Input {
+        key,
+        ctrl: false,
+        alt: true,
+        shift: true,
+    };
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+
+    let mut t = TextArea::from(["foo = 1", "x", "barbaz = 2"]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+    for k in [Key::Down, Key::Down, Key::Right, Key::Right, Key::Right] {
+        t.input(alt_shift(k));
+    }
+    assert_eq!(t.block_selection(), Some(((0, 3), (2, 6))));
+    assert_eq!(t.cursor(), (2, 6));
+
+    // Typing replaces the selected columns and pads the short line
+    assert!(t.input(key(Key::Char(':'))));
+    assert_eq!(t.lines(), ["foo:1", "x  :", "bar: = 2"]);
+    assert_eq!(t.block_selection(), Some(((0, 4), (2, 4))));
+
+    // Backspace deletes the column before the zero-width block
+    assert!(t.input(key(Key::Backspace)));
+    assert_eq!(t.lines(), ["foo1", "x  ", "bar = 2"]);
+    assert_eq!(t.cursor(), (2, 3));
+
+    // Deleting in virtual space does not pad lines
+    t.input(alt_shift(Key::Right));
+    t.input(alt_shift(Key::Right));
+    t.input(alt_shift(Key::Right));
+    assert!(t.input(key(Key::Delete)));
+    assert_eq!(t.lines(), ["foo", "x  ", "bar2"]);
+
+    // All rows are undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo1", "x  ", "bar = 2"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo:1", "x  :", "bar: = 2"]);
+
+    // Other keys cancel the block selection
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.input(alt_shift(Key::Down));
+    t.input(alt_shift(Key::End));
+    t.input(key(Key::Left));
+    assert_eq!(t.block_selection(), None);
+    assert!(t.input(key(Key::Char('!'))));
+    assert_eq!(t.lines(), ["foo:1", "x  !:", "bar: = 2"]);
+}
+
+#[test]
+fn test_block_selection_max_length() {
+    let mut t = TextArea::from(["ab", "a", "abcd"]);
+    t.set_max_length(10);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.select_block(CursorMove::Down);
+    t.select_block(CursorMove::Down);
+    assert_eq!(t.block_selection(), Some(((0, 2), (2, 2))));
+
+    // The text is inserted into every row and the short line is padded, so 4 characters are added
+    let bar = Input {
+        key: Key::Char('|'),
+        ..Default::default()
+    };
+    assert!(!t.input(bar.clone()));
+    assert_eq!(t.lines(), ["ab", "a", "abcd"]);
+    assert!(!t.insert_str("|"));
+    assert_eq!(t.lines(), ["ab", "a", "abcd"]);
+
+    t.set_max_length(13);
+    assert!(t.input(bar));
+    assert_eq!(t.lines(), ["ab|", "a |", "ab|cd"]);
+    assert_eq!(t.text().chars().count(), 13);
+}
+
+#[test]
+fn test_fold() {
+    let lines = ["a", "{", "  b", "  c", "}", "d"];
+    let mut t = TextArea::from(lines);
+    let folds = |t: &TextArea| -> Vec<_> { t.folds().iter().map(|r| (r.start, r.end)).collect() };
+    assert!(!t.fold(2..2));
+    assert!(!t.fold(0..10));
+    assert!(t.fold(2..4));
+    assert_eq!(folds(&t), [(2, 4)]);
+
+    // Vertical and horizontal moves skip the folded lines
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (4, 1));
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (1, 1));
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.cursor(), (4, 0));
+    t.move_cursor(CursorMove::Back);
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(folds(&t), [(2, 4)]);
+
+    // Folding the lines with the cursor moves the cursor out of them
+    assert!(t.fold(0..2));
+    assert_eq!(folds(&t), [(0, 2), (2, 4)]);
+    assert_eq!(t.cursor(), (4, 0));
+    assert!(t.unfold(0));
+    assert_eq!(folds(&t), [(2, 4)]);
+
+    // All lines cannot be folded
+    assert!(t.fold(0..2));
+    assert!(t.fold(5..6));
+    assert!(!t.fold(4..5));
+    assert!(t.unfold(5));
+    assert!(t.unfold(0));
+    assert_eq!(folds(&t), [(2, 4)]);
+
+    // Lines inserted before the fold move it
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.insert_newline();
+    assert_eq!(folds(&t), [(3, 5)]);
+    t.undo();
+    assert_eq!(folds(&t), [(2, 4)]);
+
+    // Overlapping folds are merged
+    assert!(t.fold(3..5));
+    assert_eq!(folds(&t), [(2, 5)]);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Jumping into the fold unfolds it
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert_eq!(t.cursor(), (3, 0));
+    assert!(!t.is_folded(3));
+    assert!(t.folds().is_empty());
+
+    // The fold at the end of the text
+    assert!(t.fold(4..6));
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (3, 0));
+    t.move_cursor(CursorMove::Bottom);
+    assert_eq!(t.cursor(), (3, 0));
+    t.unfold_all();
+    assert!(t.folds().is_empty());
+}
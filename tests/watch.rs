@@ -0,0 +1,91 @@
+#![cfg(feature = "watch")]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tui_textarea::{CursorMove, LineEnding, SaveOptions, TextArea, UxEvent};
+
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, content: &str) -> Self {
+        let path = env::temp_dir().join(format!("tui-textarea-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        Self(path)
+    }
+
+    fn write(&self, content: &str) {
+        // Ensure the modification time is updated on file systems with coarse timestamps
+        thread::sleep(Duration::from_millis(10));
+        fs::write(&self.0, content).unwrap();
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn attach_and_reload() {
+    let file = TempFile::new("reload", "a\r\nb\r\nc\r\nd\r\n");
+    let mut t = TextArea::default();
+    let events = Arc::new(Mutex::new(vec![]));
+    let e = events.clone();
+    t.set_event_hook(move |ev| e.lock().unwrap().push(ev));
+
+    t.attach_file(&file.0).unwrap();
+    assert_eq!(t.attached_file(), Some(file.0.as_path()));
+    assert_eq!(t.lines(), ["a", "b", "c", "d", ""]);
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+    assert!(!t.poll_file());
+
+    t.move_cursor(CursorMove::Jump(3, 1));
+    file.write("x\r\na\r\nb\r\nd\r\n");
+    assert!(t.poll_file());
+    assert!(t.poll_file()); // Still changed but the event is not emitted again
+    assert_eq!(*events.lock().unwrap(), [UxEvent::FileChanged]);
+
+    assert!(t.reload_preserving_view().unwrap());
+    assert_eq!(t.lines(), ["x", "a", "b", "d", ""]);
+    assert_eq!(t.cursor(), (3, 1));
+    assert_eq!(t.text(), "x\r\na\r\nb\r\nd\r\n");
+    assert!(!t.poll_file());
+
+    // Reload is undone at once
+    t.undo();
+    assert_eq!(t.lines(), ["a", "b", "c", "d", ""]);
+
+    // Writing the same content is not a change
+    file.write("x\r\na\r\nb\r\nd\r\n");
+    assert!(!t.poll_file());
+
+    t.detach_file();
+    assert_eq!(t.attached_file(), None);
+    assert!(!t.poll_file());
+    assert!(!t.reload_preserving_view().unwrap());
+}
+
+#[test]
+fn save_attached_file() {
+    let file = TempFile::new("save", "a\n");
+    let mut t = TextArea::default();
+    t.attach_file(&file.0).unwrap();
+    t.insert_char('b');
+    t.save_to_path(&file.0, SaveOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(&file.0).unwrap(), "ba\n");
+    assert!(!t.poll_file()); // Own write is not reported as a change
+}
+
+#[test]
+fn attach_missing_file() {
+    let mut t = TextArea::from(["hello"]);
+    let path = env::temp_dir().join("tui-textarea-missing-file-for-test");
+    assert!(t.attach_file(path).is_err());
+    assert_eq!(t.lines(), ["hello"]);
+    assert_eq!(t.attached_file(), None);
+}